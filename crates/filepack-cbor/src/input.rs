@@ -267,8 +267,12 @@ impl Input {
 
     let (length, items) = ParsedField::encode(&fields, Receiver::Field);
 
+    let generics = self.generics(syn::parse_quote!(Encode));
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     Ok(quote! {
-      impl Encode for #name {
+      impl #impl_generics Encode for #name #ty_generics #where_clause {
         fn encode(&self, encoder: &mut Encoder) {
           let mut map = encoder.map::<u64>(#length);
           #(#items)*
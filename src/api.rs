@@ -30,6 +30,28 @@ pub(crate) mod missing {
   }
 }
 
+pub(crate) mod outboard {
+  use super::*;
+
+  #[derive(Debug, Encode, Decode, PartialEq)]
+  pub(crate) struct Response {
+    #[n(0)]
+    pub size: u64,
+    #[n(1)]
+    pub tree: Vec<u8>,
+  }
+}
+
+pub(crate) mod package {
+  use super::*;
+
+  #[derive(Debug, Encode, Decode, PartialEq)]
+  pub(crate) struct Request {
+    #[n(0)]
+    pub signatures: SortedSet<Signature>,
+  }
+}
+
 pub(crate) mod packages {
   use super::*;
 
@@ -39,3 +61,47 @@ pub(crate) mod packages {
     pub packages: SortedSet<Fingerprint>,
   }
 }
+
+pub(crate) mod revocations {
+  use super::*;
+
+  #[derive(Debug, Encode, Decode, PartialEq)]
+  pub(crate) struct Response {
+    #[n(0)]
+    pub revocations: SortedSet<Revocation>,
+  }
+}
+
+pub(crate) mod search {
+  use super::*;
+
+  #[derive(Debug, Encode, Decode, PartialEq, Serialize)]
+  pub(crate) struct Response {
+    #[n(0)]
+    pub packages: Vec<Fingerprint>,
+    #[n(1)]
+    pub total: u64,
+  }
+}
+
+pub(crate) mod signatures {
+  use super::*;
+
+  #[derive(Debug, Encode, Decode, PartialEq)]
+  pub(crate) struct Response {
+    #[n(0)]
+    pub signatures: SortedSet<Signature>,
+  }
+}
+
+pub(crate) mod usage {
+  use super::*;
+
+  #[derive(Debug, Encode, Decode, PartialEq)]
+  pub(crate) struct Response {
+    #[n(0)]
+    pub keys: BTreeMap<PublicKey, Usage>,
+    #[n(1)]
+    pub total: Usage,
+  }
+}
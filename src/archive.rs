@@ -48,6 +48,10 @@ impl Archive {
     Directory::decode_from_slice(file).context(archive_error::DirectoryDecode)
   }
 
+  pub(crate) fn directory(&self, hash: Hash) -> Result<Directory, ArchiveError> {
+    self.decode_directory(None, hash)
+  }
+
   pub(crate) fn file(&self, hash: Hash) -> Result<&[u8], ArchiveError> {
     self
       .files
@@ -64,12 +68,11 @@ impl Archive {
       .get(Self::PACKAGE)
       .context(archive_error::PackageMissing)?;
 
-    ensure! {
-      package.ty() == EntryType::Directory,
-      archive_error::PackageType { ty: package.ty() },
-    }
+    let Entry::Directory { hash, .. } = package else {
+      return Err(ArchiveError::PackageType { ty: package.ty() });
+    };
 
-    Ok(Fingerprint(package.hash()))
+    Ok(Fingerprint(*hash))
   }
 
   pub(crate) fn load(path: &Utf8Path) -> Result<Self> {
@@ -103,6 +106,12 @@ impl Archive {
     Component::new(Self::PACKAGE).unwrap()
   }
 
+  pub(crate) fn proof(&self, path: &RelativePath) -> Result<Option<Proof>, ArchiveError> {
+    Proof::new(self.fingerprint()?, path, |hash| {
+      Ok((self.directory(hash)?, self.file(hash)?.to_vec()))
+    })
+  }
+
   pub(crate) fn signatures_component() -> &'static Component {
     Component::new(Self::SIGNATURES).unwrap()
   }
@@ -126,15 +135,12 @@ impl Archive {
     let mut entries = BTreeMap::new();
     for (name, entry) in &directory.entries {
       let crate_entry = match entry {
-        Entry::File { hash, size } => {
+        Entry::File { hash, .. } => {
           if self.files.contains_key(hash) {
             loose.remove(hash);
             embedded.insert(RelativePath::join_opt(prefix, name), *hash);
           }
-          DirectoryTreeEntry::File(File {
-            hash: *hash,
-            size: *size,
-          })
+          DirectoryTreeEntry::File(entry.to_file().unwrap())
         }
         Entry::Directory { hash, totals, .. } => {
           DirectoryTreeEntry::Directory(self.unpack_directory(
@@ -145,6 +151,7 @@ impl Archive {
             *totals,
           )?)
         }
+        Entry::Symlink { .. } => DirectoryTreeEntry::Symlink(entry.to_symlink().unwrap()),
       };
       entries.insert(name.clone(), crate_entry);
     }
@@ -221,6 +228,7 @@ impl Archive {
             signatures.insert(signature);
           }
           Entry::Directory { .. } => return Err(ArchiveError::SignaturesDirectory),
+          Entry::Symlink { .. } => return Err(ArchiveError::SignaturesSymlink),
         }
       }
 
@@ -334,7 +342,7 @@ mod tests {
 
     let root = builder.directory(&root).unwrap();
 
-    let archive = builder.build(root.hash());
+    let archive = builder.build(root.hash().unwrap());
 
     assert_matches!(
       archive.unpack(),
@@ -388,6 +396,7 @@ mod tests {
       .create_file(
         &"bar".parse().unwrap(),
         File {
+          executable: false,
           hash: Hash::bytes(b"bar"),
           size: u64::MAX,
         },
@@ -398,6 +407,7 @@ mod tests {
       .create_file(
         &"foo".parse().unwrap(),
         File {
+          executable: false,
           hash: Hash::bytes(b"foo"),
           size: 1,
         },
@@ -437,8 +447,8 @@ mod tests {
     let entry = builder.directory(&package).unwrap();
 
     let package = Entry::Directory {
-      hash: entry.hash(),
-      size: entry.size(),
+      hash: entry.hash().unwrap(),
+      size: entry.size().unwrap(),
       totals: Totals {
         directories: 0,
         directory_size: 0,
@@ -456,7 +466,7 @@ mod tests {
 
     let root = builder.directory(&root).unwrap();
 
-    let archive = builder.build(root.hash());
+    let archive = builder.build(root.hash().unwrap());
 
     assert_matches!(
       archive.unpack(),
@@ -476,7 +486,7 @@ mod tests {
             files: 1,
           },
         },
-      }) if hash == entry.hash(),
+      }) if Some(hash) == entry.hash(),
     );
   }
 
@@ -488,6 +498,7 @@ mod tests {
       .create_file(
         &"foo".parse().unwrap(),
         File {
+          executable: false,
           hash: Hash::bytes(b"bar"),
           size: u64::MAX,
         },
@@ -499,6 +510,9 @@ mod tests {
     let statement = Statement {
       fingerprint: Fingerprint::from_bytes([0; Fingerprint::LEN]),
       timestamp: None,
+      expires: None,
+      purpose: None,
+      comment: None,
     };
 
     let manifest = Manifest {
@@ -618,6 +632,9 @@ mod tests {
     let statement = Statement {
       fingerprint: manifest.fingerprint(),
       timestamp: None,
+      expires: None,
+      purpose: None,
+      comment: None,
     };
     let signature = private_key.sign(&statement);
 
@@ -646,6 +663,9 @@ mod tests {
     let statement = Statement {
       fingerprint: Fingerprint::from_bytes([0; Fingerprint::LEN]),
       timestamp: None,
+      expires: None,
+      purpose: None,
+      comment: None,
     };
 
     let mut encoder = Encoder::new();
@@ -670,7 +690,7 @@ mod tests {
 
     let root = builder.directory(&root).unwrap();
 
-    let archive = builder.build(root.hash());
+    let archive = builder.build(root.hash().unwrap());
 
     assert_matches!(
       archive.unpack(),
@@ -704,7 +724,7 @@ mod tests {
 
     let root = builder.directory(&root).unwrap();
 
-    let archive = builder.build(root.hash());
+    let archive = builder.build(root.hash().unwrap());
 
     assert_matches!(
       archive.unpack(),
@@ -730,7 +750,7 @@ mod tests {
 
     let root = builder.directory(&root).unwrap();
 
-    let archive = builder.build(root.hash());
+    let archive = builder.build(root.hash().unwrap());
 
     assert_matches!(archive.unpack(), Err(ArchiveError::SignaturesDirectory));
   }
@@ -746,7 +766,7 @@ mod tests {
 
     let root = builder.directory(&root).unwrap();
 
-    let archive = builder.build(root.hash());
+    let archive = builder.build(root.hash().unwrap());
 
     assert_matches!(archive.unpack(), Err(ArchiveError::SignaturesMissing));
   }
@@ -760,8 +780,8 @@ mod tests {
     let entry = builder.directory(&Directory::new()).unwrap();
 
     let signatures = Entry::Directory {
-      hash: entry.hash(),
-      size: entry.size(),
+      hash: entry.hash().unwrap(),
+      size: entry.size().unwrap(),
       totals: Totals {
         directories: 0,
         directory_size: 0,
@@ -777,7 +797,7 @@ mod tests {
 
     let root = builder.directory(&root).unwrap();
 
-    let archive = builder.build(root.hash());
+    let archive = builder.build(root.hash().unwrap());
 
     assert_matches!(
       archive.unpack(),
@@ -797,7 +817,7 @@ mod tests {
             files: 1,
           },
         }
-      }) if hash == entry.hash(),
+      }) if Some(hash) == entry.hash(),
     );
   }
 
@@ -827,7 +847,7 @@ mod tests {
 
     let root = builder.directory(&root).unwrap();
 
-    let archive = builder.build(root.hash());
+    let archive = builder.build(root.hash().unwrap());
 
     assert_matches!(
       archive.unpack(),
@@ -852,7 +872,7 @@ mod tests {
 
     let root = builder.directory(&root).unwrap();
 
-    let archive = builder.build(root.hash());
+    let archive = builder.build(root.hash().unwrap());
 
     assert_matches!(
       archive.unpack(),
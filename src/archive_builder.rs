@@ -74,8 +74,9 @@ impl ArchiveBuilder {
         .iter()
         .map(|(name, entry)| {
           let entry = match entry {
-            DirectoryTreeEntry::File(file) => Ok(Entry::file(file.hash, file.size)),
+            DirectoryTreeEntry::File(file) => Ok(Entry::from(*file)),
             DirectoryTreeEntry::Directory(directory) => self.pack_directory(directory),
+            DirectoryTreeEntry::Symlink(symlink) => Ok(Entry::from(symlink.clone())),
           };
 
           Ok((name.clone(), entry?))
@@ -23,6 +23,8 @@ pub enum ArchiveError {
   SignaturesDirectory,
   #[snafu(display("archive missing signatures directory"))]
   SignaturesMissing,
+  #[snafu(display("found symlink in `signatures` directory"))]
+  SignaturesSymlink,
   #[snafu(display("expected archive `signatures` entry to be directory but found {ty}"))]
   SignaturesType { ty: EntryType },
   #[snafu(display("archive contains unexpected embedded files: {paths}"))]
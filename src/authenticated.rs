@@ -8,17 +8,55 @@ use {
   subcommand::serve::AuthConfig,
 };
 
-pub(crate) struct Authenticated;
+pub(crate) enum Authenticated {
+  Key {
+    issuer: PublicKey,
+    roles: BTreeSet<Role>,
+  },
+  Unrestricted,
+}
+
+impl Authenticated {
+  pub(crate) fn authorize(&self, role: Role) -> ServerResult {
+    match self {
+      Self::Key { issuer, roles } => {
+        ensure!(
+          roles.contains(&Role::Admin) || roles.contains(&role),
+          server_error::RoleMissing {
+            issuer: *issuer,
+            role,
+          },
+        );
+        Ok(())
+      }
+      Self::Unrestricted => Ok(()),
+    }
+  }
+
+  pub(crate) fn is_admin(&self) -> bool {
+    match self {
+      Self::Key { roles, .. } => roles.contains(&Role::Admin),
+      Self::Unrestricted => true,
+    }
+  }
+
+  pub(crate) fn issuer(&self) -> Option<PublicKey> {
+    match self {
+      Self::Key { issuer, .. } => Some(*issuer),
+      Self::Unrestricted => None,
+    }
+  }
+}
 
 impl<S: Send + Sync> FromRequestParts<S> for Authenticated {
   type Rejection = ServerError;
 
   async fn from_request_parts(parts: &mut Parts, _state: &S) -> ServerResult<Self> {
     let Some(auth) = parts.extensions.get::<Arc<AuthConfig>>().cloned() else {
-      return Ok(Self);
+      return Ok(Self::Unrestricted);
     };
 
-    let admin = auth.admin.context(server_error::WriteForbidden)?;
+    ensure!(!auth.keys.is_empty(), server_error::WriteForbidden);
 
     let TypedHeader(Authorization(bearer)) = parts
       .extract::<TypedHeader<Authorization<Bearer>>>()
@@ -31,8 +69,16 @@ impl<S: Send + Sync> FromRequestParts<S> for Authenticated {
         }
       })?;
 
-    Token::verify(admin, auth.audience.as_deref(), bearer.token())?;
+    let issuer = Token::issuer(bearer.token())?;
+
+    let roles = auth
+      .keys
+      .get(&issuer)
+      .context(server_error::AuthorizationIssuerUnknown { issuer })?
+      .clone();
+
+    Token::verify(issuer, auth.audience.as_deref(), bearer.token())?;
 
-    Ok(Self)
+    Ok(Self::Key { issuer, roles })
   }
 }
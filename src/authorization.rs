@@ -0,0 +1,164 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Authorization {
+  pub(crate) keys: BTreeMap<KeyName, AuthorizedKey>,
+}
+
+impl Authorization {
+  pub(crate) fn load(path: &Utf8Path) -> Result<Self> {
+    filesystem::read_to_string(path)?
+      .parse()
+      .context(error::AuthorizationLoad { path })
+  }
+
+  pub(crate) fn quotas(&self) -> BTreeMap<PublicKey, Quota> {
+    self
+      .keys
+      .values()
+      .filter(|authorized| !authorized.quota.is_unlimited())
+      .map(|authorized| (authorized.key, authorized.quota))
+      .collect()
+  }
+
+  pub(crate) fn roles(&self) -> BTreeMap<PublicKey, BTreeSet<Role>> {
+    self
+      .keys
+      .values()
+      .map(|authorized| (authorized.key, authorized.roles.clone()))
+      .collect()
+  }
+}
+
+impl FromStr for Authorization {
+  type Err = AuthorizationError;
+
+  fn from_str(yaml: &str) -> Result<Self, Self::Err> {
+    let authorization =
+      serde_yaml::from_str::<Self>(yaml).context(authorization_error::Deserialize)?;
+
+    let mut names = BTreeMap::new();
+    for (second, authorized) in &authorization.keys {
+      if let Some(first) = names.insert(authorized.key, second) {
+        return Err(
+          authorization_error::KeyDuplicate {
+            first: first.clone(),
+            second: second.clone(),
+          }
+          .build(),
+        );
+      }
+    }
+
+    Ok(authorization)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn duplicate_key() {
+    let key = PrivateKey::generate().public_key();
+
+    assert_matches!(
+      unindent(&format!(
+        "
+          keys:
+            alice:
+              key: {key}
+              roles: [upload]
+            bob:
+              key: {key}
+              roles: [delete]
+        "
+      ))
+      .parse::<Authorization>(),
+      Err(AuthorizationError::KeyDuplicate { first, second })
+        if first.to_string() == "alice" && second.to_string() == "bob",
+    );
+  }
+
+  #[test]
+  fn quotas() {
+    let alice = PrivateKey::generate().public_key();
+    let bob = PrivateKey::generate().public_key();
+
+    let authorization = unindent(&format!(
+      "
+        keys:
+          alice:
+            key: {alice}
+            quota:
+              bytes: 1000
+              files: 10
+            roles: [upload]
+          bob:
+            key: {bob}
+            roles: [upload]
+      "
+    ))
+    .parse::<Authorization>()
+    .unwrap();
+
+    assert_eq!(
+      authorization.quotas(),
+      [(
+        alice,
+        Quota {
+          bytes: Some(1000),
+          files: Some(10),
+        },
+      )]
+      .into(),
+    );
+  }
+
+  #[test]
+  fn roles() {
+    let alice = PrivateKey::generate().public_key();
+    let bob = PrivateKey::generate().public_key();
+
+    let authorization = unindent(&format!(
+      "
+        keys:
+          alice:
+            key: {alice}
+            roles: [admin]
+          bob:
+            key: {bob}
+            roles: [upload, delete, gc]
+      "
+    ))
+    .parse::<Authorization>()
+    .unwrap();
+
+    assert_eq!(
+      authorization.roles(),
+      [
+        (alice, [Role::Admin].into()),
+        (bob, [Role::Delete, Role::Gc, Role::Upload].into()),
+      ]
+      .into(),
+    );
+  }
+
+  #[test]
+  fn unknown_role() {
+    assert_matches!(
+      unindent(&format!(
+        "
+          keys:
+            alice:
+              key: {}
+              roles: [root]
+        ",
+        PrivateKey::generate().public_key(),
+      ))
+      .parse::<Authorization>(),
+      Err(AuthorizationError::Deserialize { .. }),
+    );
+  }
+}
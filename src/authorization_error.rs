@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub enum AuthorizationError {
+  #[snafu(display("failed to deserialize authorization config"))]
+  Deserialize { source: serde_yaml::Error },
+  #[snafu(display("keys `{first}` and `{second}` have the same public key"))]
+  KeyDuplicate { first: KeyName, second: KeyName },
+}
@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct AuthorizedKey {
+  pub(crate) key: PublicKey,
+  #[serde(default)]
+  pub(crate) quota: Quota,
+  pub(crate) roles: BTreeSet<Role>,
+}
@@ -18,6 +18,10 @@ impl<'a> Bech32Decoder<'a> {
     Ok(array)
   }
 
+  pub(crate) fn byte_vec(&mut self, len: usize) -> Result<Vec<u8>, Bech32Error> {
+    Ok(self.bytes(len)?.collect())
+  }
+
   fn bytes(&mut self, len: usize) -> Result<impl Iterator<Item = u8>, Bech32Error> {
     let fe_len = (len * 8).div_ceil(5);
 
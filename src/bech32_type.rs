@@ -2,23 +2,29 @@ use super::*;
 
 #[derive(Clone, Copy, Debug, EnumIter, PartialEq)]
 pub enum Bech32Type {
+  EncryptedPrivateKey,
   Fingerprint,
   PrivateKey,
   PublicKey,
+  Revocation,
   Signature,
 }
 
 impl Bech32Type {
   pub(crate) fn hrp(self) -> &'static Hrp {
+    static ENCRYPTED_PRIVATE_KEY: Hrp = Hrp::parse_unchecked("encrypted");
     static FINGERPRINT: Hrp = Hrp::parse_unchecked("package");
     static PRIVATE_KEY: Hrp = Hrp::parse_unchecked("private");
     static PUBLIC_KEY: Hrp = Hrp::parse_unchecked("public");
+    static REVOCATION: Hrp = Hrp::parse_unchecked("revocation");
     static SIGNATURE: Hrp = Hrp::parse_unchecked("signature");
 
     match self {
+      Self::EncryptedPrivateKey => &ENCRYPTED_PRIVATE_KEY,
       Self::Fingerprint => &FINGERPRINT,
       Self::PrivateKey => &PRIVATE_KEY,
       Self::PublicKey => &PUBLIC_KEY,
+      Self::Revocation => &REVOCATION,
       Self::Signature => &SIGNATURE,
     }
   }
@@ -27,9 +33,11 @@ impl Bech32Type {
 impl Display for Bech32Type {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     match self {
+      Self::EncryptedPrivateKey => write!(f, "encrypted private key"),
       Self::Fingerprint => write!(f, "package fingerprint"),
       Self::PrivateKey => write!(f, "private key"),
       Self::PublicKey => write!(f, "public key"),
+      Self::Revocation => write!(f, "revocation"),
       Self::Signature => write!(f, "signature"),
     }
   }
@@ -0,0 +1,37 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub enum BundleError {
+  #[snafu(display("invalid bundle manifest"))]
+  Archive { source: ArchiveError },
+  #[snafu(display("bundle missing contents: {hashes}"))]
+  ContentMissing { hashes: Ticked<Hash> },
+  #[snafu(display("bundle content {hash} out of order"))]
+  ContentOrder { hash: Hash },
+  #[snafu(display("bundle content {hash} has size {actual} but expected {expected}"))]
+  ContentSize {
+    actual: u64,
+    expected: u64,
+    hash: Hash,
+  },
+  #[snafu(display("bundle contains unexpected content {hash}"))]
+  ContentUnexpected { hash: Hash },
+  #[snafu(display("failed to decode bundle"))]
+  Decode { source: DecodeError },
+  #[snafu(display("bundle file {hash} out of order"))]
+  FileOrder { hash: Hash },
+  #[snafu(display("I/O error reading bundle"))]
+  Io { source: io::Error },
+  #[snafu(display("expected bundle key {expected} but found {actual}"))]
+  Key { actual: u64, expected: u64 },
+  #[snafu(display("expected bundle to have {expected} keys but found {actual}"))]
+  Length { actual: u64, expected: u64 },
+  #[snafu(display("bundle has trailing bytes"))]
+  TrailingBytes,
+  #[snafu(display("expected bundle {expected} but found {actual}"))]
+  UnexpectedType {
+    actual: MajorType,
+    expected: MajorType,
+  },
+}
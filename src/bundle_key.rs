@@ -0,0 +1,18 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u64)]
+pub(crate) enum BundleKey {
+  Version = 0,
+  Root = 1,
+  Files = 2,
+  Contents = 3,
+}
+
+impl BundleKey {
+  pub(crate) const COUNT: u64 = 4;
+
+  pub(crate) fn value(self) -> u64 {
+    self as u64
+  }
+}
@@ -0,0 +1,357 @@
+use super::*;
+
+type Content<'a, R> = (Hash, VerifyingReader<io::Take<&'a mut R>>);
+
+pub(crate) struct BundleReader<R> {
+  archive: Archive,
+  contents: BTreeMap<Hash, u64>,
+  last: Option<Hash>,
+  manifest: Manifest,
+  reader: R,
+  remaining: u64,
+}
+
+impl<R: Read> BundleReader<R> {
+  pub(crate) fn archive(&self) -> &Archive {
+    &self.archive
+  }
+
+  fn expect(reader: &mut R, expected: MajorType) -> Result<u64, BundleError> {
+    let Head { major_type, value } = Decoder::new(&Self::head(reader)?)
+      .head()
+      .context(bundle_error::Decode)?;
+
+    ensure! {
+      major_type == expected,
+      bundle_error::UnexpectedType {
+        actual: major_type,
+        expected,
+      },
+    }
+
+    Ok(value)
+  }
+
+  pub(crate) fn finish(mut self) -> Result<(), BundleError> {
+    ensure! {
+      self.contents.is_empty(),
+      bundle_error::ContentMissing {
+        hashes: self.contents.into_keys().collect::<BTreeSet<Hash>>(),
+      },
+    }
+
+    let mut buffer = [0];
+
+    ensure! {
+      self.reader.read(&mut buffer).context(bundle_error::Io)? == 0,
+      bundle_error::TrailingBytes,
+    }
+
+    Ok(())
+  }
+
+  fn head(reader: &mut R) -> Result<Vec<u8>, BundleError> {
+    let mut head = vec![0];
+
+    reader.read_exact(&mut head).context(bundle_error::Io)?;
+
+    let len = match head[0] & 0b11111 {
+      24 => 1,
+      25 => 2,
+      26 => 4,
+      27 => 8,
+      _ => 0,
+    };
+
+    head.resize(1 + len, 0);
+
+    reader
+      .read_exact(&mut head[1..])
+      .context(bundle_error::Io)?;
+
+    Ok(head)
+  }
+
+  fn item<T: Decode>(reader: &mut R) -> Result<T, BundleError> {
+    let mut item = Self::head(reader)?;
+
+    let Head { major_type, value } = Decoder::new(&item).head().context(bundle_error::Decode)?;
+
+    if let MajorType::Bytes | MajorType::Text = major_type {
+      let n = reader
+        .take(value)
+        .read_to_end(&mut item)
+        .context(bundle_error::Io)?;
+
+      if n.into_u64() != value {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof)).context(bundle_error::Io);
+      }
+    }
+
+    T::decode_from_slice(&item).context(bundle_error::Decode)
+  }
+
+  fn key(reader: &mut R, expected: BundleKey) -> Result<(), BundleError> {
+    let actual = Self::item::<u64>(reader)?;
+
+    ensure! {
+      actual == expected.value(),
+      bundle_error::Key {
+        actual,
+        expected: expected.value(),
+      },
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn manifest(&self) -> &Manifest {
+    &self.manifest
+  }
+
+  pub(crate) fn new(mut reader: R) -> Result<Self, BundleError> {
+    let length = Self::expect(&mut reader, MajorType::Map)?;
+
+    ensure! {
+      length == BundleKey::COUNT,
+      bundle_error::Length {
+        actual: length,
+        expected: BundleKey::COUNT,
+      },
+    }
+
+    Self::key(&mut reader, BundleKey::Version)?;
+    let version = Self::item(&mut reader)?;
+
+    Self::key(&mut reader, BundleKey::Root)?;
+    let root = Self::item(&mut reader)?;
+
+    Self::key(&mut reader, BundleKey::Files)?;
+    let mut files = BTreeMap::new();
+    for _ in 0..Self::expect(&mut reader, MajorType::Map)? {
+      let hash = Self::item::<Hash>(&mut reader)?;
+
+      ensure! {
+        files.last_key_value().is_none_or(|(last, _)| *last < hash),
+        bundle_error::FileOrder { hash },
+      }
+
+      files.insert(hash, Self::item::<Vec<u8>>(&mut reader)?);
+    }
+
+    Self::key(&mut reader, BundleKey::Contents)?;
+    let remaining = Self::expect(&mut reader, MajorType::Map)?;
+
+    let archive = Archive {
+      version,
+      root,
+      files,
+    };
+
+    let manifest = archive.unpack().context(bundle_error::Archive)?;
+
+    let contents = manifest
+      .contents()
+      .into_iter()
+      .map(|(hash, (_path, file))| (hash, file.size))
+      .collect();
+
+    Ok(Self {
+      archive,
+      contents,
+      last: None,
+      manifest,
+      reader,
+      remaining,
+    })
+  }
+
+  pub(crate) fn next(&mut self) -> Result<Option<Content<R>>, BundleError> {
+    if self.remaining == 0 {
+      return Ok(None);
+    }
+
+    let hash = Self::item::<Hash>(&mut self.reader)?;
+
+    ensure! {
+      self.last.is_none_or(|last| last < hash),
+      bundle_error::ContentOrder { hash },
+    }
+
+    let expected = self
+      .contents
+      .remove(&hash)
+      .context(bundle_error::ContentUnexpected { hash })?;
+
+    let actual = Self::expect(&mut self.reader, MajorType::Bytes)?;
+
+    ensure! {
+      actual == expected,
+      bundle_error::ContentSize {
+        actual,
+        expected,
+        hash,
+      },
+    }
+
+    self.last = Some(hash);
+    self.remaining -= 1;
+
+    Ok(Some((
+      hash,
+      VerifyingReader::new(
+        (&mut self.reader).take(actual),
+        File {
+          executable: false,
+          hash,
+          size: actual,
+        },
+      ),
+    )))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn bundle(manifest: &Manifest, contents: &[&[u8]]) -> Vec<u8> {
+    let mut writer = BundleWriter::new(Vec::new(), manifest).unwrap();
+
+    let mut contents = contents.to_vec();
+    contents.sort_by_key(|content| Hash::bytes(content));
+
+    for content in contents {
+      writer.content(File::new(content), content).unwrap();
+    }
+
+    writer.finish().unwrap()
+  }
+
+  #[test]
+  fn content_hash_mismatch() {
+    let manifest = manifest(&[("foo", b"foo")]);
+
+    let mut bundle = bundle(&manifest, &[b"foo"]);
+
+    *bundle.last_mut().unwrap() = b'x';
+
+    assert_matches!(
+      read(&bundle),
+      Err(BundleError::Io { source }) if source.kind() == io::ErrorKind::InvalidData,
+    );
+  }
+
+  #[test]
+  fn content_missing() {
+    let manifest = manifest(&[("foo", b"foo")]);
+
+    let mut bundle = Vec::new();
+    drop(BundleWriter::new(&mut bundle, &manifest).unwrap());
+
+    *bundle.last_mut().unwrap() = 0xa0;
+
+    assert_matches!(
+      read(&bundle),
+      Err(BundleError::ContentMissing { hashes }) if hashes.0 == [Hash::bytes(b"foo")].into(),
+    );
+  }
+
+  #[test]
+  fn duplicate_contents_are_stored_once() {
+    let manifest = manifest(&[("bar", b"foo"), ("foo", b"foo")]);
+
+    assert_eq!(read(&bundle(&manifest, &[b"foo"])).unwrap(), [b"foo"]);
+  }
+
+  #[test]
+  fn key_mismatch() {
+    let mut bundle = bundle(&manifest(&[]), &[]);
+
+    bundle[1] = 0x01;
+
+    assert_matches!(
+      read(&bundle),
+      Err(BundleError::Key {
+        actual: 1,
+        expected: 0,
+      }),
+    );
+  }
+
+  fn manifest(files: &[(&str, &[u8])]) -> Manifest {
+    let mut package = DirectoryTree::new();
+
+    for (path, content) in files {
+      package
+        .create_file(&path.parse().unwrap(), File::new(content))
+        .unwrap();
+    }
+
+    Manifest {
+      embedded: BTreeMap::new(),
+      package,
+      signatures: BTreeSet::new(),
+    }
+  }
+
+  #[test]
+  fn manifest_round_trip() {
+    let manifest = manifest(&[("bar", b"bar"), ("baz/qux", b"qux"), ("foo", b"")]);
+
+    let bundle = bundle(&manifest, &[b"bar", b"qux", b""]);
+
+    let reader = BundleReader::new(bundle.as_slice()).unwrap();
+
+    assert_eq!(*reader.manifest(), manifest);
+
+    assert_eq!(
+      reader.archive().fingerprint().unwrap(),
+      manifest.fingerprint()
+    );
+
+    let mut contents = read(&bundle).unwrap();
+    contents.sort();
+
+    assert_eq!(contents, [b"".to_vec(), b"bar".to_vec(), b"qux".to_vec()]);
+  }
+
+  fn read(bundle: &[u8]) -> Result<Vec<Vec<u8>>, BundleError> {
+    let mut reader = BundleReader::new(bundle)?;
+
+    let mut contents = Vec::new();
+
+    while let Some((_hash, mut content)) = reader.next()? {
+      let mut buffer = Vec::new();
+      content.read_to_end(&mut buffer).context(bundle_error::Io)?;
+      contents.push(buffer);
+    }
+
+    reader.finish()?;
+
+    Ok(contents)
+  }
+
+  #[test]
+  fn trailing_bytes() {
+    let mut bundle = bundle(&manifest(&[]), &[]);
+
+    bundle.push(0);
+
+    assert_matches!(read(&bundle), Err(BundleError::TrailingBytes));
+  }
+
+  #[test]
+  fn truncated() {
+    let manifest = manifest(&[("foo", b"foo")]);
+
+    let mut bundle = bundle(&manifest, &[b"foo"]);
+
+    bundle.pop();
+
+    assert_matches!(
+      read(&bundle),
+      Err(BundleError::Io { source }) if source.kind() == io::ErrorKind::InvalidData,
+    );
+  }
+}
@@ -0,0 +1,61 @@
+use super::*;
+
+pub(crate) struct BundleWriter<W> {
+  contents: BTreeMap<Hash, u64>,
+  writer: W,
+}
+
+impl<W: Write> BundleWriter<W> {
+  pub(crate) fn content(&mut self, file: File, reader: impl Read) -> io::Result<()> {
+    assert_eq!(
+      self.contents.pop_first(),
+      Some((file.hash, file.size)),
+      "unexpected bundle content",
+    );
+
+    let mut encoder = Encoder::new();
+    file.hash.encode(&mut encoder);
+    encoder.head(MajorType::Bytes.head(file.size));
+    self.writer.write_all(&encoder.finish())?;
+
+    io::copy(&mut VerifyingReader::new(reader, file), &mut self.writer)?;
+
+    Ok(())
+  }
+
+  pub(crate) fn finish(mut self) -> io::Result<W> {
+    assert!(self.contents.is_empty(), "missing bundle content");
+    self.writer.flush()?;
+    Ok(self.writer)
+  }
+
+  pub(crate) fn new(mut writer: W, manifest: &Manifest) -> io::Result<Self> {
+    let archive = Archive::pack(manifest).unwrap();
+
+    let contents = manifest
+      .contents()
+      .into_iter()
+      .map(|(hash, (_path, file))| (hash, file.size))
+      .collect::<BTreeMap<Hash, u64>>();
+
+    let mut encoder = Encoder::new();
+
+    encoder.head(MajorType::Map.head(BundleKey::COUNT));
+
+    BundleKey::Version.value().encode(&mut encoder);
+    archive.version.encode(&mut encoder);
+
+    BundleKey::Root.value().encode(&mut encoder);
+    archive.root.encode(&mut encoder);
+
+    BundleKey::Files.value().encode(&mut encoder);
+    archive.files.encode(&mut encoder);
+
+    BundleKey::Contents.value().encode(&mut encoder);
+    encoder.head(MajorType::Map.head(contents.len().into_u64()));
+
+    writer.write_all(&encoder.finish())?;
+
+    Ok(Self { contents, writer })
+  }
+}
@@ -1,6 +1,6 @@
 use {
   super::*,
-  axum::extract::{FromRequest, Request},
+  axum::extract::{FromRequest, OptionalFromRequest, Request},
 };
 
 pub(crate) struct Cbor<T, const LIMIT: usize>(pub(crate) T);
@@ -19,6 +19,24 @@ impl<T: Decode, S: Send + Sync, const LIMIT: usize> FromRequest<S> for Cbor<T, L
   }
 }
 
+impl<T: Decode, S: Send + Sync, const LIMIT: usize> OptionalFromRequest<S> for Cbor<T, LIMIT> {
+  type Rejection = ServerError;
+
+  async fn from_request(request: Request, _state: &S) -> ServerResult<Option<Self>> {
+    let bytes = axum::body::to_bytes(request.into_body(), LIMIT)
+      .await
+      .context(server_error::CborBody)?;
+
+    if bytes.is_empty() {
+      return Ok(None);
+    }
+
+    Ok(Some(Self(
+      T::decode_from_slice(&bytes).context(server_error::CborDecode)?,
+    )))
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use {super::*, tokio::runtime::Runtime};
@@ -27,13 +45,28 @@ mod tests {
   fn body_exceeding_limit_is_rejected() {
     let request = Request::builder().body(Body::from(vec![0; 5])).unwrap();
 
-    let result = Runtime::new()
-      .unwrap()
-      .block_on(Cbor::<Vec<u8>, 4>::from_request(request, &()));
+    let result =
+      Runtime::new()
+        .unwrap()
+        .block_on(<Cbor<Vec<u8>, 4> as FromRequest<()>>::from_request(
+          request,
+          &(),
+        ));
 
     assert_matches!(
       result.map(|Cbor(value)| value),
       Err(ServerError::CborBody { .. }),
     );
   }
+
+  #[test]
+  fn empty_optional_body_is_none() {
+    let request = Request::builder().body(Body::empty()).unwrap();
+
+    let result = Runtime::new().unwrap().block_on(
+      <Cbor<Vec<u8>, 4> as OptionalFromRequest<()>>::from_request(request, &()),
+    );
+
+    assert_matches!(result.map(|cbor| cbor.map(|Cbor(value)| value)), Ok(None));
+  }
 }
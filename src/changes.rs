@@ -0,0 +1,401 @@
+use super::*;
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub(crate) struct Changes {
+  pub(crate) added: BTreeMap<RelativePath, File>,
+  pub(crate) added_directories: BTreeSet<RelativePath>,
+  pub(crate) added_symlinks: BTreeMap<RelativePath, Symlink>,
+  pub(crate) modified: BTreeMap<RelativePath, Modification<File>>,
+  pub(crate) modified_symlinks: BTreeMap<RelativePath, Modification<Symlink>>,
+  pub(crate) moved: BTreeMap<RelativePath, Move>,
+  pub(crate) removed: BTreeMap<RelativePath, File>,
+  pub(crate) removed_directories: BTreeSet<RelativePath>,
+  pub(crate) removed_symlinks: BTreeMap<RelativePath, Symlink>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Modification<T> {
+  pub(crate) new: T,
+  pub(crate) old: T,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Move {
+  pub(crate) file: File,
+  pub(crate) from: RelativePath,
+}
+
+impl Changes {
+  fn compare(
+    &mut self,
+    old: (&Archive, Option<&Directory>),
+    new: (&Archive, Option<&Directory>),
+    prefix: Option<&RelativePath>,
+  ) -> Result<(), ArchiveError> {
+    if let Some(prefix) = prefix {
+      let old_empty = old.1.is_some_and(|directory| directory.entries.is_empty());
+      let new_empty = new.1.is_some_and(|directory| directory.entries.is_empty());
+
+      if old_empty && !new_empty {
+        self.removed_directories.insert(prefix.clone());
+      }
+
+      if new_empty && !old_empty {
+        self.added_directories.insert(prefix.clone());
+      }
+    }
+
+    let names = old
+      .1
+      .into_iter()
+      .chain(new.1)
+      .flat_map(|directory| directory.entries.keys())
+      .collect::<BTreeSet<&ComponentBuf>>();
+
+    for name in names {
+      let old_entry = old.1.and_then(|directory| directory.entries.get(name));
+      let new_entry = new.1.and_then(|directory| directory.entries.get(name));
+
+      if old_entry == new_entry {
+        continue;
+      }
+
+      let path = RelativePath::join_opt(prefix, name);
+
+      match (
+        old_entry.and_then(Entry::to_file),
+        new_entry.and_then(Entry::to_file),
+      ) {
+        (Some(old), Some(new)) => {
+          self.modified.insert(path, Modification { new, old });
+          continue;
+        }
+        (Some(old), _) => {
+          self.removed.insert(path.clone(), old);
+        }
+        (_, Some(new)) => {
+          self.added.insert(path.clone(), new);
+        }
+        (None, None) => {}
+      }
+
+      match (
+        old_entry.and_then(Entry::to_symlink),
+        new_entry.and_then(Entry::to_symlink),
+      ) {
+        (Some(old), Some(new)) => {
+          self
+            .modified_symlinks
+            .insert(path, Modification { new, old });
+          continue;
+        }
+        (Some(old), _) => {
+          self.removed_symlinks.insert(path.clone(), old);
+        }
+        (_, Some(new)) => {
+          self.added_symlinks.insert(path.clone(), new);
+        }
+        (None, None) => {}
+      }
+
+      let old_directory = match old_entry {
+        Some(Entry::Directory { hash, .. }) => Some(old.0.directory(*hash)?),
+        _ => None,
+      };
+
+      let new_directory = match new_entry {
+        Some(Entry::Directory { hash, .. }) => Some(new.0.directory(*hash)?),
+        _ => None,
+      };
+
+      if old_directory.is_some() || new_directory.is_some() {
+        self.compare(
+          (old.0, old_directory.as_ref()),
+          (new.0, new_directory.as_ref()),
+          Some(&path),
+        )?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn detect_moves(&mut self) {
+    let mut sources = BTreeMap::<Hash, Vec<RelativePath>>::new();
+
+    for (path, file) in self.removed.iter().rev() {
+      sources.entry(file.hash).or_default().push(path.clone());
+    }
+
+    let added = self.added.keys().cloned().collect::<Vec<RelativePath>>();
+
+    for path in added {
+      let file = self.added[&path];
+
+      let Some(from) = sources.get_mut(&file.hash).and_then(Vec::pop) else {
+        continue;
+      };
+
+      self.added.remove(&path);
+      self.removed.remove(&from);
+      self.moved.insert(path, Move { file, from });
+    }
+  }
+
+  pub(crate) fn new(old: &Archive, new: &Archive) -> Result<Self, ArchiveError> {
+    let mut changes = Self::default();
+
+    let old_package = old.fingerprint()?;
+    let new_package = new.fingerprint()?;
+
+    if old_package != new_package {
+      changes.compare(
+        (old, Some(&old.directory(old_package.into())?)),
+        (new, Some(&new.directory(new_package.into())?)),
+        None,
+      )?;
+    }
+
+    changes.detect_moves();
+
+    Ok(changes)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn added_and_removed() {
+    let changes = Changes::new(
+      &archive(&[("foo", b"foo")], &[]),
+      &archive(&[("bar", b"bar")], &[]),
+    )
+    .unwrap();
+
+    assert_eq!(
+      changes,
+      Changes {
+        added: BTreeMap::from([("bar".parse().unwrap(), File::new(b"bar"))]),
+        removed: BTreeMap::from([("foo".parse().unwrap(), File::new(b"foo"))]),
+        ..default()
+      },
+    );
+  }
+
+  fn archive(files: &[(&str, &[u8])], directories: &[&str]) -> Archive {
+    let mut package = DirectoryTree::new();
+
+    for (path, content) in files {
+      package
+        .create_file(&path.parse().unwrap(), File::new(content))
+        .unwrap();
+    }
+
+    for path in directories {
+      package.create_directory(&path.parse().unwrap()).unwrap();
+    }
+
+    Archive::pack(&Manifest {
+      embedded: BTreeMap::new(),
+      package,
+      signatures: BTreeSet::new(),
+    })
+    .unwrap()
+  }
+
+  #[test]
+  fn empty_directories() {
+    let changes = Changes::new(
+      &archive(&[("foo/bar", b"bar")], &["baz"]),
+      &archive(&[], &["foo", "qux"]),
+    )
+    .unwrap();
+
+    assert_eq!(
+      changes,
+      Changes {
+        added_directories: BTreeSet::from(["foo".parse().unwrap(), "qux".parse().unwrap()]),
+        removed: BTreeMap::from([("foo/bar".parse().unwrap(), File::new(b"bar"))]),
+        removed_directories: BTreeSet::from(["baz".parse().unwrap()]),
+        ..default()
+      },
+    );
+  }
+
+  #[test]
+  fn file_replaced_with_directory() {
+    let changes = Changes::new(
+      &archive(&[("foo", b"foo")], &[]),
+      &archive(&[("foo/bar", b"bar")], &[]),
+    )
+    .unwrap();
+
+    assert_eq!(
+      changes,
+      Changes {
+        added: BTreeMap::from([("foo/bar".parse().unwrap(), File::new(b"bar"))]),
+        removed: BTreeMap::from([("foo".parse().unwrap(), File::new(b"foo"))]),
+        ..default()
+      },
+    );
+  }
+
+  #[test]
+  fn identical() {
+    let archive = archive(&[("foo/bar", b"bar"), ("baz", b"baz")], &["qux"]);
+    assert_eq!(
+      Changes::new(&archive, &archive).unwrap(),
+      Changes::default()
+    );
+  }
+
+  #[test]
+  fn identical_subtrees_are_not_decoded() {
+    let old = archive(&[("foo/bar", b"bar"), ("baz", b"old")], &[]);
+    let mut new = archive(&[("foo/bar", b"bar"), ("baz", b"new")], &[]);
+
+    let mut subdirectory = Directory::new();
+    subdirectory.insert_file("bar", b"bar");
+    let (_cbor, hash) = subdirectory.cbor();
+    new.files.remove(&hash).unwrap();
+
+    assert_eq!(
+      Changes::new(&old, &new).unwrap(),
+      Changes {
+        modified: BTreeMap::from([(
+          "baz".parse().unwrap(),
+          Modification {
+            new: File::new(b"new"),
+            old: File::new(b"old"),
+          },
+        )]),
+        ..default()
+      },
+    );
+  }
+
+  #[test]
+  fn modified() {
+    let changes = Changes::new(
+      &archive(&[("foo", b"old")], &[]),
+      &archive(&[("foo", b"new")], &[]),
+    )
+    .unwrap();
+
+    assert_eq!(
+      changes,
+      Changes {
+        modified: BTreeMap::from([(
+          "foo".parse().unwrap(),
+          Modification {
+            new: File::new(b"new"),
+            old: File::new(b"old"),
+          },
+        )]),
+        ..default()
+      },
+    );
+  }
+
+  #[test]
+  fn moved() {
+    let changes = Changes::new(
+      &archive(&[("a/foo", b"foo"), ("b", b"bar"), ("c", b"bar")], &[]),
+      &archive(&[("d/foo", b"foo"), ("e", b"bar")], &[]),
+    )
+    .unwrap();
+
+    assert_eq!(
+      changes,
+      Changes {
+        moved: BTreeMap::from([
+          (
+            "d/foo".parse().unwrap(),
+            Move {
+              file: File::new(b"foo"),
+              from: "a/foo".parse().unwrap(),
+            },
+          ),
+          (
+            "e".parse().unwrap(),
+            Move {
+              file: File::new(b"bar"),
+              from: "b".parse().unwrap(),
+            },
+          ),
+        ]),
+        removed: BTreeMap::from([("c".parse().unwrap(), File::new(b"bar"))]),
+        ..default()
+      },
+    );
+  }
+
+  #[test]
+  fn symlinks() {
+    fn archive(files: &[&str], symlinks: &[(&str, &str)]) -> Archive {
+      let mut package = DirectoryTree::new();
+
+      for path in files {
+        package
+          .create_file(&path.parse().unwrap(), File::new(path.as_bytes()))
+          .unwrap();
+      }
+
+      for (path, target) in symlinks {
+        package
+          .create_symlink(
+            &path.parse().unwrap(),
+            Symlink {
+              target: target.parse().unwrap(),
+            },
+          )
+          .unwrap();
+      }
+
+      Archive::pack(&Manifest {
+        embedded: BTreeMap::new(),
+        package,
+        signatures: BTreeSet::new(),
+      })
+      .unwrap()
+    }
+
+    fn symlink(target: &str) -> Symlink {
+      Symlink {
+        target: target.parse().unwrap(),
+      }
+    }
+
+    let changes = Changes::new(
+      &archive(&["a", "b"], &[("c", "a"), ("d", "a"), ("e", "a")]),
+      &archive(&["a", "c"], &[("b", "a"), ("d", "c"), ("f", "a")]),
+    )
+    .unwrap();
+
+    assert_eq!(
+      changes,
+      Changes {
+        added: BTreeMap::from([("c".parse().unwrap(), File::new(b"c"))]),
+        added_symlinks: BTreeMap::from([
+          ("b".parse().unwrap(), symlink("a")),
+          ("f".parse().unwrap(), symlink("a")),
+        ]),
+        modified_symlinks: BTreeMap::from([(
+          "d".parse().unwrap(),
+          Modification {
+            new: symlink("c"),
+            old: symlink("a"),
+          },
+        )]),
+        removed: BTreeMap::from([("b".parse().unwrap(), File::new(b"b"))]),
+        removed_symlinks: BTreeMap::from([
+          ("c".parse().unwrap(), symlink("a")),
+          ("e".parse().unwrap(), symlink("a")),
+        ]),
+        ..default()
+      },
+    );
+  }
+}
@@ -0,0 +1,26 @@
+use {super::*, tokio::sync::mpsc::Sender};
+
+pub(crate) struct ChannelWriter {
+  sender: Sender<io::Result<Vec<u8>>>,
+}
+
+impl ChannelWriter {
+  pub(crate) fn new(sender: Sender<io::Result<Vec<u8>>>) -> Self {
+    Self { sender }
+  }
+}
+
+impl Write for ChannelWriter {
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self
+      .sender
+      .blocking_send(Ok(buf.to_vec()))
+      .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+
+    Ok(buf.len())
+  }
+}
@@ -7,6 +7,22 @@ pub(crate) struct Client {
 }
 
 impl Client {
+  pub(crate) fn add_signatures(
+    &self,
+    fingerprint: Fingerprint,
+    signatures: &BTreeSet<Signature>,
+  ) -> Result {
+    self.post_with_body(
+      &format!("api/package/{fingerprint}/signatures"),
+      SignatureFile {
+        signatures: signatures.clone(),
+      }
+      .to_string(),
+    )?;
+
+    Ok(())
+  }
+
   fn delete(&self, path: &str) -> Result<reqwest::blocking::Response> {
     self
       .request(self.client.delete(self.url(path)))?
@@ -23,6 +39,27 @@ impl Client {
     self.get(&format!("file/{hash}"))
   }
 
+  pub(crate) fn file_opt(&self, hash: Hash) -> Result<Option<reqwest::blocking::Response>> {
+    let response = self.request(self.client.get(self.file_url(hash)))?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    Ok(Some(response.check_status()?))
+  }
+
+  pub(crate) fn file_range(&self, hash: Hash, start: u64) -> Result<reqwest::blocking::Response> {
+    self
+      .request(
+        self
+          .client
+          .get(self.file_url(hash))
+          .header(reqwest::header::RANGE, format!("bytes={start}-")),
+      )?
+      .check_status()
+  }
+
   pub(crate) fn file_url(&self, hash: Hash) -> Url {
     self.url(&format!("file/{hash}"))
   }
@@ -61,6 +98,41 @@ impl Client {
     )
   }
 
+  pub(crate) fn name(
+    &self,
+    public_key: PublicKey,
+    name: &PackageName,
+  ) -> Result<Option<NameRecord>> {
+    let response = self.request(
+      self
+        .client
+        .get(self.url(&format!("api/name/{public_key}/{name}"))),
+    )?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    let record = response.check_status()?.cbor::<NameRecord>()?;
+
+    ensure! {
+      record.public_key() == public_key && record.name() == name,
+      error::NameRecordMismatch {
+        expected_name: name.clone(),
+        expected_public_key: public_key,
+        name: record.name().clone(),
+        public_key: record.public_key(),
+      },
+    }
+
+    record.verify().context(error::NameRecordInvalid {
+      name: name.clone(),
+      public_key,
+    })?;
+
+    Ok(Some(record))
+  }
+
   pub(crate) fn new(options: &Options, server: Url, auth: Option<&KeyName>) -> Result<Self> {
     install_default_crypto_provider()?;
 
@@ -102,6 +174,20 @@ impl Client {
     })
   }
 
+  pub(crate) fn outboard_opt(&self, hash: Hash) -> Result<Option<Outboard>> {
+    let response = self.request(self.client.get(self.url(&format!("api/outboard/{hash}"))))?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    let response = response.check_status()?.cbor::<api::outboard::Response>()?;
+
+    Outboard::new(hash, response.size, &response.tree)
+      .context(error::Outboard { hash })
+      .map(Some)
+  }
+
   pub(crate) fn packages(&self) -> Result<SortedSet<Fingerprint>> {
     Ok(
       self
@@ -127,6 +213,18 @@ impl Client {
       .check_status()
   }
 
+  pub(crate) fn publish_name(&self, record: &NameRecord) -> Result {
+    self.post_with_body("api/name", record.encode_to_vec())?;
+
+    Ok(())
+  }
+
+  pub(crate) fn publish_revocation(&self, revocation: &Revocation) -> Result {
+    self.post_with_body("api/revocation", revocation.encode_to_vec())?;
+
+    Ok(())
+  }
+
   fn put(
     &self,
     path: &str,
@@ -137,6 +235,12 @@ impl Client {
       .check_status()
   }
 
+  pub(crate) fn put_bundle(&self, body: reqwest::blocking::Body) -> Result {
+    self.put("api/bundle", body)?;
+
+    Ok(())
+  }
+
   pub(crate) fn put_file(&self, hash: Hash, body: reqwest::blocking::Body) -> Result {
     self.put(&format!("file/{hash}"), body)?;
 
@@ -152,6 +256,46 @@ impl Client {
     builder.send().context(error::Request)
   }
 
+  pub(crate) fn resolve_name(
+    &self,
+    options: &Options,
+    public_key: PublicKey,
+    name: &PackageName,
+  ) -> Result<Fingerprint> {
+    let record = self.name(public_key, name)?.context(error::NameNotFound {
+      name: name.clone(),
+      public_key,
+    })?;
+
+    let mut names = NameStore::load(options)?;
+
+    if names.update(&record)? {
+      names.save()?;
+    }
+
+    Ok(record.fingerprint())
+  }
+
+  pub(crate) fn revocations(&self) -> Result<SortedSet<Revocation>> {
+    Ok(
+      self
+        .get("api/revocations")?
+        .cbor::<api::revocations::Response>()?
+        .revocations,
+    )
+  }
+
+  pub(crate) fn signatures(&self, fingerprint: Fingerprint) -> Result<BTreeSet<Signature>> {
+    Ok(
+      self
+        .get(&format!("api/package/{fingerprint}/signatures"))?
+        .cbor::<api::signatures::Response>()?
+        .signatures
+        .into_iter()
+        .collect(),
+    )
+  }
+
   fn url(&self, path: &str) -> Url {
     self.server.join(path).unwrap()
   }
@@ -162,8 +306,17 @@ impl Client {
     Ok(())
   }
 
-  pub(crate) fn verify_package(&self, fingerprint: Fingerprint) -> Result {
-    self.post(&format!("api/package/{fingerprint}"))?;
+  pub(crate) fn verify_package(
+    &self,
+    fingerprint: Fingerprint,
+    signatures: &BTreeSet<Signature>,
+  ) -> Result {
+    let body = api::package::Request {
+      signatures: signatures.clone().into(),
+    }
+    .encode_to_vec();
+
+    self.post_with_body(&format!("api/package/{fingerprint}"), body)?;
 
     Ok(())
   }
@@ -0,0 +1,70 @@
+use super::*;
+
+#[derive(
+  Clone, Debug, DecodeFromStr, EncodeDisplay, Eq, Ord, PartialEq, PartialOrd, SerializeDisplay,
+)]
+pub struct Comment(String);
+
+impl Comment {
+  pub(crate) const MAX_LEN: usize = 128;
+
+  pub(crate) fn as_str(&self) -> &str {
+    &self.0
+  }
+}
+
+impl Display for Comment {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl FromStr for Comment {
+  type Err = CommentError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ensure! {
+      s.len() <= Self::MAX_LEN,
+      comment_error::Length { len: s.len() },
+    }
+
+    ensure! {
+      !s.chars().any(char::is_control),
+      comment_error::Control,
+    }
+
+    Ok(Self(s.into()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn control_characters_are_rejected() {
+    assert_eq!(
+      "foo\nbar".parse::<Comment>().unwrap_err().to_string(),
+      "comment may not contain control characters",
+    );
+  }
+
+  #[test]
+  fn encoding() {
+    assert_encoding("foo".parse::<Comment>().unwrap());
+  }
+
+  #[test]
+  fn length() {
+    "a".repeat(Comment::MAX_LEN).parse::<Comment>().unwrap();
+
+    assert_eq!(
+      "a"
+        .repeat(Comment::MAX_LEN + 1)
+        .parse::<Comment>()
+        .unwrap_err()
+        .to_string(),
+      "comment length 129 exceeds maximum of 128 bytes",
+    );
+  }
+}
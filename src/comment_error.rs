@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub enum CommentError {
+  #[snafu(display("comment may not contain control characters"))]
+  Control,
+  #[snafu(display("comment length {len} exceeds maximum of {} bytes", Comment::MAX_LEN))]
+  Length { len: usize },
+}
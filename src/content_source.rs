@@ -0,0 +1,42 @@
+use super::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ContentSource {
+  Directory(Utf8PathBuf),
+  Server(Url),
+}
+
+impl FromStr for ContentSource {
+  type Err = UrlError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if s.contains("://") {
+      Ok(Self::Server(CheckedUrl::check(s)?))
+    } else {
+      Ok(Self::Directory(s.into()))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    assert_eq!(
+      "foo/bar".parse::<ContentSource>().unwrap(),
+      ContentSource::Directory("foo/bar".into()),
+    );
+
+    assert_eq!(
+      "https://example.com".parse::<ContentSource>().unwrap(),
+      ContentSource::Server("https://example.com".parse().unwrap()),
+    );
+
+    assert_matches!(
+      "ftp://example.com".parse::<ContentSource>(),
+      Err(UrlError::Scheme { .. }),
+    );
+  }
+}
@@ -1,7 +1,12 @@
 use super::*;
 
+#[allow(clippy::arbitrary_source_item_ordering)]
 #[derive(Encode)]
 pub(crate) enum Context {
   #[n(0)]
   Statement,
+  #[n(1)]
+  Revocation,
+  #[n(2)]
+  Name,
 }
@@ -6,6 +6,11 @@ pub(crate) enum DatabaseMetadata {
   Schema = 0,
 }
 
+impl DatabaseMetadata {
+  pub(crate) const TABLE: redb::TableDefinition<'static, Self, u64> =
+    redb::TableDefinition::new("metadata");
+}
+
 impl redb::Key for DatabaseMetadata {
   fn compare(a: &[u8], b: &[u8]) -> Ordering {
     u64::compare(a, b)
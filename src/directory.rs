@@ -33,6 +33,7 @@ impl Directory {
             files: 0,
           })
           .context(totals_error::Overflow)?,
+        Entry::Symlink { .. } => Totals::default(),
       };
 
       totals = totals
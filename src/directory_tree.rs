@@ -25,7 +25,7 @@ impl DirectoryTree {
 
     match entry {
       DirectoryTreeEntry::Directory(directory) => Ok(directory),
-      DirectoryTreeEntry::File(_file) => Err(
+      DirectoryTreeEntry::File(_) | DirectoryTreeEntry::Symlink(_) => Err(
         error::Internal {
           message: "entry `{component}` already contains file",
         }
@@ -34,14 +34,14 @@ impl DirectoryTree {
     }
   }
 
-  pub(crate) fn create_file(&mut self, path: &RelativePath, file: File) -> Result {
+  fn create_entry(&mut self, path: &RelativePath, entry: DirectoryTreeEntry) -> Result {
     let mut components = path.components().peekable();
 
     let mut current = self;
     while let Some(component) = components.next() {
       if components.peek().is_none() {
         ensure! {
-          current.entries.insert(component.to_owned(), DirectoryTreeEntry::File(file)).is_none(),
+          current.entries.insert(component.to_owned(), entry).is_none(),
           error::Internal {
             message: "entry `{component}` already contains file",
           }
@@ -55,6 +55,14 @@ impl DirectoryTree {
     Ok(())
   }
 
+  pub(crate) fn create_file(&mut self, path: &RelativePath, file: File) -> Result {
+    self.create_entry(path, DirectoryTreeEntry::File(file))
+  }
+
+  pub(crate) fn create_symlink(&mut self, path: &RelativePath, symlink: Symlink) -> Result {
+    self.create_entry(path, DirectoryTreeEntry::Symlink(symlink))
+  }
+
   pub fn new() -> Self {
     Self::default()
   }
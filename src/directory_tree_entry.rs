@@ -5,4 +5,5 @@ use super::*;
 pub(crate) enum DirectoryTreeEntry {
   Directory(DirectoryTree),
   File(File),
+  Symlink(Symlink),
 }
@@ -0,0 +1,165 @@
+use {
+  super::*,
+  argon2::Argon2,
+  chacha20poly1305::{
+    XChaCha20Poly1305,
+    aead::{Aead, KeyInit, Payload},
+  },
+  rand::RngCore,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct EncryptedPrivateKey {
+  ciphertext: [u8; Self::CIPHERTEXT_LEN],
+  nonce: [u8; Self::NONCE_LEN],
+  public_key: PublicKey,
+  salt: [u8; Self::SALT_LEN],
+}
+
+impl EncryptedPrivateKey {
+  const CIPHERTEXT_LEN: usize = PrivateKey::LEN + 16;
+  const NONCE_LEN: usize = 24;
+  const SALT_LEN: usize = 16;
+
+  fn cipher(passphrase: &str, salt: &[u8; Self::SALT_LEN]) -> XChaCha20Poly1305 {
+    let mut key = [0; 32];
+
+    Argon2::default()
+      .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+      .unwrap();
+
+    XChaCha20Poly1305::new(&key.into())
+  }
+
+  pub(crate) fn decrypt(&self, passphrase: &str) -> Result<PrivateKey, PrivateKeyError> {
+    let plaintext = Self::cipher(passphrase, &self.salt)
+      .decrypt(
+        &self.nonce.into(),
+        Payload {
+          aad: &self.public_key.inner().to_bytes(),
+          msg: &self.ciphertext,
+        },
+      )
+      .ok()
+      .context(private_key_error::Decrypt)?;
+
+    let private_key = PrivateKey::from_bytes(plaintext.try_into().unwrap());
+
+    ensure!(
+      private_key.public_key() == self.public_key,
+      private_key_error::PublicKeyMismatch,
+    );
+
+    Ok(private_key)
+  }
+
+  pub(crate) fn encrypt(private_key: &PrivateKey, passphrase: &str) -> Self {
+    let mut salt = [0; Self::SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+
+    let mut nonce = [0; Self::NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let public_key = private_key.public_key();
+
+    let ciphertext = Self::cipher(passphrase, &salt)
+      .encrypt(
+        &nonce.into(),
+        Payload {
+          aad: &public_key.inner().to_bytes(),
+          msg: &private_key.as_secret_bytes(),
+        },
+      )
+      .unwrap()
+      .try_into()
+      .unwrap();
+
+    Self {
+      ciphertext,
+      nonce,
+      public_key,
+      salt,
+    }
+  }
+
+  pub(crate) fn is_encrypted(key: &str) -> bool {
+    let prefix = format!("{}1", Bech32Type::EncryptedPrivateKey.hrp());
+    key
+      .get(..prefix.len())
+      .is_some_and(|actual| actual.eq_ignore_ascii_case(&prefix))
+  }
+
+  pub(crate) fn public_key(&self) -> PublicKey {
+    self.public_key
+  }
+}
+
+impl Display for EncryptedPrivateKey {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let mut encoder = Bech32Encoder::new(Bech32Type::EncryptedPrivateKey);
+    encoder.bytes(&self.public_key.inner().to_bytes());
+    encoder.bytes(&self.salt);
+    encoder.bytes(&self.nonce);
+    encoder.bytes(&self.ciphertext);
+    write!(f, "{encoder}")
+  }
+}
+
+impl FromStr for EncryptedPrivateKey {
+  type Err = PrivateKeyError;
+
+  fn from_str(key: &str) -> Result<Self, Self::Err> {
+    let mut decoder = Bech32Decoder::new(Bech32Type::EncryptedPrivateKey, key)?;
+    let public_key = decoder.byte_array()?;
+    let salt = decoder.byte_array()?;
+    let nonce = decoder.byte_array()?;
+    let ciphertext = decoder.byte_array()?;
+    decoder.done()?;
+
+    Ok(Self {
+      ciphertext,
+      nonce,
+      public_key: PublicKey::from_bytes(public_key).context(private_key_error::PublicKey)?,
+      salt,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn incorrect_passphrase() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    assert_eq!(
+      EncryptedPrivateKey::encrypt(&private_key, "foo")
+        .decrypt("bar")
+        .unwrap_err()
+        .to_string(),
+      "incorrect passphrase",
+    );
+  }
+
+  #[test]
+  fn is_encrypted() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let encrypted = EncryptedPrivateKey::encrypt(&private_key, "foo").to_string();
+    assert!(EncryptedPrivateKey::is_encrypted(&encrypted));
+    assert!(EncryptedPrivateKey::is_encrypted(&encrypted.to_uppercase()));
+    assert!(!EncryptedPrivateKey::is_encrypted(test::PRIVATE_KEY));
+  }
+
+  #[test]
+  fn round_trip() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let encrypted = EncryptedPrivateKey::encrypt(&private_key, "foo");
+    assert!(encrypted.to_string().starts_with("encrypted1a"));
+    let parsed = encrypted
+      .to_string()
+      .parse::<EncryptedPrivateKey>()
+      .unwrap();
+    assert_eq!(parsed, encrypted);
+    assert_eq!(parsed.decrypt("foo").unwrap(), private_key);
+  }
+}
@@ -2,6 +2,7 @@ use super::*;
 
 #[allow(clippy::arbitrary_source_item_ordering)]
 #[derive(Clone, Debug, Decode, Encode, EnumDiscriminants, PartialEq)]
+#[cbor(validate)]
 #[strum_discriminants(
   allow(clippy::arbitrary_source_item_ordering),
   derive(Display),
@@ -15,6 +16,8 @@ pub enum Entry {
     hash: Hash,
     #[n(1)]
     size: u64,
+    #[n(2)]
+    executable: Option<bool>,
   },
   #[n(1)]
   Directory {
@@ -25,6 +28,11 @@ pub enum Entry {
     #[n(2)]
     totals: Totals,
   },
+  #[n(2)]
+  Symlink {
+    #[n(0)]
+    target: RelativePath,
+  },
 }
 
 impl Entry {
@@ -33,18 +41,48 @@ impl Entry {
   }
 
   pub fn file(hash: Hash, size: u64) -> Self {
-    Self::File { hash, size }
+    Self::File {
+      hash,
+      size,
+      executable: None,
+    }
+  }
+
+  pub fn hash(&self) -> Option<Hash> {
+    match self {
+      Self::File { hash, .. } | Self::Directory { hash, .. } => Some(*hash),
+      Self::Symlink { .. } => None,
+    }
+  }
+
+  pub fn size(&self) -> Option<u64> {
+    match self {
+      Self::File { size, .. } | Self::Directory { size, .. } => Some(*size),
+      Self::Symlink { .. } => None,
+    }
   }
 
-  pub fn hash(&self) -> Hash {
+  pub(crate) fn to_file(&self) -> Option<File> {
     match self {
-      Self::File { hash, .. } | Self::Directory { hash, .. } => *hash,
+      Self::File {
+        hash,
+        size,
+        executable,
+      } => Some(File {
+        executable: executable.unwrap_or_default(),
+        hash: *hash,
+        size: *size,
+      }),
+      Self::Directory { .. } | Self::Symlink { .. } => None,
     }
   }
 
-  pub fn size(&self) -> u64 {
+  pub(crate) fn to_symlink(&self) -> Option<Symlink> {
     match self {
-      Self::File { size, .. } | Self::Directory { size, .. } => *size,
+      Self::Symlink { target } => Some(Symlink {
+        target: target.clone(),
+      }),
+      Self::Directory { .. } | Self::File { .. } => None,
     }
   }
 
@@ -53,6 +91,44 @@ impl Entry {
   }
 }
 
+impl From<File> for Entry {
+  fn from(file: File) -> Self {
+    Self::File {
+      hash: file.hash,
+      size: file.size,
+      executable: file.executable.then_some(true),
+    }
+  }
+}
+
+impl From<Symlink> for Entry {
+  fn from(symlink: Symlink) -> Self {
+    Self::Symlink {
+      target: symlink.target,
+    }
+  }
+}
+
+impl Validate for Entry {
+  fn validate(&self) -> Result<(), DecodeError> {
+    if let Self::File {
+      executable: Some(false),
+      ..
+    } = self
+    {
+      return Err(
+        decode_error::UnexpectedValue {
+          actual: "false".to_owned(),
+          expected: "true",
+        }
+        .build(),
+      );
+    }
+
+    Ok(())
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -62,6 +138,13 @@ mod tests {
     assert_encoding(Entry::File {
       size: 100,
       hash: Hash::bytes(b"foo"),
+      executable: None,
+    });
+
+    assert_encoding(Entry::File {
+      size: 100,
+      hash: Hash::bytes(b"foo"),
+      executable: Some(true),
     });
 
     assert_encoding(Entry::Directory {
@@ -74,5 +157,44 @@ mod tests {
         files: 3,
       },
     });
+
+    assert_encoding(Entry::Symlink {
+      target: "foo/bar".parse().unwrap(),
+    });
+  }
+
+  #[test]
+  fn escaping_symlink_target_is_rejected() {
+    let mut cbor = Entry::Symlink {
+      target: "ab/foo".parse().unwrap(),
+    }
+    .encode_to_vec();
+
+    let i = cbor
+      .windows(6)
+      .position(|window| window == b"ab/foo")
+      .unwrap();
+
+    cbor[i..i + 6].copy_from_slice(b"../foo");
+
+    assert!(Entry::decode_from_slice(&cbor).is_err());
+  }
+
+  #[test]
+  fn executable_false_is_rejected() {
+    let cbor = Entry::File {
+      hash: Hash::bytes(b"foo"),
+      size: 100,
+      executable: Some(false),
+    }
+    .encode_to_vec();
+
+    assert_matches!(
+      Entry::decode_from_slice(&cbor),
+      Err(DecodeError::UnexpectedValue {
+        actual,
+        expected: "true",
+      }) if actual == "false",
+    );
   }
 }
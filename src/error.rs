@@ -26,6 +26,12 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     source: AudioPositionError,
   },
+  #[snafu(display("invalid authorization config `{path}`"))]
+  AuthorizationLoad {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: AuthorizationError,
+  },
   #[snafu(display("failed to decode bech32 `{bech32}`"))]
   Bech32Decode {
     backtrace: Option<Backtrace>,
@@ -70,6 +76,18 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     source: io::Error,
   },
+  #[snafu(display("failed to bundle `{path}`"))]
+  Bundle {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: io::Error,
+  },
+  #[snafu(display("failed to read bundle `{path}`"))]
+  BundleRead {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: BundleError,
+  },
   #[snafu(display("failed to build HTTP client"))]
   ClientBuild {
     backtrace: Option<Backtrace>,
@@ -119,6 +137,12 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     source: redb::TransactionError,
   },
+  #[snafu(display("failed to decode directory `{hash}`"))]
+  DecodeDirectory {
+    backtrace: Option<Backtrace>,
+    hash: Hash,
+    source: DecodeError,
+  },
   #[snafu(display("failed to decode manifest at `{path}`"))]
   DecodeManifest {
     backtrace: Option<Backtrace>,
@@ -131,6 +155,12 @@ pub enum Error {
     path: DisplayPath,
     source: DecodeError,
   },
+  #[snafu(display("failed to decode proof at `{path}`"))]
+  DecodeProof {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: DecodeError,
+  },
   #[snafu(display("failed to decode response from `{url}`"))]
   DecodeResponse {
     backtrace: Option<Backtrace>,
@@ -155,12 +185,35 @@ pub enum Error {
     path: DisplayPath,
     source: serde_yaml::Error,
   },
+  #[snafu(display("failed to deserialize names at `{path}`"))]
+  DeserializeNames {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: serde_yaml::Error,
+  },
+  #[snafu(display("failed to deserialize trusted keys at `{path}`"))]
+  DeserializeTrusted {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: serde_yaml::Error,
+  },
+  #[snafu(display("failed to compare manifests"))]
+  Diff {
+    backtrace: Option<Backtrace>,
+    source: ArchiveError,
+  },
   #[snafu(display("directory `{hash}` totals error"))]
   DirectoryTotals {
     backtrace: Option<Backtrace>,
     hash: Hash,
     source: TotalsError,
   },
+  #[snafu(display("downloaded block at offset {offset} of file {hash} does not match outboard"))]
+  DownloadBlockMismatch {
+    backtrace: Option<Backtrace>,
+    hash: Hash,
+    offset: u64,
+  },
   #[snafu(display("downloaded file hash mismatch: expected {expected} but got {actual}"))]
   DownloadHashMismatch {
     actual: Hash,
@@ -185,11 +238,29 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     count: usize,
   },
+  #[snafu(display("failed to export `{path}`"))]
+  Export {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: io::Error,
+  },
   #[snafu(display("found {} not referenced in metadata", Count::new(paths.len(), "extra file")))]
   ExtraFiles {
     backtrace: Option<Backtrace>,
     paths: Vec<RelativePath>,
   },
+  #[snafu(display("extracted file `{path}` hash mismatch: expected {expected} but got {actual}"))]
+  ExtractHashMismatch {
+    actual: Hash,
+    backtrace: Option<Backtrace>,
+    expected: Hash,
+    path: DisplayPath,
+  },
+  #[snafu(display("package file `{path}` would be overwritten by extracted manifest"))]
+  ExtractManifestConflict {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+  },
   #[snafu(display("extraneous directory not in manifest: `{path}`"))]
   ExtraneousDirectory {
     backtrace: Option<Backtrace>,
@@ -200,6 +271,11 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     path: DisplayPath,
   },
+  #[snafu(display("extraneous symlink not in manifest: `{path}`"))]
+  ExtraneousSymlink {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+  },
   #[snafu(display("`{path}` already exists"))]
   FileAlreadyExists {
     backtrace: Option<Backtrace>,
@@ -231,6 +307,12 @@ pub enum Error {
   },
   #[snafu(display("failed to get home directory"))]
   HomeDir { backtrace: Option<Backtrace> },
+  #[snafu(display("invalid ignore file `{path}`"))]
+  IgnoreFile {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: ignore::Error,
+  },
   #[snafu(display("ignored path `{path}` is included in package"))]
   IgnoredPath {
     backtrace: Option<Backtrace>,
@@ -272,6 +354,11 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     message: String,
   },
+  #[snafu(display("deletion of key `{name}` not confirmed"))]
+  KeyDeletionUnconfirmed {
+    backtrace: Option<Backtrace>,
+    name: crate::KeyName,
+  },
   #[snafu(display(
     "public key `{}` doesn't match private key `{}`",
     key.public_key_filename(),
@@ -384,6 +471,56 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     fingerprint: Fingerprint,
   },
+  #[snafu(display("name `{name}` for key `{public_key}` not found"))]
+  NameNotFound {
+    backtrace: Option<Backtrace>,
+    name: PackageName,
+    public_key: PublicKey,
+  },
+  #[snafu(display("invalid signature on name `{name}` for key `{public_key}`"))]
+  NameRecordInvalid {
+    backtrace: Option<Backtrace>,
+    name: PackageName,
+    public_key: PublicKey,
+    source: DalekSignatureError,
+  },
+  #[snafu(display(
+    "expected name `{expected_name}` for key `{expected_public_key}` but server returned name \
+    `{name}` for key `{public_key}`"
+  ))]
+  NameRecordMismatch {
+    backtrace: Option<Backtrace>,
+    expected_name: PackageName,
+    expected_public_key: PublicKey,
+    name: PackageName,
+    public_key: PublicKey,
+  },
+  #[snafu(display(
+    "name `{name}` for key `{public_key}` has sequence number {sequence}, but sequence number \
+    {seen} was previously seen"
+  ))]
+  NameRollback {
+    backtrace: Option<Backtrace>,
+    name: PackageName,
+    public_key: PublicKey,
+    seen: u64,
+    sequence: u64,
+  },
+  #[snafu(display("invalid outboard for file {hash}"))]
+  Outboard {
+    backtrace: Option<Backtrace>,
+    hash: Hash,
+    source: OutboardError,
+  },
+  #[snafu(display("passphrase may not be empty"))]
+  PassphraseEmpty { backtrace: Option<Backtrace> },
+  #[snafu(display("passphrases do not match"))]
+  PassphraseMismatch { backtrace: Option<Backtrace> },
+  #[snafu(display("failed to read passphrase, set `FILEPACK_PASSPHRASE` or run in a terminal"))]
+  PassphraseRead {
+    backtrace: Option<Backtrace>,
+    source: io::Error,
+  },
   #[snafu(display("invalid path `{path}`"))]
   Path {
     path: DisplayPath,
@@ -394,17 +531,48 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     path: PathBuf,
   },
+  #[snafu(display("invalid policy `{path}`"))]
+  PolicyLoad {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: PolicyError,
+  },
+  #[snafu(display("package does not satisfy policy `{path}`"))]
+  PolicyUnsatisfied {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+  },
+  #[snafu(display("private key already encrypted: `{path}`"))]
+  PrivateKeyAlreadyEncrypted {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+  },
   #[snafu(display("private key already exists: `{}`", path.display()))]
   PrivateKeyAlreadyExists {
     backtrace: Option<Backtrace>,
     path: PathBuf,
   },
+  #[snafu(display("invalid private key on standard input"))]
+  PrivateKeyImport {
+    backtrace: Option<Backtrace>,
+    source: PrivateKeyError,
+  },
+  #[snafu(display("private key is already in keychain as `{name}`"))]
+  PrivateKeyImportDuplicate {
+    backtrace: Option<Backtrace>,
+    name: crate::KeyName,
+  },
   #[snafu(display("invalid private key `{path}`"))]
   PrivateKeyLoad {
     backtrace: Option<Backtrace>,
     path: DisplayPath,
     source: PrivateKeyError,
   },
+  #[snafu(display("private key not encrypted: `{path}`"))]
+  PrivateKeyNotEncrypted {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+  },
   #[snafu(display("private key not found: `{path}`"))]
   PrivateKeyNotFound {
     backtrace: Option<Backtrace>,
@@ -416,6 +584,17 @@ pub enum Error {
     mode: Mode,
     path: DisplayPath,
   },
+  #[snafu(display("manifest does not contain file `{path}`"))]
+  ProofFileNotFound {
+    backtrace: Option<Backtrace>,
+    path: RelativePath,
+  },
+  #[snafu(display("invalid proof at `{path}`"))]
+  ProofInvalid {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: ProofError,
+  },
   #[snafu(display("public key already exists: `{}`", path.display()))]
   PublicKeyAlreadyExists {
     backtrace: Option<Backtrace>,
@@ -459,6 +638,12 @@ pub enum Error {
     source: reqwest::Error,
     url: Url,
   },
+  #[snafu(display("failed to read body from response from {url}"))]
+  ResponseRead {
+    backtrace: Option<Backtrace>,
+    source: io::Error,
+    url: Url,
+  },
   #[snafu(display("response from {url} failed with status {status}: {body}"))]
   ResponseStatus {
     backtrace: Option<Backtrace>,
@@ -466,6 +651,24 @@ pub enum Error {
     status: StatusCode,
     url: Url,
   },
+  #[snafu(display("invalid revocation on line {line} of `{path}`"))]
+  RevocationFileLoad {
+    backtrace: Option<Backtrace>,
+    line: usize,
+    path: DisplayPath,
+    source: RevocationError,
+  },
+  #[snafu(display("invalid revocation for key `{public_key}`"))]
+  RevocationInvalid {
+    backtrace: Option<Backtrace>,
+    public_key: PublicKey,
+    source: DalekSignatureError,
+  },
+  #[snafu(display("key `{public_key}` cannot be its own replacement"))]
+  RevocationSelfReplacement {
+    backtrace: Option<Backtrace>,
+    public_key: PublicKey,
+  },
   #[snafu(display("failed to install rustls ring crypto provider"))]
   RustlsProvider { backtrace: Option<Backtrace> },
   #[snafu(display("failed to serialize JSON to stdout"))]
@@ -483,6 +686,24 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     source: io::Error,
   },
+  #[snafu(display("signature expiration time `{expires}` is in the past"))]
+  SignatureExpirationInPast {
+    backtrace: Option<Backtrace>,
+    expires: jiff::Timestamp,
+  },
+  #[snafu(display("signature for key `{identifier}` expired at `{expires}`"))]
+  SignatureExpired {
+    backtrace: Option<Backtrace>,
+    expires: jiff::Timestamp,
+    identifier: KeyIdentifier,
+  },
+  #[snafu(display("invalid signature on line {line} of `{path}`"))]
+  SignatureFileLoad {
+    backtrace: Option<Backtrace>,
+    line: usize,
+    path: DisplayPath,
+    source: SignatureError,
+  },
   #[snafu(display(
     "signature fingerprint `{signature}` does not match package fingerprint `{package}`"
   ))]
@@ -502,6 +723,23 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     identifier: KeyIdentifier,
   },
+  #[snafu(display("signature by key `{identifier}` is outside of its trusted validity window"))]
+  SignatureOutsideValidity {
+    backtrace: Option<Backtrace>,
+    identifier: KeyIdentifier,
+  },
+  #[snafu(display("signature for key `{identifier}` does not have purpose `{purpose}`"))]
+  SignaturePurposeMismatch {
+    backtrace: Option<Backtrace>,
+    identifier: KeyIdentifier,
+    purpose: Purpose,
+  },
+  #[snafu(display("signature by key `{public_key}` not trusted, key was revoked at `{revoked}`"))]
+  SignatureRevoked {
+    backtrace: Option<Backtrace>,
+    public_key: PublicKey,
+    revoked: jiff::Timestamp,
+  },
   #[snafu(display(
     "file with hash `{hash}` has size {manifest} in manifest but size {disk} on disk"
   ))]
@@ -511,6 +749,33 @@ pub enum Error {
     hash: Hash,
     manifest: u64,
   },
+  #[snafu(display("directory `{hash}` not found in any source"))]
+  SourceMissingDirectory {
+    backtrace: Option<Backtrace>,
+    hash: Hash,
+  },
+  #[snafu(display("file `{path}` with hash `{hash}` not found in any source"))]
+  SourceMissingFile {
+    backtrace: Option<Backtrace>,
+    hash: Hash,
+    path: RelativePath,
+  },
+  #[snafu(display("content source `{path}` is not a directory"))]
+  SourceNotDirectory {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+  },
+  #[snafu(display("failed to sign with ssh-agent"))]
+  SshAgent {
+    backtrace: Option<Backtrace>,
+    source: SshError,
+  },
+  #[snafu(display("invalid OpenSSH private key `{path}`"))]
+  SshPrivateKeyLoad {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    source: SshError,
+  },
   #[snafu(display("metadata `{path}` already exists"))]
   StaleMetadata {
     backtrace: Option<Backtrace>,
@@ -521,10 +786,41 @@ pub enum Error {
     backtrace: Option<Backtrace>,
     source: io::Error,
   },
-  #[snafu(display("symlink at `{path}`"))]
-  Symlink {
+  #[snafu(display("symlink at `{path}` points outside of package: `{target}`"))]
+  SymlinkOutsidePackage {
+    backtrace: Option<Backtrace>,
+    path: DisplayPath,
+    target: Utf8PathBuf,
+  },
+  #[snafu(display("tar archive contains duplicate entry `{path}`"))]
+  TarDuplicateEntry {
+    backtrace: Option<Backtrace>,
+    path: RelativePath,
+  },
+  #[snafu(display("tar archive entry `{path}` has unsupported type {ty}"))]
+  TarEntryType {
+    backtrace: Option<Backtrace>,
+    path: RelativePath,
+    ty: String,
+  },
+  #[snafu(display(
+    "file `{path}` referenced by metadata precedes `metadata.yaml` in tar archive read from \
+     standard input"
+  ))]
+  TarMetadataOrder {
+    backtrace: Option<Backtrace>,
+    path: RelativePath,
+  },
+  #[snafu(display("failed to read tar archive `{path}`"))]
+  TarRead {
     backtrace: Option<Backtrace>,
     path: DisplayPath,
+    source: io::Error,
+  },
+  #[snafu(display("failed to create temporary directory"))]
+  TempDir {
+    backtrace: Option<Backtrace>,
+    source: io::Error,
   },
   #[snafu(display("failed to get current time"))]
   Time {
@@ -535,12 +831,45 @@ pub enum Error {
   TokenOverHttp { backtrace: Option<Backtrace> },
   #[snafu(display("total file size overflowed 64-bit integer"))]
   TotalFileSizeOverflow { backtrace: Option<Backtrace> },
+  #[snafu(display("trusted key `{name}` already exists"))]
+  TrustedKeyAlreadyExists {
+    backtrace: Option<Backtrace>,
+    name: crate::KeyName,
+  },
+  #[snafu(display("public key `{public_key}` is already trusted as `{name}`"))]
+  TrustedKeyDuplicate {
+    backtrace: Option<Backtrace>,
+    name: crate::KeyName,
+    public_key: PublicKey,
+  },
+  #[snafu(display("key `{name}` already exists in keychain"))]
+  TrustedKeyInKeychain {
+    backtrace: Option<Backtrace>,
+    name: crate::KeyName,
+  },
+  #[snafu(display("trusted key `{name}` not found"))]
+  TrustedKeyNotFound {
+    backtrace: Option<Backtrace>,
+    name: crate::KeyName,
+  },
+  #[snafu(display("validity window start {after} is not before end {before}"))]
+  TrustedKeyValidity {
+    after: jiff::Timestamp,
+    backtrace: Option<Backtrace>,
+    before: jiff::Timestamp,
+  },
   #[snafu(display("failed to unarchive manifest"))]
   UnarchiveManifest {
     backtrace: Option<Backtrace>,
     path: DisplayPath,
     source: ArchiveError,
   },
+  #[snafu(display("failed to unarchive package `{fingerprint}`"))]
+  UnarchivePackage {
+    backtrace: Option<Backtrace>,
+    fingerprint: Fingerprint,
+    source: ArchiveError,
+  },
   #[snafu(display("manifest `{path}` contains unexpected embedded files: {unexpected}"))]
   UnexpectedEmbeddedFiles {
     backtrace: Option<Backtrace>,
@@ -0,0 +1,9 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum ExportFormat {
+  Tar,
+  #[value(name = "tar.zst")]
+  TarZst,
+  Zip,
+}
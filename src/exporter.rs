@@ -0,0 +1,154 @@
+use {
+  super::*,
+  zip::{
+    CompressionMethod, DateTime, System, ZipWriter,
+    write::{SimpleFileOptions, StreamWriter},
+  },
+};
+
+pub(crate) enum Exporter<W: Write> {
+  Tar(tar::Builder<W>),
+  TarZst(tar::Builder<zstd::Encoder<'static, W>>),
+  Zip(ZipWriter<StreamWriter<W>>),
+}
+
+impl<W: Write> Exporter<W> {
+  const DIRECTORY_MODE: u32 = 0o755;
+  const EXECUTABLE_MODE: u32 = 0o755;
+  const FILE_MODE: u32 = 0o644;
+  const SYMLINK_MODE: u32 = 0o777;
+
+  pub(crate) fn directory(&mut self, path: &RelativePath) -> io::Result<()> {
+    let path = format!("{path}/");
+
+    match self {
+      Self::Tar(builder) => Self::tar_directory(builder, &path),
+      Self::TarZst(builder) => Self::tar_directory(builder, &path),
+      Self::Zip(writer) => {
+        writer.add_directory(path, Self::zip_options(Self::DIRECTORY_MODE, 0))?;
+        Ok(())
+      }
+    }
+  }
+
+  pub(crate) fn file(
+    &mut self,
+    path: &RelativePath,
+    file: File,
+    reader: impl Read,
+  ) -> io::Result<()> {
+    let mode = if file.executable {
+      Self::EXECUTABLE_MODE
+    } else {
+      Self::FILE_MODE
+    };
+
+    self.write(
+      path.as_ref(),
+      mode,
+      file.size,
+      VerifyingReader::new(reader, file),
+    )
+  }
+
+  pub(crate) fn finish(self) -> io::Result<W> {
+    match self {
+      Self::Tar(builder) => builder.into_inner(),
+      Self::TarZst(builder) => builder.into_inner()?.finish(),
+      Self::Zip(writer) => Ok(writer.finish()?.into_inner()),
+    }
+  }
+
+  pub(crate) fn new(format: ExportFormat, writer: W, manifest: &Manifest) -> io::Result<Self> {
+    let mut exporter = match format {
+      ExportFormat::Tar => Self::Tar(tar::Builder::new(writer)),
+      ExportFormat::TarZst => Self::TarZst(tar::Builder::new(zstd::Encoder::new(
+        writer,
+        zstd::DEFAULT_COMPRESSION_LEVEL,
+      )?)),
+      ExportFormat::Zip => Self::Zip(ZipWriter::new_stream(writer)),
+    };
+
+    let archive = Archive::pack(&Manifest {
+      embedded: BTreeMap::new(),
+      package: manifest.package.clone(),
+      signatures: BTreeSet::new(),
+    })
+    .unwrap()
+    .encode_to_vec();
+
+    exporter.write(
+      Manifest::FILENAME,
+      Self::FILE_MODE,
+      archive.len().into_u64(),
+      archive.as_slice(),
+    )?;
+
+    Ok(exporter)
+  }
+
+  pub(crate) fn symlink(&mut self, path: &RelativePath, symlink: &Symlink) -> io::Result<()> {
+    let link = symlink.link(path);
+
+    match self {
+      Self::Tar(builder) => Self::tar_symlink(builder, path, &link),
+      Self::TarZst(builder) => Self::tar_symlink(builder, path, &link),
+      Self::Zip(writer) => {
+        writer.add_symlink(path, link, Self::zip_options(Self::SYMLINK_MODE, 0))?;
+        Ok(())
+      }
+    }
+  }
+
+  fn tar_directory<T: Write>(builder: &mut tar::Builder<T>, path: &str) -> io::Result<()> {
+    let mut header = Self::tar_header(tar::EntryType::Directory, Self::DIRECTORY_MODE, 0);
+    builder.append_data(&mut header, path, io::empty())
+  }
+
+  fn tar_header(ty: tar::EntryType, mode: u32, size: u64) -> tar::Header {
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(ty);
+    header.set_gid(0);
+    header.set_mode(mode);
+    header.set_mtime(0);
+    header.set_size(size);
+    header.set_uid(0);
+    header
+  }
+
+  fn tar_symlink<T: Write>(
+    builder: &mut tar::Builder<T>,
+    path: &RelativePath,
+    link: &Utf8Path,
+  ) -> io::Result<()> {
+    let mut header = Self::tar_header(tar::EntryType::Symlink, Self::SYMLINK_MODE, 0);
+    builder.append_link(&mut header, path, link)
+  }
+
+  fn write(&mut self, path: &str, mode: u32, size: u64, mut reader: impl Read) -> io::Result<()> {
+    match self {
+      Self::Tar(builder) => {
+        let mut header = Self::tar_header(tar::EntryType::Regular, mode, size);
+        builder.append_data(&mut header, path, reader)
+      }
+      Self::TarZst(builder) => {
+        let mut header = Self::tar_header(tar::EntryType::Regular, mode, size);
+        builder.append_data(&mut header, path, reader)
+      }
+      Self::Zip(writer) => {
+        writer.start_file(path, Self::zip_options(mode, size))?;
+        io::copy(&mut reader, writer)?;
+        Ok(())
+      }
+    }
+  }
+
+  fn zip_options(mode: u32, size: u64) -> SimpleFileOptions {
+    SimpleFileOptions::default()
+      .compression_method(CompressionMethod::Deflated)
+      .large_file(size >= u64::from(u32::MAX))
+      .last_modified_time(DateTime::default())
+      .system(System::Unix)
+      .unix_permissions(mode)
+  }
+}
@@ -3,6 +3,8 @@ use super::*;
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct File {
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub(crate) executable: bool,
   pub(crate) hash: Hash,
   pub(crate) size: u64,
 }
@@ -23,21 +25,40 @@ impl File {
       style.bad()
     };
 
+    let executable_style = if expected.executable == actual.executable {
+      style.good()
+    } else {
+      style.bad()
+    };
+
     eprintln!(
       "\
 mismatched file: `{path}`
-       manifest: {} ({} bytes)
-           file: {} ({} bytes)",
+       manifest: {} ({} bytes{})
+           file: {} ({} bytes{})",
       expected.hash.style(style.good()),
       expected.size.style(style.good()),
+      expected.executable_suffix().style(style.good()),
       actual.hash.style(hash_style),
       actual.size.style(size_style),
+      actual.executable_suffix().style(executable_style),
     );
   }
 
+  fn executable_suffix(self) -> &'static str {
+    if self.executable { ", executable" } else { "" }
+  }
+
+  pub(crate) fn matches(self, expected: Self) -> bool {
+    self.hash == expected.hash
+      && self.size == expected.size
+      && (self.executable == expected.executable || !cfg!(unix))
+  }
+
   #[cfg(test)]
   pub(crate) fn new(bytes: &[u8]) -> Self {
     Self {
+      executable: false,
       hash: Hash::bytes(bytes),
       size: bytes.len().into_u64(),
     }
@@ -0,0 +1,133 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct FileStat {
+  pub(crate) changed: (i64, u32),
+  pub(crate) device: u64,
+  pub(crate) inode: u64,
+  pub(crate) modified: (i64, u32),
+  pub(crate) size: u64,
+}
+
+impl FileStat {
+  const LEN: usize = 48;
+
+  #[cfg(unix)]
+  pub(crate) fn new(metadata: &fs::Metadata) -> Option<Self> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(Self {
+      changed: (metadata.ctime(), metadata.ctime_nsec().try_into().ok()?),
+      device: metadata.dev(),
+      inode: metadata.ino(),
+      modified: (metadata.mtime(), metadata.mtime_nsec().try_into().ok()?),
+      size: metadata.size(),
+    })
+  }
+
+  #[cfg(not(unix))]
+  pub(crate) fn new(_metadata: &fs::Metadata) -> Option<Self> {
+    None
+  }
+
+  fn to_bytes(self) -> [u8; Self::LEN] {
+    let mut bytes = [0; Self::LEN];
+    bytes[0..8].copy_from_slice(&self.changed.0.to_be_bytes());
+    bytes[8..12].copy_from_slice(&self.changed.1.to_be_bytes());
+    bytes[12..20].copy_from_slice(&self.device.to_be_bytes());
+    bytes[20..28].copy_from_slice(&self.inode.to_be_bytes());
+    bytes[28..36].copy_from_slice(&self.modified.0.to_be_bytes());
+    bytes[36..40].copy_from_slice(&self.modified.1.to_be_bytes());
+    bytes[40..48].copy_from_slice(&self.size.to_be_bytes());
+    bytes
+  }
+}
+
+impl redb::Key for FileStat {
+  fn compare(a: &[u8], b: &[u8]) -> Ordering {
+    <Self as redb::Value>::from_bytes(a).cmp(&<Self as redb::Value>::from_bytes(b))
+  }
+}
+
+impl redb::Value for FileStat {
+  type AsBytes<'a>
+    = [u8; Self::LEN]
+  where
+    Self: 'a;
+
+  type SelfType<'a>
+    = FileStat
+  where
+    Self: 'a;
+
+  fn as_bytes<'a, 'b: 'a>(value: &'a Self::SelfType<'b>) -> Self::AsBytes<'a>
+  where
+    Self: 'b,
+  {
+    value.to_bytes()
+  }
+
+  fn fixed_width() -> Option<usize> {
+    Some(Self::LEN)
+  }
+
+  fn from_bytes<'a>(data: &'a [u8]) -> Self::SelfType<'a>
+  where
+    Self: 'a,
+  {
+    let u64 = |i: usize| u64::from_be_bytes(data[i..i + 8].try_into().unwrap());
+    let i64 = |i: usize| i64::from_be_bytes(data[i..i + 8].try_into().unwrap());
+    let u32 = |i: usize| u32::from_be_bytes(data[i..i + 4].try_into().unwrap());
+
+    Self {
+      changed: (i64(0), u32(8)),
+      device: u64(12),
+      inode: u64(20),
+      modified: (i64(28), u32(36)),
+      size: u64(40),
+    }
+  }
+
+  fn type_name() -> redb::TypeName {
+    TypeName::FileStat.into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn redb() {
+    test::assert_redb_impls(&[
+      FileStat {
+        changed: (0, 0),
+        device: 0,
+        inode: 0,
+        modified: (0, 0),
+        size: 0,
+      },
+      FileStat {
+        changed: (4, 5),
+        device: 1,
+        inode: 2,
+        modified: (6, 7),
+        size: 3,
+      },
+      FileStat {
+        changed: (-1, 0),
+        device: 0,
+        inode: 0,
+        modified: (i64::MIN, 0),
+        size: 0,
+      },
+      FileStat {
+        changed: (i64::MAX, u32::MAX),
+        device: u64::MAX,
+        inode: u64::MAX,
+        modified: (i64::MAX, u32::MAX),
+        size: u64::MAX,
+      },
+    ]);
+  }
+}
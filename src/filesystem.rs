@@ -50,10 +50,22 @@ pub(crate) fn open(path: &Utf8Path) -> Result<fs::File> {
   fs::File::open(path).context(error::FilesystemIo { path })
 }
 
+pub(crate) fn open_opt(path: &Utf8Path) -> Result<Option<fs::File>> {
+  match fs::File::open(path) {
+    Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+    result => result.map(Some).context(error::FilesystemIo { path }),
+  }
+}
+
 pub(crate) fn read(path: &Utf8Path) -> Result<Vec<u8>> {
   fs::read(path).context(error::FilesystemIo { path })
 }
 
+pub(crate) fn read_link(path: &Utf8Path) -> Result<Utf8PathBuf> {
+  let target = fs::read_link(path).context(error::FilesystemIo { path })?;
+  Utf8PathBuf::from_path_buf(target).map_err(|path| error::PathUnicode { path }.build())
+}
+
 pub(crate) fn read_opt(path: &Utf8Path) -> Result<Option<Vec<u8>>> {
   match fs::read(path) {
     Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
@@ -74,6 +86,44 @@ pub(crate) fn read_to_string_opt(path: &Utf8Path) -> Result<Option<String>> {
   }
 }
 
+pub(crate) fn remove_file(path: &Utf8Path) -> Result {
+  fs::remove_file(path).context(error::FilesystemIo { path })
+}
+
+pub(crate) fn rename(from: &Utf8Path, to: &Utf8Path) -> Result {
+  fs::rename(from, to).context(error::FilesystemIo { path: to })
+}
+
+#[cfg(unix)]
+pub(crate) fn set_executable(path: &Utf8Path) -> Result {
+  use std::os::unix::fs::PermissionsExt;
+  let mode = metadata(path)?.permissions().mode();
+  fs::set_permissions(path, Permissions::from_mode(mode | (mode & 0o444) >> 2))
+    .context(error::FilesystemIo { path })
+}
+
+#[cfg(not(unix))]
+pub(crate) fn set_executable(_path: &Utf8Path) -> Result {
+  Ok(())
+}
+
+#[cfg(unix)]
+pub(crate) fn symlink(target: &Utf8Path, link: &Utf8Path) -> Result {
+  std::os::unix::fs::symlink(target, link).context(error::FilesystemIo { path: link })
+}
+
+#[cfg(windows)]
+pub(crate) fn symlink(target: &Utf8Path, link: &Utf8Path) -> Result {
+  use std::os::windows::fs::{symlink_dir, symlink_file};
+
+  if link.parent().unwrap().join(target).is_dir() {
+    symlink_dir(target, link)
+  } else {
+    symlink_file(target, link)
+  }
+  .context(error::FilesystemIo { path: link })
+}
+
 pub(crate) fn write(path: &Utf8Path, contents: impl AsRef<[u8]>) -> Result {
   fs::write(path, contents).context(error::FilesystemIo { path })
 }
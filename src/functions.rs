@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, redb::ReadableTable};
 
 pub(crate) fn current_dir() -> Result<Utf8PathBuf> {
   Utf8PathBuf::from_path_buf(env::current_dir().context(error::CurrentDir)?)
@@ -43,6 +43,41 @@ pub(crate) fn now() -> Result<u64> {
   )
 }
 
+pub(crate) fn open_database(
+  path: &Utf8Path,
+  schema_version: u64,
+  initialize: impl FnOnce(&redb::WriteTransaction) -> Result,
+) -> Result<redb::Database> {
+  let database = redb::Database::create(path).context(error::DatabaseOpen { path })?;
+
+  let tx = database.begin_write()?;
+
+  if tx.list_tables()?.count() == 0 && tx.list_multimap_tables()?.count() == 0 {
+    tx.open_table(DatabaseMetadata::TABLE)?
+      .insert(DatabaseMetadata::Schema, &schema_version)?;
+
+    initialize(&tx)?;
+
+    tx.commit()?;
+  } else {
+    let actual = tx
+      .open_table(DatabaseMetadata::TABLE)?
+      .get(DatabaseMetadata::Schema)?
+      .context(error::DatabaseSchemaVersionMissing)?
+      .value();
+
+    ensure!(
+      actual == schema_version,
+      error::DatabaseSchemaVersionMismatch {
+        actual,
+        expected: schema_version,
+      },
+    );
+  }
+
+  Ok(database)
+}
+
 pub(crate) fn parse_number<T: FromStr<Err = ParseIntError>>(s: &str) -> Result<T, NumberError> {
   ensure! {
     re::NUMBER.is_match(s),
@@ -52,6 +87,26 @@ pub(crate) fn parse_number<T: FromStr<Err = ParseIntError>>(s: &str) -> Result<T
   Ok(s.parse()?)
 }
 
+pub(crate) fn read_passphrase(prompt: &str, confirm: bool) -> Result<String> {
+  if let Ok(passphrase) = env::var("FILEPACK_PASSPHRASE") {
+    ensure!(!passphrase.is_empty(), error::PassphraseEmpty);
+    return Ok(passphrase);
+  }
+
+  let passphrase = rpassword::prompt_password(prompt).context(error::PassphraseRead)?;
+
+  ensure!(!passphrase.is_empty(), error::PassphraseEmpty);
+
+  if confirm {
+    let confirmation =
+      rpassword::prompt_password("confirm passphrase: ").context(error::PassphraseRead)?;
+
+    ensure!(passphrase == confirmation, error::PassphraseMismatch);
+  }
+
+  Ok(passphrase)
+}
+
 pub(crate) fn transfer_tempfile(hash: Hash, path: &Utf8Path) -> io::Result<NamedTempFile> {
   tempfile::Builder::new()
     .prefix(&format!("{hash}-"))
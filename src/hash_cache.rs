@@ -0,0 +1,143 @@
+use {
+  super::*,
+  redb::{Database, ReadableDatabase, TableDefinition},
+  std::{mem, sync::Mutex},
+};
+
+const FILES: TableDefinition<FileStat, Hash> = TableDefinition::new("files");
+const SCHEMA_VERSION: u64 = 1;
+
+pub(crate) struct HashCache {
+  database: Database,
+  pending: Mutex<Vec<(FileStat, Hash)>>,
+}
+
+impl HashCache {
+  pub(crate) const FILENAME: &'static str = "cache.redb";
+
+  pub(crate) fn commit(&self) -> Result {
+    let pending = mem::take(&mut *self.pending.lock().unwrap());
+
+    if pending.is_empty() {
+      return Ok(());
+    }
+
+    let written = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |duration| duration.as_secs());
+
+    let tx = self.database.begin_write()?;
+
+    {
+      let mut files = tx.open_table(FILES)?;
+
+      for (stat, hash) in pending {
+        // files modified in the same second the cache is written may be
+        // modified again without their stat changing, so are not cached
+        if stat.modified.0 >= written.try_into().unwrap_or(i64::MAX) {
+          continue;
+        }
+
+        files.insert(stat, hash)?;
+      }
+    }
+
+    tx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn get(&self, stat: FileStat) -> io::Result<Option<Hash>> {
+    let tx = self.database.begin_read().map_err(io::Error::other)?;
+
+    let files = tx.open_table(FILES).map_err(io::Error::other)?;
+
+    Ok(
+      files
+        .get(stat)
+        .map_err(io::Error::other)?
+        .map(|hash| hash.value()),
+    )
+  }
+
+  pub(crate) fn insert(&self, stat: FileStat, hash: Hash) {
+    self.pending.lock().unwrap().push((stat, hash));
+  }
+
+  pub(crate) fn load(options: &Options) -> Result<Self> {
+    let data_dir = options.data_dir()?;
+
+    filesystem::create_dir_all(&data_dir)?;
+
+    let database = open_database(&data_dir.join(Self::FILENAME), SCHEMA_VERSION, |tx| {
+      tx.open_table(FILES)?;
+      Ok(())
+    })?;
+
+    Ok(Self {
+      database,
+      pending: Mutex::new(Vec::new()),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cached_hash_is_returned() {
+    let (_tempdir, path) = tempdir();
+
+    let options = Options::try_parse_from(["filepack", "--data-dir", path.as_str()]).unwrap();
+
+    let stat = FileStat {
+      changed: (1, 2),
+      device: 3,
+      inode: 4,
+      modified: (5, 6),
+      size: 7,
+    };
+
+    let hash = Hash::bytes(b"foo");
+
+    {
+      let cache = HashCache::load(&options).unwrap();
+      assert_eq!(cache.get(stat).unwrap(), None);
+      cache.insert(stat, hash);
+      assert_eq!(cache.get(stat).unwrap(), None);
+      cache.commit().unwrap();
+      assert_eq!(cache.get(stat).unwrap(), Some(hash));
+    }
+
+    let cache = HashCache::load(&options).unwrap();
+    assert_eq!(cache.get(stat).unwrap(), Some(hash));
+  }
+
+  #[test]
+  fn racily_clean_entries_are_not_cached() {
+    let (_tempdir, path) = tempdir();
+
+    let options = Options::try_parse_from(["filepack", "--data-dir", path.as_str()]).unwrap();
+
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap()
+      .as_secs()
+      .try_into()
+      .unwrap();
+
+    let stat = FileStat {
+      changed: (now, 0),
+      device: 3,
+      inode: 4,
+      modified: (now, 0),
+      size: 7,
+    };
+
+    let cache = HashCache::load(&options).unwrap();
+    cache.insert(stat, Hash::bytes(b"foo"));
+    cache.commit().unwrap();
+    assert_eq!(cache.get(stat).unwrap(), None);
+  }
+}
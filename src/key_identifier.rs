@@ -14,6 +14,14 @@ impl FromStr for KeyIdentifier {
       return Ok(Self::Literal(name.parse()?));
     }
 
+    if name.starts_with("ssh-") {
+      return Ok(Self::Literal(
+        ssh::parse_public_key(name)
+          .map_err(Box::new)
+          .context(public_key_error::Ssh { key: name })?,
+      ));
+    }
+
     Ok(Self::Name(name.parse()?))
   }
 }
@@ -6,11 +6,7 @@ pub(crate) struct Keychain {
 }
 
 impl Keychain {
-  pub(crate) fn generate_key(&mut self, name: &KeyName) -> Result {
-    if !filesystem::exists(&self.path)? {
-      filesystem::create_dir_all_with_mode(&self.path, 0o700)?;
-    }
-
+  fn check_available(&self, name: &KeyName) -> Result {
     ensure! {
       !self.keys.contains_key(name),
       error::PublicKeyAlreadyExists { path: self.path.join(name.public_key_filename()) },
@@ -23,25 +19,68 @@ impl Keychain {
       error::PrivateKeyAlreadyExists { path: private_path },
     }
 
-    let private_key = PrivateKey::generate();
+    Ok(())
+  }
 
-    filesystem::write_with_mode(
-      &private_path,
-      format!("{}\n", private_key.display_secret()),
-      0o600,
-    )?;
+  pub(crate) fn decrypt_key(&self, name: &KeyName) -> Result {
+    let path = self.path.join(name.private_key_filename());
 
-    let public_key = private_key.public_key();
+    ensure! {
+      self.is_encrypted(name)?,
+      error::PrivateKeyNotEncrypted { path },
+    }
 
-    let public_path = self.path.join(name.public_key_filename());
+    let private_key = self.private_key(name)?;
 
-    filesystem::write(&public_path, format!("{public_key}\n"))?;
+    filesystem::write_with_mode(&path, format!("{}\n", private_key.display_secret()), 0o600)
+  }
 
-    self.keys.insert(name.clone(), public_key);
+  pub(crate) fn delete_key(&mut self, name: &KeyName) -> Result {
+    self.public_key(name)?;
+
+    filesystem::remove_file(&self.path.join(name.private_key_filename()))?;
+    filesystem::remove_file(&self.path.join(name.public_key_filename()))?;
+
+    self.keys.remove(name);
 
     Ok(())
   }
 
+  pub(crate) fn encrypt_key(&self, name: &KeyName) -> Result {
+    let path = self.path.join(name.private_key_filename());
+
+    ensure! {
+      !self.is_encrypted(name)?,
+      error::PrivateKeyAlreadyEncrypted { path },
+    }
+
+    let private_key = self.private_key(name)?;
+
+    let passphrase = read_passphrase("new passphrase: ", true)?;
+
+    filesystem::write_with_mode(
+      &path,
+      format!(
+        "{}\n",
+        EncryptedPrivateKey::encrypt(&private_key, &passphrase)
+      ),
+      0o600,
+    )
+  }
+
+  pub(crate) fn generate_key(&mut self, name: &KeyName, passphrase: Option<&str>) -> Result {
+    self.check_available(name)?;
+
+    let private_key = PrivateKey::generate();
+
+    let contents = match passphrase {
+      Some(passphrase) => EncryptedPrivateKey::encrypt(&private_key, passphrase).to_string(),
+      None => private_key.display_secret().to_string(),
+    };
+
+    self.insert_key(name, &contents, private_key.public_key())
+  }
+
   pub(crate) fn identifier_public_key(&self, identifier: &KeyIdentifier) -> Result<PublicKey> {
     match identifier {
       KeyIdentifier::Literal(key) => Ok(*key),
@@ -49,6 +88,70 @@ impl Keychain {
     }
   }
 
+  pub(crate) fn import_key(&mut self, name: &KeyName, private_key: &str) -> Result {
+    self.check_available(name)?;
+
+    let private_key = private_key.trim();
+
+    let (contents, public_key) = if EncryptedPrivateKey::is_encrypted(private_key) {
+      let private_key = private_key
+        .parse::<EncryptedPrivateKey>()
+        .context(error::PrivateKeyImport)?;
+      (private_key.to_string(), private_key.public_key())
+    } else {
+      let private_key = private_key
+        .parse::<PrivateKey>()
+        .context(error::PrivateKeyImport)?;
+      (
+        private_key.display_secret().to_string(),
+        private_key.public_key(),
+      )
+    };
+
+    if let Some(existing) = self.name(public_key) {
+      return Err(
+        error::PrivateKeyImportDuplicate {
+          name: existing.clone(),
+        }
+        .build(),
+      );
+    }
+
+    self.insert_key(name, &contents, public_key)
+  }
+
+  fn insert_key(&mut self, name: &KeyName, private_key: &str, public_key: PublicKey) -> Result {
+    if !filesystem::exists(&self.path)? {
+      filesystem::create_dir_all_with_mode(&self.path, 0o700)?;
+    }
+
+    filesystem::write_with_mode(
+      &self.path.join(name.private_key_filename()),
+      format!("{private_key}\n"),
+      0o600,
+    )?;
+
+    filesystem::write(
+      &self.path.join(name.public_key_filename()),
+      format!("{public_key}\n"),
+    )?;
+
+    self.keys.insert(name.clone(), public_key);
+
+    Ok(())
+  }
+
+  pub(crate) fn is_encrypted(&self, name: &KeyName) -> Result<bool> {
+    self.public_key(name)?;
+
+    let path = self.path.join(name.private_key_filename());
+
+    let private_key = filesystem::read_to_string_opt(&path)?
+      .ok_or_else(|| error::PrivateKeyNotFound { path }.build())?;
+
+    Ok(EncryptedPrivateKey::is_encrypted(private_key.trim()))
+  }
+
   pub(crate) fn load(options: &Options) -> Result<Self> {
     let path = options.data_dir()?.join("keychain");
 
@@ -131,17 +234,20 @@ impl Keychain {
     Ok(Self { keys, path })
   }
 
-  pub(crate) fn public_key(&self, name: &KeyName) -> Result<PublicKey> {
+  pub(crate) fn mode(&self, name: &KeyName) -> Result<Mode> {
+    self.public_key(name)?;
+    filesystem::mode(&self.path.join(name.private_key_filename()))
+  }
+
+  pub(crate) fn name(&self, public_key: PublicKey) -> Option<&KeyName> {
     self
       .keys
-      .get(name)
-      .copied()
-      .context(error::PublicKeyNotFound {
-        path: self.path.join(name.public_key_filename()),
-      })
+      .iter()
+      .find(|(_name, key)| **key == public_key)
+      .map(|(name, _key)| name)
   }
 
-  pub(crate) fn sign(&self, name: &KeyName, statement: &Statement) -> Result<Signature> {
+  pub(crate) fn private_key(&self, name: &KeyName) -> Result<PrivateKey> {
     let public_key = self.public_key(name)?;
 
     let private_key = PrivateKey::load(&self.path.join(name.private_key_filename()))?;
@@ -153,6 +259,41 @@ impl Keychain {
       }
     }
 
-    Ok(private_key.sign(statement))
+    Ok(private_key)
+  }
+
+  pub(crate) fn public_key(&self, name: &KeyName) -> Result<PublicKey> {
+    self
+      .keys
+      .get(name)
+      .copied()
+      .context(error::PublicKeyNotFound {
+        path: self.path.join(name.public_key_filename()),
+      })
+  }
+
+  pub(crate) fn rename_key(&mut self, from: &KeyName, to: &KeyName) -> Result {
+    let public_key = self.public_key(from)?;
+
+    self.check_available(to)?;
+
+    filesystem::rename(
+      &self.path.join(from.private_key_filename()),
+      &self.path.join(to.private_key_filename()),
+    )?;
+
+    filesystem::rename(
+      &self.path.join(from.public_key_filename()),
+      &self.path.join(to.public_key_filename()),
+    )?;
+
+    self.keys.remove(from);
+    self.keys.insert(to.clone(), public_key);
+
+    Ok(())
+  }
+
+  pub(crate) fn sign(&self, name: &KeyName, statement: &Statement) -> Result<Signature> {
+    Ok(self.private_key(name)?.sign(statement))
   }
 }
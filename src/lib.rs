@@ -31,22 +31,33 @@ use {
     audio_position_error::AudioPositionError,
     audio_type::AudioType,
     authenticated::Authenticated,
+    authorization::Authorization,
+    authorization_error::AuthorizationError,
+    authorized_key::AuthorizedKey,
     bech32_decoder::Bech32Decoder,
     bech32_encoder::Bech32Encoder,
     bech32_error::Bech32Error,
     bech32_type::Bech32Type,
     bit_reader::BitReader,
+    bundle_error::BundleError,
+    bundle_key::BundleKey,
+    bundle_reader::BundleReader,
+    bundle_writer::BundleWriter,
     cause::Cause,
     cbor::Cbor,
     cbor_response::CborResponse,
+    changes::Changes,
+    channel_writer::ChannelWriter,
     checked_url::CheckedUrl,
     chroma_subsampling::ChromaSubsampling,
     client::Client,
     codec::Codec,
     color_info::ColorInfo,
     color_type::ColorType,
+    comment_error::CommentError,
     component::Component,
     component_error::ComponentError,
+    content_source::ContentSource,
     context::Context,
     count::Count,
     dalek_signature_error::DalekSignatureError,
@@ -62,18 +73,23 @@ use {
     display_path::DisplayPath,
     display_sample_rate::DisplaySampleRate,
     display_secret::DisplaySecret,
+    encrypted_private_key::EncryptedPrivateKey,
     entries::Entries,
     envelope::Envelope,
     exif_decoder::ExifDecoder,
     exif_error::ExifError,
+    export_format::ExportFormat,
+    exporter::Exporter,
     file::File,
+    file_stat::FileStat,
     flac_decoder::FlacDecoder,
     float_ext::FloatExt,
     format::Format,
     functions::{
-      current_dir, decode_path, default, format_size, is_lowercase_hex, now, parse_number,
-      transfer_tempfile,
+      current_dir, decode_path, default, format_size, is_lowercase_hex, now, open_database,
+      parse_number, read_passphrase, transfer_tempfile,
     },
+    hash_cache::HashCache,
     hash_error::HashError,
     hashing_writer::HashingWriter,
     head::Head,
@@ -97,28 +113,56 @@ use {
     mp3_decoder::Mp3Decoder,
     mp3_error::Mp3Error,
     mp4_decoder::Mp4Decoder,
+    name_record::NameRecord,
+    name_statement::NameStatement,
+    name_store::NameStore,
     number_error::NumberError,
     options::Options,
     or::Or,
     ordinal::Ordinal,
     orientation::Orientation,
+    outboard::Outboard,
+    outboard_error::OutboardError,
+    outboard_hasher::OutboardHasher,
     owo_colorize_ext::OwoColorizeExt,
     package::Package,
+    package_name::PackageName,
+    package_name_error::PackageNameError,
     path_error::PathError,
     percent_encode::PercentEncode,
+    policy::Policy,
+    policy_error::PolicyError,
+    policy_evaluation::PolicyEvaluation,
+    policy_rule::PolicyRule,
     private_key_error::PrivateKeyError,
+    proof::Proof,
+    proof_error::ProofError,
     public_key_error::PublicKeyError,
+    quota::Quota,
+    quotas::Quotas,
     reqwest_response_ext::ReqwestResponseExt,
+    resolution::Resolution,
     resource::Resource,
     resource_type::ResourceType,
+    revocation_error::RevocationError,
+    revocation_statement::RevocationStatement,
+    revocation_store::RevocationStore,
+    role::Role,
     rotation::Rotation,
+    search::Search,
+    search_order::SearchOrder,
     server::Server,
     server_error::ServerError,
     sign_options::SignOptions,
     signature_error::SignatureError,
+    signature_file::SignatureFile,
+    signer::Signer,
+    ssh_error::SshError,
     static_asset::StaticAsset,
     style::Style,
     subcommand::Subcommand,
+    symlink::Symlink,
+    tar_walk::TarWalk,
     templates::PageHtml,
     text_error::TextError,
     ticked::Ticked,
@@ -128,17 +172,24 @@ use {
     totals_error::TotalsError,
     track::Track,
     track_info::TrackInfo,
+    trust_store::TrustStore,
+    trusted_key::TrustedKey,
     type_name::TypeName,
     url_error::UrlError,
+    usage::Usage,
     utf8_path_ext::Utf8PathExt,
     validate::Validate,
+    verifying_reader::VerifyingReader,
     version::Version,
     video::Video,
     video_error::VideoError,
     video_metadata::VideoMetadata,
     video_type::VideoType,
     view::View,
+    walk::Walk,
     webm_decoder::WebmDecoder,
+    year_range::YearRange,
+    year_range_error::YearRangeError,
   },
   axum::{
     body::Body,
@@ -168,8 +219,8 @@ use {
   regex::Regex,
   serde::{Deserialize, Deserializer, Serialize, Serializer},
   serde_with::{
-    DeserializeFromStr, MapPreventDuplicates, SerializeDisplay, SetPreventDuplicates, serde_as,
-    skip_serializing_none,
+    DeserializeFromStr, MapPreventDuplicates, NoneAsEmptyString, SerializeDisplay,
+    SetPreventDuplicates, serde_as, skip_serializing_none,
   },
   snafu::{ErrorCompat, IntoError, OptionExt, ResultExt, Snafu, ensure},
   std::{
@@ -186,7 +237,7 @@ use {
     iter,
     net::SocketAddr,
     num::{NonZeroUsize, ParseIntError, TryFromIntError},
-    ops::{Bound, Deref},
+    ops::{Bound, Deref, Range, RangeInclusive},
     path::{Path, PathBuf},
     process::{self, ExitCode},
     ptr,
@@ -213,6 +264,7 @@ use {
 
 pub use self::{
   array_encoder::ArrayEncoder,
+  comment::Comment,
   component_buf::ComponentBuf,
   decode::Decode,
   decoder::Decoder,
@@ -238,7 +290,9 @@ pub use self::{
   page::Page,
   private_key::PrivateKey,
   public_key::PublicKey,
+  purpose::Purpose,
   relative_path::RelativePath,
+  revocation::Revocation,
   signature::Signature,
   sorted_set::SortedSet,
   statement::Statement,
@@ -283,23 +337,35 @@ mod audio_metadata;
 mod audio_position_error;
 mod audio_type;
 mod authenticated;
+mod authorization;
+mod authorization_error;
+mod authorized_key;
 mod bech32_decoder;
 mod bech32_encoder;
 mod bech32_error;
 mod bech32_type;
 mod bit_reader;
+mod bundle_error;
+mod bundle_key;
+mod bundle_reader;
+mod bundle_writer;
 mod cause;
 mod cbor;
 mod cbor_response;
+mod changes;
+mod channel_writer;
 mod checked_url;
 mod chroma_subsampling;
 mod client;
 mod codec;
 mod color_info;
 mod color_type;
+mod comment;
+mod comment_error;
 mod component;
 mod component_buf;
 mod component_error;
+mod content_source;
 mod context;
 mod count;
 mod dalek_signature_error;
@@ -322,13 +388,17 @@ mod display_sample_rate;
 mod display_secret;
 mod encode;
 mod encoder;
+mod encrypted_private_key;
 mod entries;
 mod entry;
 mod envelope;
 mod error;
 mod exif_decoder;
 mod exif_error;
+mod export_format;
+mod exporter;
 mod file;
+mod file_stat;
 mod filesystem;
 mod fingerprint;
 mod flac_builder;
@@ -337,6 +407,7 @@ mod float_ext;
 mod format;
 mod functions;
 mod hash;
+mod hash_cache;
 mod hash_error;
 mod hashing_writer;
 mod head;
@@ -367,38 +438,69 @@ mod mp3_decoder;
 mod mp3_error;
 mod mp4_builder;
 mod mp4_decoder;
+mod name_record;
+mod name_statement;
+mod name_store;
 mod number_error;
 mod open_graph_image;
 mod options;
 mod or;
 mod ordinal;
 mod orientation;
+mod outboard;
+mod outboard_error;
+mod outboard_hasher;
 mod owo_colorize_ext;
 mod package;
+mod package_name;
+mod package_name_error;
 mod page;
 mod path_error;
 mod percent_encode;
+mod policy;
+mod policy_error;
+mod policy_evaluation;
+mod policy_rule;
 mod private_key;
 mod private_key_error;
 mod progress_bar;
+mod proof;
+mod proof_error;
 mod public_key;
 mod public_key_error;
+mod purpose;
+mod quota;
+mod quotas;
 mod re;
 mod relative_path;
 mod reqwest_response_ext;
+mod resolution;
 mod resource;
 mod resource_type;
+mod revocation;
+mod revocation_error;
+mod revocation_statement;
+mod revocation_store;
+mod role;
 mod rotation;
+mod search;
+mod search_order;
 mod server;
 mod server_error;
 mod sign_options;
 mod signature;
 mod signature_error;
+mod signature_file;
+mod signer;
 mod sorted_set;
+mod ssh;
+mod ssh_error;
 mod statement;
 mod static_asset;
 mod style;
 mod subcommand;
+mod symlink;
+mod tar_walk;
 pub mod templates;
 mod text;
 mod text_error;
@@ -410,17 +512,24 @@ mod totals;
 mod totals_error;
 mod track;
 mod track_info;
+mod trust_store;
+mod trusted_key;
 mod type_name;
 mod url_error;
+mod usage;
 mod utf8_path_ext;
 mod validate;
+mod verifying_reader;
 mod version;
 mod video;
 mod video_error;
 mod video_metadata;
 mod video_type;
 mod view;
+mod walk;
 mod webm_decoder;
+mod year_range;
+mod year_range_error;
 
 #[cfg(test)]
 mod derive;
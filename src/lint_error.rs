@@ -14,6 +14,8 @@ pub(crate) enum LintError {
   CoverArtMissing,
   #[snafu(display("possible junk file"))]
   Junk,
+  #[snafu(display("symlink target `{target}` does not exist"))]
+  SymlinkDangling { target: RelativePath },
   #[snafu(display("Windows does not allow filenames that begin with spaces"))]
   WindowsLeadingSpace,
   #[snafu(display("Windows does not allow filenames that begin with `{character}`"))]
@@ -23,7 +23,7 @@ impl LintGroup {
         WindowsTrailingSpace,
       ]
       .into(),
-      Self::Content => [CoverArtMissing].into(),
+      Self::Content => [CoverArtMissing, SymlinkDangling].into(),
       Self::Distribution => &Self::Junk.lints() | &Self::Compatibility.lints(),
       Self::Junk => [Junk].into(),
     }
@@ -14,6 +14,18 @@ pub struct Manifest {
 impl Manifest {
   pub(crate) const FILENAME: &'static str = "manifest.filepack";
 
+  pub(crate) fn contents(&self) -> BTreeMap<Hash, (RelativePath, File)> {
+    let mut contents = BTreeMap::new();
+
+    for (path, file) in self.files() {
+      if !self.embedded.contains_key(&file.hash) {
+        contents.entry(file.hash).or_insert((path, file));
+      }
+    }
+
+    contents
+  }
+
   pub(crate) fn empty_directories(&self) -> BTreeSet<RelativePath> {
     let mut empty = BTreeSet::new();
 
@@ -28,7 +40,7 @@ impl Manifest {
     empty
   }
 
-  fn entries(&self) -> Entries {
+  pub(crate) fn entries(&self) -> Entries {
     self.into()
   }
 
@@ -109,28 +121,49 @@ impl Manifest {
     filesystem::write(path, cbor)
   }
 
-  pub(crate) fn sign(
-    &mut self,
-    options: SignOptions,
-    keychain: &Keychain,
-    key: &KeyName,
-  ) -> Result {
-    let statement = self.statement(options.timestamp)?;
+  pub(crate) fn sign(&mut self, options: SignOptions, signer: &Signer) -> Result {
+    let statement = self.statement(options)?;
 
-    let signature = keychain.sign(key, &statement)?;
+    let signature = signer.sign(&statement)?;
 
     self.signatures.insert(signature);
 
     Ok(())
   }
 
-  pub(crate) fn statement(&self, timestamp: bool) -> Result<Statement> {
+  pub(crate) fn statement(&self, options: SignOptions) -> Result<Statement> {
+    let expires = if let Some(expires) = options.expires {
+      ensure! {
+        expires > jiff::Timestamp::now(),
+        error::SignatureExpirationInPast { expires },
+      }
+
+      Some(u64::try_from(expires.as_second()).unwrap())
+    } else {
+      None
+    };
+
     Ok(Statement {
       fingerprint: self.fingerprint(),
-      timestamp: timestamp.then(now).transpose()?,
+      timestamp: options.timestamp.then(now).transpose()?,
+      expires,
+      purpose: options.purpose,
+      comment: options.comment,
     })
   }
 
+  pub(crate) fn symlinks(&self) -> BTreeMap<RelativePath, Symlink> {
+    let mut symlinks = BTreeMap::new();
+
+    for (path, entry) in self.entries() {
+      if let DirectoryTreeEntry::Symlink(symlink) = entry {
+        symlinks.insert(path, symlink.clone());
+      }
+    }
+
+    symlinks
+  }
+
   pub(crate) fn verify_signatures(&self) -> Result {
     let fingerprint = self.fingerprint();
 
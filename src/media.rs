@@ -6,7 +6,7 @@ use super::*;
 #[serde(deny_unknown_fields, rename_all = "snake_case", tag = "type")]
 #[strum(serialize_all = "kebab-case")]
 #[strum_discriminants(
-  derive(Display),
+  derive(Display, EnumIter, EnumString),
   name(MediaType),
   strum(serialize_all = "kebab-case"),
   vis(pub)
@@ -163,6 +163,31 @@ impl Metadata {
     Ok(())
   }
 
+  pub(crate) fn search_terms(&self) -> BTreeSet<String> {
+    let mut text = [&self.creator, &self.description, &self.title]
+      .into_iter()
+      .flatten()
+      .map(Text::as_str)
+      .collect::<Vec<&str>>();
+
+    if let Some(creator) = self
+      .package
+      .as_ref()
+      .and_then(|package| package.creator.as_ref())
+    {
+      text.push(creator);
+    }
+
+    if let Some(Media::Audio { items }) = &self.media {
+      for audio in items {
+        text.push(&audio.album);
+        text.push(&audio.artist);
+      }
+    }
+
+    text.into_iter().flat_map(Search::terms).collect()
+  }
+
   pub(crate) fn validate(&self, root: &Utf8Path) -> Result {
     if let Some(readme) = &self.readme {
       Self::check_readme(readme)?;
@@ -709,6 +734,54 @@ mod tests {
     );
   }
 
+  #[test]
+  fn search_terms() {
+    let metadata = Metadata {
+      creator: Some("Alice".parse().unwrap()),
+      description: Some("An album.".parse().unwrap()),
+      media: Some(Media::Audio {
+        items: vec![Audio {
+          album: "Greatest Hits".parse().unwrap(),
+          artist: "Bob".parse().unwrap(),
+          channels: 2,
+          disc: 1,
+          discs: 1,
+          path: "foo.flac".parse().unwrap(),
+          sample_bits: Some(16),
+          sample_rate: 44100,
+          samples: 0,
+          size: 0,
+          title: "Untitled".parse().unwrap(),
+          track: 1,
+          tracks: 1,
+          ty: AudioType::Flac,
+        }],
+      }),
+      package: Some(Package {
+        colophon: None,
+        creator: Some("Carol".parse().unwrap()),
+        description: None,
+        homepage: None,
+        time: None,
+        title: None,
+      }),
+      title: Some("Foo Bar".parse().unwrap()),
+      ..default()
+    };
+
+    assert_eq!(
+      metadata.search_terms(),
+      [
+        "alice", "an", "album", "bar", "bob", "carol", "foo", "greatest", "hits",
+      ]
+      .into_iter()
+      .map(str::to_owned)
+      .collect(),
+    );
+
+    assert!(Metadata::default().search_terms().is_empty());
+  }
+
   #[test]
   fn valid_artwork() {
     #[track_caller]
@@ -1,9 +1,13 @@
 use super::*;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, SerializeDisplay)]
 pub struct Mode(u32);
 
 impl Mode {
+  pub(crate) fn is_executable(self) -> bool {
+    self.0 & 0o100 != 0
+  }
+
   pub(crate) fn is_secure(self) -> bool {
     self.0.trailing_zeros() >= 6
   }
@@ -15,6 +19,12 @@ impl Display for Mode {
   }
 }
 
+impl From<u32> for Mode {
+  fn from(mode: u32) -> Self {
+    Self(mode)
+  }
+}
+
 #[cfg(unix)]
 impl From<Permissions> for Mode {
   fn from(permissions: Permissions) -> Self {
@@ -41,6 +51,14 @@ mod tests {
     assert_eq!(Mode(0o7777).to_string(), "0777");
   }
 
+  #[test]
+  fn is_executable() {
+    assert!(!Mode(0o644).is_executable());
+    assert!(!Mode(0o655).is_executable());
+    assert!(Mode(0o744).is_executable());
+    assert!(Mode(0o755).is_executable());
+  }
+
   #[test]
   fn is_secure() {
     assert!(Mode(0o000).is_secure());
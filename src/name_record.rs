@@ -0,0 +1,119 @@
+use super::*;
+
+#[allow(clippy::arbitrary_source_item_ordering)]
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub(crate) struct NameRecord {
+  #[n(0)]
+  statement: NameStatement,
+  #[n(1)]
+  #[cbor(
+    decode_with = NameRecord::decode_signature,
+    encode_with = NameRecord::encode_signature
+  )]
+  signature: ed25519_dalek::Signature,
+}
+
+impl NameRecord {
+  fn decode_signature(decoder: &mut Decoder) -> Result<ed25519_dalek::Signature, DecodeError> {
+    Ok(ed25519_dalek::Signature::from_bytes(&decoder.byte_array()?))
+  }
+
+  fn encode_signature(signature: &ed25519_dalek::Signature, encoder: &mut Encoder) {
+    encoder.bytes(&signature.to_bytes());
+  }
+
+  pub(crate) fn fingerprint(&self) -> Fingerprint {
+    self.statement.fingerprint
+  }
+
+  pub(crate) fn name(&self) -> &PackageName {
+    &self.statement.name
+  }
+
+  pub(crate) fn new(
+    private_key: &PrivateKey,
+    name: PackageName,
+    fingerprint: Fingerprint,
+    sequence: u64,
+  ) -> Self {
+    use ed25519_dalek::Signer;
+
+    let statement = NameStatement {
+      public_key: private_key.public_key(),
+      name,
+      fingerprint,
+      sequence,
+    };
+
+    let signature = private_key
+      .inner_secret()
+      .sign(statement.digest().as_bytes());
+
+    Self {
+      statement,
+      signature,
+    }
+  }
+
+  pub(crate) fn public_key(&self) -> PublicKey {
+    self.statement.public_key
+  }
+
+  pub(crate) fn sequence(&self) -> u64 {
+    self.statement.sequence
+  }
+
+  pub(crate) fn verify(&self) -> Result<(), DalekSignatureError> {
+    self
+      .statement
+      .public_key
+      .inner()
+      .verify_strict(self.statement.digest().as_bytes(), &self.signature)
+      .map_err(DalekSignatureError)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn modifying_fingerprint_invalidates_record() {
+    let mut record = record();
+    record.verify().unwrap();
+    record.statement.fingerprint = Fingerprint::from(Hash::bytes(b"foo"));
+    record.verify().unwrap_err();
+  }
+
+  #[test]
+  fn modifying_name_invalidates_record() {
+    let mut record = record();
+    record.statement.name = "stable".parse().unwrap();
+    record.verify().unwrap_err();
+  }
+
+  #[test]
+  fn modifying_sequence_invalidates_record() {
+    let mut record = record();
+    record.statement.sequence = 2;
+    record.verify().unwrap_err();
+  }
+
+  fn record() -> NameRecord {
+    NameRecord::new(
+      &test::PRIVATE_KEY.parse().unwrap(),
+      "latest".parse().unwrap(),
+      test::FINGERPRINT.parse().unwrap(),
+      1,
+    )
+  }
+
+  #[test]
+  fn round_trip() {
+    let record = record();
+    let decoded = NameRecord::decode_from_slice(&record.encode_to_vec()).unwrap();
+    decoded.verify().unwrap();
+    assert_eq!(decoded, record);
+    test::assert_encoding(record);
+  }
+}
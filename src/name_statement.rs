@@ -0,0 +1,52 @@
+use super::*;
+
+#[allow(clippy::arbitrary_source_item_ordering)]
+#[derive(Clone, Debug, Decode, Encode, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct NameStatement {
+  #[n(0)]
+  pub(crate) public_key: PublicKey,
+  #[n(1)]
+  pub(crate) name: PackageName,
+  #[n(2)]
+  pub(crate) fingerprint: Fingerprint,
+  #[n(3)]
+  pub(crate) sequence: u64,
+}
+
+impl NameStatement {
+  pub(crate) fn digest(&self) -> Hash {
+    let envelope = Envelope {
+      application: Application::Filepack,
+      context: Context::Name,
+      statement: self.clone(),
+    };
+
+    Hash::bytes(&envelope.encode_to_vec())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn digest() {
+    let statement = NameStatement {
+      public_key: test::PUBLIC_KEY.parse().unwrap(),
+      name: "latest".parse().unwrap(),
+      fingerprint: test::FINGERPRINT.parse().unwrap(),
+      sequence: 1,
+    };
+
+    let mut encoder = Encoder::new();
+
+    {
+      let mut encoder = encoder.map::<u64>(3);
+      encoder.item(0, "filepack");
+      encoder.item(1, 2);
+      encoder.item(2, &statement);
+    }
+
+    assert_eq!(statement.digest(), Hash::bytes(&encoder.finish()));
+  }
+}
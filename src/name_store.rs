@@ -0,0 +1,106 @@
+use super::*;
+
+#[derive(Debug)]
+pub(crate) struct NameStore {
+  names: BTreeMap<PublicKey, BTreeMap<PackageName, u64>>,
+  path: Utf8PathBuf,
+}
+
+impl NameStore {
+  const FILENAME: &'static str = "names.yaml";
+
+  pub(crate) fn load(options: &Options) -> Result<Self> {
+    let path = options.data_dir()?.join(Self::FILENAME);
+
+    let names = match filesystem::read_to_string_opt(&path)? {
+      Some(yaml) => serde_yaml::from_str(&yaml).context(error::DeserializeNames { path: &path })?,
+      None => BTreeMap::new(),
+    };
+
+    Ok(Self { names, path })
+  }
+
+  pub(crate) fn save(&self) -> Result {
+    filesystem::create_dir_all(self.path.parent().unwrap())?;
+    filesystem::write(&self.path, serde_yaml::to_string(&self.names).unwrap())
+  }
+
+  pub(crate) fn update(&mut self, record: &NameRecord) -> Result<bool> {
+    let sequences = self.names.entry(record.public_key()).or_default();
+
+    if let Some(&seen) = sequences.get(record.name()) {
+      ensure! {
+        seen <= record.sequence(),
+        error::NameRollback {
+          name: record.name().clone(),
+          public_key: record.public_key(),
+          seen,
+          sequence: record.sequence(),
+        },
+      }
+
+      if seen == record.sequence() {
+        return Ok(false);
+      }
+    }
+
+    sequences.insert(record.name().clone(), record.sequence());
+
+    Ok(true)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn record(key: &PrivateKey, sequence: u64) -> NameRecord {
+    NameRecord::new(
+      key,
+      "latest".parse().unwrap(),
+      test::FINGERPRINT.parse().unwrap(),
+      sequence,
+    )
+  }
+
+  #[test]
+  fn rollback_is_rejected() {
+    let key = PrivateKey::generate();
+
+    let mut store = store();
+
+    assert!(store.update(&record(&key, 2)).unwrap());
+
+    assert_matches!(
+      store.update(&record(&key, 1)).unwrap_err(),
+      Error::NameRollback {
+        seen: 2,
+        sequence: 1,
+        ..
+      },
+    );
+  }
+
+  #[test]
+  fn sequence_increases() {
+    let key = PrivateKey::generate();
+
+    let mut store = store();
+
+    assert!(store.update(&record(&key, 1)).unwrap());
+    assert!(!store.update(&record(&key, 1)).unwrap());
+    assert!(store.update(&record(&key, 3)).unwrap());
+
+    assert_eq!(
+      store.names[&key.public_key()][&"latest".parse::<PackageName>().unwrap()],
+      3,
+    );
+  }
+
+  fn store() -> NameStore {
+    NameStore {
+      names: BTreeMap::new(),
+      path: Utf8PathBuf::new(),
+    }
+  }
+}
@@ -2,6 +2,8 @@ use super::*;
 
 #[derive(Parser)]
 pub(crate) struct Options {
+  #[arg(skip)]
+  pub(crate) cache: Option<HashCache>,
   #[arg(
     long,
     help = "Store local data, including private keys, in <DATA_DIR>",
@@ -17,6 +19,14 @@ pub(crate) struct Options {
 }
 
 impl Options {
+  pub(crate) fn commit_cache(&self) -> Result {
+    if let Some(cache) = &self.cache {
+      cache.commit()?;
+    }
+
+    Ok(())
+  }
+
   pub(crate) fn data_dir(&self) -> Result<Utf8PathBuf> {
     if let Some(path) = &self.data_dir {
       Ok(path.into())
@@ -30,7 +40,45 @@ impl Options {
     }
   }
 
+  pub(crate) fn enable_cache(&mut self) -> Result {
+    if self.cache.is_none() {
+      self.cache = Some(HashCache::load(self)?);
+    }
+
+    Ok(())
+  }
+
   pub(crate) fn hash_file(&self, path: &Utf8Path) -> io::Result<File> {
+    let metadata = fs::metadata(path)?;
+
+    let executable = Mode::from(metadata.permissions()).is_executable();
+
+    let Some(cache) = &self.cache else {
+      return self.hash_file_uncached(path, executable);
+    };
+
+    let Some(stat) = FileStat::new(&metadata) else {
+      return self.hash_file_uncached(path, executable);
+    };
+
+    if let Some(hash) = cache.get(stat)? {
+      return Ok(File {
+        executable,
+        hash,
+        size: stat.size,
+      });
+    }
+
+    let file = self.hash_file_uncached(path, executable)?;
+
+    if file.size == stat.size && FileStat::new(&fs::metadata(path)?) == Some(stat) {
+      cache.insert(stat, file.hash);
+    }
+
+    Ok(file)
+  }
+
+  fn hash_file_uncached(&self, path: &Utf8Path, executable: bool) -> io::Result<File> {
     let mut hasher = Hasher::new();
 
     if self.parallel {
@@ -42,6 +90,7 @@ impl Options {
     }
 
     Ok(File {
+      executable,
       hash: hasher.finalize().into(),
       size: hasher.count(),
     })
@@ -0,0 +1,250 @@
+use {
+  super::*,
+  blake3::hazmat::{ChainingValue, HasherExt, Mode, merge_subtrees_non_root, merge_subtrees_root},
+};
+
+/// Chaining values of the fixed-size blocks of a file, which allow each block
+/// to be verified independently against the file's hash. Files that fit in a
+/// single block have an empty outboard, and are verified by hashing them in
+/// full.
+#[derive(Debug)]
+pub(crate) struct Outboard {
+  hash: Hash,
+  size: u64,
+  tree: Vec<ChainingValue>,
+}
+
+impl Outboard {
+  pub(crate) const BLOCK_SIZE: u64 = 1 << 18;
+  pub(crate) const CV_LEN: usize = blake3::OUT_LEN;
+
+  pub(crate) fn block(&self, offset: u64) -> Range<u64> {
+    let start = offset / Self::BLOCK_SIZE * Self::BLOCK_SIZE;
+    start..start.saturating_add(Self::BLOCK_SIZE).min(self.size)
+  }
+
+  fn block_cv(offset: u64, data: &[u8]) -> ChainingValue {
+    Hasher::new()
+      .set_input_offset(offset)
+      .update(data)
+      .finalize_non_root()
+  }
+
+  pub(crate) fn blocks(size: u64) -> u64 {
+    size.div_ceil(Self::BLOCK_SIZE).max(1)
+  }
+
+  pub(crate) fn hash(&self) -> Hash {
+    self.hash
+  }
+
+  fn merge(tree: &[ChainingValue]) -> ChainingValue {
+    if let [cv] = tree {
+      return *cv;
+    }
+
+    let (left, right) = tree.split_at(Self::split(tree.len()));
+
+    merge_subtrees_non_root(&Self::merge(left), &Self::merge(right), Mode::Hash)
+  }
+
+  pub(crate) fn new(hash: Hash, size: u64, tree: &[u8]) -> Result<Self, OutboardError> {
+    ensure! {
+      tree.len().is_multiple_of(Self::CV_LEN),
+      outboard_error::Length { length: tree.len() },
+    }
+
+    let tree = tree
+      .chunks_exact(Self::CV_LEN)
+      .map(|cv| cv.try_into().unwrap())
+      .collect::<Vec<ChainingValue>>();
+
+    let blocks = Self::blocks(size);
+
+    let expected = if blocks == 1 { 0 } else { blocks };
+
+    ensure! {
+      tree.len().into_u64() == expected,
+      outboard_error::BlockCount {
+        actual: tree.len().into_u64(),
+        expected,
+        size,
+      },
+    }
+
+    if blocks > 1 {
+      let (left, right) = tree.split_at(Self::split(tree.len()));
+
+      let actual = Hash::from(merge_subtrees_root(
+        &Self::merge(left),
+        &Self::merge(right),
+        Mode::Hash,
+      ));
+
+      ensure! {
+        actual == hash,
+        outboard_error::Root { actual, expected: hash },
+      }
+    }
+
+    Ok(Self { hash, size, tree })
+  }
+
+  pub(crate) fn size(&self) -> u64 {
+    self.size
+  }
+
+  fn split(len: usize) -> usize {
+    1 << (len - 1).ilog2()
+  }
+
+  pub(crate) fn verify_block(&self, offset: u64, data: &[u8]) -> bool {
+    let block = self.block(offset);
+
+    if block.start != offset || block.end - block.start != data.len().into_u64() {
+      return false;
+    }
+
+    if self.tree.is_empty() {
+      return Hash::bytes(data) == self.hash;
+    }
+
+    let index = usize::try_from(offset / Self::BLOCK_SIZE).unwrap();
+
+    Self::block_cv(offset, data) == self.tree[index]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn block_count_mismatch() {
+    let data = data(Outboard::BLOCK_SIZE * 3);
+
+    let mut hasher = OutboardHasher::new();
+    hasher.update(&data);
+    let tree = hasher.finalize();
+
+    assert_matches!(
+      Outboard::new(
+        Hash::bytes(&data),
+        data.len().into_u64(),
+        &tree[..Outboard::CV_LEN * 2],
+      ),
+      Err(OutboardError::BlockCount {
+        actual: 2,
+        expected: 3,
+        ..
+      }),
+    );
+
+    assert_matches!(
+      Outboard::new(Hash::bytes(b"foo"), 3, &tree),
+      Err(OutboardError::BlockCount {
+        actual: 3,
+        expected: 0,
+        ..
+      }),
+    );
+  }
+
+  #[test]
+  fn blocks() {
+    assert_eq!(Outboard::blocks(0), 1);
+    assert_eq!(Outboard::blocks(1), 1);
+    assert_eq!(Outboard::blocks(Outboard::BLOCK_SIZE), 1);
+    assert_eq!(Outboard::blocks(Outboard::BLOCK_SIZE + 1), 2);
+    assert_eq!(Outboard::blocks(Outboard::BLOCK_SIZE * 5), 5);
+  }
+
+  fn data(size: u64) -> Vec<u8> {
+    (0..size).map(|i| (i % 251) as u8).collect()
+  }
+
+  #[test]
+  fn length_mismatch() {
+    assert_matches!(
+      Outboard::new(Hash::bytes(b""), 0, &[0; 7]),
+      Err(OutboardError::Length { length: 7 }),
+    );
+  }
+
+  fn outboard(data: &[u8]) -> Outboard {
+    let mut hasher = OutboardHasher::new();
+    hasher.update(data);
+    Outboard::new(Hash::bytes(data), data.len().into_u64(), &hasher.finalize()).unwrap()
+  }
+
+  #[test]
+  fn root_mismatch() {
+    let data = data(Outboard::BLOCK_SIZE * 2);
+
+    let mut hasher = OutboardHasher::new();
+    hasher.update(&data);
+    let mut tree = hasher.finalize();
+
+    tree[0] ^= 1;
+
+    assert_matches!(
+      Outboard::new(Hash::bytes(&data), data.len().into_u64(), &tree),
+      Err(OutboardError::Root { .. }),
+    );
+  }
+
+  #[test]
+  fn single_block() {
+    for size in [0, 1, Outboard::BLOCK_SIZE] {
+      let data = data(size);
+
+      let outboard = outboard(&data);
+
+      assert!(outboard.tree.is_empty());
+
+      assert!(outboard.verify_block(0, &data));
+
+      if let Some((last, rest)) = data.split_last() {
+        assert!(!outboard.verify_block(0, rest));
+
+        let mut corrupt = data.clone();
+        *corrupt.last_mut().unwrap() = last.wrapping_add(1);
+        assert!(!outboard.verify_block(0, &corrupt));
+      }
+    }
+  }
+
+  #[test]
+  fn verify_blocks() {
+    for blocks in [2, 3, 4, 5, 8, 9] {
+      for extra in [1, Outboard::BLOCK_SIZE / 2, Outboard::BLOCK_SIZE] {
+        let size = Outboard::BLOCK_SIZE * (blocks - 1) + extra;
+
+        let data = data(size);
+
+        let outboard = outboard(&data);
+
+        assert_eq!(outboard.tree.len().into_u64(), blocks);
+
+        let mut offset = 0;
+
+        while offset < size {
+          let block = outboard.block(offset);
+
+          let content =
+            &data[usize::try_from(block.start).unwrap()..usize::try_from(block.end).unwrap()];
+
+          assert!(outboard.verify_block(offset, content));
+
+          assert!(!outboard.verify_block(offset, &content[1..]));
+
+          let mut corrupt = content.to_vec();
+          corrupt[0] ^= 1;
+          assert!(!outboard.verify_block(offset, &corrupt));
+
+          offset = block.end;
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,20 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub enum OutboardError {
+  #[snafu(display(
+    "outboard for {size} byte file should contain {} but contains {}",
+    Count::new(*expected, "block"),
+    Count::new(*actual, "block"),
+  ))]
+  BlockCount {
+    actual: u64,
+    expected: u64,
+    size: u64,
+  },
+  #[snafu(display("outboard length {length} is not a multiple of {}", Outboard::CV_LEN))]
+  Length { length: usize },
+  #[snafu(display("outboard root mismatch: expected {expected} but got {actual}"))]
+  Root { actual: Hash, expected: Hash },
+}
@@ -0,0 +1,51 @@
+use {super::*, blake3::hazmat::HasherExt};
+
+pub(crate) struct OutboardHasher {
+  block: Hasher,
+  filled: u64,
+  offset: u64,
+  tree: Vec<u8>,
+}
+
+impl OutboardHasher {
+  pub(crate) fn finalize(mut self) -> Vec<u8> {
+    if self.offset + self.filled <= Outboard::BLOCK_SIZE {
+      return Vec::new();
+    }
+
+    if self.filled > 0 {
+      self.tree.extend(self.block.finalize_non_root());
+    }
+
+    self.tree
+  }
+
+  pub(crate) fn new() -> Self {
+    Self {
+      block: Hasher::new(),
+      filled: 0,
+      offset: 0,
+      tree: Vec::new(),
+    }
+  }
+
+  pub(crate) fn update(&mut self, mut data: &[u8]) {
+    while !data.is_empty() {
+      if self.filled == Outboard::BLOCK_SIZE {
+        self.tree.extend(self.block.finalize_non_root());
+        self.offset += Outboard::BLOCK_SIZE;
+        self.filled = 0;
+        self.block = Hasher::new();
+        self.block.set_input_offset(self.offset);
+      }
+
+      let n = usize::try_from(Outboard::BLOCK_SIZE - self.filled)
+        .unwrap_or(usize::MAX)
+        .min(data.len());
+
+      self.block.update(&data[..n]);
+      self.filled += n.into_u64();
+      data = &data[n..];
+    }
+  }
+}
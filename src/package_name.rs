@@ -0,0 +1,81 @@
+use super::*;
+
+#[derive(
+  Clone,
+  Debug,
+  DecodeFromStr,
+  DeserializeFromStr,
+  EncodeDisplay,
+  Eq,
+  Ord,
+  PartialEq,
+  PartialOrd,
+  SerializeDisplay,
+)]
+pub struct PackageName(String);
+
+impl PackageName {
+  pub(crate) const MAX_LEN: usize = 128;
+
+  pub(crate) fn as_str(&self) -> &str {
+    &self.0
+  }
+}
+
+impl Display for PackageName {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl FromStr for PackageName {
+  type Err = PackageNameError;
+
+  fn from_str(name: &str) -> Result<Self, Self::Err> {
+    ensure! {
+      re::KEY_NAME.is_match(name) && name.len() <= Self::MAX_LEN,
+      package_name_error::Invalid { name },
+    }
+
+    Ok(Self(name.into()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn encoding() {
+    assert_cbor("latest".parse::<PackageName>().unwrap(), "666c6174657374");
+  }
+
+  #[test]
+  fn validity() {
+    #[track_caller]
+    fn valid(s: &str) {
+      assert_eq!(s.parse::<PackageName>().unwrap().as_str(), s);
+    }
+
+    #[track_caller]
+    fn invalid(s: &str) {
+      assert_eq!(
+        s.parse::<PackageName>().unwrap_err().to_string(),
+        format!("invalid package name `{s}`"),
+      );
+    }
+
+    valid("0");
+    valid("latest");
+    valid("foo-bar");
+    valid(&"a".repeat(128));
+
+    invalid("");
+    invalid("-");
+    invalid("foo-");
+    invalid("foo/bar");
+    invalid("Latest");
+    invalid("foo.bar");
+    invalid(&"a".repeat(129));
+  }
+}
@@ -0,0 +1,8 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub enum PackageNameError {
+  #[snafu(display("invalid package name `{name}`"))]
+  Invalid { name: String },
+}
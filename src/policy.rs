@@ -0,0 +1,227 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Policy {
+  pub(crate) keys: BTreeMap<KeyName, PublicKey>,
+  pub(crate) rules: BTreeMap<String, PolicyRule>,
+}
+
+impl Policy {
+  pub(crate) fn evaluate<'a>(
+    &'a self,
+    signatures: impl IntoIterator<Item = &'a Signature>,
+  ) -> PolicyEvaluation<'a> {
+    let public_keys = signatures
+      .into_iter()
+      .map(Signature::public_key)
+      .collect::<BTreeSet<PublicKey>>();
+
+    PolicyEvaluation {
+      policy: self,
+      signers: self
+        .keys
+        .iter()
+        .filter(|(_name, key)| public_keys.contains(key))
+        .map(|(name, _key)| name)
+        .collect(),
+    }
+  }
+
+  pub(crate) fn load(path: &Utf8Path) -> Result<Self> {
+    filesystem::read_to_string(path)?
+      .parse()
+      .context(error::PolicyLoad { path })
+  }
+}
+
+impl FromStr for Policy {
+  type Err = PolicyError;
+
+  fn from_str(yaml: &str) -> Result<Self, Self::Err> {
+    let policy = serde_yaml::from_str::<Self>(yaml).context(policy_error::Deserialize)?;
+
+    let mut names = BTreeMap::new();
+    for (second, key) in &policy.keys {
+      if let Some(first) = names.insert(*key, second) {
+        return Err(
+          policy_error::KeyDuplicate {
+            first: first.clone(),
+            second: second.clone(),
+          }
+          .build(),
+        );
+      }
+    }
+
+    ensure!(!policy.rules.is_empty(), policy_error::RulesMissing);
+
+    for (name, rule) in &policy.rules {
+      for key in &rule.keys {
+        ensure! {
+          policy.keys.contains_key(key),
+          policy_error::KeyUnknown {
+            key: key.clone(),
+            rule: name.clone(),
+          },
+        }
+      }
+
+      ensure! {
+        rule.threshold > 0 && rule.threshold <= rule.keys.len().into_u64(),
+        policy_error::Threshold {
+          keys: rule.keys.len(),
+          rule: name.clone(),
+          threshold: rule.threshold,
+        },
+      }
+    }
+
+    Ok(policy)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::fmt::Write};
+
+  #[test]
+  fn duplicate_key() {
+    let alice = PrivateKey::generate();
+
+    assert_matches!(
+      policy(
+        &[("alice", &alice), ("bob", &alice)],
+        "
+          rules:
+            release:
+              keys: [alice, bob]
+              threshold: 1
+        ",
+      ),
+      Err(PolicyError::KeyDuplicate { first, second })
+        if first.to_string() == "alice" && second.to_string() == "bob",
+    );
+  }
+
+  fn policy(keys: &[(&str, &PrivateKey)], rules: &str) -> Result<Policy, PolicyError> {
+    let mut yaml = "keys:\n".to_owned();
+
+    for (name, key) in keys {
+      writeln!(yaml, "  {name}: {}", key.public_key()).unwrap();
+    }
+
+    yaml.push_str(&unindent(rules));
+
+    yaml.parse()
+  }
+
+  #[test]
+  fn rules_missing() {
+    assert_matches!(
+      policy(&[("alice", &PrivateKey::generate())], "rules: {}\n"),
+      Err(PolicyError::RulesMissing),
+    );
+  }
+
+  fn sign(key: &PrivateKey) -> Signature {
+    key.sign(&Statement {
+      fingerprint: test::FINGERPRINT.parse().unwrap(),
+      timestamp: None,
+      expires: None,
+      purpose: None,
+      comment: None,
+    })
+  }
+
+  #[test]
+  fn threshold() {
+    let alice = PrivateKey::generate();
+    let bob = PrivateKey::generate();
+    let carol = PrivateKey::generate();
+    let dave = PrivateKey::generate();
+
+    let policy = policy(
+      &[
+        ("alice", &alice),
+        ("bob", &bob),
+        ("carol", &carol),
+        ("dave", &dave),
+      ],
+      "
+        rules:
+          release:
+            keys: [alice, bob, carol, dave]
+            threshold: 2
+      ",
+    )
+    .unwrap();
+
+    let signatures = [sign(&alice)];
+    let evaluation = policy.evaluate(&signatures);
+    assert!(!evaluation.is_satisfied());
+    assert_eq!(evaluation.signers.len(), 1);
+
+    let signatures = [sign(&alice), sign(&PrivateKey::generate())];
+    assert!(!policy.evaluate(&signatures).is_satisfied());
+
+    let signatures = [sign(&bob), sign(&dave)];
+    let evaluation = policy.evaluate(&signatures);
+    assert!(evaluation.is_satisfied());
+    assert_eq!(
+      evaluation
+        .signers
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>(),
+      ["bob", "dave"],
+    );
+  }
+
+  #[test]
+  fn threshold_out_of_range() {
+    let alice = PrivateKey::generate();
+
+    for threshold in [0, 2] {
+      assert_matches!(
+        policy(
+          &[("alice", &alice)],
+          &format!(
+            "
+              rules:
+                release:
+                  keys: [alice]
+                  threshold: {threshold}
+            "
+          ),
+        ),
+        Err(PolicyError::Threshold { keys: 1, .. }),
+      );
+    }
+  }
+
+  #[test]
+  fn unknown_fields_are_rejected() {
+    assert_matches!(
+      policy(&[], "rules: {}\nfoo: bar\n"),
+      Err(PolicyError::Deserialize { .. }),
+    );
+  }
+
+  #[test]
+  fn unknown_key() {
+    assert_matches!(
+      policy(
+        &[("alice", &PrivateKey::generate())],
+        "
+          rules:
+            release:
+              keys: [alice, bob]
+              threshold: 1
+        ",
+      ),
+      Err(PolicyError::KeyUnknown { key, rule })
+        if key.to_string() == "bob" && rule == "release",
+    );
+  }
+}
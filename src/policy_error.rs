@@ -0,0 +1,22 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub enum PolicyError {
+  #[snafu(display("failed to deserialize policy"))]
+  Deserialize { source: serde_yaml::Error },
+  #[snafu(display("keys `{first}` and `{second}` have the same public key"))]
+  KeyDuplicate { first: KeyName, second: KeyName },
+  #[snafu(display("rule `{rule}` references unknown key `{key}`"))]
+  KeyUnknown { key: KeyName, rule: String },
+  #[snafu(display("policy has no rules"))]
+  RulesMissing,
+  #[snafu(display(
+    "rule `{rule}` threshold {threshold} must be between 1 and its number of keys, {keys}"
+  ))]
+  Threshold {
+    keys: usize,
+    rule: String,
+    threshold: u64,
+  },
+}
@@ -0,0 +1,60 @@
+use super::*;
+
+pub(crate) struct PolicyEvaluation<'a> {
+  pub(crate) policy: &'a Policy,
+  pub(crate) signers: BTreeSet<&'a KeyName>,
+}
+
+impl PolicyEvaluation<'_> {
+  pub(crate) fn is_satisfied(&self) -> bool {
+    self
+      .policy
+      .rules
+      .values()
+      .any(|rule| rule.is_satisfied(&self.signers))
+  }
+}
+
+impl Display for PolicyEvaluation<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let style = Style::stderr();
+
+    write!(f, "policy signers:")?;
+
+    if self.signers.is_empty() {
+      write!(f, " none")?;
+    }
+
+    for (i, signer) in self.signers.iter().enumerate() {
+      if i > 0 {
+        write!(f, ",")?;
+      }
+
+      write!(f, " {}", signer.style(style.good()))?;
+    }
+
+    writeln!(f)?;
+
+    for (name, rule) in &self.policy.rules {
+      let signed = rule.signed(&self.signers);
+
+      if rule.is_satisfied(&self.signers) {
+        writeln!(
+          f,
+          "policy rule `{name}` {}: {signed} of {} required signatures",
+          "satisfied".style(style.good()),
+          rule.threshold,
+        )?;
+      } else {
+        writeln!(
+          f,
+          "policy rule `{name}` {}: {signed} of {} required signatures",
+          "not satisfied".style(style.bad()),
+          rule.threshold,
+        )?;
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct PolicyRule {
+  pub(crate) keys: BTreeSet<KeyName>,
+  pub(crate) threshold: u64,
+}
+
+impl PolicyRule {
+  pub(crate) fn is_satisfied(&self, signers: &BTreeSet<&KeyName>) -> bool {
+    self.signed(signers) >= self.threshold
+  }
+
+  pub(crate) fn signed(&self, signers: &BTreeSet<&KeyName>) -> u64 {
+    self
+      .keys
+      .iter()
+      .filter(|key| signers.contains(key))
+      .count()
+      .into_u64()
+  }
+}
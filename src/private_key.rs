@@ -35,12 +35,23 @@ impl PrivateKey {
     let private_key = filesystem::read_to_string_opt(path)?
       .ok_or_else(|| error::PrivateKeyNotFound { path }.build())?;
 
-    let private_key = private_key
-      .trim()
-      .parse::<Self>()
-      .context(error::PrivateKeyLoad { path })?;
+    let private_key = private_key.trim();
+
+    if EncryptedPrivateKey::is_encrypted(private_key) {
+      let encrypted = private_key
+        .parse::<EncryptedPrivateKey>()
+        .context(error::PrivateKeyLoad { path })?;
+
+      let passphrase = read_passphrase(&format!("passphrase for `{path}`: "), false)?;
 
-    Ok(private_key)
+      return encrypted
+        .decrypt(&passphrase)
+        .context(error::PrivateKeyLoad { path });
+    }
+
+    private_key
+      .parse::<Self>()
+      .context(error::PrivateKeyLoad { path })
   }
 
   pub fn public_key(&self) -> PublicKey {
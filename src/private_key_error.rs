@@ -5,6 +5,10 @@ use super::*;
 pub enum PrivateKeyError {
   #[snafu(transparent)]
   Bech32 { source: Bech32Error },
+  #[snafu(display("incorrect passphrase"))]
+  Decrypt,
+  #[snafu(display("invalid embedded public key"))]
+  PublicKey { source: PublicKeyError },
   #[snafu(display("private key derived public key does not match embedded public key"))]
   PublicKeyMismatch,
 }
@@ -0,0 +1,250 @@
+use super::*;
+
+#[allow(clippy::arbitrary_source_item_ordering)]
+#[derive(Clone, Debug, Decode, Encode, PartialEq)]
+pub(crate) struct Proof {
+  #[n(0)]
+  pub(crate) version: Version,
+  #[n(1)]
+  pub(crate) path: RelativePath,
+  #[n(2)]
+  pub(crate) directories: Vec<Vec<u8>>,
+}
+
+impl Proof {
+  pub(crate) fn new<E>(
+    fingerprint: Fingerprint,
+    path: &RelativePath,
+    mut directory: impl FnMut(Hash) -> Result<(Directory, Vec<u8>), E>,
+  ) -> Result<Option<Self>, E> {
+    let mut hash = Hash::from(fingerprint);
+
+    let mut directories = Vec::new();
+
+    let mut components = path.components().peekable();
+
+    while let Some(component) = components.next() {
+      let (directory, cbor) = directory(hash)?;
+
+      directories.push(cbor);
+
+      match (directory.entries.get(component), components.peek()) {
+        (Some(Entry::File { .. }), None) => {
+          return Ok(Some(Self {
+            directories,
+            path: path.clone(),
+            version: Version::Zero,
+          }));
+        }
+        (Some(Entry::Directory { hash: child, .. }), Some(_)) => hash = *child,
+        _ => break,
+      }
+    }
+
+    Ok(None)
+  }
+
+  pub(crate) fn verify(&self, fingerprint: Fingerprint) -> Result<File, ProofError> {
+    ensure! {
+      self.version == Version::Zero,
+      proof_error::UnsupportedVersion { version: self.version },
+    }
+
+    let components = self.path.components().collect::<Vec<&Component>>();
+
+    ensure! {
+      components.len() == self.directories.len(),
+      proof_error::DirectoryCount {
+        actual: self.directories.len(),
+        expected: components.len(),
+        path: self.path.clone(),
+      },
+    }
+
+    let mut expected = Hash::from(fingerprint);
+
+    for (i, (cbor, component)) in self.directories.iter().zip(components).enumerate() {
+      let actual = Hash::bytes(cbor);
+
+      ensure! {
+        actual == expected,
+        proof_error::DirectoryHash { actual, expected },
+      }
+
+      let directory = Directory::decode_from_slice(cbor)
+        .context(proof_error::DirectoryDecode { hash: actual })?;
+
+      let entry = directory
+        .entries
+        .get(component)
+        .context(proof_error::EntryMissing {
+          component: component.to_owned(),
+          directory: actual,
+        })?;
+
+      let last = i + 1 == self.directories.len();
+
+      match (entry, last) {
+        (Entry::File { .. }, true) => return Ok(entry.to_file().unwrap()),
+        (Entry::Directory { hash, .. }, false) => expected = *hash,
+        _ => {
+          return Err(
+            proof_error::EntryTypeMismatch {
+              actual: entry.ty(),
+              component: component.to_owned(),
+              expected: if last {
+                EntryType::File
+              } else {
+                EntryType::Directory
+              },
+            }
+            .build(),
+          );
+        }
+      }
+    }
+
+    Err(proof_error::Empty.build())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn archive() -> Archive {
+    let mut package = DirectoryTree::new();
+
+    package
+      .create_file(&"foo".parse().unwrap(), File::new(b"foo"))
+      .unwrap();
+
+    package
+      .create_file(&"bar/baz".parse().unwrap(), File::new(b"baz"))
+      .unwrap();
+
+    Archive::pack(&Manifest {
+      embedded: BTreeMap::new(),
+      package,
+      signatures: BTreeSet::new(),
+    })
+    .unwrap()
+  }
+
+  #[test]
+  fn directory_count_mismatch() {
+    let archive = archive();
+
+    let mut proof = archive.proof(&"bar/baz".parse().unwrap()).unwrap().unwrap();
+
+    proof.directories.pop();
+
+    assert_matches!(
+      proof.verify(archive.fingerprint().unwrap()),
+      Err(ProofError::DirectoryCount {
+        actual: 1,
+        expected: 2,
+        ..
+      }),
+    );
+  }
+
+  #[test]
+  fn directory_hash_mismatch() {
+    let archive = archive();
+
+    let mut proof = archive.proof(&"bar/baz".parse().unwrap()).unwrap().unwrap();
+
+    proof.directories.swap(0, 1);
+
+    assert_matches!(
+      proof.verify(archive.fingerprint().unwrap()),
+      Err(ProofError::DirectoryHash { .. }),
+    );
+  }
+
+  #[test]
+  fn encoding() {
+    let proof = archive()
+      .proof(&"bar/baz".parse().unwrap())
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(
+      Proof::decode_from_slice(&proof.encode_to_vec()).unwrap(),
+      proof
+    );
+  }
+
+  #[test]
+  fn missing_paths_have_no_proof() {
+    let archive = archive();
+
+    assert_eq!(archive.proof(&"qux".parse().unwrap()).unwrap(), None);
+    assert_eq!(archive.proof(&"bar".parse().unwrap()).unwrap(), None);
+    assert_eq!(archive.proof(&"foo/bar".parse().unwrap()).unwrap(), None);
+    assert_eq!(archive.proof(&"bar/qux".parse().unwrap()).unwrap(), None);
+  }
+
+  #[test]
+  fn path_mismatch() {
+    let archive = archive();
+
+    let mut proof = archive.proof(&"foo".parse().unwrap()).unwrap().unwrap();
+
+    proof.path = "bar".parse().unwrap();
+
+    assert_matches!(
+      proof.verify(archive.fingerprint().unwrap()),
+      Err(ProofError::EntryTypeMismatch {
+        actual: EntryType::Directory,
+        expected: EntryType::File,
+        ..
+      }),
+    );
+
+    proof.path = "qux".parse().unwrap();
+
+    assert_matches!(
+      proof.verify(archive.fingerprint().unwrap()),
+      Err(ProofError::EntryMissing { .. }),
+    );
+  }
+
+  #[test]
+  fn valid() {
+    let archive = archive();
+
+    let fingerprint = archive.fingerprint().unwrap();
+
+    assert_eq!(
+      archive
+        .proof(&"foo".parse().unwrap())
+        .unwrap()
+        .unwrap()
+        .verify(fingerprint)
+        .unwrap(),
+      File::new(b"foo"),
+    );
+
+    assert_eq!(
+      archive
+        .proof(&"bar/baz".parse().unwrap())
+        .unwrap()
+        .unwrap()
+        .verify(fingerprint)
+        .unwrap(),
+      File::new(b"baz"),
+    );
+  }
+
+  #[test]
+  fn wrong_fingerprint() {
+    let proof = archive().proof(&"foo".parse().unwrap()).unwrap().unwrap();
+
+    assert_matches!(
+      proof.verify(Fingerprint(Hash::bytes(b"foo"))),
+      Err(ProofError::DirectoryHash { .. }),
+    );
+  }
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub enum ProofError {
+  #[snafu(display(
+    "proof contains {} but path `{path}` has {}",
+    Count::irregular(*actual, "directory", "directories"),
+    Count::new(*expected, "component"),
+  ))]
+  DirectoryCount {
+    actual: usize,
+    expected: usize,
+    path: RelativePath,
+  },
+  #[snafu(display("failed to decode proof directory {hash}"))]
+  DirectoryDecode { hash: Hash, source: DecodeError },
+  #[snafu(display("proof directory hash mismatch: expected {expected} but got {actual}"))]
+  DirectoryHash { actual: Hash, expected: Hash },
+  #[snafu(display("proof contains no directories"))]
+  Empty,
+  #[snafu(display("proof directory {directory} missing entry `{component}`"))]
+  EntryMissing {
+    component: ComponentBuf,
+    directory: Hash,
+  },
+  #[snafu(display("expected proof entry `{component}` to be {expected} but found {actual}"))]
+  EntryTypeMismatch {
+    actual: EntryType,
+    component: ComponentBuf,
+    expected: EntryType,
+  },
+  #[snafu(display("proof version {version:?} not supported"))]
+  UnsupportedVersion { version: Version },
+}
@@ -89,6 +89,46 @@ impl PartialOrd for PublicKey {
   }
 }
 
+impl redb::Key for PublicKey {
+  fn compare(a: &[u8], b: &[u8]) -> Ordering {
+    a.cmp(b)
+  }
+}
+
+impl redb::Value for PublicKey {
+  type AsBytes<'a>
+    = &'a [u8; Self::LEN]
+  where
+    Self: 'a;
+
+  type SelfType<'a>
+    = PublicKey
+  where
+    Self: 'a;
+
+  fn as_bytes<'a, 'b: 'a>(value: &'a Self::SelfType<'b>) -> Self::AsBytes<'a>
+  where
+    Self: 'b,
+  {
+    value.0.as_bytes()
+  }
+
+  fn fixed_width() -> Option<usize> {
+    Some(Self::LEN)
+  }
+
+  fn from_bytes<'a>(data: &'a [u8]) -> Self::SelfType<'a>
+  where
+    Self: 'a,
+  {
+    Self::from_bytes(data.try_into().unwrap()).unwrap()
+  }
+
+  fn type_name() -> redb::TypeName {
+    TypeName::PublicKey.into()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -99,6 +139,16 @@ mod tests {
     assert_eq!(key.to_string().parse::<PublicKey>().unwrap(), key);
   }
 
+  #[test]
+  fn redb() {
+    test::assert_redb_impls(&[
+      PrivateKey::generate().public_key(),
+      PrivateKey::generate().public_key(),
+      PrivateKey::generate().public_key(),
+      test::PUBLIC_KEY.parse::<PublicKey>().unwrap(),
+    ]);
+  }
+
   #[test]
   fn weak_public_keys_are_forbidden() {
     assert_matches!(
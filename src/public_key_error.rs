@@ -12,6 +12,8 @@ pub enum PublicKeyError {
   },
   #[snafu(display("invalid public key name `{name}`"))]
   Name { name: String },
+  #[snafu(display("invalid OpenSSH public key `{key}`"))]
+  Ssh { key: String, source: Box<SshError> },
   #[snafu(display("weak public key: `{key}`"))]
   Weak { key: String },
 }
@@ -0,0 +1,36 @@
+use super::*;
+
+#[derive(
+  Clone,
+  Copy,
+  Debug,
+  DecodeFromStr,
+  Display,
+  EncodeDisplay,
+  EnumString,
+  Eq,
+  Ord,
+  PartialEq,
+  PartialOrd,
+  Serialize,
+  ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Purpose {
+  Mirror,
+  Release,
+  Review,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn encoding() {
+    assert_cbor(Purpose::Release, "6772656c65617365");
+    assert_encoding(Purpose::Mirror);
+    assert_encoding(Purpose::Review);
+  }
+}
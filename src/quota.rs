@@ -0,0 +1,77 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Quota {
+  #[serde(default)]
+  pub(crate) bytes: Option<u64>,
+  #[serde(default)]
+  pub(crate) files: Option<u64>,
+}
+
+impl Quota {
+  pub(crate) fn allows(self, usage: Usage) -> bool {
+    self.bytes.is_none_or(|bytes| usage.bytes <= bytes)
+      && self.files.is_none_or(|files| usage.files <= files)
+  }
+
+  pub(crate) fn is_unlimited(self) -> bool {
+    self.bytes.is_none() && self.files.is_none()
+  }
+}
+
+impl Display for Quota {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match (self.bytes, self.files) {
+      (Some(bytes), Some(files)) => write!(
+        f,
+        "{} and {}",
+        Count::new(bytes, "byte"),
+        Count::new(files, "file"),
+      ),
+      (Some(bytes), None) => write!(f, "{}", Count::new(bytes, "byte")),
+      (None, Some(files)) => write!(f, "{}", Count::new(files, "file")),
+      (None, None) => write!(f, "unlimited"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn allows() {
+    #[track_caller]
+    fn case(bytes: Option<u64>, files: Option<u64>, usage: (u64, u64), expected: bool) {
+      assert_eq!(
+        Quota { bytes, files }.allows(Usage {
+          bytes: usage.0,
+          files: usage.1,
+        }),
+        expected,
+      );
+    }
+
+    case(None, None, (u64::MAX, u64::MAX), true);
+    case(Some(10), None, (10, u64::MAX), true);
+    case(Some(10), None, (11, 0), false);
+    case(None, Some(2), (u64::MAX, 2), true);
+    case(None, Some(2), (0, 3), false);
+    case(Some(10), Some(2), (10, 2), true);
+    case(Some(10), Some(2), (10, 3), false);
+  }
+
+  #[test]
+  fn display() {
+    #[track_caller]
+    fn case(bytes: Option<u64>, files: Option<u64>, expected: &str) {
+      assert_eq!(Quota { bytes, files }.to_string(), expected);
+    }
+
+    case(None, None, "unlimited");
+    case(Some(1), None, "1 byte");
+    case(None, Some(2), "2 files");
+    case(Some(100), Some(1), "100 bytes and 1 file");
+  }
+}
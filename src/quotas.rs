@@ -0,0 +1,8 @@
+use super::*;
+
+#[derive(Debug, Default)]
+pub(crate) struct Quotas {
+  pub(crate) exempt_shared: bool,
+  pub(crate) keys: BTreeMap<PublicKey, Quota>,
+  pub(crate) server: Quota,
+}
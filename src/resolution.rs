@@ -0,0 +1,6 @@
+use super::*;
+
+pub(crate) enum Resolution {
+  File(Hash),
+  Symlink(RelativePath),
+}
@@ -0,0 +1,230 @@
+use super::*;
+
+const REPLACEMENT: Fe32 = Fe32::R;
+
+#[allow(clippy::arbitrary_source_item_ordering)]
+#[derive(Clone, Debug, Decode, Encode, DeserializeFromStr, Eq, PartialEq, SerializeDisplay)]
+pub struct Revocation {
+  #[n(0)]
+  statement: RevocationStatement,
+  #[n(1)]
+  #[cbor(
+    decode_with = Revocation::decode_signature,
+    encode_with = Revocation::encode_signature
+  )]
+  signature: ed25519_dalek::Signature,
+}
+
+impl Revocation {
+  fn comparison_key(&self) -> (&RevocationStatement, [u8; 64]) {
+    (&self.statement, self.signature.to_bytes())
+  }
+
+  fn decode_signature(decoder: &mut Decoder) -> Result<ed25519_dalek::Signature, DecodeError> {
+    Ok(ed25519_dalek::Signature::from_bytes(&decoder.byte_array()?))
+  }
+
+  fn encode_signature(signature: &ed25519_dalek::Signature, encoder: &mut Encoder) {
+    encoder.bytes(&signature.to_bytes());
+  }
+
+  pub(crate) fn new(private_key: &PrivateKey, time: u64, replacement: Option<PublicKey>) -> Self {
+    use ed25519_dalek::Signer;
+
+    let statement = RevocationStatement {
+      public_key: private_key.public_key(),
+      time,
+      replacement,
+    };
+
+    let signature = private_key
+      .inner_secret()
+      .sign(statement.digest().as_bytes());
+
+    Self {
+      statement,
+      signature,
+    }
+  }
+
+  pub fn public_key(&self) -> PublicKey {
+    self.statement.public_key
+  }
+
+  pub fn replacement(&self) -> Option<PublicKey> {
+    self.statement.replacement
+  }
+
+  pub(crate) fn revokes(&self, signature: &Signature) -> bool {
+    signature.public_key() == self.statement.public_key
+      && signature
+        .statement()
+        .timestamp
+        .is_none_or(|timestamp| timestamp >= self.statement.time)
+  }
+
+  pub fn time(&self) -> u64 {
+    self.statement.time
+  }
+
+  pub(crate) fn timestamp(&self) -> jiff::Timestamp {
+    i64::try_from(self.statement.time)
+      .ok()
+      .and_then(|second| jiff::Timestamp::from_second(second).ok())
+      .unwrap_or(jiff::Timestamp::MAX)
+  }
+
+  pub(crate) fn verify(&self) -> Result<(), DalekSignatureError> {
+    self
+      .statement
+      .public_key
+      .inner()
+      .verify_strict(self.statement.digest().as_bytes(), &self.signature)
+      .map_err(DalekSignatureError)
+  }
+}
+
+impl Display for Revocation {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let mut encoder = Bech32Encoder::new(Bech32Type::Revocation);
+    encoder.bytes(&self.statement.public_key.inner().to_bytes());
+    encoder.bytes(&self.statement.time.to_le_bytes());
+    encoder.bytes(&self.signature.to_bytes());
+    if let Some(replacement) = self.statement.replacement {
+      encoder.fe(REPLACEMENT);
+      encoder.bytes(&replacement.inner().to_bytes());
+    }
+    write!(f, "{encoder}")
+  }
+}
+
+impl FromStr for Revocation {
+  type Err = RevocationError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut decoder = Bech32Decoder::new(Bech32Type::Revocation, s)?;
+    let public_key = decoder.byte_array()?;
+    let time = u64::from_le_bytes(decoder.byte_array()?);
+    let signature = decoder.byte_array()?;
+
+    let mut tag = decoder.fe();
+
+    let replacement = if tag == Some(REPLACEMENT) {
+      let replacement =
+        PublicKey::from_bytes(decoder.byte_array()?).context(revocation_error::PublicKey)?;
+      tag = decoder.fe();
+      Some(replacement)
+    } else {
+      None
+    };
+
+    if let Some(tag) = tag {
+      return Err(revocation_error::Field { tag }.build());
+    }
+
+    decoder.done()?;
+
+    Ok(Self {
+      statement: RevocationStatement {
+        public_key: PublicKey::from_bytes(public_key).context(revocation_error::PublicKey)?,
+        time,
+        replacement,
+      },
+      signature: ed25519_dalek::Signature::from_bytes(&signature),
+    })
+  }
+}
+
+impl Ord for Revocation {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.comparison_key().cmp(&other.comparison_key())
+  }
+}
+
+impl PartialOrd for Revocation {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn modifying_replacement_invalidates_revocation() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let mut revocation = Revocation::new(&private_key, 1000, None);
+    revocation.verify().unwrap();
+    revocation.statement.replacement = Some(PrivateKey::generate().public_key());
+    revocation.verify().unwrap_err();
+  }
+
+  #[test]
+  fn modifying_time_invalidates_revocation() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let mut revocation = Revocation::new(&private_key, 1000, None);
+    revocation.statement.time = 2000;
+    revocation.verify().unwrap_err();
+  }
+
+  #[test]
+  fn revokes() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let revocation = Revocation::new(&private_key, 1000, None);
+
+    let signature = |key: &PrivateKey, timestamp| {
+      key.sign(&Statement {
+        fingerprint: test::FINGERPRINT.parse().unwrap(),
+        timestamp,
+        expires: None,
+        purpose: None,
+        comment: None,
+      })
+    };
+
+    assert!(!revocation.revokes(&signature(&private_key, Some(999))));
+    assert!(revocation.revokes(&signature(&private_key, Some(1000))));
+    assert!(revocation.revokes(&signature(&private_key, None)));
+    assert!(!revocation.revokes(&signature(&PrivateKey::generate(), None)));
+  }
+
+  #[test]
+  fn round_trip() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let revocation = Revocation::new(&private_key, 1000, None);
+    let parsed = revocation.to_string().parse::<Revocation>().unwrap();
+    assert_eq!(parsed, revocation);
+    parsed.verify().unwrap();
+    test::assert_encoding(revocation);
+  }
+
+  #[test]
+  fn round_trip_with_replacement() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let revocation = Revocation::new(
+      &private_key,
+      u64::MAX,
+      Some(PrivateKey::generate().public_key()),
+    );
+    let parsed = revocation.to_string().parse::<Revocation>().unwrap();
+    assert_eq!(parsed, revocation);
+    parsed.verify().unwrap();
+    test::assert_encoding(revocation);
+  }
+
+  #[test]
+  fn unexpected_field_error() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let revocation = Revocation::new(&private_key, 1000, None).to_string();
+    let mut s = revocation[..revocation.len() - 6].to_string();
+    s.push('z');
+    assert_eq!(
+      test::checksum(&s)
+        .parse::<Revocation>()
+        .unwrap_err()
+        .to_string(),
+      "unexpected revocation field `z`",
+    );
+  }
+}
@@ -0,0 +1,12 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub enum RevocationError {
+  #[snafu(transparent)]
+  Bech32 { source: Bech32Error },
+  #[snafu(display("unexpected revocation field `{tag}`"))]
+  Field { tag: Fe32 },
+  #[snafu(display("revocation public key invalid"))]
+  PublicKey { source: PublicKeyError },
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+#[allow(clippy::arbitrary_source_item_ordering)]
+#[derive(Clone, Debug, Decode, Encode, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct RevocationStatement {
+  #[n(0)]
+  pub(crate) public_key: PublicKey,
+  #[n(1)]
+  pub(crate) time: u64,
+  #[n(2)]
+  pub(crate) replacement: Option<PublicKey>,
+}
+
+impl RevocationStatement {
+  pub(crate) fn digest(&self) -> Hash {
+    let envelope = Envelope {
+      application: Application::Filepack,
+      context: Context::Revocation,
+      statement: self.clone(),
+    };
+
+    Hash::bytes(&envelope.encode_to_vec())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn digest() {
+    let statement = RevocationStatement {
+      public_key: test::PUBLIC_KEY.parse().unwrap(),
+      time: 1000,
+      replacement: None,
+    };
+
+    let mut encoder = Encoder::new();
+
+    {
+      let mut encoder = encoder.map::<u64>(3);
+      encoder.item(0, "filepack");
+      encoder.item(1, 1);
+      encoder.item(2, &statement);
+    }
+
+    assert_eq!(statement.digest(), Hash::bytes(&encoder.finish()));
+  }
+
+  #[test]
+  fn digest_differs_from_statement_digest() {
+    let public_key = test::PUBLIC_KEY.parse::<PublicKey>().unwrap();
+
+    let revocation = RevocationStatement {
+      public_key,
+      time: 0,
+      replacement: None,
+    };
+
+    let statement = Statement {
+      fingerprint: Fingerprint::from_bytes(public_key.inner().to_bytes()),
+      timestamp: None,
+      expires: None,
+      purpose: None,
+      comment: None,
+    };
+
+    assert_ne!(revocation.digest(), statement.digest());
+  }
+}
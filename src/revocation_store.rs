@@ -0,0 +1,233 @@
+use super::*;
+
+#[derive(Debug)]
+pub(crate) struct RevocationStore {
+  path: Utf8PathBuf,
+  revocations: BTreeMap<PublicKey, Revocation>,
+}
+
+impl RevocationStore {
+  const FILENAME: &'static str = "revocations.txt";
+
+  pub(crate) fn add(&mut self, revocation: Revocation) -> Result<bool> {
+    revocation.verify().context(error::RevocationInvalid {
+      public_key: revocation.public_key(),
+    })?;
+
+    if let Some(existing) = self.revocations.get(&revocation.public_key())
+      && (existing.time() <= revocation.time()
+        || existing
+          .replacement()
+          .is_some_and(|replacement| revocation.replacement() != Some(replacement)))
+    {
+      return Ok(false);
+    }
+
+    self.revocations.insert(revocation.public_key(), revocation);
+
+    Ok(true)
+  }
+
+  pub(crate) fn get(&self, public_key: PublicKey) -> Option<&Revocation> {
+    self.revocations.get(&public_key)
+  }
+
+  pub(crate) fn load(options: &Options) -> Result<Self> {
+    let path = options.data_dir()?.join(Self::FILENAME);
+
+    let mut store = Self {
+      revocations: BTreeMap::new(),
+      path,
+    };
+
+    let Some(s) = filesystem::read_to_string_opt(&store.path)? else {
+      return Ok(store);
+    };
+
+    for (i, line) in s.lines().enumerate() {
+      let line = line.trim();
+
+      if line.is_empty() {
+        continue;
+      }
+
+      let revocation = line.parse().context(error::RevocationFileLoad {
+        line: i + 1,
+        path: &store.path,
+      })?;
+
+      store.add(revocation)?;
+    }
+
+    Ok(store)
+  }
+
+  pub(crate) fn revocations(&self) -> impl Iterator<Item = &Revocation> {
+    self.revocations.values()
+  }
+
+  pub(crate) fn revoked(&self, signature: &Signature) -> Option<&Revocation> {
+    self
+      .get(signature.public_key())
+      .filter(|revocation| revocation.revokes(signature))
+  }
+
+  pub(crate) fn save(&self) -> Result {
+    filesystem::create_dir_all(self.path.parent().unwrap())?;
+
+    filesystem::write(&self.path, self.to_string())
+  }
+
+  pub(crate) fn successors(
+    &self,
+    public_key: PublicKey,
+    pinned: impl Fn(PublicKey) -> bool,
+  ) -> Vec<PublicKey> {
+    let mut successors = Vec::new();
+
+    let mut current = public_key;
+
+    while let Some(replacement) =
+      self
+        .get(current)
+        .and_then(Revocation::replacement)
+        .filter(|replacement| {
+          *replacement != public_key && !successors.contains(replacement) && pinned(*replacement)
+        })
+    {
+      successors.push(replacement);
+      current = replacement;
+    }
+
+    successors
+  }
+}
+
+impl Display for RevocationStore {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    for revocation in self.revocations.values() {
+      writeln!(f, "{revocation}")?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn conflicting_replacement_is_ignored() {
+    let key = PrivateKey::generate();
+    let owner = PrivateKey::generate();
+    let attacker = PrivateKey::generate();
+
+    let mut store = store();
+
+    assert!(
+      store
+        .add(Revocation::new(&key, 2000, Some(owner.public_key())))
+        .unwrap()
+    );
+    assert!(
+      !store
+        .add(Revocation::new(&key, 1000, Some(attacker.public_key())))
+        .unwrap()
+    );
+    assert!(!store.add(Revocation::new(&key, 1000, None)).unwrap());
+    assert!(
+      store
+        .add(Revocation::new(&key, 1000, Some(owner.public_key())))
+        .unwrap()
+    );
+
+    assert_eq!(
+      store.get(key.public_key()).unwrap().replacement(),
+      Some(owner.public_key()),
+    );
+  }
+
+  #[test]
+  fn earliest_revocation_wins() {
+    let key = PrivateKey::generate();
+
+    let mut store = store();
+
+    assert!(store.add(Revocation::new(&key, 2000, None)).unwrap());
+    assert!(store.add(Revocation::new(&key, 1000, None)).unwrap());
+    assert!(!store.add(Revocation::new(&key, 1500, None)).unwrap());
+
+    assert_eq!(store.get(key.public_key()).unwrap().time(), 1000);
+  }
+
+  #[test]
+  fn invalid_revocations_are_rejected() {
+    let key = PrivateKey::generate();
+
+    let revocation = Revocation::new(&key, 1000, None)
+      .encode_to_vec()
+      .into_iter()
+      .rev()
+      .enumerate()
+      .map(|(i, byte)| if i == 0 { byte ^ 1 } else { byte })
+      .rev()
+      .collect::<Vec<u8>>();
+
+    assert_matches!(
+      store()
+        .add(Revocation::decode_from_slice(&revocation).unwrap())
+        .unwrap_err(),
+      Error::RevocationInvalid { .. },
+    );
+  }
+
+  fn store() -> RevocationStore {
+    RevocationStore {
+      path: Utf8PathBuf::new(),
+      revocations: BTreeMap::new(),
+    }
+  }
+
+  #[test]
+  fn successors() {
+    let a = PrivateKey::generate();
+    let b = PrivateKey::generate();
+    let c = PrivateKey::generate();
+
+    let mut store = store();
+
+    assert!(store.successors(a.public_key(), |_| true).is_empty());
+
+    store
+      .add(Revocation::new(&a, 1000, Some(b.public_key())))
+      .unwrap();
+    store
+      .add(Revocation::new(&b, 2000, Some(c.public_key())))
+      .unwrap();
+
+    assert_eq!(
+      store.successors(a.public_key(), |_| true),
+      [b.public_key(), c.public_key()],
+    );
+
+    store
+      .add(Revocation::new(&c, 3000, Some(a.public_key())))
+      .unwrap();
+
+    assert_eq!(
+      store.successors(a.public_key(), |_| true),
+      [b.public_key(), c.public_key()],
+    );
+
+    assert_eq!(
+      store.successors(a.public_key(), |key| key != c.public_key()),
+      [b.public_key()],
+    );
+
+    assert!(
+      store
+        .successors(a.public_key(), |key| key != b.public_key())
+        .is_empty()
+    );
+  }
+}
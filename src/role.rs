@@ -0,0 +1,11 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Deserialize, Display, Eq, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Role {
+  Admin,
+  Delete,
+  Gc,
+  Upload,
+}
@@ -0,0 +1,136 @@
+use super::*;
+
+#[serde_as]
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub(crate) struct Search {
+  #[serde(default)]
+  #[serde_as(as = "NoneAsEmptyString")]
+  pub(crate) language: Option<Language>,
+  #[serde(default)]
+  #[serde_as(as = "NoneAsEmptyString")]
+  pub(crate) media: Option<MediaType>,
+  pub(crate) page: Option<u64>,
+  #[serde(default)]
+  #[serde_as(as = "NoneAsEmptyString")]
+  pub(crate) q: Option<String>,
+  #[serde(default)]
+  #[serde_as(as = "NoneAsEmptyString")]
+  pub(crate) sort: Option<SearchOrder>,
+  #[serde(default)]
+  #[serde_as(as = "NoneAsEmptyString")]
+  pub(crate) year: Option<YearRange>,
+}
+
+impl Search {
+  pub(crate) const PAGE_SIZE: u64 = 50;
+
+  pub(crate) fn offset(&self) -> u64 {
+    (self.page() - 1).saturating_mul(Self::PAGE_SIZE)
+  }
+
+  pub(crate) fn page(&self) -> u64 {
+    self.page.unwrap_or(1).max(1)
+  }
+
+  pub(crate) fn query_terms(&self) -> BTreeSet<String> {
+    self.q.as_deref().map(Self::terms).unwrap_or_default()
+  }
+
+  pub(crate) fn sort(&self) -> SearchOrder {
+    self.sort.unwrap_or_default()
+  }
+
+  pub(crate) fn terms(text: &str) -> BTreeSet<String> {
+    text
+      .split(|c: char| !c.is_alphanumeric())
+      .filter(|term| !term.is_empty())
+      .map(str::to_lowercase)
+      .collect()
+  }
+
+  pub(crate) fn url(&self, page: u64) -> String {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+
+    if let Some(q) = &self.q {
+      query.append_pair("q", q);
+    }
+
+    if let Some(media) = self.media {
+      query.append_pair("media", &media.to_string());
+    }
+
+    if let Some(language) = &self.language {
+      query.append_pair("language", &language.to_string());
+    }
+
+    if let Some(year) = self.year {
+      query.append_pair("year", &year.to_string());
+    }
+
+    if let Some(sort) = self.sort {
+      query.append_pair("sort", &sort.to_string());
+    }
+
+    if page > 1 {
+      query.append_pair("page", &page.to_string());
+    }
+
+    let query = query.finish();
+
+    if query.is_empty() {
+      "/search".into()
+    } else {
+      format!("/search?{query}")
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn offset() {
+    #[track_caller]
+    fn case(page: Option<u64>, offset: u64) {
+      assert_eq!(Search { page, ..default() }.offset(), offset,);
+    }
+
+    case(None, 0);
+    case(Some(0), 0);
+    case(Some(1), 0);
+    case(Some(2), Search::PAGE_SIZE);
+    case(Some(u64::MAX), u64::MAX);
+  }
+
+  #[test]
+  fn terms() {
+    assert_eq!(
+      Search::terms("Foo, BAR-baz  qux! foo"),
+      ["bar", "baz", "foo", "qux"]
+        .into_iter()
+        .map(str::to_owned)
+        .collect(),
+    );
+  }
+
+  #[test]
+  fn url() {
+    assert_eq!(Search::default().url(1), "/search");
+
+    assert_eq!(Search::default().url(2), "/search?page=2");
+
+    assert_eq!(
+      Search {
+        language: Some("en".parse().unwrap()),
+        media: Some(MediaType::Audio),
+        page: Some(3),
+        q: Some("foo bar".into()),
+        sort: Some(SearchOrder::Time),
+        year: Some("1970..1979".parse().unwrap()),
+      }
+      .url(4),
+      "/search?q=foo+bar&media=audio&language=en&year=1970..1979&sort=time&page=4",
+    );
+  }
+}
@@ -0,0 +1,9 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Default, Display, EnumIter, EnumString, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub(crate) enum SearchOrder {
+  Time,
+  #[default]
+  Title,
+}
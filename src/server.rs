@@ -1,21 +1,145 @@
 use {
   super::*,
-  redb::{Database, ReadOnlyTable, ReadableDatabase, ReadableTable, TableDefinition},
-  templates::PackageHtml,
+  redb::{
+    Database, MultimapTableDefinition, ReadOnlyTable, ReadableDatabase, ReadableMultimapTable,
+    ReadableTable, TableDefinition,
+  },
+  templates::{PackageHtml, SearchHtml},
 };
 
 const DIRECTORIES: TableDefinition<Hash, ()> = TableDefinition::new("directories");
-const METADATA: TableDefinition<DatabaseMetadata, u64> = TableDefinition::new("metadata");
+const FILES: TableDefinition<Hash, u64> = TableDefinition::new("files");
+const KEY_PENDING_USAGE: TableDefinition<PublicKey, (u64, u64)> =
+  TableDefinition::new("key_pending_usage");
+const KEY_SHARED_USAGE: TableDefinition<PublicKey, (u64, u64)> =
+  TableDefinition::new("key_shared_usage");
+const KEY_USAGE: TableDefinition<PublicKey, (u64, u64)> = TableDefinition::new("key_usage");
+const MAX_KEY_SIGNATURES: usize = 16;
+const MAX_PACKAGE_SIGNATURES: usize = 256;
+const MAX_SYMLINKS: usize = 40;
+const NAMES: TableDefinition<(PublicKey, &str), &[u8]> = TableDefinition::new("names");
+const OWNERS: TableDefinition<Fingerprint, PublicKey> = TableDefinition::new("owners");
 const PACKAGES: TableDefinition<Fingerprint, ()> = TableDefinition::new("packages");
-const SCHEMA_VERSION: u64 = 1;
+const PENDING: TableDefinition<Hash, PublicKey> = TableDefinition::new("pending");
+const REFERENCES: TableDefinition<(Hash, Option<PublicKey>), u64> =
+  TableDefinition::new("references");
+const SCHEMA_VERSION: u64 = 8;
+const SEARCH_LANGUAGES: MultimapTableDefinition<&str, Fingerprint> =
+  MultimapTableDefinition::new("search_languages");
+const SEARCH_MEDIA: MultimapTableDefinition<&str, Fingerprint> =
+  MultimapTableDefinition::new("search_media");
+const SEARCH_TERMS: MultimapTableDefinition<&str, Fingerprint> =
+  MultimapTableDefinition::new("search_terms");
+const SEARCH_TIMES: TableDefinition<Fingerprint, (i64, i64)> = TableDefinition::new("search_times");
+const SEARCH_TITLES: TableDefinition<Fingerprint, &str> = TableDefinition::new("search_titles");
+const SEARCH_YEARS: MultimapTableDefinition<i64, Fingerprint> =
+  MultimapTableDefinition::new("search_years");
+const SIGNATURES: MultimapTableDefinition<Fingerprint, &str> =
+  MultimapTableDefinition::new("signatures");
+const TOTAL_USAGE: TableDefinition<(), (u64, u64)> = TableDefinition::new("total_usage");
+
+// bytes remaining under the server quota, and under the uploader's key quota
+type RemainingBytes = (Option<u64>, Option<(PublicKey, Quota, u64)>);
 
 pub(crate) struct Server {
   database: Database,
   files: Utf8PathBuf,
   incoming: Utf8PathBuf,
+  outboards: Utf8PathBuf,
+  revocations: Utf8PathBuf,
 }
 
 impl Server {
+  fn add_references(
+    tx: &redb::WriteTransaction,
+    files: &BTreeMap<Hash, u64>,
+    owner: Option<PublicKey>,
+  ) -> ServerResult {
+    let mut pending = tx.open_table(PENDING)?;
+    let mut pending_usages = tx.open_table(KEY_PENDING_USAGE)?;
+    let mut references = tx.open_table(REFERENCES)?;
+    let mut shared = tx.open_table(KEY_SHARED_USAGE)?;
+    let mut usages = tx.open_table(KEY_USAGE)?;
+
+    for (&hash, &size) in files {
+      if let Some(uploader) = pending.remove(&hash)?.map(|uploader| uploader.value()) {
+        Self::update_usage(&mut pending_usages, uploader, |usage| usage.remove(size))?;
+      }
+
+      let count = references
+        .get(&(hash, owner))?
+        .map_or(0, |count| count.value());
+
+      references.insert(&(hash, owner), count + 1)?;
+
+      if count > 0 {
+        continue;
+      }
+
+      if let Some(owner) = owner {
+        Self::update_usage(&mut usages, owner, |usage| usage.add(size))?;
+      }
+
+      let others = Self::file_owners(&references, hash)?
+        .into_iter()
+        .filter(|other| *other != owner)
+        .collect::<Vec<Option<PublicKey>>>();
+
+      if others.is_empty() {
+        continue;
+      }
+
+      if let Some(owner) = owner {
+        Self::update_usage(&mut shared, owner, |usage| usage.add(size))?;
+      }
+
+      if let [Some(other)] = others.as_slice() {
+        Self::update_usage(&mut shared, *other, |usage| usage.add(size))?;
+      }
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn add_revocation(&self, revocation: &Revocation) -> ServerResult {
+    revocation
+      .verify()
+      .context(server_error::RevocationInvalid {
+        public_key: revocation.public_key(),
+      })?;
+
+    if let Some(existing) = self.revocation(revocation.public_key())?
+      && existing.time() <= revocation.time()
+    {
+      return Ok(());
+    }
+
+    let path = self.revocations.join(revocation.public_key().to_string());
+
+    fs::write(&path, revocation.to_string()).context(server_error::FilesystemIo { path: &path })
+  }
+
+  pub(crate) fn add_signatures(
+    &self,
+    fingerprint: Fingerprint,
+    signatures: &BTreeSet<Signature>,
+  ) -> ServerResult {
+    self.check_signatures(fingerprint, signatures, None)?;
+
+    let tx = self.database.begin_write()?;
+
+    ensure!(
+      tx.open_table(PACKAGES)?.get(&fingerprint)?.is_some(),
+      server_error::PackageNotFound { fingerprint },
+    );
+
+    Self::insert_signatures(&tx, fingerprint, signatures)?;
+
+    tx.commit()?;
+
+    Ok(())
+  }
+
   pub(crate) fn artwork(&self, fingerprint: Fingerprint) -> ServerResult<Resource> {
     let artwork = self
       .package_metadata(fingerprint)?
@@ -27,7 +151,96 @@ impl Server {
     Ok(self.open_file(hash)?.ty(artwork.resource_type()))
   }
 
-  pub(crate) fn delete_package(&self, fingerprint: Fingerprint) -> ServerResult {
+  fn bundle_directories(
+    archive: &Archive,
+    hash: Hash,
+    directories: &mut Vec<Hash>,
+  ) -> ServerResult {
+    let directory = archive
+      .directory(hash)
+      .context(bundle_error::Archive)
+      .context(server_error::Bundle)?;
+
+    for entry in directory.entries.values() {
+      if let Entry::Directory { hash, .. } = entry {
+        Self::bundle_directories(archive, *hash, directories)?;
+      }
+    }
+
+    directories.push(hash);
+
+    Ok(())
+  }
+
+  fn check_remaining_bytes(
+    (server, key): RemainingBytes,
+    size: u64,
+    quotas: &Quotas,
+  ) -> ServerResult {
+    ensure!(
+      server.is_none_or(|remaining| size <= remaining),
+      server_error::QuotaExceeded {
+        quota: quotas.server,
+      },
+    );
+
+    if let Some((key, quota, remaining)) = key {
+      ensure!(
+        size <= remaining,
+        server_error::KeyQuotaExceeded { key, quota },
+      );
+    }
+
+    Ok(())
+  }
+
+  fn check_signatures(
+    &self,
+    fingerprint: Fingerprint,
+    signatures: &BTreeSet<Signature>,
+    policy: Option<&Policy>,
+  ) -> ServerResult {
+    for signature in signatures {
+      ensure!(
+        signature.verify(fingerprint).is_ok(),
+        server_error::PackageSignatureInvalid {
+          fingerprint,
+          public_key: signature.public_key(),
+        },
+      );
+    }
+
+    if let Some(policy) = policy {
+      let now = u64::try_from(jiff::Timestamp::now().as_second()).unwrap_or_default();
+
+      let mut current = Vec::new();
+
+      for signature in signatures {
+        if signature.statement().is_expired_at(now)
+          || self
+            .revocation(signature.public_key())?
+            .is_some_and(|revocation| revocation.revokes(signature))
+        {
+          continue;
+        }
+
+        current.push(signature);
+      }
+
+      ensure!(
+        policy.evaluate(current).is_satisfied(),
+        server_error::PackagePolicyUnsatisfied { fingerprint },
+      );
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn delete_package(
+    &self,
+    fingerprint: Fingerprint,
+    authenticated: &Authenticated,
+  ) -> ServerResult {
     let tx = self.database.begin_write()?;
 
     ensure!(
@@ -35,6 +248,32 @@ impl Server {
       server_error::PackageNotFound { fingerprint },
     );
 
+    let owner = tx
+      .open_table(OWNERS)?
+      .remove(&fingerprint)?
+      .map(|owner| owner.value());
+
+    if let Some(issuer) = authenticated.issuer()
+      && !authenticated.is_admin()
+    {
+      ensure!(
+        owner == Some(issuer),
+        server_error::PackageDeleteForbidden {
+          fingerprint,
+          issuer,
+        },
+      );
+    }
+
+    if let Some(metadata) = self.metadata(fingerprint)? {
+      Self::unindex_package(&tx, fingerprint, &metadata)?;
+    }
+
+    Self::remove_references(&tx, &self.package_files(fingerprint)?, owner)?;
+
+    tx.open_multimap_table(SIGNATURES)?
+      .remove_all(&fingerprint)?;
+
     tx.commit()?;
 
     Ok(())
@@ -61,6 +300,67 @@ impl Server {
     self.read_directory(hash)
   }
 
+  fn directory_tree(&self, hash: Hash) -> ServerResult<DirectoryTree> {
+    let mut tree = DirectoryTree::new();
+
+    for (name, entry) in self.read_directory(hash)?.entries {
+      let entry = match entry {
+        Entry::Directory { hash, .. } => DirectoryTreeEntry::Directory(self.directory_tree(hash)?),
+        Entry::File { .. } => DirectoryTreeEntry::File(entry.to_file().unwrap()),
+        Entry::Symlink { target } => DirectoryTreeEntry::Symlink(Symlink { target }),
+      };
+
+      tree.entries.insert(name, entry);
+    }
+
+    Ok(tree)
+  }
+
+  pub(crate) fn export_package(
+    &self,
+    manifest: &Manifest,
+    format: ExportFormat,
+    writer: impl Write,
+  ) -> io::Result<()> {
+    let mut exporter = Exporter::new(format, writer, manifest)?;
+
+    let entries = manifest
+      .entries()
+      .collect::<BTreeMap<RelativePath, &DirectoryTreeEntry>>();
+
+    for (path, entry) in entries {
+      match entry {
+        DirectoryTreeEntry::Directory(_) => exporter.directory(&path)?,
+        DirectoryTreeEntry::File(file) => {
+          let resource = self.open_file(file.hash).map_err(io::Error::other)?;
+          exporter.file(&path, *file, resource.file)?;
+        }
+        DirectoryTreeEntry::Symlink(symlink) => exporter.symlink(&path, symlink)?,
+      }
+    }
+
+    exporter.finish()?.flush()
+  }
+
+  fn file_owners(
+    references: &impl ReadableTable<(Hash, Option<PublicKey>), u64>,
+    hash: Hash,
+  ) -> ServerResult<Vec<Option<PublicKey>>> {
+    let mut owners = Vec::new();
+
+    for entry in references.range((hash, None)..)? {
+      let (file, owner) = entry?.0.value();
+
+      if file != hash {
+        break;
+      }
+
+      owners.push(owner);
+    }
+
+    Ok(owners)
+  }
+
   fn file_path(&self, hash: Hash) -> Utf8PathBuf {
     self.files.join(hash.to_string())
   }
@@ -125,6 +425,7 @@ impl Server {
             Entry::File { hash, .. } => {
               marked.insert(*hash);
             }
+            Entry::Symlink { .. } => {}
           }
         }
       }
@@ -153,11 +454,40 @@ impl Server {
       files_removed.insert(hash);
     }
 
+    {
+      let mut files = tx.open_table(FILES)?;
+
+      let mut pending = tx.open_table(PENDING)?;
+
+      let mut pending_usages = tx.open_table(KEY_PENDING_USAGE)?;
+
+      let mut total = tx.open_table(TOTAL_USAGE)?;
+
+      for hash in &files_removed {
+        let Some(size) = files.remove(hash)?.map(|size| size.value()) else {
+          continue;
+        };
+
+        Self::update_total(&mut total, |usage| usage.remove(size))?;
+
+        if let Some(uploader) = pending.remove(hash)?.map(|uploader| uploader.value()) {
+          Self::update_usage(&mut pending_usages, uploader, |usage| usage.remove(size))?;
+        }
+      }
+    }
+
     tx.commit()?;
 
     for &hash in &files_removed {
       let path = self.file_path(hash);
       fs::remove_file(&path).context(server_error::FilesystemIo { path })?;
+
+      let path = self.outboard_path(hash);
+      if let Err(err) = fs::remove_file(&path)
+        && err.kind() != io::ErrorKind::NotFound
+      {
+        return Err(server_error::FilesystemIo { path }.into_error(err));
+      }
     }
 
     Ok(api::gc::Response {
@@ -167,6 +497,160 @@ impl Server {
     })
   }
 
+  pub(crate) fn import_bundle(
+    &self,
+    bundle: NamedTempFile,
+    policy: Option<&Policy>,
+    owner: Option<PublicKey>,
+    quotas: &Quotas,
+  ) -> ServerResult {
+    let file = bundle.reopen().context(server_error::FilesystemIo {
+      path: &self.incoming,
+    })?;
+
+    let mut bundle = BundleReader::new(BufReader::new(file)).context(server_error::Bundle)?;
+
+    let fingerprint = bundle.manifest().fingerprint();
+
+    let signatures = bundle.manifest().signatures.clone();
+
+    self.check_signatures(fingerprint, &signatures, policy)?;
+
+    let mut directories = Vec::new();
+
+    Self::bundle_directories(bundle.archive(), fingerprint.into(), &mut directories)?;
+
+    for &hash in directories.iter().chain(bundle.manifest().embedded.keys()) {
+      let file = bundle
+        .archive()
+        .file(hash)
+        .context(bundle_error::Archive)
+        .context(server_error::Bundle)?;
+
+      self.store_file(hash, file, owner, quotas)?;
+    }
+
+    while let Some((hash, reader)) = bundle.next().context(server_error::Bundle)? {
+      self.store_file(hash, reader, owner, quotas)?;
+    }
+
+    bundle.finish().context(server_error::Bundle)?;
+
+    for hash in directories {
+      self.verify_directory(hash)?;
+    }
+
+    self.verify_package(fingerprint, &signatures, policy, owner, quotas)
+  }
+
+  fn index_package(
+    tx: &redb::WriteTransaction,
+    fingerprint: Fingerprint,
+    metadata: &Metadata,
+  ) -> ServerResult {
+    let mut terms = tx.open_multimap_table(SEARCH_TERMS)?;
+
+    for term in metadata.search_terms() {
+      terms.insert(term.as_str(), &fingerprint)?;
+    }
+
+    if let Some(language) = &metadata.language {
+      tx.open_multimap_table(SEARCH_LANGUAGES)?
+        .insert(language.to_string().as_str(), &fingerprint)?;
+    }
+
+    if let Some(media) = &metadata.media {
+      tx.open_multimap_table(SEARCH_MEDIA)?
+        .insert(media.name(), &fingerprint)?;
+    }
+
+    if let Some(time) = &metadata.time {
+      tx.open_table(SEARCH_TIMES)?
+        .insert(&fingerprint, time.sort_key())?;
+
+      tx.open_multimap_table(SEARCH_YEARS)?
+        .insert(time.year(), &fingerprint)?;
+    }
+
+    if let Some(title) = &metadata.title {
+      tx.open_table(SEARCH_TITLES)?
+        .insert(&fingerprint, title.to_lowercase().as_str())?;
+    }
+
+    Ok(())
+  }
+
+  fn insert_signatures(
+    tx: &redb::WriteTransaction,
+    fingerprint: Fingerprint,
+    signatures: &BTreeSet<Signature>,
+  ) -> ServerResult {
+    let mut table = tx.open_multimap_table(SIGNATURES)?;
+
+    let mut existing = table
+      .get(&fingerprint)?
+      .map(|signature| {
+        signature?
+          .value()
+          .parse::<Signature>()
+          .context(server_error::SignatureCorrupt { fingerprint })
+      })
+      .collect::<ServerResult<BTreeSet<Signature>>>()?;
+
+    // signatures may be added without authentication, so limit how many a
+    // package may accumulate
+    for signature in signatures {
+      if existing.contains(signature) {
+        continue;
+      }
+
+      ensure!(
+        existing.len() < MAX_PACKAGE_SIGNATURES,
+        server_error::PackageSignatureLimit {
+          fingerprint,
+          limit: MAX_PACKAGE_SIGNATURES,
+        },
+      );
+
+      let public_key = signature.public_key();
+
+      ensure!(
+        existing
+          .iter()
+          .filter(|existing| existing.public_key() == public_key)
+          .count()
+          < MAX_KEY_SIGNATURES,
+        server_error::KeySignatureLimit {
+          fingerprint,
+          limit: MAX_KEY_SIGNATURES,
+          public_key,
+        },
+      );
+
+      table.insert(&fingerprint, signature.to_string().as_str())?;
+
+      existing.insert(signature.clone());
+    }
+
+    Ok(())
+  }
+
+  fn key_usage(
+    key: PublicKey,
+    usages: &impl ReadableTable<PublicKey, (u64, u64)>,
+    shared: &impl ReadableTable<PublicKey, (u64, u64)>,
+    pending: &impl ReadableTable<PublicKey, (u64, u64)>,
+    exempt_shared: bool,
+  ) -> ServerResult<Usage> {
+    let mut usage = Self::read_usage(usages, key)?;
+
+    if exempt_shared {
+      usage = usage.without(Self::read_usage(shared, key)?);
+    }
+
+    Ok(usage.with(Self::read_usage(pending, key)?))
+  }
+
   pub(crate) fn media_item(
     &self,
     fingerprint: Fingerprint,
@@ -215,11 +699,11 @@ impl Server {
   fn metadata_cbor(&self, fingerprint: Fingerprint) -> ServerResult<Option<Vec<u8>>> {
     let directory = self.read_directory(fingerprint.into())?;
 
-    let Some(entry) = directory.entries.get(Metadata::CBOR_FILENAME) else {
+    let Some(Entry::File { hash, .. }) = directory.entries.get(Metadata::CBOR_FILENAME) else {
       return Ok(None);
     };
 
-    Ok(Some(self.read_file(entry.hash())?))
+    Ok(Some(self.read_file(*hash)?))
   }
 
   pub(crate) fn missing(&self, hashes: &[Hash]) -> ServerResult<BTreeSet<Hash>> {
@@ -239,6 +723,23 @@ impl Server {
     Ok(missing)
   }
 
+  pub(crate) fn name(&self, public_key: PublicKey, name: &PackageName) -> ServerResult<NameRecord> {
+    let tx = self.database.begin_read()?;
+
+    let record = tx
+      .open_table(NAMES)?
+      .get((public_key, name.as_str()))?
+      .context(server_error::NameNotFound {
+        name: name.clone(),
+        public_key,
+      })?;
+
+    NameRecord::decode_from_slice(record.value()).context(server_error::NameCorrupt {
+      name: name.clone(),
+      public_key,
+    })
+  }
+
   pub(crate) fn open_file(&self, hash: Hash) -> ServerResult<Resource> {
     let path = self.file_path(hash);
 
@@ -265,11 +766,64 @@ impl Server {
     })
   }
 
+  pub(crate) fn outboard(&self, hash: Hash) -> ServerResult<api::outboard::Response> {
+    let resource = self.open_file(hash)?;
+
+    let size = resource.content_length;
+
+    if Outboard::blocks(size) == 1 {
+      return Ok(api::outboard::Response {
+        size,
+        tree: Vec::new(),
+      });
+    }
+
+    let path = self.outboard_path(hash);
+
+    let tree = match fs::read(&path) {
+      Ok(tree) => tree,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => {
+        let mut hasher = OutboardHasher::new();
+
+        let mut reader = BufReader::new(resource.file);
+
+        let mut buffer = vec![0; 64 * KIB];
+
+        loop {
+          let n = reader
+            .read(&mut buffer)
+            .context(server_error::FileIo { hash })?;
+
+          if n == 0 {
+            break;
+          }
+
+          hasher.update(&buffer[..n]);
+        }
+
+        let tree = hasher.finalize();
+
+        Outboard::new(hash, size, &tree).context(server_error::FileCorrupt { hash })?;
+
+        self.write_outboard(hash, &tree)?;
+
+        tree
+      }
+      Err(err) => return Err(server_error::FilesystemIo { path }.into_error(err)),
+    };
+
+    Ok(api::outboard::Response { size, tree })
+  }
+
+  fn outboard_path(&self, hash: Hash) -> Utf8PathBuf {
+    self.outboards.join(hash.to_string())
+  }
+
   pub(crate) fn package_file(
     &self,
     fingerprint: Fingerprint,
     path: &RelativePath,
-  ) -> ServerResult<Hash> {
+  ) -> ServerResult<Resolution> {
     let tx = self.database.begin_read()?;
 
     let packages = tx.open_table(PACKAGES)?;
@@ -284,6 +838,42 @@ impl Server {
       .context(server_error::PackageFileNotFound { fingerprint, path })
   }
 
+  fn package_files(&self, fingerprint: Fingerprint) -> ServerResult<BTreeMap<Hash, u64>> {
+    let root = Hash::from(fingerprint);
+
+    let path = self.file_path(root);
+
+    let mut files = BTreeMap::new();
+
+    files.insert(
+      root,
+      path
+        .metadata()
+        .context(server_error::FilesystemIo { path: &path })?
+        .len(),
+    );
+
+    let mut stack = vec![root];
+
+    while let Some(hash) = stack.pop() {
+      for entry in self.read_directory(hash)?.entries.into_values() {
+        match entry {
+          Entry::Directory { hash, size, .. } => {
+            if files.insert(hash, size).is_none() {
+              stack.push(hash);
+            }
+          }
+          Entry::File { hash, size, .. } => {
+            files.insert(hash, size);
+          }
+          Entry::Symlink { .. } => {}
+        }
+      }
+    }
+
+    Ok(files)
+  }
+
   pub(crate) fn package_html(
     &self,
     fingerprint: Fingerprint,
@@ -301,6 +891,8 @@ impl Server {
 
     let totals = directory.totals().unwrap();
 
+    let signatures = Self::signatures_ext(&tx, fingerprint)?;
+
     let colophon = if let Some(metadata) = &metadata
       && let Some(package) = &metadata.package
       && let Some(colophon) = &package.colophon
@@ -325,10 +917,26 @@ impl Server {
       metadata,
       mounted,
       readme,
+      signatures,
       totals,
     })
   }
 
+  pub(crate) fn package_manifest(&self, fingerprint: Fingerprint) -> ServerResult<Manifest> {
+    let tx = self.database.begin_read()?;
+
+    ensure!(
+      tx.open_table(PACKAGES)?.get(&fingerprint)?.is_some(),
+      server_error::PackageNotFound { fingerprint },
+    );
+
+    Ok(Manifest {
+      embedded: BTreeMap::new(),
+      package: self.directory_tree(fingerprint.into())?,
+      signatures: BTreeSet::new(),
+    })
+  }
+
   pub(crate) fn package_metadata(&self, fingerprint: Fingerprint) -> ServerResult<Metadata> {
     self
       .package_metadata_opt(fingerprint)?
@@ -359,24 +967,104 @@ impl Server {
     self.metadata(fingerprint)
   }
 
-  pub(crate) fn packages(&self) -> ServerResult<Vec<(Fingerprint, Option<Metadata>, Totals)>> {
+  pub(crate) fn package_proof(
+    &self,
+    fingerprint: Fingerprint,
+    path: &RelativePath,
+  ) -> ServerResult<Proof> {
+    let tx = self.database.begin_read()?;
+
+    let packages = tx.open_table(PACKAGES)?;
+
+    ensure!(
+      packages.get(&fingerprint)?.is_some(),
+      server_error::PackageNotFound { fingerprint },
+    );
+
+    Proof::new(
+      fingerprint,
+      path,
+      |hash| -> ServerResult<(Directory, Vec<u8>)> {
+        let cbor = self.read_file(hash)?;
+
+        let directory =
+          Directory::decode_from_slice(&cbor).context(server_error::DirectoryDecode { hash })?;
+
+        Ok((directory, cbor))
+      },
+    )?
+    .context(server_error::PackageFileNotFound { fingerprint, path })
+  }
+
+  fn package_summary(
+    &self,
+    directories: &ReadOnlyTable<Hash, ()>,
+    fingerprint: Fingerprint,
+  ) -> ServerResult<(Fingerprint, Option<Metadata>, Totals)> {
+    let totals = self
+      .directory_ext(directories, fingerprint.into())?
+      .totals()
+      .unwrap();
+
+    Ok((fingerprint, self.metadata(fingerprint)?, totals))
+  }
+
+  pub(crate) fn packages(&self) -> ServerResult<Vec<(Fingerprint, Option<Metadata>, Totals)>> {
     let tx = self.database.begin_read()?;
 
     let directories = tx.open_table(DIRECTORIES)?;
 
     tx.open_table(PACKAGES)?
       .iter()?
-      .map(|entry| {
-        let fingerprint = entry?.0.value();
+      .map(|entry| self.package_summary(&directories, entry?.0.value()))
+      .collect()
+  }
 
-        let totals = self
-          .directory_ext(&directories, fingerprint.into())?
-          .totals()
-          .unwrap();
+  pub(crate) fn publish_name(&self, record: &NameRecord) -> ServerResult {
+    let public_key = record.public_key();
+    let name = record.name();
 
-        Ok((fingerprint, self.metadata(fingerprint)?, totals))
-      })
-      .collect()
+    record.verify().context(server_error::NameInvalid {
+      name: name.clone(),
+      public_key,
+    })?;
+
+    let tx = self.database.begin_write()?;
+
+    {
+      let mut names = tx.open_table(NAMES)?;
+
+      if let Some(existing) = names.get((public_key, name.as_str()))? {
+        let existing =
+          NameRecord::decode_from_slice(existing.value()).context(server_error::NameCorrupt {
+            name: name.clone(),
+            public_key,
+          })?;
+
+        if existing == *record {
+          return Ok(());
+        }
+
+        ensure!(
+          existing.sequence() < record.sequence(),
+          server_error::NameSequenceStale {
+            current: existing.sequence(),
+            name: name.clone(),
+            public_key,
+            sequence: record.sequence(),
+          },
+        );
+      }
+
+      names.insert(
+        (public_key, name.as_str()),
+        record.encode_to_vec().as_slice(),
+      )?;
+    }
+
+    tx.commit()?;
+
+    Ok(())
   }
 
   fn read_directory(&self, hash: Hash) -> ServerResult<Directory> {
@@ -398,27 +1086,603 @@ impl Server {
     })
   }
 
-  fn resolve_path(&self, root: Fingerprint, path: &RelativePath) -> ServerResult<Option<Hash>> {
-    let mut components = path.components().peekable();
+  fn read_usage(
+    table: &impl ReadableTable<PublicKey, (u64, u64)>,
+    key: PublicKey,
+  ) -> ServerResult<Usage> {
+    Ok(
+      table
+        .get(&key)?
+        .map(|usage| Usage::from(usage.value()))
+        .unwrap_or_default(),
+    )
+  }
+
+  // the whole bundle body counts against the remaining quota, since it is
+  // stored before its contents can be deduplicated
+  pub(crate) async fn receive_bundle(
+    &self,
+    body: Body,
+    uploader: Option<PublicKey>,
+    quotas: &Quotas,
+  ) -> ServerResult<NamedTempFile> {
+    let remaining = self.remaining_bytes(uploader, quotas)?;
+
+    let context = server_error::FilesystemIo {
+      path: &self.incoming,
+    };
+
+    let (file, path) = tempfile::Builder::new()
+      .prefix("bundle-")
+      .suffix(".incomplete")
+      .tempfile_in(&self.incoming)
+      .context(context)?
+      .into_parts();
+
+    let mut writer = tokio::io::BufWriter::new(tokio::fs::File::from_std(file));
+
+    let mut stream = body.into_data_stream();
+
+    let mut size = 0;
+
+    while let Some(chunk) = stream.next().await {
+      let chunk = chunk.context(server_error::BundleBodyRead)?;
+      size += chunk.len().into_u64();
+      Self::check_remaining_bytes(remaining, size, quotas)?;
+      writer.write_all(&chunk).await.context(context)?;
+    }
+
+    writer.flush().await.context(context)?;
+
+    Ok(NamedTempFile::from_parts(
+      writer.into_inner().into_std().await,
+      path,
+    ))
+  }
+
+  fn record_file(
+    &self,
+    hash: Hash,
+    size: u64,
+    uploader: Option<PublicKey>,
+    quotas: &Quotas,
+  ) -> ServerResult {
+    let tx = self.database.begin_write()?;
+
+    {
+      let mut files = tx.open_table(FILES)?;
+
+      if files.get(&hash)?.is_some() {
+        return Ok(());
+      }
+
+      let mut total = tx.open_table(TOTAL_USAGE)?;
+
+      let mut usage = Self::total_usage(&total)?;
+
+      usage.add(size);
+
+      ensure!(
+        quotas.server.allows(usage),
+        server_error::QuotaExceeded {
+          quota: quotas.server,
+        },
+      );
+
+      total.insert((), <(u64, u64)>::from(usage))?;
+
+      files.insert(&hash, size)?;
+
+      if let Some(uploader) = uploader {
+        let mut pending = tx.open_table(KEY_PENDING_USAGE)?;
+
+        if let Some(&quota) = quotas.keys.get(&uploader) {
+          let mut usage = Self::key_usage(
+            uploader,
+            &tx.open_table(KEY_USAGE)?,
+            &tx.open_table(KEY_SHARED_USAGE)?,
+            &pending,
+            quotas.exempt_shared,
+          )?;
+
+          usage.add(size);
+
+          ensure!(
+            quota.allows(usage),
+            server_error::KeyQuotaExceeded {
+              key: uploader,
+              quota,
+            },
+          );
+        }
+
+        Self::update_usage(&mut pending, uploader, |usage| usage.add(size))?;
+
+        tx.open_table(PENDING)?.insert(&hash, uploader)?;
+      }
+    }
+
+    tx.commit()?;
+
+    Ok(())
+  }
+
+  fn remaining_bytes(
+    &self,
+    uploader: Option<PublicKey>,
+    quotas: &Quotas,
+  ) -> ServerResult<RemainingBytes> {
+    let tx = self.database.begin_read()?;
+
+    let server = if let Some(bytes) = quotas.server.bytes {
+      Some(bytes.saturating_sub(Self::total_usage(&tx.open_table(TOTAL_USAGE)?)?.bytes))
+    } else {
+      None
+    };
+
+    let key = if let Some(uploader) = uploader
+      && let Some(&quota) = quotas.keys.get(&uploader)
+      && let Some(bytes) = quota.bytes
+    {
+      let usage = Self::key_usage(
+        uploader,
+        &tx.open_table(KEY_USAGE)?,
+        &tx.open_table(KEY_SHARED_USAGE)?,
+        &tx.open_table(KEY_PENDING_USAGE)?,
+        quotas.exempt_shared,
+      )?;
+
+      Some((uploader, quota, bytes.saturating_sub(usage.bytes)))
+    } else {
+      None
+    };
+
+    Ok((server, key))
+  }
+
+  fn remove_references(
+    tx: &redb::WriteTransaction,
+    files: &BTreeMap<Hash, u64>,
+    owner: Option<PublicKey>,
+  ) -> ServerResult {
+    let mut references = tx.open_table(REFERENCES)?;
+    let mut shared = tx.open_table(KEY_SHARED_USAGE)?;
+    let mut usages = tx.open_table(KEY_USAGE)?;
+
+    for (&hash, &size) in files {
+      let Some(count) = references.get(&(hash, owner))?.map(|count| count.value()) else {
+        continue;
+      };
+
+      if count > 1 {
+        references.insert(&(hash, owner), count - 1)?;
+        continue;
+      }
+
+      references.remove(&(hash, owner))?;
+
+      if let Some(owner) = owner {
+        Self::update_usage(&mut usages, owner, |usage| usage.remove(size))?;
+      }
+
+      let others = Self::file_owners(&references, hash)?;
+
+      if others.is_empty() {
+        continue;
+      }
+
+      if let Some(owner) = owner {
+        Self::update_usage(&mut shared, owner, |usage| usage.remove(size))?;
+      }
+
+      if let [Some(other)] = others.as_slice() {
+        Self::update_usage(&mut shared, *other, |usage| usage.remove(size))?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn resolve_path(
+    &self,
+    root: Fingerprint,
+    path: &RelativePath,
+  ) -> ServerResult<Option<Resolution>> {
+    let components = path.components().collect::<Vec<&Component>>();
 
     let mut directory = self.read_directory(root.into())?;
-    while let Some(component) = components.next() {
-      let Some(entry) = directory.entries.get(component) else {
+    for (i, component) in components.iter().enumerate() {
+      let Some(entry) = directory.entries.get(*component) else {
         return Ok(None);
       };
 
-      if components.peek().is_none() {
-        return Ok((entry.ty() == EntryType::File).then_some(entry.hash()));
+      let last = i + 1 == components.len();
+
+      match entry {
+        Entry::Directory { hash, .. } if !last => directory = self.read_directory(*hash)?,
+        Entry::File { hash, .. } if last => return Ok(Some(Resolution::File(*hash))),
+        Entry::Symlink { target } => {
+          let mut target = target.clone();
+
+          for component in &components[i + 1..] {
+            target = target.join(component);
+          }
+
+          return Ok(Some(Resolution::Symlink(target)));
+        }
+        _ => return Ok(None),
       }
+    }
 
-      if entry.ty() != EntryType::Directory {
-        return Ok(None);
+    Ok(None)
+  }
+
+  fn revocation(&self, public_key: PublicKey) -> ServerResult<Option<Revocation>> {
+    let path = self.revocations.join(public_key.to_string());
+
+    let s = match fs::read_to_string(&path) {
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+      result => result.context(server_error::FilesystemIo { path: &path })?,
+    };
+
+    s.parse()
+      .context(server_error::RevocationCorrupt { path })
+      .map(Some)
+  }
+
+  pub(crate) fn revocations(&self) -> ServerResult<BTreeSet<Revocation>> {
+    let context = server_error::FilesystemIo {
+      path: &self.revocations,
+    };
+
+    let mut revocations = BTreeSet::new();
+
+    for entry in fs::read_dir(&self.revocations).context(context)? {
+      let entry = entry.context(context)?;
+
+      let Ok(name) = entry.file_name().into_string() else {
+        continue;
+      };
+
+      if let Ok(public_key) = name.parse()
+        && let Some(revocation) = self.revocation(public_key)?
+      {
+        revocations.insert(revocation);
+      }
+    }
+
+    Ok(revocations)
+  }
+
+  pub(crate) fn search(&self, search: &Search) -> ServerResult<api::search::Response> {
+    let tx = self.database.begin_read()?;
+
+    let mut filters = Vec::new();
+
+    let terms = tx.open_multimap_table(SEARCH_TERMS)?;
+
+    for term in search.query_terms() {
+      let mut fingerprints = BTreeSet::new();
+
+      for entry in terms.range(term.as_str()..)? {
+        let (key, values) = entry?;
+
+        if !key.value().starts_with(&term) {
+          break;
+        }
+
+        for value in values {
+          fingerprints.insert(value?.value());
+        }
       }
 
-      directory = self.read_directory(entry.hash())?;
+      filters.push(fingerprints);
     }
 
-    Ok(None)
+    if let Some(language) = &search.language {
+      filters.push(
+        tx.open_multimap_table(SEARCH_LANGUAGES)?
+          .get(language.to_string().as_str())?
+          .map(|value| Ok(value?.value()))
+          .collect::<ServerResult<BTreeSet<Fingerprint>>>()?,
+      );
+    }
+
+    if let Some(media) = search.media {
+      filters.push(
+        tx.open_multimap_table(SEARCH_MEDIA)?
+          .get(media.to_string().as_str())?
+          .map(|value| Ok(value?.value()))
+          .collect::<ServerResult<BTreeSet<Fingerprint>>>()?,
+      );
+    }
+
+    if let Some(year) = search.year {
+      let mut fingerprints = BTreeSet::new();
+
+      for entry in tx.open_multimap_table(SEARCH_YEARS)?.range(year.range())? {
+        for value in entry?.1 {
+          fingerprints.insert(value?.value());
+        }
+      }
+
+      filters.push(fingerprints);
+    }
+
+    let mut filters = filters.into_iter();
+
+    let fingerprints = if let Some(first) = filters.next() {
+      filters.fold(first, |matches, filter| {
+        matches.intersection(&filter).copied().collect()
+      })
+    } else {
+      tx.open_table(PACKAGES)?
+        .iter()?
+        .map(|entry| Ok(entry?.0.value()))
+        .collect::<ServerResult<BTreeSet<Fingerprint>>>()?
+    };
+
+    let mut fingerprints = fingerprints.into_iter().collect::<Vec<Fingerprint>>();
+
+    match search.sort() {
+      SearchOrder::Time => {
+        let times = tx.open_table(SEARCH_TIMES)?;
+
+        let mut keys = BTreeMap::new();
+
+        for &fingerprint in &fingerprints {
+          keys.insert(
+            fingerprint,
+            times.get(&fingerprint)?.map(|time| time.value()),
+          );
+        }
+
+        fingerprints.sort_by_key(|fingerprint| {
+          let key = keys[fingerprint];
+          (key.is_none(), key)
+        });
+      }
+      SearchOrder::Title => {
+        let titles = tx.open_table(SEARCH_TITLES)?;
+
+        let mut keys = BTreeMap::new();
+
+        for &fingerprint in &fingerprints {
+          keys.insert(
+            fingerprint,
+            titles
+              .get(&fingerprint)?
+              .map(|title| title.value().to_owned()),
+          );
+        }
+
+        fingerprints.sort_by(|a, b| {
+          let a = &keys[a];
+          let b = &keys[b];
+          (a.is_none(), a).cmp(&(b.is_none(), b))
+        });
+      }
+    }
+
+    let total = fingerprints.len().into_u64();
+
+    let packages = fingerprints
+      .into_iter()
+      .skip(usize::try_from(search.offset()).unwrap_or(usize::MAX))
+      .take(Search::PAGE_SIZE.try_into().unwrap())
+      .collect();
+
+    Ok(api::search::Response { packages, total })
+  }
+
+  pub(crate) fn search_html(&self, search: Search) -> ServerResult<SearchHtml> {
+    let api::search::Response { packages, total } = self.search(&search)?;
+
+    let tx = self.database.begin_read()?;
+
+    let directories = tx.open_table(DIRECTORIES)?;
+
+    let packages = packages
+      .into_iter()
+      .map(|fingerprint| self.package_summary(&directories, fingerprint))
+      .collect::<ServerResult<Vec<(Fingerprint, Option<Metadata>, Totals)>>>()?;
+
+    Ok(SearchHtml {
+      packages,
+      search,
+      total,
+    })
+  }
+
+  pub(crate) fn signatures(&self, fingerprint: Fingerprint) -> ServerResult<BTreeSet<Signature>> {
+    let tx = self.database.begin_read()?;
+
+    ensure!(
+      tx.open_table(PACKAGES)?.get(&fingerprint)?.is_some(),
+      server_error::PackageNotFound { fingerprint },
+    );
+
+    Self::signatures_ext(&tx, fingerprint)
+  }
+
+  fn signatures_ext(
+    tx: &redb::ReadTransaction,
+    fingerprint: Fingerprint,
+  ) -> ServerResult<BTreeSet<Signature>> {
+    tx.open_multimap_table(SIGNATURES)?
+      .get(&fingerprint)?
+      .map(|signature| {
+        signature?
+          .value()
+          .parse()
+          .context(server_error::SignatureCorrupt { fingerprint })
+      })
+      .collect()
+  }
+
+  fn store_file(
+    &self,
+    hash: Hash,
+    mut reader: impl Read,
+    uploader: Option<PublicKey>,
+    quotas: &Quotas,
+  ) -> ServerResult {
+    let path = self.file_path(hash);
+
+    if path
+      .try_exists()
+      .context(server_error::FilesystemIo { path: &path })?
+    {
+      io::copy(&mut reader, &mut io::sink())
+        .context(bundle_error::Io)
+        .context(server_error::Bundle)?;
+
+      return Ok(());
+    }
+
+    let mut tempfile =
+      transfer_tempfile(hash, &self.incoming).context(server_error::FilesystemIo {
+        path: &self.incoming,
+      })?;
+
+    let mut outboard = OutboardHasher::new();
+
+    let mut buffer = vec![0; 64 * KIB];
+
+    let mut size = 0;
+
+    loop {
+      let n = reader
+        .read(&mut buffer)
+        .context(bundle_error::Io)
+        .context(server_error::Bundle)?;
+
+      if n == 0 {
+        break;
+      }
+
+      outboard.update(&buffer[..n]);
+
+      size += n.into_u64();
+
+      tempfile
+        .write_all(&buffer[..n])
+        .context(server_error::FilesystemIo {
+          path: &self.incoming,
+        })?;
+    }
+
+    self.record_file(hash, size, uploader, quotas)?;
+
+    let tree = outboard.finalize();
+
+    if !tree.is_empty() {
+      self.write_outboard(hash, &tree)?;
+    }
+
+    tempfile
+      .persist(&path)
+      .map_err(|error| error.error)
+      .context(server_error::FilesystemIo { path })?;
+
+    Ok(())
+  }
+
+  fn total_usage(total: &impl ReadableTable<(), (u64, u64)>) -> ServerResult<Usage> {
+    Ok(
+      total
+        .get(())?
+        .map(|usage| Usage::from(usage.value()))
+        .unwrap_or_default(),
+    )
+  }
+
+  fn unindex_package(
+    tx: &redb::WriteTransaction,
+    fingerprint: Fingerprint,
+    metadata: &Metadata,
+  ) -> ServerResult {
+    let mut terms = tx.open_multimap_table(SEARCH_TERMS)?;
+
+    for term in metadata.search_terms() {
+      terms.remove(term.as_str(), &fingerprint)?;
+    }
+
+    if let Some(language) = &metadata.language {
+      tx.open_multimap_table(SEARCH_LANGUAGES)?
+        .remove(language.to_string().as_str(), &fingerprint)?;
+    }
+
+    if let Some(media) = &metadata.media {
+      tx.open_multimap_table(SEARCH_MEDIA)?
+        .remove(media.name(), &fingerprint)?;
+    }
+
+    if let Some(time) = &metadata.time {
+      tx.open_table(SEARCH_TIMES)?.remove(&fingerprint)?;
+
+      tx.open_multimap_table(SEARCH_YEARS)?
+        .remove(time.year(), &fingerprint)?;
+    }
+
+    if metadata.title.is_some() {
+      tx.open_table(SEARCH_TITLES)?.remove(&fingerprint)?;
+    }
+
+    Ok(())
+  }
+
+  fn update_total(
+    total: &mut redb::Table<(), (u64, u64)>,
+    f: impl FnOnce(&mut Usage),
+  ) -> ServerResult {
+    let mut usage = Self::total_usage(total)?;
+    f(&mut usage);
+    total.insert((), <(u64, u64)>::from(usage))?;
+    Ok(())
+  }
+
+  fn update_usage(
+    table: &mut redb::Table<PublicKey, (u64, u64)>,
+    key: PublicKey,
+    f: impl FnOnce(&mut Usage),
+  ) -> ServerResult {
+    let mut usage = Self::read_usage(table, key)?;
+
+    f(&mut usage);
+
+    if usage == Usage::default() {
+      table.remove(&key)?;
+    } else {
+      table.insert(&key, <(u64, u64)>::from(usage))?;
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn usage(&self, exempt_shared: bool) -> ServerResult<api::usage::Response> {
+    let tx = self.database.begin_read()?;
+
+    let pending = tx.open_table(KEY_PENDING_USAGE)?;
+    let shared = tx.open_table(KEY_SHARED_USAGE)?;
+    let usages = tx.open_table(KEY_USAGE)?;
+
+    let mut keys = BTreeMap::new();
+
+    for entry in usages.iter()?.chain(pending.iter()?) {
+      let key = entry?.0.value();
+
+      let usage = Self::key_usage(key, &usages, &shared, &pending, exempt_shared)?;
+
+      if usage != Usage::default() {
+        keys.insert(key, usage);
+      }
+    }
+
+    Ok(api::usage::Response {
+      keys,
+      total: Self::total_usage(&tx.open_table(TOTAL_USAGE)?)?,
+    })
   }
 
   fn verified_package_file(
@@ -426,9 +1690,17 @@ impl Server {
     fingerprint: Fingerprint,
     path: &RelativePath,
   ) -> ServerResult<Hash> {
-    self
-      .resolve_path(fingerprint, path)?
-      .context(server_error::PackageFileMissing { fingerprint, path })
+    let mut current = path.clone();
+
+    for _ in 0..MAX_SYMLINKS {
+      match self.resolve_path(fingerprint, &current)? {
+        Some(Resolution::File(hash)) => return Ok(hash),
+        Some(Resolution::Symlink(target)) => current = target,
+        None => break,
+      }
+    }
+
+    Err(server_error::PackageFileMissing { fingerprint, path }.build())
   }
 
   pub(crate) fn verify_directory(&self, hash: Hash) -> ServerResult {
@@ -444,13 +1716,17 @@ impl Server {
       let mut directories = tx.open_table(DIRECTORIES)?;
 
       for (name, entry) in &directory.entries {
-        let path = self.file_path(entry.hash());
+        let (Some(entry_hash), Some(entry_size)) = (entry.hash(), entry.size()) else {
+          continue;
+        };
+
+        let path = self.file_path(entry_hash);
 
         let metadata = path.metadata().map_err(|error| {
           if error.kind() == io::ErrorKind::NotFound {
             server_error::DirectoryEntryMissing {
               directory: hash,
-              hash: entry.hash(),
+              hash: entry_hash,
               name,
               ty: entry.ty(),
             }
@@ -461,26 +1737,26 @@ impl Server {
         })?;
 
         ensure! {
-          metadata.len() == entry.size(),
+          metadata.len() == entry_size,
           server_error::DirectoryEntrySizeMismatch {
             actual: metadata.len(),
             directory: hash,
             entry: name,
-            expected: entry.size(),
+            expected: entry_size,
           },
         }
 
         if let Entry::Directory { totals, .. } = entry {
           ensure!(
-            directories.get(&entry.hash())?.is_some(),
+            directories.get(&entry_hash)?.is_some(),
             server_error::DirectoryUnverified {
               directory: hash,
-              subdirectory: entry.hash(),
+              subdirectory: entry_hash,
             },
           );
 
           self
-            .read_directory(entry.hash())?
+            .read_directory(entry_hash)?
             .totals()
             .unwrap()
             .expect(*totals)
@@ -499,7 +1775,16 @@ impl Server {
     Ok(())
   }
 
-  pub(crate) fn verify_package(&self, fingerprint: Fingerprint) -> ServerResult {
+  pub(crate) fn verify_package(
+    &self,
+    fingerprint: Fingerprint,
+    signatures: &BTreeSet<Signature>,
+    policy: Option<&Policy>,
+    owner: Option<PublicKey>,
+    quotas: &Quotas,
+  ) -> ServerResult {
+    self.check_signatures(fingerprint, signatures, policy)?;
+
     ensure!(
       self
         .database
@@ -510,10 +1795,13 @@ impl Server {
       server_error::PackageRootUnverified { fingerprint },
     );
 
-    if let Some(metadata) = self.metadata_cbor(fingerprint)? {
-      let metadata = Metadata::decode_from_slice(&metadata)
-        .context(server_error::PackageMetadataDecode { fingerprint })?;
+    let metadata = self
+      .metadata_cbor(fingerprint)?
+      .map(|metadata| Metadata::decode_from_slice(&metadata))
+      .transpose()
+      .context(server_error::PackageMetadataDecode { fingerprint })?;
 
+    if let Some(metadata) = &metadata {
       for path in metadata.files() {
         ensure!(
           self.resolve_path(fingerprint, &path)?.is_some(),
@@ -524,46 +1812,80 @@ impl Server {
 
     let tx = self.database.begin_write()?;
 
-    tx.open_table(PACKAGES)?.insert(&fingerprint, &())?;
+    let registered = tx
+      .open_table(PACKAGES)?
+      .insert(&fingerprint, &())?
+      .is_some();
 
-    tx.commit()?;
+    let existing = tx
+      .open_table(OWNERS)?
+      .get(&fingerprint)?
+      .map(|owner| owner.value());
 
-    Ok(())
-  }
+    if !registered {
+      Self::add_references(&tx, &self.package_files(fingerprint)?, owner)?;
+    }
 
-  pub(crate) fn with_data_dir(data_dir: &Utf8Path) -> Result<Self> {
-    let path = data_dir.join("database.redb");
-    let database = Database::create(&path).context(error::DatabaseOpen { path })?;
+    if let Some(owner) = owner
+      && existing.is_none()
+    {
+      tx.open_table(OWNERS)?.insert(&fingerprint, owner)?;
 
-    let tx = database.begin_write()?;
+      if registered {
+        let files = self.package_files(fingerprint)?;
+        Self::remove_references(&tx, &files, None)?;
+        Self::add_references(&tx, &files, Some(owner))?;
+      }
 
-    if tx.list_tables()?.count() == 0 && tx.list_multimap_tables()?.count() == 0 {
-      {
-        tx.open_table(METADATA)?
-          .insert(DatabaseMetadata::Schema, &SCHEMA_VERSION)?;
+      if let Some(&quota) = quotas.keys.get(&owner) {
+        let usage = Self::key_usage(
+          owner,
+          &tx.open_table(KEY_USAGE)?,
+          &tx.open_table(KEY_SHARED_USAGE)?,
+          &tx.open_table(KEY_PENDING_USAGE)?,
+          quotas.exempt_shared,
+        )?;
 
-        tx.open_table(DIRECTORIES)?;
-        tx.open_table(PACKAGES)?;
+        ensure!(
+          quota.allows(usage),
+          server_error::KeyQuotaExceeded { key: owner, quota },
+        );
       }
+    }
 
-      tx.commit()?;
-    } else {
-      let actual = tx
-        .open_table(METADATA)?
-        .get(DatabaseMetadata::Schema)?
-        .context(error::DatabaseSchemaVersionMissing)?
-        .value();
+    if let Some(metadata) = &metadata {
+      Self::index_package(&tx, fingerprint, metadata)?;
+    }
 
-      ensure!(
-        actual == SCHEMA_VERSION,
-        error::DatabaseSchemaVersionMismatch {
-          actual,
-          expected: SCHEMA_VERSION,
-        },
-      );
+    Self::insert_signatures(&tx, fingerprint, signatures)?;
 
-      drop(tx);
-    }
+    tx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn with_data_dir(data_dir: &Utf8Path) -> Result<Self> {
+    let database = open_database(&data_dir.join("database.redb"), SCHEMA_VERSION, |tx| {
+      tx.open_table(DIRECTORIES)?;
+      tx.open_table(FILES)?;
+      tx.open_table(KEY_PENDING_USAGE)?;
+      tx.open_table(KEY_SHARED_USAGE)?;
+      tx.open_table(KEY_USAGE)?;
+      tx.open_table(NAMES)?;
+      tx.open_table(OWNERS)?;
+      tx.open_table(PACKAGES)?;
+      tx.open_table(PENDING)?;
+      tx.open_table(REFERENCES)?;
+      tx.open_multimap_table(SEARCH_LANGUAGES)?;
+      tx.open_multimap_table(SEARCH_MEDIA)?;
+      tx.open_multimap_table(SEARCH_TERMS)?;
+      tx.open_table(SEARCH_TIMES)?;
+      tx.open_table(SEARCH_TITLES)?;
+      tx.open_multimap_table(SEARCH_YEARS)?;
+      tx.open_multimap_table(SIGNATURES)?;
+      tx.open_table(TOTAL_USAGE)?;
+      Ok(())
+    })?;
 
     let files = data_dir.join("files");
     filesystem::create_dir_all(&files)?;
@@ -571,14 +1893,40 @@ impl Server {
     let incoming = data_dir.join("incoming");
     filesystem::create_dir_all(&incoming)?;
 
+    let outboards = data_dir.join("outboards");
+    filesystem::create_dir_all(&outboards)?;
+
+    let revocations = data_dir.join("revocations");
+    filesystem::create_dir_all(&revocations)?;
+
     Ok(Self {
       database,
       files,
       incoming,
+      outboards,
+      revocations,
     })
   }
 
-  pub(crate) async fn write_file(&self, hash: Hash, body: Body) -> ServerResult {
+  pub(crate) async fn write_file(
+    &self,
+    hash: Hash,
+    body: Body,
+    uploader: Option<PublicKey>,
+    quotas: &Quotas,
+  ) -> ServerResult {
+    let path = self.file_path(hash);
+
+    let exists = tokio::fs::try_exists(&path)
+      .await
+      .context(server_error::FilesystemIo { path: &path })?;
+
+    let remaining = if exists {
+      (None, None)
+    } else {
+      self.remaining_bytes(uploader, quotas)?
+    };
+
     let (file, temp_path) = transfer_tempfile(hash, &self.incoming)
       .context(server_error::FilesystemIo {
         path: &self.incoming,
@@ -591,13 +1939,23 @@ impl Server {
 
     let mut hasher = Hasher::new();
 
+    let mut outboard = OutboardHasher::new();
+
     let mut stream = body.into_data_stream();
 
+    let mut size = 0;
+
     while let Some(chunk) = stream.next().await {
       let chunk = chunk.context(server_error::UploadBodyRead { hash })?;
 
+      size += chunk.len().into_u64();
+
+      Self::check_remaining_bytes(remaining, size, quotas)?;
+
       hasher.update(&chunk);
 
+      outboard.update(&chunk);
+
       writer
         .write_all(&chunk)
         .await
@@ -620,8 +1978,6 @@ impl Server {
       },
     );
 
-    let path = self.file_path(hash);
-
     if tokio::fs::try_exists(&path)
       .await
       .context(server_error::FilesystemIo { path: &path })?
@@ -629,6 +1985,14 @@ impl Server {
       return Ok(());
     }
 
+    self.record_file(hash, size, uploader, quotas)?;
+
+    let tree = outboard.finalize();
+
+    if !tree.is_empty() {
+      self.write_outboard(hash, &tree)?;
+    }
+
     temp_path
       .persist(&path)
       .map_err(|error| error.error)
@@ -636,6 +2000,28 @@ impl Server {
 
     Ok(())
   }
+
+  fn write_outboard(&self, hash: Hash, tree: &[u8]) -> ServerResult {
+    let mut tempfile =
+      transfer_tempfile(hash, &self.incoming).context(server_error::FilesystemIo {
+        path: &self.incoming,
+      })?;
+
+    tempfile
+      .write_all(tree)
+      .context(server_error::FilesystemIo {
+        path: &self.incoming,
+      })?;
+
+    let path = self.outboard_path(hash);
+
+    tempfile
+      .persist(&path)
+      .map_err(|error| error.error)
+      .context(server_error::FilesystemIo { path })?;
+
+    Ok(())
+  }
 }
 
 #[cfg(test)]
@@ -649,7 +2035,7 @@ mod tests {
     {
       let database = Database::create(data_dir.join("database.redb")).unwrap();
       let tx = database.begin_write().unwrap();
-      tx.open_table(METADATA)
+      tx.open_table(DatabaseMetadata::TABLE)
         .unwrap()
         .insert(DatabaseMetadata::Schema, &SCHEMA_VERSION + 1)
         .unwrap();
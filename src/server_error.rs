@@ -7,10 +7,21 @@ pub enum ServerError {
   ArtworkNotFound { fingerprint: Fingerprint },
   #[snafu(display("invalid authorization token"))]
   AuthorizationInvalid { source: jsonwebtoken::errors::Error },
+  #[snafu(display("invalid authorization token issuer `{issuer}`"))]
+  AuthorizationIssuerInvalid {
+    issuer: String,
+    source: PublicKeyError,
+  },
+  #[snafu(display("unknown authorization token issuer {issuer}"))]
+  AuthorizationIssuerUnknown { issuer: PublicKey },
   #[snafu(display("malformed authorization header"))]
   AuthorizationMalformed,
   #[snafu(display("missing authorization header"))]
   AuthorizationMissing,
+  #[snafu(display("invalid bundle: {source}"))]
+  Bundle { source: BundleError },
+  #[snafu(display("error reading body of bundle upload"))]
+  BundleBodyRead { source: axum::Error },
   #[snafu(display("failed to read request body"))]
   CborBody { source: axum::Error },
   #[snafu(display("failed to decode request body"))]
@@ -57,6 +68,8 @@ pub enum ServerError {
   DirectoryTotals { hash: Hash, source: TotalsError },
   #[snafu(display("directory {directory} references unverified subdirectory {subdirectory}"))]
   DirectoryUnverified { directory: Hash, subdirectory: Hash },
+  #[snafu(display("file {hash} is corrupt"))]
+  FileCorrupt { hash: Hash, source: OutboardError },
   #[snafu(display("I/O error on file {hash}"))]
   FileIo { hash: Hash, source: io::Error },
   #[snafu(display("file with hash {hash} not found"))]
@@ -70,6 +83,16 @@ pub enum ServerError {
   FingerprintParse { source: Bech32Error },
   #[snafu(display("response invalid"))]
   InvalidResponse { source: http::Error },
+  #[snafu(display("upload exceeds quota of {quota} for key {key}"))]
+  KeyQuotaExceeded { key: PublicKey, quota: Quota },
+  #[snafu(display(
+    "key {public_key} already has maximum of {limit} signatures on package {fingerprint}"
+  ))]
+  KeySignatureLimit {
+    fingerprint: Fingerprint,
+    limit: usize,
+    public_key: PublicKey,
+  },
   #[snafu(display(
     "{} {index} does not exist, package {fingerprint} has {}",
     ty.item_noun(),
@@ -89,6 +112,38 @@ pub enum ServerError {
   },
   #[snafu(display("media type {ty} does not have items"))]
   MediaTypeDoesNotHaveItems { ty: crate::MediaType },
+  #[snafu(display("stored record for name `{name}` of key {public_key} is corrupt"))]
+  NameCorrupt {
+    name: PackageName,
+    public_key: PublicKey,
+    source: DecodeError,
+  },
+  #[snafu(display("invalid signature on name `{name}` for key {public_key}"))]
+  NameInvalid {
+    name: PackageName,
+    public_key: PublicKey,
+    source: DalekSignatureError,
+  },
+  #[snafu(display("name `{name}` for key {public_key} not found"))]
+  NameNotFound {
+    name: PackageName,
+    public_key: PublicKey,
+  },
+  #[snafu(display(
+    "sequence number {sequence} for name `{name}` of key {public_key} is not greater than \
+    current sequence number {current}"
+  ))]
+  NameSequenceStale {
+    current: u64,
+    name: PackageName,
+    public_key: PublicKey,
+    sequence: u64,
+  },
+  #[snafu(display("key {issuer} may not delete package {fingerprint}"))]
+  PackageDeleteForbidden {
+    fingerprint: Fingerprint,
+    issuer: PublicKey,
+  },
   #[snafu(display("file `{path}` missing from package {fingerprint}"))]
   PackageFileMissing {
     fingerprint: Fingerprint,
@@ -122,10 +177,43 @@ pub enum ServerError {
   PackageNotFound { fingerprint: Fingerprint },
   #[snafu(display("package {fingerprint} not mounted"))]
   PackageNotMounted { fingerprint: Fingerprint },
+  #[snafu(display("package {fingerprint} does not satisfy signature policy"))]
+  PackagePolicyUnsatisfied { fingerprint: Fingerprint },
   #[snafu(display("package {fingerprint} root directory is unverified"))]
   PackageRootUnverified { fingerprint: Fingerprint },
+  #[snafu(display("invalid signature for package {fingerprint} by key {public_key}"))]
+  PackageSignatureInvalid {
+    fingerprint: Fingerprint,
+    public_key: PublicKey,
+  },
+  #[snafu(display("package {fingerprint} already has maximum of {limit} signatures"))]
+  PackageSignatureLimit {
+    fingerprint: Fingerprint,
+    limit: usize,
+  },
   #[snafu(display("page not found"))]
   PageNotFound,
+  #[snafu(display("upload exceeds server quota of {quota}"))]
+  QuotaExceeded { quota: Quota },
+  #[snafu(display("stored revocation at {path} is corrupt"))]
+  RevocationCorrupt {
+    path: Utf8PathBuf,
+    source: RevocationError,
+  },
+  #[snafu(display("invalid revocation for key {public_key}"))]
+  RevocationInvalid {
+    public_key: PublicKey,
+    source: DalekSignatureError,
+  },
+  #[snafu(display("key {issuer} does not have role `{role}`"))]
+  RoleMissing { issuer: PublicKey, role: Role },
+  #[snafu(display("stored signature for package {fingerprint} is corrupt"))]
+  SignatureCorrupt {
+    fingerprint: Fingerprint,
+    source: SignatureError,
+  },
+  #[snafu(display("invalid signature on line {line}: {source}"))]
+  SignatureParse { line: usize, source: SignatureError },
   #[snafu(display("error reading body of upload with hash {hash}"))]
   UploadBodyRead { hash: Hash, source: axum::Error },
   #[snafu(display("expected upload with hash {expected} but got {actual}"))]
@@ -139,8 +227,12 @@ impl ServerError {
     match self {
       Self::ArtworkNotFound { .. }
       | Self::AuthorizationInvalid { .. }
+      | Self::AuthorizationIssuerInvalid { .. }
+      | Self::AuthorizationIssuerUnknown { .. }
       | Self::AuthorizationMalformed
       | Self::AuthorizationMissing
+      | Self::Bundle { .. }
+      | Self::BundleBodyRead { .. }
       | Self::CborBody { .. }
       | Self::CborDecode { .. }
       | Self::DirectoryDecode { .. }
@@ -150,13 +242,20 @@ impl ServerError {
       | Self::DirectoryNotFound { .. }
       | Self::DirectoryTotals { .. }
       | Self::DirectoryUnverified { .. }
+      | Self::FileCorrupt { .. }
       | Self::FileIo { .. }
       | Self::FileNotFound { .. }
       | Self::FingerprintParse { .. }
       | Self::InvalidResponse { .. }
+      | Self::KeyQuotaExceeded { .. }
+      | Self::KeySignatureLimit { .. }
       | Self::MediaItemDoesNotExist { .. }
       | Self::MediaType { .. }
       | Self::MediaTypeDoesNotHaveItems { .. }
+      | Self::NameInvalid { .. }
+      | Self::NameNotFound { .. }
+      | Self::NameSequenceStale { .. }
+      | Self::PackageDeleteForbidden { .. }
       | Self::PackageFileMissing { .. }
       | Self::PackageFileNotFound { .. }
       | Self::PackageMediaMetadataNotFound { .. }
@@ -166,8 +265,15 @@ impl ServerError {
       | Self::PackageMetadataNotFound { .. }
       | Self::PackageNotFound { .. }
       | Self::PackageNotMounted { .. }
+      | Self::PackagePolicyUnsatisfied { .. }
       | Self::PackageRootUnverified { .. }
+      | Self::PackageSignatureInvalid { .. }
+      | Self::PackageSignatureLimit { .. }
       | Self::PageNotFound
+      | Self::QuotaExceeded { .. }
+      | Self::RevocationInvalid { .. }
+      | Self::RoleMissing { .. }
+      | Self::SignatureParse { .. }
       | Self::UploadBodyRead { .. }
       | Self::UploadHashMismatch { .. }
       | Self::WriteForbidden => self.to_string(),
@@ -177,12 +283,17 @@ impl ServerError {
       | Self::DatabaseTable { .. }
       | Self::DatabaseTransaction { .. } => "database error".into(),
       Self::FilesystemIo { .. } => "filesystem I/O error".into(),
+      Self::NameCorrupt { .. } => "name store error".into(),
+      Self::RevocationCorrupt { .. } => "revocation store error".into(),
+      Self::SignatureCorrupt { .. } => "signature store error".into(),
     }
   }
 
   fn status_code(&self) -> StatusCode {
     match self {
       Self::AuthorizationInvalid { .. }
+      | Self::AuthorizationIssuerInvalid { .. }
+      | Self::AuthorizationIssuerUnknown { .. }
       | Self::AuthorizationMalformed
       | Self::AuthorizationMissing => StatusCode::UNAUTHORIZED,
       Self::Database { .. }
@@ -190,12 +301,18 @@ impl ServerError {
       | Self::DatabaseStorage { .. }
       | Self::DatabaseTable { .. }
       | Self::DatabaseTransaction { .. }
+      | Self::FileCorrupt { .. }
       | Self::FileIo { .. }
       | Self::FilesystemIo { .. }
       | Self::InvalidResponse { .. }
+      | Self::NameCorrupt { .. }
       | Self::PackageFileMissing { .. }
-      | Self::PackageMetadataCorrupt { .. } => StatusCode::INTERNAL_SERVER_ERROR,
-      Self::CborBody { .. }
+      | Self::PackageMetadataCorrupt { .. }
+      | Self::RevocationCorrupt { .. }
+      | Self::SignatureCorrupt { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+      Self::Bundle { .. }
+      | Self::BundleBodyRead { .. }
+      | Self::CborBody { .. }
       | Self::CborDecode { .. }
       | Self::DirectoryDecode { .. }
       | Self::DirectoryEntryMissing { .. }
@@ -204,9 +321,13 @@ impl ServerError {
       | Self::DirectoryTotals { .. }
       | Self::DirectoryUnverified { .. }
       | Self::FingerprintParse { .. }
+      | Self::NameInvalid { .. }
       | Self::PackageMetadataDecode { .. }
       | Self::PackageMetadataFileMissing { .. }
       | Self::PackageRootUnverified { .. }
+      | Self::PackageSignatureInvalid { .. }
+      | Self::RevocationInvalid { .. }
+      | Self::SignatureParse { .. }
       | Self::UploadBodyRead { .. }
       | Self::UploadHashMismatch { .. } => StatusCode::BAD_REQUEST,
       Self::ArtworkNotFound { .. }
@@ -215,13 +336,22 @@ impl ServerError {
       | Self::MediaItemDoesNotExist { .. }
       | Self::MediaType { .. }
       | Self::MediaTypeDoesNotHaveItems { .. }
+      | Self::NameNotFound { .. }
       | Self::PackageFileNotFound { .. }
       | Self::PackageMediaMetadataNotFound { .. }
       | Self::PackageMetadataNotFound { .. }
       | Self::PackageNotFound { .. }
       | Self::PackageNotMounted { .. }
       | Self::PageNotFound => StatusCode::NOT_FOUND,
-      Self::WriteForbidden => StatusCode::FORBIDDEN,
+      Self::PackageDeleteForbidden { .. }
+      | Self::PackagePolicyUnsatisfied { .. }
+      | Self::RoleMissing { .. }
+      | Self::WriteForbidden => StatusCode::FORBIDDEN,
+      Self::NameSequenceStale { .. } => StatusCode::CONFLICT,
+      Self::KeyQuotaExceeded { .. }
+      | Self::KeySignatureLimit { .. }
+      | Self::PackageSignatureLimit { .. }
+      | Self::QuotaExceeded { .. } => StatusCode::INSUFFICIENT_STORAGE,
     }
   }
 }
@@ -1,3 +1,9 @@
+use super::*;
+
+#[derive(Default)]
 pub(crate) struct SignOptions {
+  pub(crate) comment: Option<Comment>,
+  pub(crate) expires: Option<jiff::Timestamp>,
+  pub(crate) purpose: Option<Purpose>,
   pub(crate) timestamp: bool,
 }
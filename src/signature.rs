@@ -1,5 +1,8 @@
 use super::*;
 
+const COMMENT: Fe32 = Fe32::C;
+const EXPIRES: Fe32 = Fe32::E;
+const PURPOSE: Fe32 = Fe32::P;
 const TIMESTAMP: Fe32 = Fe32::T;
 
 #[allow(clippy::arbitrary_source_item_ordering)]
@@ -19,10 +22,20 @@ impl Signature {
     (self.public_key, &self.statement, self.signature.to_bytes())
   }
 
+  fn decode_bech32_text(decoder: &mut Bech32Decoder) -> Result<String, SignatureError> {
+    let [len] = decoder.byte_array()?;
+    String::from_utf8(decoder.byte_vec(len.into())?).context(signature_error::Utf8)
+  }
+
   fn decode_signature(decoder: &mut Decoder) -> Result<ed25519_dalek::Signature, DecodeError> {
     Ok(ed25519_dalek::Signature::from_bytes(&decoder.byte_array()?))
   }
 
+  fn encode_bech32_text(encoder: &mut Bech32Encoder, text: &str) {
+    encoder.bytes(&[u8::try_from(text.len()).unwrap()]);
+    encoder.bytes(text.as_bytes());
+  }
+
   fn encode_signature(signature: &ed25519_dalek::Signature, encoder: &mut Encoder) {
     encoder.bytes(&signature.to_bytes());
   }
@@ -77,6 +90,18 @@ impl Display for Signature {
       encoder.fe(TIMESTAMP);
       encoder.bytes(&timestamp.to_le_bytes());
     }
+    if let Some(expires) = self.statement.expires {
+      encoder.fe(EXPIRES);
+      encoder.bytes(&expires.to_le_bytes());
+    }
+    if let Some(purpose) = self.statement.purpose {
+      encoder.fe(PURPOSE);
+      Self::encode_bech32_text(&mut encoder, &purpose.to_string());
+    }
+    if let Some(comment) = &self.statement.comment {
+      encoder.fe(COMMENT);
+      Self::encode_bech32_text(&mut encoder, comment.as_str());
+    }
     write!(f, "{encoder}")
   }
 }
@@ -90,17 +115,56 @@ impl FromStr for Signature {
     let fingerprint = decoder.byte_array()?;
     let signature = decoder.byte_array()?;
 
-    let timestamp = match decoder.fe() {
-      None => None,
-      Some(TIMESTAMP) => Some(u64::from_le_bytes(decoder.byte_array()?)),
-      Some(tag) => return Err(signature_error::Field { tag }.build()),
+    let mut tag = decoder.fe();
+
+    let timestamp = if tag == Some(TIMESTAMP) {
+      let timestamp = u64::from_le_bytes(decoder.byte_array()?);
+      tag = decoder.fe();
+      Some(timestamp)
+    } else {
+      None
     };
 
+    let expires = if tag == Some(EXPIRES) {
+      let expires = u64::from_le_bytes(decoder.byte_array()?);
+      tag = decoder.fe();
+      Some(expires)
+    } else {
+      None
+    };
+
+    let purpose = if tag == Some(PURPOSE) {
+      let purpose = Self::decode_bech32_text(&mut decoder)?
+        .parse()
+        .context(signature_error::Purpose)?;
+      tag = decoder.fe();
+      Some(purpose)
+    } else {
+      None
+    };
+
+    let comment = if tag == Some(COMMENT) {
+      let comment = Self::decode_bech32_text(&mut decoder)?
+        .parse()
+        .context(signature_error::Comment)?;
+      tag = decoder.fe();
+      Some(comment)
+    } else {
+      None
+    };
+
+    if let Some(tag) = tag {
+      return Err(signature_error::Field { tag }.build());
+    }
+
     decoder.done()?;
     Ok(Self {
       statement: Statement {
         fingerprint: Fingerprint::from_bytes(fingerprint),
         timestamp,
+        expires,
+        purpose,
+        comment,
       },
       signature: ed25519_dalek::Signature::from_bytes(&signature),
       public_key: PublicKey::from_bytes(public_key).context(signature_error::PublicKey)?,
@@ -131,6 +195,9 @@ mod tests {
     let statement = Statement {
       fingerprint,
       timestamp: Some(1000),
+      expires: None,
+      purpose: None,
+      comment: None,
     };
     let mut signature = private_key.sign(&statement);
     signature.statement.fingerprint = Fingerprint::from_bytes(default());
@@ -147,6 +214,9 @@ mod tests {
     let statement = Statement {
       fingerprint,
       timestamp: Some(1000),
+      expires: None,
+      purpose: None,
+      comment: None,
     };
     let mut signature = private_key.sign(&statement);
     signature.statement.timestamp = Some(2000);
@@ -163,6 +233,9 @@ mod tests {
     let statement = Statement {
       fingerprint,
       timestamp: Some(1000),
+      expires: None,
+      purpose: None,
+      comment: None,
     };
     let mut signature = private_key.sign(&statement);
     signature.statement.timestamp = None;
@@ -172,6 +245,39 @@ mod tests {
     );
   }
 
+  #[test]
+  fn round_trip_with_all_fields() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let fingerprint = test::FINGERPRINT.parse::<Fingerprint>().unwrap();
+    let signature = private_key.sign(&Statement {
+      fingerprint,
+      timestamp: Some(1000),
+      expires: Some(2000),
+      purpose: Some(Purpose::Review),
+      comment: Some("looks good".parse().unwrap()),
+    });
+    let parsed = signature.to_string().parse::<Signature>().unwrap();
+    assert_eq!(parsed, signature);
+    parsed.verify(fingerprint).unwrap();
+    test::assert_encoding(signature);
+  }
+
+  #[test]
+  fn round_trip_with_maximum_length_comment() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+    let signature = private_key.sign(&Statement {
+      fingerprint: test::FINGERPRINT.parse().unwrap(),
+      timestamp: Some(u64::MAX),
+      expires: Some(u64::MAX),
+      purpose: Some(Purpose::Release),
+      comment: Some("a".repeat(Comment::MAX_LEN).parse().unwrap()),
+    });
+    assert_eq!(
+      signature.to_string().parse::<Signature>().unwrap(),
+      signature,
+    );
+  }
+
   #[test]
   fn signature_begins_with_pubkey_and_fingerprint() {
     let prefix = format!(
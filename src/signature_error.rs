@@ -5,8 +5,14 @@ use super::*;
 pub enum SignatureError {
   #[snafu(transparent)]
   Bech32 { source: Bech32Error },
+  #[snafu(display("invalid signature comment"))]
+  Comment { source: CommentError },
   #[snafu(display("unexpected signature field `{tag}`"))]
   Field { tag: Fe32 },
   #[snafu(display("signature public key invalid"))]
   PublicKey { source: PublicKeyError },
+  #[snafu(display("invalid signature purpose"))]
+  Purpose { source: strum::ParseError },
+  #[snafu(display("signature text not valid UTF-8"))]
+  Utf8 { source: std::string::FromUtf8Error },
 }
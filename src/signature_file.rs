@@ -0,0 +1,112 @@
+use super::*;
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct SignatureFile {
+  pub(crate) signatures: BTreeSet<Signature>,
+}
+
+impl SignatureFile {
+  pub(crate) fn load(path: &Utf8Path) -> Result<Self> {
+    Self::parse(path, &filesystem::read_to_string(path)?)
+  }
+
+  pub(crate) fn load_opt(path: &Utf8Path) -> Result<Self> {
+    match filesystem::read_to_string_opt(path)? {
+      Some(s) => Self::parse(path, &s),
+      None => Ok(Self::default()),
+    }
+  }
+
+  fn parse(path: &Utf8Path, s: &str) -> Result<Self> {
+    let mut signatures = BTreeSet::new();
+
+    for (i, line) in s.lines().enumerate() {
+      let line = line.trim();
+
+      if line.is_empty() {
+        continue;
+      }
+
+      signatures.insert(
+        line
+          .parse()
+          .context(error::SignatureFileLoad { line: i + 1, path })?,
+      );
+    }
+
+    Ok(Self { signatures })
+  }
+
+  pub(crate) fn save(&self, path: &Utf8Path) -> Result {
+    filesystem::write(path, self.to_string())
+  }
+}
+
+impl Display for SignatureFile {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    for signature in &self.signatures {
+      writeln!(f, "{signature}")?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn blank_lines_are_ignored() {
+    let (_dir, path) = tempdir();
+
+    let path = path.join("foo.signature");
+
+    filesystem::write(&path, format!("\n  {}\n\n", test::SIGNATURE)).unwrap();
+
+    assert_eq!(
+      SignatureFile::load(&path).unwrap(),
+      SignatureFile {
+        signatures: [test::SIGNATURE.parse().unwrap()].into(),
+      },
+    );
+  }
+
+  #[test]
+  fn invalid_signature() {
+    let (_dir, path) = tempdir();
+
+    let path = path.join("foo.signature");
+
+    filesystem::write(&path, format!("{}\nfoo\n", test::SIGNATURE)).unwrap();
+
+    assert_matches!(
+      SignatureFile::load(&path).unwrap_err(),
+      Error::SignatureFileLoad { line: 2, .. },
+    );
+  }
+
+  #[test]
+  fn round_trip() {
+    let (_dir, path) = tempdir();
+
+    let path = path.join("foo.signature");
+
+    assert_eq!(
+      SignatureFile::load_opt(&path).unwrap(),
+      SignatureFile::default()
+    );
+
+    let file = SignatureFile {
+      signatures: [test::SIGNATURE.parse().unwrap()].into(),
+    };
+
+    file.save(&path).unwrap();
+
+    assert_eq!(
+      filesystem::read_to_string(&path).unwrap(),
+      format!("{}\n", test::SIGNATURE),
+    );
+
+    assert_eq!(SignatureFile::load(&path).unwrap(), file);
+  }
+}
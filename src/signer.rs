@@ -0,0 +1,24 @@
+use super::*;
+
+pub(crate) enum Signer<'a> {
+  Keychain {
+    key: &'a KeyName,
+    keychain: &'a Keychain,
+  },
+  PrivateKey(PrivateKey),
+  SshAgent(Option<PublicKey>),
+}
+
+impl Signer<'_> {
+  pub(crate) fn sign(&self, statement: &Statement) -> Result<Signature> {
+    match self {
+      Self::Keychain { key, keychain } => keychain.sign(key, statement),
+      Self::PrivateKey(private_key) => Ok(private_key.sign(statement)),
+      Self::SshAgent(key) => {
+        let signature = ssh::agent_sign(*key, statement).context(error::SshAgent)?;
+        signature.verify(statement.fingerprint)?;
+        Ok(signature)
+      }
+    }
+  }
+}
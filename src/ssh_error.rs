@@ -0,0 +1,46 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub enum SshError {
+  #[snafu(display("I/O error communicating with ssh-agent"))]
+  Agent { source: io::Error },
+  #[snafu(display("ssh-agent does not hold key {public_key}"))]
+  AgentIdentityNotFound { public_key: super::PublicKey },
+  #[snafu(display("ssh-agent has no Ed25519 keys"))]
+  AgentKeyNotFound,
+  #[snafu(display("ssh-agent refused to sign"))]
+  AgentRefused,
+  #[snafu(display("unexpected ssh-agent response type {ty}"))]
+  AgentResponse { ty: u8 },
+  #[snafu(display("ssh-agent response of {len} bytes exceeds maximum of {max} bytes"))]
+  AgentResponseLength { len: usize, max: usize },
+  #[snafu(display("`SSH_AUTH_SOCK` not set"))]
+  AgentSocket,
+  #[snafu(display("ssh-agent signing only supported on unix"))]
+  AgentUnsupported,
+  #[snafu(display("invalid base64"))]
+  Base64 { source: base64::DecodeError },
+  #[snafu(display("OpenSSH private key check bytes do not match"))]
+  Check,
+  #[snafu(display(
+    "encrypted OpenSSH private keys are not supported, decrypt with `ssh-keygen -p`"
+  ))]
+  Encrypted,
+  #[snafu(display("OpenSSH private key files containing {count} keys are not supported"))]
+  KeyCount { count: u32 },
+  #[snafu(display("unsupported key type `{ty}`, only `ssh-ed25519` is supported"))]
+  KeyType { ty: String },
+  #[snafu(display("invalid OpenSSH private key header"))]
+  PrivateKeyHeader,
+  #[snafu(display("invalid Ed25519 public key"))]
+  PublicKey { source: PublicKeyError },
+  #[snafu(display("invalid OpenSSH public key"))]
+  PublicKeyFormat,
+  #[snafu(display("OpenSSH private key does not match embedded public key"))]
+  PublicKeyMismatch,
+  #[snafu(display("{len} trailing bytes"))]
+  Trailing { len: usize },
+  #[snafu(display("truncated"))]
+  Truncated,
+}
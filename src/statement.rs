@@ -1,11 +1,18 @@
 use super::*;
 
+#[allow(clippy::arbitrary_source_item_ordering)]
 #[derive(Clone, Debug, Decode, Encode, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Statement {
   #[n(0)]
   pub fingerprint: Fingerprint,
   #[n(1)]
   pub timestamp: Option<u64>,
+  #[n(2)]
+  pub expires: Option<u64>,
+  #[n(3)]
+  pub purpose: Option<Purpose>,
+  #[n(4)]
+  pub comment: Option<Comment>,
 }
 
 impl Statement {
@@ -18,6 +25,10 @@ impl Statement {
 
     Hash::bytes(&envelope.encode_to_vec())
   }
+
+  pub(crate) fn is_expired_at(&self, now: u64) -> bool {
+    self.expires.is_some_and(|expires| expires <= now)
+  }
 }
 
 #[cfg(test)]
@@ -40,11 +51,25 @@ mod tests {
     assert_eq!(statement.digest(), Hash::bytes(&bytes));
   }
 
+  #[test]
+  fn digest_with_all_fields() {
+    case(Statement {
+      fingerprint: Fingerprint::from_bytes([0; Fingerprint::LEN]),
+      timestamp: Some(1000),
+      expires: Some(2000),
+      purpose: Some(Purpose::Release),
+      comment: Some("foo".parse().unwrap()),
+    });
+  }
+
   #[test]
   fn digest_with_timestamp() {
     case(Statement {
       fingerprint: Fingerprint::from_bytes([0; Fingerprint::LEN]),
       timestamp: Some(1000),
+      expires: None,
+      purpose: None,
+      comment: None,
     });
   }
 
@@ -53,6 +78,46 @@ mod tests {
     case(Statement {
       fingerprint: Fingerprint::from_bytes([0; Fingerprint::LEN]),
       timestamp: None,
+      expires: None,
+      purpose: None,
+      comment: None,
     });
   }
+
+  #[test]
+  fn encoding() {
+    assert_cbor(
+      Statement {
+        fingerprint: Fingerprint::from_bytes([0; Fingerprint::LEN]),
+        timestamp: None,
+        expires: None,
+        purpose: None,
+        comment: None,
+      },
+      concat!(
+        "a1",
+        "00",
+        "5820",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+      ),
+    );
+  }
+
+  #[test]
+  fn is_expired_at() {
+    let mut statement = Statement {
+      fingerprint: Fingerprint::from_bytes([0; Fingerprint::LEN]),
+      timestamp: None,
+      expires: None,
+      purpose: None,
+      comment: None,
+    };
+
+    assert!(!statement.is_expired_at(1000));
+
+    statement.expires = Some(1000);
+
+    assert!(!statement.is_expired_at(999));
+    assert!(statement.is_expired_at(1000));
+  }
 }
@@ -8,10 +8,15 @@ use {
 
 mod archive;
 mod bech32;
+mod bundle;
+mod check_proof;
 mod contains;
 mod create;
 mod delete;
+mod diff;
 mod download;
+mod export;
+mod extract;
 mod files;
 mod fingerprint;
 mod gc;
@@ -19,15 +24,22 @@ mod hash;
 mod info;
 mod key;
 mod keygen;
+mod keys;
 mod languages;
 mod lints;
 mod man;
 mod manifest;
 mod metadata;
+mod name;
+mod prove;
+mod revocation;
 pub(crate) mod serve;
 mod sign;
 mod signatures;
 mod size;
+mod status;
+mod trust;
+mod unbundle;
 mod upload;
 mod verify;
 
@@ -53,14 +65,24 @@ pub(crate) enum Subcommand {
   Archive(archive::Archive),
   #[command(about = "Encode and decode bech32")]
   Bech32(bech32::Bech32),
+  #[command(about = "Bundle package into a single file")]
+  Bundle(bundle::Bundle),
+  #[command(about = "Check proof that package contains file")]
+  CheckProof(check_proof::CheckProof),
   #[command(about = "Check if manifest contains file")]
   Contains(contains::Contains),
   #[command(about = "Create manifest")]
   Create(create::Create),
   #[command(about = "Delete package from a filepack server")]
   Delete(delete::Delete),
+  #[command(about = "Compare manifest with manifest or directory")]
+  Diff(diff::Diff),
   #[command(about = "Download package or file from a filepack server")]
   Download(download::Download),
+  #[command(about = "Export package as tar or zip archive")]
+  Export(export::Export),
+  #[command(about = "Extract package from content sources")]
+  Extract(extract::Extract),
   #[command(about = "List manifest files")]
   Files(files::Files),
   #[command(about = "Print package fingerprint")]
@@ -71,10 +93,12 @@ pub(crate) enum Subcommand {
   Hash(hash::Hash),
   #[command(about = "Print info")]
   Info,
-  #[command(about = "Print public key")]
+  #[command(about = "Print public key or manage private key encryption")]
   Key(key::Key),
   #[command(about = "Generate key pair")]
   Keygen(keygen::Keygen),
+  #[command(about = "Manage keychain keys", subcommand)]
+  Keys(keys::Keys),
   #[command(about = "List language codes")]
   Languages(languages::Languages),
   #[command(about = "List lint groups")]
@@ -85,6 +109,12 @@ pub(crate) enum Subcommand {
   Manifest(manifest::Manifest),
   #[command(about = "Print metadata")]
   Metadata(metadata::Metadata),
+  #[command(about = "Manage signed package names", subcommand)]
+  Name(name::Name),
+  #[command(about = "Prove that package contains file")]
+  Prove(prove::Prove),
+  #[command(about = "Manage key revocations", subcommand)]
+  Revocation(revocation::Revocation),
   #[command(about = "Start a filepack server")]
   Serve(serve::Serve),
   #[command(about = "Sign manifest")]
@@ -93,6 +123,12 @@ pub(crate) enum Subcommand {
   Signatures(signatures::Signatures),
   #[command(about = "Print manifest total file size")]
   Size(size::Size),
+  #[command(about = "Compare directory against manifest")]
+  Status(status::Status),
+  #[command(about = "Manage trusted public keys", subcommand)]
+  Trust(trust::Trust),
+  #[command(about = "Unpack package from bundle")]
+  Unbundle(unbundle::Unbundle),
   #[command(about = "Upload package or file to a filepack server")]
   Upload(upload::Upload),
   #[command(about = "Verify manifest")]
@@ -104,10 +140,15 @@ impl Subcommand {
     match self {
       Self::Archive(archive) => archive.run(),
       Self::Bech32(bech32) => bech32.run(),
+      Self::Bundle(bundle) => bundle.run(options),
+      Self::CheckProof(check_proof) => check_proof.run(options),
       Self::Contains(contains) => contains.run(options),
       Self::Create(create) => create.run(options),
       Self::Delete(delete) => delete.run(options),
+      Self::Diff(diff) => diff.run(options),
       Self::Download(download) => download.run(options),
+      Self::Export(export) => export.run(),
+      Self::Extract(extract) => extract.run(options),
       Self::Files(files) => files.run(),
       Self::Fingerprint(fingerprint) => fingerprint.run(),
       Self::Gc(gc) => gc.run(options),
@@ -115,15 +156,22 @@ impl Subcommand {
       Self::Info => info::run(options),
       Self::Key(key) => key.run(options),
       Self::Keygen(keygen) => keygen.run(options),
+      Self::Keys(keys) => keys.run(options),
       Self::Languages(languages) => languages.run(),
       Self::Lints => lints::run(),
       Self::Man => man::run(),
       Self::Manifest(manifest) => manifest.run(),
       Self::Metadata(metadata) => metadata.run(),
+      Self::Name(name) => name.run(options),
+      Self::Prove(prove) => prove.run(),
+      Self::Revocation(revocation) => revocation.run(options),
       Self::Serve(serve) => serve.run(options),
       Self::Sign(sign) => sign.run(options),
-      Self::Signatures(signatures) => signatures.run(),
+      Self::Signatures(signatures) => signatures.run(options),
       Self::Size(size) => size.run(),
+      Self::Status(status) => status.run(options),
+      Self::Trust(trust) => trust.run(options),
+      Self::Unbundle(unbundle) => unbundle.run(options),
       Self::Upload(upload) => upload.run(options),
       Self::Verify(verify) => verify.run(options),
     }
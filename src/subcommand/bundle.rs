@@ -0,0 +1,62 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Bundle {
+  #[arg(help = MANIFEST_PATH_HELP, long)]
+  manifest: Option<Utf8PathBuf>,
+  #[arg(help = "Write bundle to <OUTPUT>")]
+  output: Utf8PathBuf,
+}
+
+impl Bundle {
+  pub(crate) fn run(self, options: Options) -> Result {
+    ensure! {
+      !filesystem::exists(&self.output)?,
+      error::FileAlreadyExists { path: &self.output },
+    }
+
+    let (path, manifest) = Manifest::load_with_opt_path(self.manifest.as_deref())?;
+
+    let root = path.parent().unwrap();
+
+    let directory = match self.output.parent() {
+      Some(parent) if !parent.as_str().is_empty() => parent,
+      _ => Utf8Path::new("."),
+    };
+
+    let tempfile = transfer_tempfile(manifest.fingerprint().into(), directory)
+      .context(error::FilesystemIo { path: directory })?;
+
+    let mut writer =
+      BundleWriter::new(io::BufWriter::new(tempfile), &manifest).context(error::Bundle {
+        path: Manifest::FILENAME,
+      })?;
+
+    let contents = manifest.contents();
+
+    let bar = progress_bar::new(
+      &options,
+      contents.values().map(|(_path, file)| file.size).sum(),
+    );
+
+    for (path, file) in contents.values() {
+      let source = root.join(path);
+
+      writer
+        .content(*file, bar.wrap_read(filesystem::open(&source)?))
+        .context(error::Bundle { path })?;
+    }
+
+    writer
+      .finish()
+      .and_then(|writer| writer.into_inner().map_err(io::IntoInnerError::into_error))
+      .context(error::FilesystemIo { path: &self.output })?
+      .persist_noclobber(&self.output)
+      .map_err(|error| error.error)
+      .context(error::FilesystemIo { path: &self.output })?;
+
+    bar.finish();
+
+    Ok(())
+  }
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct CheckProof {
+  #[arg(help = "Check that proven file matches <FILE>", long)]
+  file: Option<Utf8PathBuf>,
+  #[arg(help = "Check proof against package <FINGERPRINT>", long)]
+  fingerprint: Fingerprint,
+  #[arg(help = "Load proof from <PROOF>")]
+  proof: Utf8PathBuf,
+}
+
+#[derive(Serialize)]
+struct Output {
+  hash: Hash,
+  path: RelativePath,
+  size: u64,
+}
+
+impl CheckProof {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let proof = Proof::decode_from_slice(&filesystem::read(&self.proof)?)
+      .context(error::DecodeProof { path: &self.proof })?;
+
+    let expected = proof
+      .verify(self.fingerprint)
+      .context(error::ProofInvalid { path: &self.proof })?;
+
+    if let Some(path) = self.file {
+      let actual = options
+        .hash_file(&path)
+        .context(error::FilesystemIo { path: &path })?;
+
+      if !actual.matches(expected) {
+        File::eprint_mismatch(actual, expected, path.as_str());
+        return Err(error::FileMismatch { path }.build());
+      }
+    }
+
+    let output = Output {
+      hash: expected.hash,
+      path: proof.path,
+      size: expected.size,
+    };
+
+    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+
+    Ok(())
+  }
+}
@@ -6,6 +6,16 @@ pub(crate) struct Create {
   deny: Option<LintGroup>,
   #[arg(help = "Overwrite manifest if it already exists", long)]
   force: bool,
+  #[arg(
+    conflicts_with = "root",
+    help = "Create manifest for files in tar archive <FILE>, which may be compressed with gzip \
+            or zstd, without extracting it. If <FILE> is `-`, read tar archive from standard \
+            input. Manifest is written to the directory containing <FILE>, or to the current \
+            directory if reading from standard input.",
+    long,
+    value_name = "FILE"
+  )]
+  from_tar: Option<Utf8PathBuf>,
   #[arg(help = "Ignore <PATH>", long, value_name = "PATH")]
   ignore: Vec<RelativePath>,
   #[arg(default_value_t = KeyName::DEFAULT, help = "Sign with <KEY>", long, requires = "sign")]
@@ -21,41 +31,36 @@ pub(crate) struct Create {
   sign: bool,
   #[arg(help = TIMESTAMP_HELP, long)]
   timestamp: bool,
+  #[arg(
+    help = "Update existing manifest, reusing cached hashes of unchanged files and keeping \
+            signatures if package fingerprint is unchanged",
+    long
+  )]
+  update: bool,
 }
 
 impl Create {
-  pub(crate) fn run(self, options: Options) -> Result {
-    let current_dir = current_dir()?;
-
-    let root = self.root.unwrap_or_else(|| current_dir.clone());
-
-    let manifest_path = if let Some(path) = self.manifest {
-      path
-    } else {
-      root.join(Manifest::FILENAME)
-    };
-
+  fn directory(
+    &self,
+    options: &Options,
+    manifest_path: &Utf8Path,
+    root: &Utf8Path,
+  ) -> Result<(DirectoryTree, Option<Vec<u8>>, Option<Manifest>)> {
     let path = root.join(Metadata::YAML_FILENAME);
 
     let metadata = if let Some(yaml) = filesystem::read_to_string_opt(&path)? {
-      let mut metadata = Metadata::deserialize(&path, &yaml)?;
-
-      metadata.populate(&root)?;
-
-      metadata.validate(&root)?;
-
-      let cbor = metadata.encode_to_vec();
+      let metadata = Self::metadata(&path, &yaml, root)?;
 
       let path = root.join(Metadata::CBOR_FILENAME);
 
       ensure! {
-        self.force || !filesystem::exists(&path)?,
+        self.force || self.update || !filesystem::exists(&path)?,
         error::MetadataAlreadyExists {
           path,
         },
       }
 
-      Some((metadata, cbor))
+      Some(metadata)
     } else {
       let path = root.join(Metadata::CBOR_FILENAME);
 
@@ -69,52 +74,89 @@ impl Create {
       None
     };
 
-    let cleaned_manifest = current_dir.join(&manifest_path).lexiclean();
+    let mut walk = Walk::new(root, manifest_path, &self.ignore)?;
 
-    let mut paths = HashMap::new();
+    self.lint(&walk.paths, &walk.symlinks, metadata.as_ref(), root)?;
 
-    let mut case_conflicts = HashMap::<RelativePath, Vec<RelativePath>>::new();
+    if let Some((metadata, _cbor)) = &metadata {
+      let files = walk
+        .files
+        .keys()
+        .cloned()
+        .collect::<HashSet<RelativePath>>();
 
-    let mut lint_errors = 0u64;
+      metadata.check_files(&files)?;
 
-    let mut empty = Vec::new();
+      if metadata.media.is_some() {
+        metadata.check_extras(&files, &walk.empty)?;
+      }
+    }
 
-    let lints = self.deny.map(LintGroup::lints).unwrap_or_default();
+    let existing = self.existing(manifest_path)?;
 
-    for entry in WalkDir::new(&root).sort_by_file_name() {
-      let entry = entry?;
+    if let Some((_metadata, cbor)) = &metadata {
+      filesystem::write(&root.join(Metadata::CBOR_FILENAME), cbor)?;
 
-      if entry.depth() == 0 {
-        continue;
-      }
+      walk.files.insert(
+        Metadata::CBOR_FILENAME.parse().unwrap(),
+        cbor.len().into_u64(),
+      );
+    }
 
-      let path = decode_path(entry.path())?;
+    let bar = progress_bar::new(options, walk.file_size()?);
 
-      let cleaned_path = current_dir.join(path).lexiclean();
+    let mut package = DirectoryTree::new();
 
-      if cleaned_path == cleaned_manifest {
-        continue;
-      }
+    for path in &walk.empty {
+      package.create_directory(path)?;
+    }
+
+    for path in walk.files.keys() {
+      let file = options
+        .hash_file(&root.join(path))
+        .context(error::FilesystemIo { path })?;
+      package.create_file(path, file)?;
+      bar.inc(file.size);
+    }
 
-      let relative = path.strip_prefix(&root).unwrap();
+    options.commit_cache()?;
 
-      let relative = RelativePath::try_from(relative).context(error::Path { path: relative })?;
+    for (path, symlink) in walk.symlinks {
+      package.create_symlink(&path, symlink)?;
+    }
 
-      if self
-        .ignore
-        .iter()
-        .any(|ignore| relative.starts_with(ignore))
-      {
-        continue;
-      }
+    Ok((package, metadata.map(|(_metadata, cbor)| cbor), existing))
+  }
 
+  fn existing(&self, manifest_path: &Utf8Path) -> Result<Option<Manifest>> {
+    if self.update && filesystem::exists(manifest_path)? {
+      Ok(Some(Manifest::load_with_path(manifest_path)?))
+    } else {
       ensure! {
-        !entry.file_type().is_symlink(),
-        error::Symlink { path },
+        self.force || !manifest_path.try_exists().context(error::FilesystemIo { path: manifest_path })?,
+        error::ManifestAlreadyExists {
+          path: manifest_path,
+        },
       }
 
-      let metadata = filesystem::metadata(path)?;
+      Ok(None)
+    }
+  }
+
+  fn lint(
+    &self,
+    paths: &[RelativePath],
+    symlinks: &BTreeMap<RelativePath, Symlink>,
+    metadata: Option<&(Metadata, Vec<u8>)>,
+    root: &Utf8Path,
+  ) -> Result {
+    let mut case_conflicts = HashMap::<RelativePath, Vec<RelativePath>>::new();
+
+    let mut lint_errors = 0u64;
+
+    let lints = self.deny.map(LintGroup::lints).unwrap_or_default();
 
+    for relative in paths {
       if let Some(lint) = relative.lint(&lints) {
         eprintln!("error: path failed lint: `{relative}`");
         eprintln!("       └─ {lint}");
@@ -127,15 +169,6 @@ impl Create {
           .or_default()
           .push(relative.clone());
       }
-
-      empty.pop_if(|dir| relative.starts_with(dir));
-
-      if entry.file_type().is_dir() {
-        empty.push(relative);
-        continue;
-      }
-
-      paths.insert(relative, metadata.len());
     }
 
     for mut originals in case_conflicts.into_values() {
@@ -156,12 +189,29 @@ impl Create {
       }
     }
 
+    if lints.contains(&Lint::SymlinkDangling) {
+      let paths = paths.iter().collect::<HashSet<&RelativePath>>();
+
+      for (path, symlink) in symlinks {
+        if !paths.contains(&symlink.target) {
+          eprintln!("error: path failed lint: `{path}`");
+          eprintln!(
+            "       └─ {}",
+            LintError::SymlinkDangling {
+              target: symlink.target.clone(),
+            }
+          );
+          lint_errors += 1;
+        }
+      }
+    }
+
     if lints.contains(&Lint::CoverArtMissing)
-      && let Some((metadata, _cbor)) = &metadata
+      && let Some((metadata, _cbor)) = metadata
       && let Some(Media::Audio { items }) = &metadata.media
     {
       for audio in items {
-        if !audio.has_cover_art(&root)? {
+        if !audio.has_cover_art(root)? {
           eprintln!("error: path failed lint: `{}`", audio.path());
           eprintln!("       └─ {}", LintError::CoverArtMissing);
           lint_errors += 1;
@@ -173,56 +223,49 @@ impl Create {
       return Err(error::Lint { count: lint_errors }.build());
     }
 
-    if let Some((metadata, _cbor)) = &metadata {
-      let files = paths.keys().cloned().collect::<HashSet<RelativePath>>();
+    Ok(())
+  }
 
-      metadata.check_files(&files)?;
+  fn metadata(path: &Utf8Path, yaml: &str, root: &Utf8Path) -> Result<(Metadata, Vec<u8>)> {
+    let mut metadata = Metadata::deserialize(path, yaml)?;
 
-      if metadata.media.is_some() {
-        metadata.check_extras(&files, &empty)?;
-      }
-    }
+    metadata.populate(root)?;
 
-    ensure! {
-      self.force || !manifest_path.try_exists().context(error::FilesystemIo { path: &manifest_path })?,
-      error::ManifestAlreadyExists {
-        path: manifest_path,
-      },
-    }
+    metadata.validate(root)?;
 
-    if let Some((_metadata, cbor)) = &metadata {
-      filesystem::write(&root.join(Metadata::CBOR_FILENAME), cbor)?;
+    let cbor = metadata.encode_to_vec();
 
-      paths.insert(
-        Metadata::CBOR_FILENAME.parse().unwrap(),
-        cbor.len().into_u64(),
-      );
-    }
+    Ok((metadata, cbor))
+  }
 
-    let mut total_file_size = 0u64;
-    for size in paths.values() {
-      total_file_size = total_file_size
-        .checked_add(*size)
-        .context(error::TotalFileSizeOverflow)?;
+  pub(crate) fn run(self, mut options: Options) -> Result {
+    if self.update {
+      options.enable_cache()?;
     }
 
-    let bar = progress_bar::new(&options, total_file_size);
+    let current_dir = current_dir()?;
 
-    let mut package = DirectoryTree::new();
+    let root = self.root.clone().unwrap_or(current_dir);
 
-    for path in empty {
-      package.create_directory(&path)?;
-    }
+    let manifest_path = if let Some(path) = &self.manifest {
+      path.clone()
+    } else if let Some(tar) = &self.from_tar {
+      if tar == TarWalk::STDIN {
+        Manifest::FILENAME.into()
+      } else {
+        tar.parent().unwrap().join(Manifest::FILENAME)
+      }
+    } else {
+      root.join(Manifest::FILENAME)
+    };
 
-    for (path, _size) in paths {
-      let file = options
-        .hash_file(&root.join(&path))
-        .context(error::FilesystemIo { path: &path })?;
-      package.create_file(&path, file)?;
-      bar.inc(file.size);
-    }
+    let (package, cbor, existing) = if let Some(tar) = &self.from_tar {
+      self.tar(&options, &manifest_path, tar)?
+    } else {
+      self.directory(&options, &manifest_path, &root)?
+    };
 
-    let embedded = if let Some((_metadata, cbor)) = metadata {
+    let embedded = if let Some(cbor) = cbor {
       BTreeMap::from([(Hash::bytes(&cbor), cbor)])
     } else {
       BTreeMap::new()
@@ -234,14 +277,23 @@ impl Create {
       signatures: BTreeSet::new(),
     };
 
+    if let Some(existing) = existing
+      && existing.fingerprint() == manifest.fingerprint()
+    {
+      manifest.signatures = existing.signatures;
+    }
+
     if self.sign {
       let keychain = Keychain::load(&options)?;
       manifest.sign(
         SignOptions {
           timestamp: self.timestamp,
+          ..default()
+        },
+        &Signer::Keychain {
+          key: &self.key,
+          keychain: &keychain,
         },
-        &keychain,
-        &self.key,
       )?;
     }
 
@@ -249,4 +301,94 @@ impl Create {
 
     Ok(())
   }
+
+  fn tar(
+    &self,
+    options: &Options,
+    manifest_path: &Utf8Path,
+    tar: &Utf8Path,
+  ) -> Result<(DirectoryTree, Option<Vec<u8>>, Option<Manifest>)> {
+    let existing = self.existing(manifest_path)?;
+
+    let size = if tar == TarWalk::STDIN {
+      0
+    } else {
+      filesystem::metadata(tar)?.len()
+    };
+
+    let bar = progress_bar::new(options, size);
+
+    let mut walk = TarWalk::new(tar, &self.ignore, &bar)?;
+
+    bar.finish_and_clear();
+
+    let root = decode_path(walk.staging.path())?;
+
+    let cbor_path = Metadata::CBOR_FILENAME.parse::<RelativePath>().unwrap();
+
+    let metadata = if let Some(yaml) = &walk.metadata {
+      let metadata = Self::metadata(Metadata::YAML_FILENAME.as_ref(), yaml, root)?;
+
+      ensure! {
+        self.force || self.update || walk.files.remove(&cbor_path).is_none(),
+        error::MetadataAlreadyExists {
+          path: Metadata::CBOR_FILENAME,
+        },
+      }
+
+      Some(metadata)
+    } else {
+      ensure! {
+        !walk.files.contains_key(&cbor_path),
+        error::StaleMetadata {
+          path: Metadata::CBOR_FILENAME,
+        },
+      }
+
+      None
+    };
+
+    self.lint(&walk.paths, &walk.symlinks, metadata.as_ref(), root)?;
+
+    if let Some((metadata, _cbor)) = &metadata {
+      let files = walk
+        .files
+        .keys()
+        .cloned()
+        .collect::<HashSet<RelativePath>>();
+
+      metadata.check_files(&files)?;
+
+      if metadata.media.is_some() {
+        metadata.check_extras(&files, &walk.empty)?;
+      }
+    }
+
+    let mut package = DirectoryTree::new();
+
+    for path in &walk.empty {
+      package.create_directory(path)?;
+    }
+
+    for (path, file) in walk.files {
+      package.create_file(&path, file)?;
+    }
+
+    if let Some((_metadata, cbor)) = &metadata {
+      package.create_file(
+        &cbor_path,
+        File {
+          executable: false,
+          hash: Hash::bytes(cbor),
+          size: cbor.len().into_u64(),
+        },
+      )?;
+    }
+
+    for (path, symlink) in walk.symlinks {
+      package.create_symlink(&path, symlink)?;
+    }
+
+    Ok((package, metadata.map(|(_metadata, cbor)| cbor), existing))
+  }
 }
@@ -0,0 +1,121 @@
+use super::*;
+
+#[derive(Parser)]
+#[command(group = ArgGroup::new("target").required(true))]
+pub(crate) struct Diff {
+  #[arg(long = "format", default_value_t)]
+  format: Format,
+  #[arg(
+    help = "Compare from manifest <FROM>. May be path to manifest or to directory containing \
+            manifest named `manifest.filepack`."
+  )]
+  from: Utf8PathBuf,
+  #[arg(group = "target", help = "Compare to files in <ROOT> directory", long)]
+  root: Option<Utf8PathBuf>,
+  #[arg(
+    group = "target",
+    help = "Compare to manifest <TO>. May be path to manifest or to directory containing manifest \
+            named `manifest.filepack`."
+  )]
+  to: Option<Utf8PathBuf>,
+}
+
+impl Diff {
+  fn load(path: &Utf8Path) -> Result<Archive> {
+    let (path, archive) = Archive::load_with_opt_path(Some(path))?;
+
+    archive
+      .unpack()
+      .context(error::UnarchiveManifest { path })?;
+
+    Ok(archive)
+  }
+
+  fn pack(options: &Options, root: &Utf8Path) -> Result<Archive> {
+    let walk = Walk::new(root, &root.join(Manifest::FILENAME), &[])?;
+
+    let bar = progress_bar::new(options, walk.file_size()?);
+
+    let mut package = DirectoryTree::new();
+
+    for path in &walk.empty {
+      package.create_directory(path)?;
+    }
+
+    for path in walk.files.keys() {
+      let file = options
+        .hash_file(&root.join(path))
+        .context(error::FilesystemIo { path })?;
+      package.create_file(path, file)?;
+      bar.inc(file.size);
+    }
+
+    for (path, symlink) in walk.symlinks {
+      package.create_symlink(&path, symlink)?;
+    }
+
+    let manifest = Manifest {
+      embedded: BTreeMap::new(),
+      package,
+      signatures: BTreeSet::new(),
+    };
+
+    Archive::pack(&manifest).context(error::ManifestTotals { path: root })
+  }
+
+  pub(crate) fn run(self, options: Options) -> Result {
+    let from = Self::load(&self.from)?;
+
+    let to = if let Some(root) = &self.root {
+      Self::pack(&options, root)?
+    } else {
+      Self::load(self.to.as_ref().unwrap())?
+    };
+
+    let changes = Changes::new(&from, &to).context(error::Diff)?;
+
+    match self.format {
+      Format::Json => println!("{}", serde_json::to_string(&changes).unwrap()),
+      Format::JsonPretty => println!("{}", serde_json::to_string_pretty(&changes).unwrap()),
+      Format::Tsv => {
+        for path in changes.added.keys() {
+          println!("added\t{path}");
+        }
+
+        for path in &changes.added_directories {
+          println!("added-directory\t{path}");
+        }
+
+        for path in changes.added_symlinks.keys() {
+          println!("added-symlink\t{path}");
+        }
+
+        for path in changes.modified.keys() {
+          println!("modified\t{path}");
+        }
+
+        for path in changes.modified_symlinks.keys() {
+          println!("modified-symlink\t{path}");
+        }
+
+        for (path, moved) in &changes.moved {
+          println!("moved\t{}\t{path}", moved.from);
+        }
+
+        for path in changes.removed.keys() {
+          println!("removed\t{path}");
+        }
+
+        for path in &changes.removed_directories {
+          println!("removed-directory\t{path}");
+        }
+
+        for path in changes.removed_symlinks.keys() {
+          println!("removed-symlink\t{path}");
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
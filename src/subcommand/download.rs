@@ -1,10 +1,11 @@
-use {super::*, reqwest::blocking::Response};
+use super::*;
 
 struct Context {
   client: Client,
   entries: u64,
   entries_downloaded: u64,
   progress_bar: ProgressBar,
+  resume: bool,
 }
 
 #[derive(Parser)]
@@ -17,6 +18,21 @@ pub(crate) struct Download {
     value_name = "HASH"
   )]
   file: Option<Hash>,
+  #[arg(
+    help = "Resolve package name published by <KEY>",
+    long,
+    requires = "name",
+    value_name = "KEY"
+  )]
+  key: Option<KeyIdentifier>,
+  #[arg(
+    group = "target",
+    help = "Download package with <NAME>",
+    long,
+    requires = "key",
+    value_name = "NAME"
+  )]
+  name: Option<PackageName>,
   #[arg(help = "Download to <PATH>", value_name = "PATH")]
   output: Utf8PathBuf,
   #[arg(
@@ -26,6 +42,12 @@ pub(crate) struct Download {
     value_name = "FINGERPRINT"
   )]
   package: Option<Fingerprint>,
+  #[arg(
+    help = "Resume interrupted package download into existing <PATH>, keeping files and \
+            symlinks already downloaded if they match the package",
+    long
+  )]
+  resume: bool,
   #[arg(help = "Download from server at <URL>", long, value_name = "URL", value_parser = CheckedUrl::check)]
   server: Url,
 }
@@ -39,11 +61,16 @@ impl Download {
 
     let client = Client::new(options, self.server.clone(), None)?;
 
-    let response = client.file(hash)?;
-
-    let bar = progress_bar::new(options, response.content_length().unwrap_or(0));
-
-    Self::write_response(&client, response, hash, path, &bar)?;
+    let bar = if let Some(outboard) = client.outboard_opt(hash)? {
+      let bar = progress_bar::new(options, outboard.size());
+      Self::write_file(&client, &outboard, path, &bar)?;
+      bar
+    } else {
+      let response = client.file(hash)?;
+      let bar = progress_bar::new(options, response.content_length().unwrap_or_default());
+      Self::write_whole_file(&client, hash, response, path, &bar)?;
+      bar
+    };
 
     bar.finish();
 
@@ -52,7 +79,7 @@ impl Download {
 
   fn download_package(&self, options: &Options, fingerprint: Fingerprint) -> Result {
     ensure! {
-      !filesystem::exists(&self.output)?,
+      self.resume || !filesystem::exists(&self.output)?,
       error::FileAlreadyExists { path: &self.output },
     }
 
@@ -64,6 +91,8 @@ impl Download {
 
     let mut files = Vec::new();
 
+    let mut symlinks = Vec::new();
+
     let mut totals = None::<Totals>;
 
     let mut entries_downloaded = 0;
@@ -128,8 +157,13 @@ impl Download {
       for (component, entry) in directory.entries {
         let path = path.join(component);
         match entry {
-          Entry::File { hash, .. } => files.push((hash, path)),
+          Entry::File {
+            hash,
+            size,
+            executable,
+          } => files.push((hash, size, path, executable.unwrap_or_default())),
           Entry::Directory { hash, totals, .. } => stack.push((hash, path, Some(totals))),
+          Entry::Symlink { target } => symlinks.push((path, Symlink { target })),
         }
       }
     }
@@ -141,17 +175,36 @@ impl Download {
       entries: totals.files.saturating_add(totals.directories),
       entries_downloaded,
       progress_bar: progress_bar.unwrap(),
+      resume: self.resume,
     };
 
-    for (hash, path) in &files {
-      Self::download_package_file(&mut context, *hash, path)?;
+    for (hash, size, path, executable) in &files {
+      Self::download_package_file(&mut context, *hash, *size, path)?;
+
+      if *executable {
+        filesystem::set_executable(path)?;
+      }
+    }
+
+    for (path, symlink) in &symlinks {
+      let relative = path.strip_prefix(&self.output).unwrap();
+
+      let relative = RelativePath::try_from(relative).context(error::Path { path: relative })?;
+
+      let target = symlink.link(&relative);
+
+      if self.resume && path.is_symlink() && filesystem::read_link(path)? == target {
+        continue;
+      }
+
+      filesystem::symlink(&target, path)?;
     }
 
     let metadata_path = self.output.join(Metadata::CBOR_FILENAME);
     if let Some(cbor) = filesystem::read_opt(&metadata_path)? {
       let paths = files
         .iter()
-        .map(|(_hash, path)| {
+        .map(|(_hash, _size, path, _executable)| {
           let path = path.strip_prefix(&self.output).unwrap();
           path.try_into().context(error::Path { path })
         })
@@ -173,7 +226,13 @@ impl Download {
       totals,
     );
 
-    let archive = builder.build_package(package, &BTreeSet::new()).unwrap();
+    let signatures = context.client.signatures(fingerprint)?;
+
+    for signature in &signatures {
+      signature.verify(fingerprint)?;
+    }
+
+    let archive = builder.build_package(package, &signatures).unwrap();
 
     filesystem::write(
       &self.output.join(Manifest::FILENAME),
@@ -185,15 +244,25 @@ impl Download {
     Ok(())
   }
 
-  fn download_package_file(context: &mut Context, hash: Hash, path: &Utf8Path) -> Result {
-    ensure! {
-      !filesystem::exists(path)?,
-      error::FileAlreadyExists { path },
-    }
-
-    let response = context.client.file(hash)?;
+  fn download_package_file(
+    context: &mut Context,
+    hash: Hash,
+    size: u64,
+    path: &Utf8Path,
+  ) -> Result {
+    if filesystem::exists(path)? {
+      ensure! {
+        context.resume && Self::downloaded(path, hash)?,
+        error::FileAlreadyExists { path },
+      }
 
-    Self::write_response(&context.client, response, hash, path, &context.progress_bar)?;
+      context.progress_bar.inc(size);
+    } else if let Some(outboard) = context.client.outboard_opt(hash)? {
+      Self::write_file(&context.client, &outboard, path, &context.progress_bar)?;
+    } else {
+      let response = context.client.file(hash)?;
+      Self::write_whole_file(&context.client, hash, response, path, &context.progress_bar)?;
+    }
 
     context.entries_downloaded += 1;
 
@@ -207,50 +276,210 @@ impl Download {
     Ok(())
   }
 
+  fn downloaded(path: &Utf8Path, hash: Hash) -> Result<bool> {
+    let mut hasher = Hasher::new();
+
+    hasher
+      .update_reader(filesystem::open(path)?)
+      .context(error::FilesystemIo { path })?;
+
+    Ok(Hash::from(hasher.finalize()) == hash)
+  }
+
+  fn partial_path(hash: Hash, path: &Utf8Path) -> Utf8PathBuf {
+    let output_directory = path
+      .parent()
+      .filter(|parent| !parent.as_str().is_empty())
+      .unwrap_or(Utf8Path::new("."));
+
+    output_directory.join(format!("{hash}.partial"))
+  }
+
+  fn rename_partial(partial: &Utf8Path, path: &Utf8Path) -> Result {
+    ensure! {
+      !filesystem::exists(path)?,
+      error::FileAlreadyExists { path },
+    }
+
+    fs::rename(partial, path).context(error::FilesystemIo { path })
+  }
+
   pub(crate) fn run(self, options: Options) -> Result {
     if let Some(hash) = self.file {
       self.download_file(&options, hash, &self.output)
+    } else if let Some(name) = &self.name {
+      let public_key = TrustStore::load(&options)?
+        .resolve(&Keychain::load(&options)?, self.key.as_ref().unwrap())?
+        .0;
+
+      let fingerprint = Client::new(&options, self.server.clone(), None)?
+        .resolve_name(&options, public_key, name)?;
+
+      self.download_package(&options, fingerprint)
     } else {
       self.download_package(&options, self.package.unwrap())
     }
   }
 
-  fn write_response(
+  fn verified_prefix(outboard: &Outboard, file: &mut fs::File, path: &Utf8Path) -> Result<u64> {
+    let len = file.metadata().context(error::FilesystemIo { path })?.len();
+
+    let mut verified = 0;
+
+    let mut buffer = Vec::new();
+
+    while verified < outboard.size() {
+      let block = outboard.block(verified);
+
+      if block.end > len {
+        break;
+      }
+
+      buffer.clear();
+
+      Read::by_ref(file)
+        .take(block.end - block.start)
+        .read_to_end(&mut buffer)
+        .context(error::FilesystemIo { path })?;
+
+      if !outboard.verify_block(block.start, &buffer) {
+        break;
+      }
+
+      verified = block.end;
+    }
+
+    file
+      .set_len(verified)
+      .context(error::FilesystemIo { path })?;
+
+    file
+      .seek(SeekFrom::Start(verified))
+      .context(error::FilesystemIo { path })?;
+
+    Ok(verified)
+  }
+
+  fn write_file(
+    client: &Client,
+    outboard: &Outboard,
+    path: &Utf8Path,
+    bar: &ProgressBar,
+  ) -> Result {
+    let hash = outboard.hash();
+
+    let partial = Self::partial_path(hash, path);
+
+    let mut file = fs::OpenOptions::new()
+      .create(true)
+      .read(true)
+      .truncate(false)
+      .write(true)
+      .open(&partial)
+      .context(error::FilesystemIo { path: &partial })?;
+
+    let mut verified = Self::verified_prefix(outboard, &mut file, &partial)?;
+
+    bar.inc(verified);
+
+    if outboard.size() == 0 {
+      ensure! {
+        outboard.verify_block(0, &[]),
+        error::DownloadBlockMismatch { hash, offset: 0u64 },
+      }
+    } else if verified < outboard.size() {
+      let url = client.file_url(hash);
+
+      let mut response = client.file_range(hash, verified)?;
+
+      if response.status() != StatusCode::PARTIAL_CONTENT {
+        io::copy(&mut response.by_ref().take(verified), &mut io::sink())
+          .with_context(|_| error::ResponseRead { url: url.clone() })?;
+      }
+
+      let mut buffer = Vec::new();
+
+      while verified < outboard.size() {
+        let block = outboard.block(verified);
+
+        buffer.clear();
+
+        response
+          .by_ref()
+          .take(block.end - block.start)
+          .read_to_end(&mut buffer)
+          .with_context(|_| error::ResponseRead { url: url.clone() })?;
+
+        ensure! {
+          outboard.verify_block(block.start, &buffer),
+          error::DownloadBlockMismatch { hash, offset: block.start },
+        }
+
+        file
+          .write_all(&buffer)
+          .context(error::FilesystemIo { path: &partial })?;
+
+        bar.inc(block.end - block.start);
+
+        verified = block.end;
+      }
+    }
+
+    drop(file);
+
+    Self::rename_partial(&partial, path)
+  }
+
+  fn write_whole_file(
     client: &Client,
-    mut response: Response,
     hash: Hash,
+    mut response: reqwest::blocking::Response,
     path: &Utf8Path,
     bar: &ProgressBar,
   ) -> Result {
-    let output_directory = path
-      .parent()
-      .filter(|parent| !parent.as_str().is_empty())
-      .unwrap_or(Utf8Path::new("."));
+    let url = client.file_url(hash);
 
-    let tempfile = transfer_tempfile(hash, output_directory).context(error::FilesystemIo {
-      path: output_directory,
-    })?;
+    let partial = Self::partial_path(hash, path);
 
-    let mut writer = HashingWriter::new(tempfile);
+    let mut file = fs::File::create(&partial).context(error::FilesystemIo { path: &partial })?;
 
-    response
-      .copy_to(&mut bar.wrap_write(&mut writer))
-      .with_context(|_| error::ResponseBody {
-        url: client.file_url(hash),
-      })?;
+    let mut hasher = Hasher::new();
 
-    let (actual, tempfile) = writer.finalize();
+    let mut buffer = vec![0; 64 * KIB];
 
-    ensure! {
-      actual == hash,
-      error::DownloadHashMismatch { actual, expected: hash },
+    loop {
+      let n = response
+        .read(&mut buffer)
+        .with_context(|_| error::ResponseRead { url: url.clone() })?;
+
+      if n == 0 {
+        break;
+      }
+
+      hasher.update(&buffer[..n]);
+
+      file
+        .write_all(&buffer[..n])
+        .context(error::FilesystemIo { path: &partial })?;
+
+      bar.inc(n.into_u64());
     }
 
-    tempfile
-      .persist_noclobber(path)
-      .map_err(|error| error.error)
-      .context(error::FilesystemIo { path })?;
+    drop(file);
 
-    Ok(())
+    let actual = Hash::from(hasher.finalize());
+
+    if actual != hash {
+      filesystem::remove_file(&partial)?;
+      return Err(
+        error::DownloadHashMismatch {
+          actual,
+          expected: hash,
+        }
+        .build(),
+      );
+    }
+
+    Self::rename_partial(&partial, path)
   }
 }
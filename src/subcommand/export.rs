@@ -0,0 +1,67 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Export {
+  #[arg(help = "Write archive in <FORMAT>", long, value_name = "FORMAT")]
+  format: ExportFormat,
+  #[arg(help = MANIFEST_PATH_HELP, long)]
+  manifest: Option<Utf8PathBuf>,
+  #[arg(help = "Write archive to <OUTPUT>")]
+  output: Utf8PathBuf,
+}
+
+impl Export {
+  pub(crate) fn run(self) -> Result {
+    ensure! {
+      !filesystem::exists(&self.output)?,
+      error::FileAlreadyExists { path: &self.output },
+    }
+
+    let (path, manifest) = Manifest::load_with_opt_path(self.manifest.as_deref())?;
+
+    let root = path.parent().unwrap();
+
+    let directory = match self.output.parent() {
+      Some(parent) if !parent.as_str().is_empty() => parent,
+      _ => Utf8Path::new("."),
+    };
+
+    let tempfile = transfer_tempfile(manifest.fingerprint().into(), directory)
+      .context(error::FilesystemIo { path: directory })?;
+
+    let mut exporter = Exporter::new(self.format, io::BufWriter::new(tempfile), &manifest)
+      .context(error::Export {
+        path: Manifest::FILENAME,
+      })?;
+
+    let entries = manifest
+      .entries()
+      .collect::<BTreeMap<RelativePath, &DirectoryTreeEntry>>();
+
+    for (path, entry) in entries {
+      match entry {
+        DirectoryTreeEntry::Directory(_) => exporter.directory(&path),
+        DirectoryTreeEntry::File(file) => {
+          if let Some(content) = manifest.embedded.get(&file.hash) {
+            exporter.file(&path, *file, content.as_slice())
+          } else {
+            let source = root.join(&path);
+            exporter.file(&path, *file, filesystem::open(&source)?)
+          }
+        }
+        DirectoryTreeEntry::Symlink(symlink) => exporter.symlink(&path, symlink),
+      }
+      .context(error::Export { path: &path })?;
+    }
+
+    exporter
+      .finish()
+      .and_then(|writer| writer.into_inner().map_err(io::IntoInnerError::into_error))
+      .context(error::FilesystemIo { path: &self.output })?
+      .persist_noclobber(&self.output)
+      .map_err(|error| error.error)
+      .context(error::FilesystemIo { path: &self.output })?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,327 @@
+use super::*;
+
+enum Store {
+  Files(Utf8PathBuf),
+  Package {
+    archive: Archive,
+    files: HashMap<Hash, Utf8PathBuf>,
+  },
+  Server(Client),
+}
+
+impl Store {
+  fn directory(&self, hash: Hash) -> Result<Option<Vec<u8>>> {
+    let Some(mut reader) = self.open(hash)? else {
+      return Ok(None);
+    };
+
+    let mut cbor = Vec::new();
+
+    reader.read_to_end(&mut cbor).context(error::FilesystemIo {
+      path: hash.to_string(),
+    })?;
+
+    Ok(Some(cbor))
+  }
+
+  fn new(options: &Options, source: &ContentSource) -> Result<Self> {
+    match source {
+      ContentSource::Directory(path) => {
+        let manifest = path.join(Manifest::FILENAME);
+
+        if !filesystem::exists(&manifest)? {
+          ensure! {
+            filesystem::metadata(path)?.is_dir(),
+            error::SourceNotDirectory { path },
+          }
+
+          return Ok(Self::Files(path.clone()));
+        }
+
+        let archive = Archive::load(&manifest)?;
+
+        let files = archive
+          .unpack()
+          .context(error::UnarchiveManifest { path: &manifest })?
+          .files()
+          .into_iter()
+          .map(|(relative, file)| (file.hash, path.join(relative)))
+          .collect();
+
+        Ok(Self::Package { archive, files })
+      }
+      ContentSource::Server(url) => Ok(Self::Server(Client::new(options, url.clone(), None)?)),
+    }
+  }
+
+  fn open(&self, hash: Hash) -> Result<Option<Box<dyn Read>>> {
+    let path = match self {
+      Self::Files(directory) => directory.join(hash.to_string()),
+      Self::Package { archive, files } => {
+        if let Ok(content) = archive.file(hash) {
+          return Ok(Some(Box::new(io::Cursor::new(content.to_vec()))));
+        }
+
+        match files.get(&hash) {
+          Some(path) => path.clone(),
+          None => return Ok(None),
+        }
+      }
+      Self::Server(client) => {
+        return Ok(
+          client
+            .file_opt(hash)?
+            .map(|response| Box::new(response) as Box<dyn Read>),
+        );
+      }
+    };
+
+    Ok(filesystem::open_opt(&path)?.map(|file| Box::new(io::BufReader::new(file)) as Box<dyn Read>))
+  }
+}
+
+#[derive(Parser)]
+pub(crate) struct Extract {
+  #[arg(
+    conflicts_with = "package",
+    help = "Extract package described by manifest <MANIFEST>. May be path to manifest or to \
+            directory containing manifest named `manifest.filepack`. Defaults to \
+            `manifest.filepack` in the current directory.",
+    long
+  )]
+  manifest: Option<Utf8PathBuf>,
+  #[arg(help = "Extract to <OUTPUT>")]
+  output: Utf8PathBuf,
+  #[arg(
+    help = "Extract package with <FINGERPRINT>, fetching directories from sources",
+    long,
+    value_name = "FINGERPRINT"
+  )]
+  package: Option<Fingerprint>,
+  #[arg(
+    help = "Read file contents from <SOURCE>. May be a directory of files named by hash, a \
+            package directory containing a manifest, or a filepack server URL. Sources are tried \
+            in order.",
+    long = "source",
+    required = true,
+    value_name = "SOURCE"
+  )]
+  sources: Vec<ContentSource>,
+}
+
+impl Extract {
+  fn extract_file(
+    stores: &[Store],
+    hash: Hash,
+    path: &Utf8Path,
+    bar: &ProgressBar,
+  ) -> Result<bool> {
+    let directory = path.parent().unwrap();
+
+    let mut mismatch = None;
+
+    for store in stores {
+      let Some(mut reader) = store.open(hash)? else {
+        continue;
+      };
+
+      let tempfile =
+        transfer_tempfile(hash, directory).context(error::FilesystemIo { path: directory })?;
+
+      let mut writer = HashingWriter::new(tempfile);
+
+      let copied = io::copy(&mut reader, &mut bar.wrap_write(&mut writer))
+        .context(error::FilesystemIo { path })?;
+
+      let (actual, tempfile) = writer.finalize();
+
+      // a corrupt copy in one source should not prevent a good copy in a
+      // later source from being used
+      if actual != hash {
+        bar.dec(copied);
+        mismatch = Some(actual);
+        continue;
+      }
+
+      tempfile
+        .persist_noclobber(path)
+        .map_err(|error| error.error)
+        .context(error::FilesystemIo { path })?;
+
+      return Ok(true);
+    }
+
+    if let Some(actual) = mismatch {
+      return Err(
+        error::ExtractHashMismatch {
+          actual,
+          expected: hash,
+          path,
+        }
+        .build(),
+      );
+    }
+
+    Ok(false)
+  }
+
+  fn fetch(stores: &[Store], fingerprint: Fingerprint) -> Result<Archive> {
+    let mut builder = ArchiveBuilder::new();
+
+    let mut stack = vec![Hash::from(fingerprint)];
+
+    while let Some(hash) = stack.pop() {
+      if builder.files.contains_key(&hash) {
+        continue;
+      }
+
+      let mut cbor = None;
+      let mut mismatch = None;
+
+      for store in stores {
+        let Some(candidate) = store.directory(hash)? else {
+          continue;
+        };
+
+        let actual = Hash::bytes(&candidate);
+
+        if actual == hash {
+          cbor = Some(candidate);
+          break;
+        }
+
+        mismatch = Some(actual);
+      }
+
+      if let Some(actual) = mismatch.filter(|_| cbor.is_none()) {
+        return Err(
+          error::DownloadHashMismatch {
+            actual,
+            expected: hash,
+          }
+          .build(),
+        );
+      }
+
+      let cbor = cbor.context(error::SourceMissingDirectory { hash })?;
+
+      let directory =
+        Directory::decode_from_slice(&cbor).context(error::DecodeDirectory { hash })?;
+
+      for entry in directory.entries.values() {
+        if let Entry::Directory { hash, .. } = entry {
+          stack.push(*hash);
+        }
+      }
+
+      builder.files.insert(hash, cbor);
+    }
+
+    let hash = fingerprint.into();
+
+    let cbor = &builder.files[&hash];
+
+    let totals = Directory::decode_from_slice(cbor)
+      .context(error::DecodeDirectory { hash })?
+      .totals()
+      .context(error::DirectoryTotals { hash })?;
+
+    let package = Entry::directory(hash, cbor.len().into_u64(), totals);
+
+    builder
+      .build_package(package, &BTreeSet::new())
+      .context(error::DirectoryTotals { hash })
+  }
+
+  pub(crate) fn run(self, options: Options) -> Result {
+    ensure! {
+      !filesystem::exists(&self.output)?,
+      error::FileAlreadyExists { path: &self.output },
+    }
+
+    let mut stores = self
+      .sources
+      .iter()
+      .map(|source| Store::new(&options, source))
+      .collect::<Result<Vec<Store>>>()?;
+
+    let (archive, (manifest, totals)) = if let Some(fingerprint) = self.package {
+      let archive = Self::fetch(&stores, fingerprint)?;
+
+      let unpacked = archive
+        .unpack_with_totals()
+        .context(error::UnarchivePackage { fingerprint })?;
+
+      (archive, unpacked)
+    } else {
+      let (path, archive) = Archive::load_with_opt_path(self.manifest.as_deref())?;
+
+      let unpacked = archive
+        .unpack_with_totals()
+        .context(error::UnarchiveManifest { path })?;
+
+      (archive, unpacked)
+    };
+
+    let encoded = archive.encode_to_vec();
+
+    stores.insert(
+      0,
+      Store::Package {
+        archive,
+        files: HashMap::new(),
+      },
+    );
+
+    let files = manifest.files();
+
+    ensure! {
+      !files.keys().any(|path| *path == Manifest::FILENAME),
+      error::ExtractManifestConflict {
+        path: Manifest::FILENAME,
+      },
+    }
+
+    let bar = progress_bar::new(&options, totals.file_size);
+
+    filesystem::create_dir_all(&self.output)?;
+
+    for path in manifest.empty_directories() {
+      filesystem::create_dir_all(&self.output.join(path))?;
+    }
+
+    for (path, file) in &files {
+      let destination = self.output.join(path);
+
+      filesystem::create_dir_all(destination.parent().unwrap())?;
+
+      if !Self::extract_file(&stores, file.hash, &destination, &bar)? {
+        return Err(
+          error::SourceMissingFile {
+            hash: file.hash,
+            path: path.clone(),
+          }
+          .build(),
+        );
+      }
+
+      if file.executable {
+        filesystem::set_executable(&destination)?;
+      }
+    }
+
+    for (path, symlink) in manifest.symlinks() {
+      let destination = self.output.join(&path);
+
+      filesystem::create_dir_all(destination.parent().unwrap())?;
+
+      filesystem::symlink(&symlink.link(&path), &destination)?;
+    }
+
+    filesystem::write(&self.output.join(Manifest::FILENAME), encoded)?;
+
+    bar.finish();
+
+    Ok(())
+  }
+}
@@ -1,15 +1,108 @@
 use super::*;
 
+#[derive(clap::Subcommand)]
+enum Action {
+  #[command(about = "Decrypt private key")]
+  Decrypt,
+  #[command(about = "Encrypt private key with a passphrase")]
+  Encrypt,
+  #[command(about = "Revoke key, optionally naming its replacement")]
+  Revoke {
+    #[arg(
+      help = "Name <KEY> as replacement for revoked key",
+      long,
+      value_name = "KEY"
+    )]
+    replacement: Option<KeyIdentifier>,
+    #[arg(
+      help = "Revoke signatures made at or after <TIME>, defaults to the current time",
+      long,
+      value_name = "TIME"
+    )]
+    time: Option<jiff::Timestamp>,
+  },
+}
+
 #[derive(Parser)]
 pub(crate) struct Key {
-  #[arg(default_value_t = KeyName::DEFAULT, help = "Print public key <KEY>", long)]
+  #[command(subcommand)]
+  action: Option<Action>,
+  #[arg(
+    default_value_t = KeyName::DEFAULT,
+    global = true,
+    help = "Print public key <KEY>",
+    long,
+  )]
   key: KeyName,
 }
 
 impl Key {
+  fn revoke(
+    &self,
+    options: &Options,
+    keychain: &Keychain,
+    replacement: Option<&KeyIdentifier>,
+    time: Option<jiff::Timestamp>,
+  ) -> Result {
+    let private_key = keychain.private_key(&self.key)?;
+
+    let replacement = replacement
+      .map(|identifier| {
+        TrustStore::load(options)?
+          .resolve(keychain, identifier)
+          .map(|(public_key, _trusted)| public_key)
+      })
+      .transpose()?;
+
+    ensure! {
+      replacement != Some(private_key.public_key()),
+      error::RevocationSelfReplacement {
+        public_key: private_key.public_key(),
+      },
+    }
+
+    let time = match time {
+      Some(time) => u64::try_from(time.as_second()).unwrap_or_default(),
+      None => now()?,
+    };
+
+    let revocation = crate::Revocation::new(&private_key, time, replacement);
+
+    let mut revocations = RevocationStore::load(options)?;
+
+    if revocations.add(revocation.clone())? {
+      revocations.save()?;
+    }
+
+    println!("{revocation}");
+
+    Ok(())
+  }
+
   pub(crate) fn run(self, options: Options) -> Result {
     let keychain = Keychain::load(&options)?;
-    println!("{}", keychain.public_key(&self.key)?);
-    Ok(())
+
+    match &self.action {
+      Some(Action::Decrypt) => keychain.decrypt_key(&self.key),
+      Some(Action::Encrypt) => keychain.encrypt_key(&self.key),
+      Some(Action::Revoke { replacement, time }) => {
+        self.revoke(&options, &keychain, replacement.as_ref(), *time)
+      }
+      None => {
+        let public_key = keychain.public_key(&self.key)?;
+
+        println!("{public_key}");
+
+        if let Some(revocation) = RevocationStore::load(&options)?.get(public_key) {
+          eprintln!("key `{}` revoked at `{}`", self.key, revocation.timestamp());
+
+          if let Some(replacement) = revocation.replacement() {
+            eprintln!("replaced by `{replacement}`");
+          }
+        }
+
+        Ok(())
+      }
+    }
   }
 }
@@ -2,12 +2,22 @@ use super::*;
 
 #[derive(Parser)]
 pub(crate) struct Keygen {
+  #[arg(help = "Encrypt private key with a passphrase", long)]
+  encrypt: bool,
   #[arg(default_value_t = KeyName::DEFAULT, help = "Generate key named <NAME>", long)]
   name: KeyName,
 }
 
 impl Keygen {
   pub(crate) fn run(self, options: Options) -> Result {
-    Keychain::load(&options)?.generate_key(&self.name)
+    let mut keychain = Keychain::load(&options)?;
+
+    let passphrase = if self.encrypt {
+      Some(read_passphrase("passphrase: ", true)?)
+    } else {
+      None
+    };
+
+    keychain.generate_key(&self.name, passphrase.as_deref())
   }
 }
@@ -0,0 +1,33 @@
+use super::*;
+
+mod delete;
+mod export;
+mod import;
+mod list;
+mod rename;
+
+#[derive(clap::Subcommand)]
+pub(crate) enum Keys {
+  #[command(about = "Delete key from keychain")]
+  Delete(delete::Delete),
+  #[command(about = "Print public key")]
+  Export(export::Export),
+  #[command(about = "Import private key from standard input")]
+  Import(import::Import),
+  #[command(about = "List keys in keychain")]
+  List(list::List),
+  #[command(about = "Rename key")]
+  Rename(rename::Rename),
+}
+
+impl Keys {
+  pub(crate) fn run(self, options: Options) -> Result {
+    match self {
+      Self::Delete(delete) => delete.run(options),
+      Self::Export(export) => export.run(options),
+      Self::Import(import) => import.run(options),
+      Self::List(list) => list.run(options),
+      Self::Rename(rename) => rename.run(options),
+    }
+  }
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Delete {
+  #[arg(help = "Delete key <NAME>")]
+  name: KeyName,
+  #[arg(help = "Delete without asking for confirmation", long)]
+  yes: bool,
+}
+
+impl Delete {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let mut keychain = Keychain::load(&options)?;
+
+    let public_key = keychain.public_key(&self.name)?;
+
+    if !self.yes {
+      eprint!(
+        "delete key `{}` with public key `{public_key}`? [y/N] ",
+        self.name,
+      );
+
+      let mut answer = String::new();
+
+      io::stdin()
+        .read_line(&mut answer)
+        .context(error::StandardInputIo)?;
+
+      ensure! {
+        matches!(answer.trim(), "y" | "Y" | "yes"),
+        error::KeyDeletionUnconfirmed { name: self.name },
+      }
+    }
+
+    keychain.delete_key(&self.name)
+  }
+}
@@ -0,0 +1,14 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Export {
+  #[arg(help = "Print public key of key <NAME>")]
+  name: KeyName,
+}
+
+impl Export {
+  pub(crate) fn run(self, options: Options) -> Result {
+    println!("{}", Keychain::load(&options)?.public_key(&self.name)?);
+    Ok(())
+  }
+}
@@ -0,0 +1,21 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Import {
+  #[arg(help = "Import key as <NAME>")]
+  name: KeyName,
+}
+
+impl Import {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let mut keychain = Keychain::load(&options)?;
+
+    let private_key = io::read_to_string(io::stdin()).context(error::StandardInputIo)?;
+
+    keychain.import_key(&self.name, &private_key)?;
+
+    println!("{}", keychain.public_key(&self.name)?);
+
+    Ok(())
+  }
+}
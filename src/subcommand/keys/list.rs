@@ -0,0 +1,56 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct List {
+  #[arg(long = "format", default_value_t)]
+  format: Format,
+}
+
+#[derive(Serialize)]
+struct Output<'a> {
+  encrypted: bool,
+  mode: Mode,
+  name: &'a KeyName,
+  public_key: PublicKey,
+  secure: bool,
+}
+
+impl List {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let keychain = Keychain::load(&options)?;
+
+    let keys = keychain
+      .keys
+      .iter()
+      .map(|(name, public_key)| {
+        let mode = keychain.mode(name)?;
+        Ok(Output {
+          encrypted: keychain.is_encrypted(name)?,
+          mode,
+          name,
+          public_key: *public_key,
+          secure: mode.is_secure(),
+        })
+      })
+      .collect::<Result<Vec<Output>>>()?;
+
+    match self.format {
+      Format::Json => println!("{}", serde_json::to_string(&keys).unwrap()),
+      Format::JsonPretty => println!("{}", serde_json::to_string_pretty(&keys).unwrap()),
+      Format::Tsv => {
+        for Output {
+          encrypted,
+          mode,
+          name,
+          public_key,
+          secure: _,
+        } in &keys
+        {
+          println!("{name}\t{public_key}\t{mode}\t{encrypted}");
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
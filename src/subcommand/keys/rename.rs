@@ -0,0 +1,15 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Rename {
+  #[arg(help = "Rename key <FROM>")]
+  from: KeyName,
+  #[arg(help = "Rename key to <TO>")]
+  to: KeyName,
+}
+
+impl Rename {
+  pub(crate) fn run(self, options: Options) -> Result {
+    Keychain::load(&options)?.rename_key(&self.from, &self.to)
+  }
+}
@@ -0,0 +1,21 @@
+use super::*;
+
+mod publish;
+mod resolve;
+
+#[derive(clap::Subcommand)]
+pub(crate) enum Name {
+  #[command(about = "Publish signed package name to server")]
+  Publish(publish::Publish),
+  #[command(about = "Resolve package name on server to fingerprint")]
+  Resolve(resolve::Resolve),
+}
+
+impl Name {
+  pub(crate) fn run(self, options: Options) -> Result {
+    match self {
+      Self::Publish(publish) => publish.run(options),
+      Self::Resolve(resolve) => resolve.run(options),
+    }
+  }
+}
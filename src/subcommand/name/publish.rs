@@ -0,0 +1,62 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Publish {
+  #[arg(help = "Authenticate with key <KEY>", long, value_name = "KEY")]
+  auth: Option<KeyName>,
+  #[arg(
+    help = "Point name at package with <FINGERPRINT>",
+    index = 2,
+    value_name = "FINGERPRINT"
+  )]
+  fingerprint: Fingerprint,
+  #[arg(default_value_t = KeyName::DEFAULT, help = "Sign name with <KEY>", long)]
+  key: KeyName,
+  #[arg(help = "Publish <NAME>", index = 1, value_name = "NAME")]
+  name: PackageName,
+  #[arg(
+    help = "Use sequence number <SEQUENCE> instead of one greater than current sequence number",
+    long,
+    value_name = "SEQUENCE"
+  )]
+  sequence: Option<u64>,
+  #[arg(
+    help = "Publish name to server at <URL>",
+    long,
+    value_name = "URL",
+    value_parser = CheckedUrl::check
+  )]
+  server: Url,
+}
+
+impl Publish {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let private_key = Keychain::load(&options)?.private_key(&self.key)?;
+
+    let client = Client::new(&options, self.server, self.auth.as_ref())?;
+
+    let sequence = match self.sequence {
+      Some(sequence) => sequence,
+      None => client
+        .name(private_key.public_key(), &self.name)?
+        .map_or(1, |record| record.sequence() + 1),
+    };
+
+    let record = NameRecord::new(&private_key, self.name, self.fingerprint, sequence);
+
+    let mut names = NameStore::load(&options)?;
+
+    names.update(&record)?;
+
+    client.publish_name(&record)?;
+
+    names.save()?;
+
+    eprintln!(
+      "published `{}` with sequence number {sequence}",
+      record.name()
+    );
+
+    Ok(())
+  }
+}
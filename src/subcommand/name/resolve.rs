@@ -0,0 +1,31 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Resolve {
+  #[arg(help = "Resolve name published by <KEY>", long, value_name = "KEY")]
+  key: KeyIdentifier,
+  #[arg(help = "Resolve <NAME>", value_name = "NAME")]
+  name: PackageName,
+  #[arg(
+    help = "Resolve name on server at <URL>",
+    long,
+    value_name = "URL",
+    value_parser = CheckedUrl::check
+  )]
+  server: Url,
+}
+
+impl Resolve {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let public_key = TrustStore::load(&options)?
+      .resolve(&Keychain::load(&options)?, &self.key)?
+      .0;
+
+    let fingerprint =
+      Client::new(&options, self.server, None)?.resolve_name(&options, public_key, &self.name)?;
+
+    println!("{fingerprint}");
+
+    Ok(())
+  }
+}
@@ -0,0 +1,30 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Prove {
+  #[arg(help = MANIFEST_PATH_HELP, long)]
+  manifest: Option<Utf8PathBuf>,
+  #[arg(help = "Write proof to <OUTPUT>", long)]
+  output: Utf8PathBuf,
+  #[arg(help = "Prove that package contains file at <PATH>")]
+  path: RelativePath,
+}
+
+impl Prove {
+  pub(crate) fn run(self) -> Result {
+    let (path, archive) = Archive::load_with_opt_path(self.manifest.as_deref())?;
+
+    archive
+      .unpack()
+      .context(error::UnarchiveManifest { path: &path })?;
+
+    let proof = archive
+      .proof(&self.path)
+      .context(error::UnarchiveManifest { path })?
+      .context(error::ProofFileNotFound { path: self.path })?;
+
+    filesystem::write(&self.output, proof.encode_to_vec())?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,29 @@
+use super::*;
+
+mod add;
+mod fetch;
+mod list;
+mod publish;
+
+#[derive(clap::Subcommand)]
+pub(crate) enum Revocation {
+  #[command(about = "Add revocation to data directory")]
+  Add(add::Add),
+  #[command(about = "Fetch revocations from server")]
+  Fetch(fetch::Fetch),
+  #[command(about = "List revocations")]
+  List(list::List),
+  #[command(about = "Publish revocations to server")]
+  Publish(publish::Publish),
+}
+
+impl Revocation {
+  pub(crate) fn run(self, options: Options) -> Result {
+    match self {
+      Self::Add(add) => add.run(options),
+      Self::Fetch(fetch) => fetch.run(options),
+      Self::List(list) => list.run(options),
+      Self::Publish(publish) => publish.run(options),
+    }
+  }
+}
@@ -0,0 +1,19 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Add {
+  #[arg(help = "Add revocation <REVOCATION>", value_name = "REVOCATION")]
+  revocation: crate::Revocation,
+}
+
+impl Add {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let mut revocations = RevocationStore::load(&options)?;
+
+    if revocations.add(self.revocation)? {
+      revocations.save()?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,34 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Fetch {
+  #[arg(
+    help = "Fetch revocations from server at <URL>",
+    long,
+    value_name = "URL",
+    value_parser = CheckedUrl::check
+  )]
+  server: Url,
+}
+
+impl Fetch {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let fetched = Client::new(&options, self.server, None)?.revocations()?;
+
+    let mut revocations = RevocationStore::load(&options)?;
+
+    let mut added = 0;
+
+    for revocation in fetched {
+      if revocations.add(revocation)? {
+        added += 1;
+      }
+    }
+
+    revocations.save()?;
+
+    eprintln!("added {}", Count::new(added, "revocation"));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct List {
+  #[arg(long = "format", default_value_t)]
+  format: Format,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize)]
+struct Output<'a> {
+  public_key: PublicKey,
+  replacement: Option<PublicKey>,
+  revocation: &'a crate::Revocation,
+  time: jiff::Timestamp,
+}
+
+impl List {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let revocations = RevocationStore::load(&options)?;
+
+    let revocations = revocations
+      .revocations()
+      .map(|revocation| Output {
+        public_key: revocation.public_key(),
+        replacement: revocation.replacement(),
+        revocation,
+        time: revocation.timestamp(),
+      })
+      .collect::<Vec<Output>>();
+
+    match self.format {
+      Format::Json => println!("{}", serde_json::to_string(&revocations).unwrap()),
+      Format::JsonPretty => println!("{}", serde_json::to_string_pretty(&revocations).unwrap()),
+      Format::Tsv => {
+        for Output {
+          public_key,
+          replacement,
+          time,
+          ..
+        } in &revocations
+        {
+          println!(
+            "{public_key}\t{time}\t{}",
+            replacement
+              .map(|replacement| replacement.to_string())
+              .unwrap_or_default(),
+          );
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,33 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Publish {
+  #[arg(help = "Authenticate with key <KEY>", long, value_name = "KEY")]
+  auth: Option<KeyName>,
+  #[arg(
+    help = "Publish revocations to server at <URL>",
+    long,
+    value_name = "URL",
+    value_parser = CheckedUrl::check
+  )]
+  server: Url,
+}
+
+impl Publish {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let revocations = RevocationStore::load(&options)?;
+
+    let client = Client::new(&options, self.server, self.auth.as_ref())?;
+
+    let mut published = 0;
+
+    for revocation in revocations.revocations() {
+      client.publish_revocation(revocation)?;
+      published += 1;
+    }
+
+    eprintln!("published {}", Count::new(published, "revocation"));
+
+    Ok(())
+  }
+}
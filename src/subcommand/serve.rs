@@ -1,14 +1,15 @@
 use {
   super::*,
   axum::{
-    Router,
+    Json, Router,
     extract::{Extension, Path, Query, Request},
     http::{HeaderValue, Uri},
     middleware::{self, Next},
     response::{IntoResponse, Redirect, Response},
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
   },
   axum_server::Handle,
+  futures_util::stream,
   hyper_util::rt::TokioTimer,
   rustls_acme::{
     AcmeConfig, EventOk, acme::LETS_ENCRYPT_PRODUCTION_DIRECTORY, axum::AxumAcceptor,
@@ -16,9 +17,14 @@ use {
   },
   std::net::TcpStream,
   templates::{
-    AudioHtml, DirectoryHtml, FilesHtml, ImageHtml, MediaHtml, PackageHtml, PackagesHtml, VideoHtml,
+    AudioHtml, DirectoryHtml, FilesHtml, ImageHtml, MediaHtml, PackagesHtml, SearchHtml, VideoHtml,
+  },
+  tokio::{
+    net::TcpListener,
+    runtime,
+    sync::mpsc,
+    task::{self, block_in_place},
   },
-  tokio::{net::TcpListener, runtime, task::block_in_place},
   tower_http::set_header::SetResponseHeaderLayer,
 };
 
@@ -33,8 +39,25 @@ type ServerConfigExtension = Extension<Arc<ServerConfig>>;
 type ServerExtension = Extension<Arc<Server>>;
 
 pub(crate) struct AuthConfig {
-  pub(crate) admin: Option<PublicKey>,
   pub(crate) audience: Option<String>,
+  pub(crate) keys: BTreeMap<PublicKey, BTreeSet<Role>>,
+}
+
+#[derive(DeserializeFromStr)]
+pub(crate) enum PackagePath {
+  Archive(Fingerprint),
+  Page(Fingerprint),
+}
+
+impl FromStr for PackagePath {
+  type Err = Bech32Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.strip_suffix(".tar") {
+      Some(fingerprint) => Ok(Self::Archive(fingerprint.parse()?)),
+      None => Ok(Self::Page(s.parse()?)),
+    }
+  }
 }
 
 #[derive(Deserialize)]
@@ -58,6 +81,8 @@ impl RedirectConfig {
 
 pub(crate) struct ServerConfig {
   pub(crate) mounts: HashSet<Fingerprint>,
+  pub(crate) policy: Option<Policy>,
+  pub(crate) quotas: Quotas,
   pub(crate) url: Option<Url>,
 }
 
@@ -102,6 +127,13 @@ pub(crate) struct Serve {
     value_name = "KEY"
   )]
   admin_key: Option<KeyIdentifier>,
+  #[arg(
+    help = "Authorize keys with roles listed in <AUTHORIZATION> to write",
+    long,
+    requires = "restrict_writes",
+    value_name = "AUTHORIZATION"
+  )]
+  authorization: Option<Utf8PathBuf>,
   #[arg(
     help = "Use <DOMAIN> as canonical domain: request ACME TLS certificates for it, accept \
             authentication tokens scoped to it, and redirect to it",
@@ -134,6 +166,29 @@ pub(crate) struct Serve {
     value_name = "FINGERPRINT"
   )]
   mounts: Vec<Fingerprint>,
+  #[arg(
+    help = "Refuse packages whose signatures do not satisfy policy <POLICY>",
+    long,
+    value_name = "POLICY"
+  )]
+  policy: Option<Utf8PathBuf>,
+  #[arg(
+    help = "Limit total size of stored files to <BYTES>",
+    long,
+    value_name = "BYTES"
+  )]
+  quota_bytes: Option<u64>,
+  #[arg(
+    help = "Do not count files shared with packages owned by other keys against per-key quotas",
+    long
+  )]
+  quota_exempt_shared: bool,
+  #[arg(
+    help = "Limit number of stored files to <FILES>",
+    long,
+    value_name = "FILES"
+  )]
+  quota_files: Option<u64>,
   #[arg(
     help = "Write listening port to <ADDRESS>",
     long,
@@ -150,7 +205,7 @@ pub(crate) struct Serve {
     value_name = "DOMAIN"
   )]
   redirects: Vec<String>,
-  #[arg(help = "Restrict writes to admin", long)]
+  #[arg(help = "Restrict writes to admin and authorized keys", long)]
   restrict_writes: bool,
 }
 
@@ -301,14 +356,28 @@ impl Serve {
   ) -> Router {
     let router = Router::new()
       .route("/", get(route::home))
+      .route("/api/bundle", put(route::api_bundle))
       .route("/api/directory/{hash}", post(route::api_verify_directory))
       .route("/api/gc", post(route::api_gc))
       .route("/api/missing", post(route::api_missing))
+      .route("/api/name", post(route::api_publish_name))
+      .route("/api/name/{public_key}/{name}", get(route::api_name))
+      .route("/api/outboard/{hash}", get(route::api_outboard))
       .route(
         "/api/package/{fingerprint}",
         delete(route::api_delete_package).post(route::api_verify_package),
       )
+      .route(
+        "/api/package/{fingerprint}/signatures",
+        get(route::api_package_signatures).post(route::api_add_package_signatures),
+      )
       .route("/api/packages", get(route::api_packages))
+      .route("/api/proof/{fingerprint}/{*path}", get(route::api_proof))
+      .route("/api/revocation", post(route::api_revocation))
+      .route("/api/revocations", get(route::api_revocations))
+      .route("/api/search", get(route::api_search))
+      .route("/api/search.json", get(route::api_search_json))
+      .route("/api/usage", get(route::api_usage))
       .route("/artwork/{fingerprint}", get(route::artwork))
       .route("/directory/{hash}", get(route::directory))
       .route("/favicon.ico", get(route::favicon))
@@ -331,6 +400,7 @@ impl Serve {
       .route("/mount/{fingerprint}", get(route::mount_redirect))
       .route("/mount/{fingerprint}/", get(route::mount))
       .route("/mount/{fingerprint}/{*path}", get(route::mount_file))
+      .route("/name/{public_key}/{name}", get(route::name))
       .route("/package/{fingerprint}", get(route::package))
       .route(
         "/package/{fingerprint}/item/{item}",
@@ -338,6 +408,7 @@ impl Serve {
       )
       .route("/package/{fingerprint}/media", get(route::package_media))
       .route("/packages", get(route::packages))
+      .route("/search", get(route::search))
       .route("/static/{*path}", get(route::static_asset))
       .fallback(route::fallback)
       .layer(Extension(server))
@@ -415,15 +486,28 @@ impl Serve {
       }
     }
 
+    let authorization = self
+      .authorization
+      .as_deref()
+      .map(Authorization::load)
+      .transpose()?;
+
     let auth_config = if self.restrict_writes {
-      let admin = if let Some(identifier) = &self.admin_key {
-        Some(Keychain::load(&options)?.identifier_public_key(identifier)?)
-      } else {
-        None
-      };
+      let mut keys = authorization
+        .as_ref()
+        .map(Authorization::roles)
+        .unwrap_or_default();
+
+      if let Some(identifier) = &self.admin_key {
+        keys
+          .entry(Keychain::load(&options)?.identifier_public_key(identifier)?)
+          .or_default()
+          .insert(Role::Admin);
+      }
+
       Some(Arc::new(AuthConfig {
-        admin,
         audience: self.domain.clone(),
+        keys,
       }))
     } else {
       None
@@ -431,7 +515,12 @@ impl Serve {
 
     let redirect_config = self.redirect_config()?;
 
-    let router = Self::router(server, auth_config, redirect_config, self.server_config());
+    let router = Self::router(
+      server,
+      auth_config,
+      redirect_config,
+      self.server_config(authorization.as_ref())?,
+    );
 
     match (self.http_port(), self.https_port()) {
       (Some(http_port), None) => {
@@ -500,11 +589,20 @@ impl Serve {
     Ok(server.handle(handle))
   }
 
-  fn server_config(&self) -> Arc<ServerConfig> {
-    Arc::new(ServerConfig {
+  fn server_config(&self, authorization: Option<&Authorization>) -> Result<Arc<ServerConfig>> {
+    Ok(Arc::new(ServerConfig {
       mounts: self.mounts.iter().copied().collect(),
+      policy: self.policy.as_deref().map(Policy::load).transpose()?,
+      quotas: Quotas {
+        exempt_shared: self.quota_exempt_shared,
+        keys: authorization.map(Authorization::quotas).unwrap_or_default(),
+        server: Quota {
+          bytes: self.quota_bytes,
+          files: self.quota_files,
+        },
+      },
       url: self.domain.as_ref().map(|_| self.redirect_url()),
-    })
+    }))
   }
 
   async fn spawn(
@@ -595,12 +693,17 @@ impl Default for Serve {
       acme_directory: LETS_ENCRYPT_PRODUCTION_DIRECTORY.parse().unwrap(),
       address: "0.0.0.0".into(),
       admin_key: None,
+      authorization: None,
       domain: None,
       http: false,
       http_port: None,
       https: false,
       https_port: None,
       mounts: Vec::new(),
+      policy: None,
+      quota_bytes: None,
+      quota_exempt_shared: false,
+      quota_files: None,
       ready_address: None,
       redirect_http_to_https: false,
       redirects: Vec::new(),
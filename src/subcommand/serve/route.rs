@@ -1,17 +1,63 @@
 use super::*;
 
+pub(crate) async fn api_add_package_signatures(
+  server: ServerExtension,
+  Path(fingerprint): Path<Fingerprint>,
+  body: String,
+) -> ServerResult {
+  let mut signatures = BTreeSet::new();
+
+  for (i, line) in body.lines().enumerate() {
+    let line = line.trim();
+
+    if line.is_empty() {
+      continue;
+    }
+
+    signatures.insert(
+      line
+        .parse()
+        .context(server_error::SignatureParse { line: i + 1 })?,
+    );
+  }
+
+  block_in_place(|| server.add_signatures(fingerprint, &signatures))
+}
+
+pub(crate) async fn api_bundle(
+  authenticated: Authenticated,
+  server: ServerExtension,
+  config: ServerConfigExtension,
+  body: Body,
+) -> ServerResult {
+  authenticated.authorize(Role::Upload)?;
+  let bundle = server
+    .receive_bundle(body, authenticated.issuer(), &config.quotas)
+    .await?;
+  block_in_place(|| {
+    server.import_bundle(
+      bundle,
+      config.policy.as_ref(),
+      authenticated.issuer(),
+      &config.quotas,
+    )
+  })
+}
+
 pub(crate) async fn api_delete_package(
-  _: Authenticated,
+  authenticated: Authenticated,
   server: ServerExtension,
   Path(fingerprint): Path<Fingerprint>,
 ) -> ServerResult {
-  block_in_place(|| server.delete_package(fingerprint))
+  authenticated.authorize(Role::Delete)?;
+  block_in_place(|| server.delete_package(fingerprint, &authenticated))
 }
 
 pub(crate) async fn api_gc(
-  _: Authenticated,
+  authenticated: Authenticated,
   server: ServerExtension,
 ) -> ServerResult<CborResponse<api::gc::Response>> {
+  authenticated.authorize(Role::Gc)?;
   block_in_place(|| Ok(CborResponse(server.gc()?)))
 }
 
@@ -26,6 +72,31 @@ pub(crate) async fn api_missing(
   })
 }
 
+pub(crate) async fn api_name(
+  server: ServerExtension,
+  Path((public_key, name)): Path<(PublicKey, PackageName)>,
+) -> ServerResult<CborResponse<NameRecord>> {
+  block_in_place(|| Ok(CborResponse(server.name(public_key, &name)?)))
+}
+
+pub(crate) async fn api_outboard(
+  server: ServerExtension,
+  hash: Path<Hash>,
+) -> ServerResult<CborResponse<api::outboard::Response>> {
+  block_in_place(|| Ok(CborResponse(server.outboard(*hash)?)))
+}
+
+pub(crate) async fn api_package_signatures(
+  server: ServerExtension,
+  Path(fingerprint): Path<Fingerprint>,
+) -> ServerResult<CborResponse<api::signatures::Response>> {
+  block_in_place(|| {
+    Ok(CborResponse(api::signatures::Response {
+      signatures: server.signatures(fingerprint)?.into(),
+    }))
+  })
+}
+
 pub(crate) async fn api_packages(
   server: ServerExtension,
 ) -> ServerResult<CborResponse<api::packages::Response>> {
@@ -36,20 +107,93 @@ pub(crate) async fn api_packages(
   })
 }
 
+pub(crate) async fn api_proof(
+  server: ServerExtension,
+  Path((fingerprint, path)): Path<(Fingerprint, RelativePath)>,
+) -> ServerResult<CborResponse<Proof>> {
+  block_in_place(|| Ok(CborResponse(server.package_proof(fingerprint, &path)?)))
+}
+
+pub(crate) async fn api_publish_name(
+  authenticated: Authenticated,
+  server: ServerExtension,
+  Cbor(record): Cbor<NameRecord, { KIB }>,
+) -> ServerResult {
+  authenticated.authorize(Role::Upload)?;
+  block_in_place(|| server.publish_name(&record))
+}
+
+pub(crate) async fn api_revocation(
+  authenticated: Authenticated,
+  server: ServerExtension,
+  Cbor(revocation): Cbor<Revocation, { KIB }>,
+) -> ServerResult {
+  authenticated.authorize(Role::Upload)?;
+  block_in_place(|| server.add_revocation(&revocation))
+}
+
+pub(crate) async fn api_revocations(
+  server: ServerExtension,
+) -> ServerResult<CborResponse<api::revocations::Response>> {
+  block_in_place(|| {
+    Ok(CborResponse(api::revocations::Response {
+      revocations: server.revocations()?.into(),
+    }))
+  })
+}
+
+pub(crate) async fn api_search(
+  server: ServerExtension,
+  Query(search): Query<Search>,
+) -> ServerResult<CborResponse<api::search::Response>> {
+  block_in_place(|| Ok(CborResponse(server.search(&search)?)))
+}
+
+pub(crate) async fn api_search_json(
+  server: ServerExtension,
+  Query(search): Query<Search>,
+) -> ServerResult<Json<api::search::Response>> {
+  block_in_place(|| Ok(Json(server.search(&search)?)))
+}
+
+pub(crate) async fn api_usage(
+  server: ServerExtension,
+  config: ServerConfigExtension,
+) -> ServerResult<CborResponse<api::usage::Response>> {
+  block_in_place(|| Ok(CborResponse(server.usage(config.quotas.exempt_shared)?)))
+}
+
 pub(crate) async fn api_verify_directory(
-  _: Authenticated,
+  authenticated: Authenticated,
   server: ServerExtension,
   hash: Path<Hash>,
 ) -> ServerResult {
+  authenticated.authorize(Role::Upload)?;
   block_in_place(|| server.verify_directory(*hash))
 }
 
 pub(crate) async fn api_verify_package(
-  _: Authenticated,
+  authenticated: Authenticated,
   server: ServerExtension,
+  config: ServerConfigExtension,
   Path(fingerprint): Path<Fingerprint>,
+  request: Option<Cbor<api::package::Request, { MIB }>>,
 ) -> ServerResult {
-  block_in_place(|| server.verify_package(fingerprint))
+  authenticated.authorize(Role::Upload)?;
+
+  let signatures = request
+    .map(|Cbor(request)| request.signatures.into_inner().into_iter().collect())
+    .unwrap_or_default();
+
+  block_in_place(|| {
+    server.verify_package(
+      fingerprint,
+      &signatures,
+      config.policy.as_ref(),
+      authenticated.issuer(),
+      &config.quotas,
+    )
+  })
 }
 
 pub(crate) async fn artwork(
@@ -196,7 +340,7 @@ pub(crate) async fn mount(
   server_config: ServerConfigExtension,
   Path(fingerprint): Path<Fingerprint>,
   range: Option<TypedHeader<headers::Range>>,
-) -> ServerResult<Resource> {
+) -> ServerResult<Response> {
   mount_file(
     server,
     server_config,
@@ -211,7 +355,7 @@ pub(crate) async fn mount_file(
   server_config: ServerConfigExtension,
   Path((fingerprint, path)): Path<(Fingerprint, RelativePath)>,
   range: Option<TypedHeader<headers::Range>>,
-) -> ServerResult<Resource> {
+) -> ServerResult<Response> {
   block_in_place(|| {
     ensure! {
       server_config.mounts.contains(&fingerprint),
@@ -220,7 +364,25 @@ pub(crate) async fn mount_file(
 
     let path = format!("static/{path}").parse::<RelativePath>().unwrap();
 
-    let hash = server.package_file(fingerprint, &path)?;
+    let hash = match server.package_file(fingerprint, &path)? {
+      Resolution::File(hash) => hash,
+      Resolution::Symlink(target) => {
+        let target = AsRef::<str>::as_ref(&target)
+          .strip_prefix("static/")
+          .context(server_error::PackageFileNotFound {
+            fingerprint,
+            path: &path,
+          })?;
+
+        return Ok(
+          Redirect::temporary(&format!(
+            "/mount/{fingerprint}/{}",
+            target.percent_encode_path(),
+          ))
+          .into_response(),
+        );
+      }
+    };
 
     let content_type = mime_guess::from_path(&path).first_or_octet_stream();
 
@@ -228,7 +390,8 @@ pub(crate) async fn mount_file(
       server
         .open_file(hash)?
         .range(range)
-        .unsandboxed_content_type(content_type),
+        .unsandboxed_content_type(content_type)
+        .into_response(),
     )
   })
 }
@@ -237,18 +400,59 @@ pub(crate) async fn mount_redirect(Path(fingerprint): Path<Fingerprint>) -> Redi
   Redirect::permanent(&format!("/mount/{fingerprint}/"))
 }
 
+pub(crate) async fn name(
+  server: ServerExtension,
+  Path((public_key, name)): Path<(PublicKey, PackageName)>,
+) -> ServerResult<Redirect> {
+  let record = block_in_place(|| server.name(public_key, &name))?;
+  Ok(Redirect::temporary(&format!(
+    "/package/{}",
+    record.fingerprint()
+  )))
+}
+
 pub(crate) async fn package(
   server: ServerExtension,
   server_config: ServerConfigExtension,
-  Path(fingerprint): Path<Fingerprint>,
-) -> PageResult<PackageHtml> {
-  block_in_place(|| {
-    Ok(
-      server
-        .package_html(fingerprint, server_config.mounts.contains(&fingerprint))?
-        .page(server_config.url.clone()),
+  Path(path): Path<PackagePath>,
+) -> ServerResult<Response> {
+  match path {
+    PackagePath::Archive(fingerprint) => package_archive(server, fingerprint),
+    PackagePath::Page(fingerprint) => block_in_place(|| {
+      Ok(
+        server
+          .package_html(fingerprint, server_config.mounts.contains(&fingerprint))?
+          .page(server_config.url.clone())
+          .into_response(),
+      )
+    }),
+  }
+}
+
+fn package_archive(server: ServerExtension, fingerprint: Fingerprint) -> ServerResult<Response> {
+  let manifest = block_in_place(|| server.package_manifest(fingerprint))?;
+
+  let (sender, receiver) = mpsc::channel(16);
+
+  task::spawn_blocking(move || {
+    let writer = io::BufWriter::with_capacity(64 * KIB, ChannelWriter::new(sender.clone()));
+
+    if let Err(err) = server.export_package(&manifest, ExportFormat::Tar, writer) {
+      sender.blocking_send(Err(err)).ok();
+    }
+  });
+
+  Response::builder()
+    .header(header::CONTENT_TYPE, "application/x-tar")
+    .header(
+      header::CONTENT_DISPOSITION,
+      format!("attachment; filename=\"{fingerprint}.tar\""),
     )
-  })
+    .body(Body::from_stream(stream::unfold(
+      receiver,
+      |mut receiver| async move { receiver.recv().await.map(|chunk| (chunk, receiver)) },
+    )))
+    .context(server_error::InvalidResponse)
 }
 
 pub(crate) async fn package_item(
@@ -355,15 +559,27 @@ pub(crate) async fn packages(
   })
 }
 
+pub(crate) async fn search(
+  server: ServerExtension,
+  server_config: ServerConfigExtension,
+  Query(search): Query<Search>,
+) -> PageResult<SearchHtml> {
+  block_in_place(|| Ok(server.search_html(search)?.page(server_config.url.clone())))
+}
+
 pub(crate) async fn static_asset(path: Path<String>) -> ServerResult<StaticAsset> {
   StaticAsset::get(&path)
 }
 
 pub(crate) async fn upload_file(
-  _: Authenticated,
+  authenticated: Authenticated,
   server: ServerExtension,
+  config: ServerConfigExtension,
   hash: Path<Hash>,
   body: Body,
 ) -> ServerResult {
-  server.write_file(*hash, body).await
+  authenticated.authorize(Role::Upload)?;
+  server
+    .write_file(*hash, body, authenticated.issuer(), &config.quotas)
+    .await
 }
@@ -4,6 +4,7 @@ use {
     body,
     http::{Method, Request, header::HeaderName},
   },
+  templates::{PackageHtml, SearchHtml},
   tokio::runtime::Runtime,
   tower::ServiceExt,
 };
@@ -27,35 +28,63 @@ impl<'a> DirectoryBuilder<'a> {
         DirectoryBuilderEntry::File(content) => {
           directory.insert_file(name, content);
         }
+        DirectoryBuilderEntry::Symlink(target) => {
+          directory.insert_entry(
+            name,
+            Entry::Symlink {
+              target: target.parse().unwrap(),
+            },
+          );
+        }
       }
     }
 
     directory
   }
 
-  fn insert(&mut self, path: &[&'a str], content: &[u8]) {
+  fn insert(&mut self, path: &[&'a str], entry: DirectoryBuilderEntry<'a>) {
     let (first, rest) = path.split_first().unwrap();
 
     if rest.is_empty() {
-      assert!(
-        self
-          .entries
-          .insert(first, DirectoryBuilderEntry::File(content.to_vec()))
-          .is_none()
-      );
+      assert!(self.entries.insert(first, entry).is_none());
     } else {
-      let entry = self
+      let parent = self
         .entries
         .entry(first)
         .or_insert_with(|| DirectoryBuilderEntry::Directory(DirectoryBuilder::default()));
 
-      match entry {
-        DirectoryBuilderEntry::Directory(child) => child.insert(rest, content),
-        DirectoryBuilderEntry::File(_) => panic!("file name `{first}` conflicts with directory"),
+      match parent {
+        DirectoryBuilderEntry::Directory(child) => child.insert(rest, entry),
+        DirectoryBuilderEntry::File(_) | DirectoryBuilderEntry::Symlink(_) => {
+          panic!("file name `{first}` conflicts with directory")
+        }
       }
     }
   }
 
+  fn tree(&self, contents: &mut BTreeMap<Hash, Vec<u8>>) -> DirectoryTree {
+    let mut tree = DirectoryTree::new();
+
+    for (name, entry) in &self.entries {
+      let entry = match entry {
+        DirectoryBuilderEntry::Directory(child) => {
+          DirectoryTreeEntry::Directory(child.tree(contents))
+        }
+        DirectoryBuilderEntry::File(content) => {
+          contents.insert(Hash::bytes(content), content.clone());
+          DirectoryTreeEntry::File(File::new(content))
+        }
+        DirectoryBuilderEntry::Symlink(target) => DirectoryTreeEntry::Symlink(Symlink {
+          target: target.parse().unwrap(),
+        }),
+      };
+
+      tree.entries.insert(name.parse().unwrap(), entry);
+    }
+
+    tree
+  }
+
   fn upload(&self, server: &TestServer) -> Hash {
     for entry in self.entries.values() {
       match entry {
@@ -65,6 +94,7 @@ impl<'a> DirectoryBuilder<'a> {
         DirectoryBuilderEntry::File(content) => {
           server.write_file(content);
         }
+        DirectoryBuilderEntry::Symlink(_) => {}
       }
     }
 
@@ -80,6 +110,7 @@ impl<'a> DirectoryBuilder<'a> {
 enum DirectoryBuilderEntry<'a> {
   Directory(DirectoryBuilder<'a>),
   File(Vec<u8>),
+  Symlink(&'a str),
 }
 
 #[derive(Default)]
@@ -88,13 +119,35 @@ struct PackageBuilder<'a> {
 }
 
 impl<'a> PackageBuilder<'a> {
+  fn bundle(&self) -> Vec<u8> {
+    let mut contents = BTreeMap::new();
+
+    let manifest = Manifest {
+      embedded: BTreeMap::new(),
+      package: self.root.tree(&mut contents),
+      signatures: BTreeSet::new(),
+    };
+
+    let mut writer = BundleWriter::new(Vec::new(), &manifest).unwrap();
+
+    for content in contents.values() {
+      writer
+        .content(File::new(content), content.as_slice())
+        .unwrap();
+    }
+
+    writer.finish().unwrap()
+  }
+
   fn directory(&self) -> Directory {
     self.root.build()
   }
 
   fn file(mut self, path: &'a str, content: &[u8]) -> Self {
     let path = path.split('/').collect::<Vec<&str>>();
-    self.root.insert(&path, content);
+    self
+      .root
+      .insert(&path, DirectoryBuilderEntry::File(content.to_vec()));
     self
   }
 
@@ -110,6 +163,14 @@ impl<'a> PackageBuilder<'a> {
     Self::default()
   }
 
+  fn symlink(mut self, path: &'a str, target: &'a str) -> Self {
+    let path = path.split('/').collect::<Vec<&str>>();
+    self
+      .root
+      .insert(&path, DirectoryBuilderEntry::Symlink(target));
+    self
+  }
+
   fn upload(self, server: &TestServer) -> Fingerprint {
     let fingerprint = Fingerprint(self.root.upload(server));
 
@@ -289,6 +350,8 @@ impl TestServer {
     TestServerBuilder {
       auth_config: None,
       mounts: HashSet::new(),
+      policy: None,
+      quotas: Quotas::default(),
       url: None,
     }
   }
@@ -328,6 +391,8 @@ impl TestServer {
 struct TestServerBuilder {
   auth_config: Option<Arc<AuthConfig>>,
   mounts: HashSet<Fingerprint>,
+  policy: Option<Policy>,
+  quotas: Quotas,
   url: Option<Url>,
 }
 
@@ -348,6 +413,8 @@ impl TestServerBuilder {
       None,
       Arc::new(ServerConfig {
         mounts: self.mounts,
+        policy: self.policy,
+        quotas: self.quotas,
         url: self.url,
       }),
     );
@@ -364,6 +431,16 @@ impl TestServerBuilder {
     self
   }
 
+  fn policy(mut self, policy: &str) -> Self {
+    self.policy = Some(policy.parse().unwrap());
+    self
+  }
+
+  fn quotas(mut self, quotas: Quotas) -> Self {
+    self.quotas = quotas;
+    self
+  }
+
   fn url(mut self, url: Url) -> Self {
     self.url = Some(url);
     self
@@ -377,1297 +454,2607 @@ fn admin_key_requires_restrict_writes() {
 }
 
 #[test]
-fn api_packages_returns_package_fingerprints() {
+fn api_name() {
   let server = TestServer::new();
 
-  server
-    .get("/api/packages")
-    .assert_body(
-      api::packages::Response {
-        packages: BTreeSet::new().into(),
-      }
-      .encode_to_vec(),
-    )
-    .send();
+  let private_key = PrivateKey::generate();
 
-  let foo = PackageBuilder::new().file("foo", b"foo").upload(&server);
-  let bar = PackageBuilder::new().file("bar", b"bar").upload(&server);
+  let name = "latest".parse::<PackageName>().unwrap();
 
-  server
-    .get("/api/packages")
-    .assert_body(
-      api::packages::Response {
-        packages: BTreeSet::from([foo, bar]).into(),
-      }
-      .encode_to_vec(),
-    )
-    .send();
-}
+  let first = NameRecord::new(
+    &private_key,
+    name.clone(),
+    test::FINGERPRINT.parse().unwrap(),
+    1,
+  );
 
-#[test]
-fn artwork_missing() {
-  let server = TestServer::new();
+  for _ in 0..2 {
+    server.post("/api/name").body(first.encode_to_vec()).send();
+  }
 
-  let artwork = b"foo";
-  server.write_file(artwork);
+  let path = format!("/api/name/{}/latest", private_key.public_key());
 
-  let metadata = Metadata {
-    artwork: Some("cover.png".parse().unwrap()),
-    ..Metadata::default()
-  };
-  let metadata_cbor = metadata.encode_to_vec();
-  server.write_file(&metadata_cbor);
+  server.get(&path).assert_body(first.encode_to_vec()).send();
 
-  let (cbor, hash) = Directory::new().insert_file("cover.png,", artwork).cbor();
-  let fingerprint = Fingerprint(hash);
-  server.write_file(&cbor);
+  let second = NameRecord::new(
+    &private_key,
+    name,
+    Fingerprint::from(Hash::bytes(b"foo")),
+    2,
+  );
 
-  server.post(format!("/api/directory/{hash}")).send();
-  server.post(format!("/api/package/{fingerprint}")).send();
+  server.post("/api/name").body(second.encode_to_vec()).send();
 
-  let mut corrupt = Directory::new();
-  corrupt.insert_file(Metadata::CBOR_FILENAME, &metadata_cbor);
+  server.get(&path).assert_body(second.encode_to_vec()).send();
+}
 
-  let corrupt = corrupt.encode_to_vec();
-  fs::write(
-    server.data_dir.join("files").join(hash.to_string()),
-    &corrupt,
+#[test]
+fn api_name_not_found() {
+  let public_key = PrivateKey::generate().public_key();
+
+  TestServer::new()
+    .get(format!("/api/name/{public_key}/latest"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("name `latest` for key {public_key} not found"))
+    .send();
+}
+
+#[test]
+fn api_name_rejects_invalid_signature() {
+  let private_key = PrivateKey::generate();
+
+  let mut record = NameRecord::new(
+    &private_key,
+    "latest".parse().unwrap(),
+    test::FINGERPRINT.parse().unwrap(),
+    1,
   )
-  .unwrap();
+  .encode_to_vec();
 
-  server
-    .get(format!("/artwork/{fingerprint}"))
-    .status(StatusCode::INTERNAL_SERVER_ERROR)
+  *record.last_mut().unwrap() ^= 1;
+
+  TestServer::new()
+    .post("/api/name")
+    .body(record)
+    .status(StatusCode::BAD_REQUEST)
     .assert_body(format!(
-      "file `cover.png` missing from package {fingerprint}",
+      "invalid signature on name `latest` for key {}",
+      private_key.public_key(),
     ))
     .send();
 }
 
 #[test]
-fn artwork_not_found_without_artwork() {
+fn api_name_rejects_stale_sequence() {
   let server = TestServer::new();
 
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata::default())
-    .upload(&server);
+  let private_key = PrivateKey::generate();
+
+  let fingerprint = test::FINGERPRINT.parse::<Fingerprint>().unwrap();
+
+  let current = NameRecord::new(&private_key, "latest".parse().unwrap(), fingerprint, 2);
 
   server
-    .get(format!("/artwork/{fingerprint}"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!("package {fingerprint} artwork not found"))
+    .post("/api/name")
+    .body(current.encode_to_vec())
     .send();
-}
 
-#[test]
-fn artwork_package_not_found() {
-  let server = TestServer::new();
+  for sequence in [1, 2] {
+    let stale = NameRecord::new(
+      &private_key,
+      "latest".parse().unwrap(),
+      Fingerprint::from(Hash::bytes(b"foo")),
+      sequence,
+    );
 
-  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
+    server
+      .post("/api/name")
+      .body(stale.encode_to_vec())
+      .status(StatusCode::CONFLICT)
+      .assert_body(format!(
+        "sequence number {sequence} for name `latest` of key {} is not greater than current \
+        sequence number 2",
+        private_key.public_key(),
+      ))
+      .send();
+  }
 
   server
-    .get(format!("/artwork/{fingerprint}"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!("package {fingerprint} not found"))
+    .get(format!("/api/name/{}/latest", private_key.public_key()))
+    .assert_body(current.encode_to_vec())
     .send();
 }
 
 #[test]
-fn artwork_response() {
-  #[track_caller]
-  fn case(filename: &str, content_type: &str) {
-    let server = TestServer::new();
+fn api_outboard() {
+  let server = TestServer::new();
 
-    let artwork = b"foo";
-    let artwork_hash = Hash::bytes(artwork);
-    server.write_file(artwork);
+  server.write_file(b"foo");
 
-    let metadata = Metadata {
-      artwork: Some(filename.parse().unwrap()),
-      ..Metadata::default()
-    };
-    let metadata_cbor = metadata.encode_to_vec();
-    server.write_file(&metadata_cbor);
+  server
+    .get(format!("/api/outboard/{}", Hash::bytes(b"foo")))
+    .assert_body(
+      api::outboard::Response {
+        size: 3,
+        tree: Vec::new(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
 
-    let (cbor, hash) = Directory::new()
-      .insert_file(filename, artwork)
-      .insert_file(Metadata::CBOR_FILENAME, &metadata_cbor)
-      .cbor();
+  let content = vec![1; usize::try_from(Outboard::BLOCK_SIZE * 2 + 1).unwrap()];
 
-    let fingerprint = Fingerprint(hash);
-    server.write_file(&cbor);
+  let hash = Hash::bytes(&content);
 
-    server.post(format!("/api/directory/{hash}")).send();
-    server.post(format!("/api/package/{fingerprint}")).send();
+  server.write_file(&content);
 
-    server
-      .get(format!("/artwork/{fingerprint}"))
-      .assert_header(header::ACCEPT_RANGES, "bytes")
-      .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
-      .assert_header(header::CONTENT_LENGTH, "3")
-      .assert_header(header::CONTENT_SECURITY_POLICY, "sandbox")
-      .assert_header(header::CONTENT_TYPE, content_type)
-      .assert_header(header::ETAG, format!("\"{artwork_hash}\""))
-      .assert_body(artwork)
-      .send();
+  let mut hasher = OutboardHasher::new();
+  hasher.update(&content);
+  let tree = hasher.finalize();
 
-    server
-      .get(format!("/artwork/{fingerprint}"))
-      .range("bytes=1-2")
-      .status(StatusCode::PARTIAL_CONTENT)
-      .assert_header(header::ACCEPT_RANGES, "bytes")
-      .assert_header(header::CONTENT_RANGE, "bytes 1-2/3")
-      .assert_header(header::CONTENT_LENGTH, "2")
-      .assert_body("oo")
-      .send();
-  }
+  assert_eq!(tree.len(), Outboard::CV_LEN * 3);
 
-  case("cover.png", "image/png");
-  case("cover.jpg", "image/jpeg");
-}
+  Outboard::new(hash, content.len().into_u64(), &tree).unwrap();
 
-#[test]
-fn closed_server_forbids_writes() {
-  TestServer::builder()
-    .auth_config(AuthConfig {
-      admin: None,
-      audience: None,
-    })
-    .build()
-    .put(format!("/file/{}", Hash::bytes(b"bar")))
-    .body("bar")
-    .status(StatusCode::FORBIDDEN)
-    .assert_body("writes forbidden")
+  server
+    .get(format!("/api/outboard/{hash}"))
+    .assert_body(
+      api::outboard::Response {
+        size: content.len().into_u64(),
+        tree: tree.clone(),
+      }
+      .encode_to_vec(),
+    )
     .send();
-}
 
-#[test]
-fn default_serve_matches_parsed() {
   assert_eq!(
-    Serve::default(),
-    Serve::try_parse_from(["filepack"]).unwrap(),
+    fs::read(server.data_dir.join("outboards").join(hash.to_string())).unwrap(),
+    tree,
   );
+
+  let missing = Hash::bytes(b"bar");
+
+  server
+    .get(format!("/api/outboard/{missing}"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("file with hash {missing} not found"))
+    .send();
 }
 
 #[test]
-fn delete_package_not_found() {
+fn api_outboard_rejects_corrupt_file() {
   let server = TestServer::new();
 
-  let fingerprint = PackageBuilder::new().fingerprint();
+  let content = vec![1; usize::try_from(Outboard::BLOCK_SIZE * 2).unwrap()];
+
+  let hash = Hash::bytes(&content);
+
+  fs::write(
+    server.data_dir.join("files").join(hash.to_string()),
+    &content[1..],
+  )
+  .unwrap();
 
   server
-    .delete(format!("/api/package/{fingerprint}"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!("package {fingerprint} not found"))
+    .get(format!("/api/outboard/{hash}"))
+    .status(StatusCode::INTERNAL_SERVER_ERROR)
+    .assert_body(format!("file {hash} is corrupt"))
     .send();
 }
 
 #[test]
-fn delete_package_rejects_missing_auth_header() {
-  let admin = PrivateKey::generate();
-  let server = TestServer::builder()
-    .auth_config(AuthConfig {
-      admin: Some(admin.public_key()),
-      audience: Some("filepack.example".into()),
-    })
-    .build();
+fn api_package_signatures() {
+  let server = TestServer::new();
 
-  let fingerprint = PackageBuilder::new().fingerprint();
+  let fingerprint = PackageBuilder::new().file("foo", b"foo").upload(&server);
+
+  let response = |signatures: &[&Signature]| {
+    api::signatures::Response {
+      signatures: signatures
+        .iter()
+        .copied()
+        .cloned()
+        .collect::<BTreeSet<Signature>>()
+        .into(),
+    }
+    .encode_to_vec()
+  };
 
   server
-    .delete(format!("/api/package/{fingerprint}"))
-    .status(StatusCode::UNAUTHORIZED)
-    .assert_body("missing authorization header")
+    .get(format!("/api/package/{fingerprint}/signatures"))
+    .assert_body(response(&[]))
+    .send();
+
+  let statement = Statement {
+    fingerprint,
+    timestamp: None,
+    expires: None,
+    purpose: None,
+    comment: None,
+  };
+
+  let alice = PrivateKey::generate().sign(&statement);
+  let bob = PrivateKey::generate().sign(&statement);
+
+  server
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(format!("{alice}\n"))
+    .send();
+
+  server
+    .get(format!("/api/package/{fingerprint}/signatures"))
+    .assert_body(response(&[&alice]))
+    .send();
+
+  server
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(format!("{alice}\n\n{bob}\n"))
+    .send();
+
+  server
+    .get(format!("/api/package/{fingerprint}/signatures"))
+    .assert_body(response(&[&alice, &bob]))
     .send();
 }
 
 #[test]
-fn delete_package_removes_package() {
+fn api_package_signatures_key_limit() {
   let server = TestServer::new();
 
   let fingerprint = PackageBuilder::new().file("foo", b"foo").upload(&server);
 
-  server.delete(format!("/api/package/{fingerprint}")).send();
+  let private_key = PrivateKey::generate();
+
+  let signatures = (0..17)
+    .map(|timestamp| {
+      private_key
+        .sign(&Statement {
+          fingerprint,
+          timestamp: Some(timestamp),
+          expires: None,
+          purpose: None,
+          comment: None,
+        })
+        .to_string()
+    })
+    .collect::<Vec<String>>();
 
   server
-    .get("/api/packages")
-    .assert_body(
-      api::packages::Response {
-        packages: BTreeSet::new().into(),
-      }
-      .encode_to_vec(),
-    )
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(signatures[..16].join("\n"))
     .send();
-}
 
-#[test]
-fn domain_required_for_canonical_domain_options() {
-  #[track_caller]
-  fn case(args: &[&str]) {
-    let err = Serve::try_parse_from(["filepack"].iter().chain(args)).unwrap_err();
-    assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
-  }
-
-  case(&["--https"]);
-  case(&["--https-port", "443"]);
-  case(&["--redirect", "bar"]);
-  case(&["--redirect-http-to-https"]);
-}
+  server
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(signatures.join("\n"))
+    .status(StatusCode::INSUFFICIENT_STORAGE)
+    .assert_body(format!(
+      "key {} already has maximum of 16 signatures on package {fingerprint}",
+      private_key.public_key(),
+    ))
+    .send();
 
-#[test]
-fn domains_include_redirects() {
-  assert_eq!(
-    Serve {
-      domain: Some("foo".into()),
-      redirects: vec!["bar".into()],
-      ..Serve::default()
-    }
-    .domains(),
-    vec!["foo".to_string(), "bar".to_string()],
-  );
+  server
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(signatures[..16].join("\n"))
+    .send();
 }
 
 #[test]
-fn download_range() {
+fn api_package_signatures_package_limit() {
   let server = TestServer::new();
 
-  let hash = Hash::bytes(b"foobarbaz");
-  server.write_file(b"foobarbaz");
+  let fingerprint = PackageBuilder::new().file("foo", b"foo").upload(&server);
+
+  let statement = Statement {
+    fingerprint,
+    timestamp: None,
+    expires: None,
+    purpose: None,
+    comment: None,
+  };
+
+  let signatures = (0..257)
+    .map(|_| PrivateKey::generate().sign(&statement).to_string())
+    .collect::<Vec<String>>();
 
   server
-    .get(format!("/file/{hash}"))
-    .range("bytes=0-2")
-    .status(StatusCode::PARTIAL_CONTENT)
-    .assert_header(header::ACCEPT_RANGES, "bytes")
-    .assert_header(header::CONTENT_DISPOSITION, "attachment")
-    .assert_header(header::CONTENT_RANGE, "bytes 0-2/9")
-    .assert_header(header::CONTENT_LENGTH, "3")
-    .assert_body("foo")
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(signatures[..256].join("\n"))
+    .send();
+
+  server
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(signatures[256].clone())
+    .status(StatusCode::INSUFFICIENT_STORAGE)
+    .assert_body(format!(
+      "package {fingerprint} already has maximum of 256 signatures"
+    ))
     .send();
 }
 
 #[test]
-fn download_response() {
+fn api_package_signatures_package_not_found() {
   let server = TestServer::new();
 
-  let hash = Hash::bytes(b"bar");
-  server.write_file(b"bar");
+  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
 
-  server
-    .get(format!("/file/{hash}"))
-    .assert_header(header::ACCEPT_RANGES, "bytes")
-    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
-    .assert_header(header::CONTENT_DISPOSITION, "attachment")
-    .assert_header(header::CONTENT_LENGTH, "3")
-    .assert_header(header::CONTENT_SECURITY_POLICY, "sandbox")
-    .assert_header(header::CONTENT_TYPE, "application/octet-stream")
-    .assert_header(header::ETAG, format!("\"{hash}\""))
-    .assert_body("bar")
-    .send();
-}
+  let signature = PrivateKey::generate().sign(&Statement {
+    fingerprint,
+    timestamp: None,
+    expires: None,
+    purpose: None,
+    comment: None,
+  });
 
-#[test]
-fn fallback() {
-  TestServer::new()
-    .get("/nonexistent")
-    .assert_static("404.html")
+  server
+    .get(format!("/api/package/{fingerprint}/signatures"))
     .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {fingerprint} not found"))
     .send();
-}
 
-#[test]
-fn favicon() {
-  TestServer::new()
-    .get("/favicon.ico")
-    .assert_static("favicon.png")
+  server
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(signature.to_string())
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {fingerprint} not found"))
     .send();
 }
 
 #[test]
-fn file_with_path_inline() {
+fn api_package_signatures_rejects_invalid_signature() {
   let server = TestServer::new();
 
-  server.write_file(b"foo");
+  let fingerprint = PackageBuilder::new().file("foo", b"foo").upload(&server);
+
+  let private_key = PrivateKey::generate();
+
+  let signature = private_key.sign(&Statement {
+    fingerprint: Fingerprint(Hash::bytes(b"bar")),
+    timestamp: None,
+    expires: None,
+    purpose: None,
+    comment: None,
+  });
 
   server
-    .get(format!("/file/{}/bar.png", Hash::bytes(b"foo")))
-    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
-    .assert_header(header::CONTENT_TYPE, "image/png")
-    .assert_header_absent(header::CONTENT_DISPOSITION)
-    .assert_body("foo")
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(signature.to_string())
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body(format!(
+      "invalid signature for package {fingerprint} by key {}",
+      private_key.public_key(),
+    ))
+    .send();
+
+  server
+    .post(format!("/api/package/{fingerprint}/signatures"))
+    .body(format!("{signature}\nfoo"))
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body(format!(
+      "invalid signature on line 2: {}",
+      "foo".parse::<Signature>().unwrap_err(),
+    ))
     .send();
 }
 
 #[test]
-fn file_with_path_markdown() {
+fn api_packages_returns_package_fingerprints() {
   let server = TestServer::new();
 
-  server.write_file(b"foo");
+  server
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::new().into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+
+  let foo = PackageBuilder::new().file("foo", b"foo").upload(&server);
+  let bar = PackageBuilder::new().file("bar", b"bar").upload(&server);
 
   server
-    .get(format!("/file/{}/bar.md", Hash::bytes(b"foo")))
-    .assert_header(header::CONTENT_SECURITY_POLICY, "sandbox")
-    .assert_header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
-    .assert_header_absent(header::CONTENT_DISPOSITION)
-    .assert_body("foo")
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::from([foo, bar]).into(),
+      }
+      .encode_to_vec(),
+    )
     .send();
 }
 
 #[test]
-fn file_with_path_nested() {
+fn api_proof() {
   let server = TestServer::new();
 
-  server.write_file(b"foo");
+  let package = PackageBuilder::new()
+    .file("foo", b"foo")
+    .file("bar/baz", b"baz");
+
+  let (root, _hash) = package.directory().cbor();
+
+  let fingerprint = package.upload(&server);
+
+  let (bar, _hash) = Directory::new().insert_file("baz", b"baz").cbor();
+
+  let proof = Proof {
+    directories: vec![root, bar],
+    path: "bar/baz".parse().unwrap(),
+    version: Version::Zero,
+  };
+
+  assert_eq!(proof.verify(fingerprint).unwrap(), File::new(b"baz"));
 
   server
-    .get(format!("/file/{}/bar/baz.png", Hash::bytes(b"foo")))
-    .assert_header(header::CONTENT_TYPE, "image/png")
-    .assert_body("foo")
+    .get(format!("/api/proof/{fingerprint}/bar/baz"))
+    .assert_body(proof.encode_to_vec())
     .send();
-}
 
-#[test]
-fn file_with_path_redirect() {
-  let hash = Hash::bytes(b"foo");
+  server
+    .get(format!("/api/proof/{fingerprint}/bar"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("file `bar` not found in package {fingerprint}"))
+    .send();
 
-  TestServer::new()
-    .get(format!("/file/{hash}/bar.txt"))
-    .status(StatusCode::TEMPORARY_REDIRECT)
-    .assert_header(header::LOCATION, format!("/file/{hash}"))
+  server
+    .get(format!("/api/proof/{fingerprint}/qux"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("file `qux` not found in package {fingerprint}"))
     .send();
-}
 
-#[test]
-fn files_empty() {
-  TestServer::new()
-    .get("/files")
-    .assert_page(FilesHtml { files: Vec::new() })
+  let missing = Fingerprint(Hash::bytes(b"foo"));
+
+  server
+    .get(format!("/api/proof/{missing}/foo"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {missing} not found"))
     .send();
 }
 
 #[test]
-fn files_non_empty() {
+fn api_revocation_rejects_invalid_revocation() {
   let server = TestServer::new();
 
-  server.write_file(b"foo");
-  server.write_file(b"bar");
-  server.write_file(b"baz");
-
-  fs::write(server.data_dir.join("files").join("not-a-hash"), "").unwrap();
+  let private_key = PrivateKey::generate();
 
-  let mut files = vec![
-    Hash::bytes(b"foo"),
-    Hash::bytes(b"bar"),
-    Hash::bytes(b"baz"),
-  ];
-  files.sort();
+  let mut revocation = Revocation::new(&private_key, 1000, None).encode_to_vec();
 
-  server.get("/files").assert_page(FilesHtml { files }).send();
-}
+  *revocation.last_mut().unwrap() ^= 1;
 
-#[test]
-fn fingerprint_redirects_to_package() {
-  TestServer::new()
-    .get(format!("/{}", test::FINGERPRINT))
-    .status(StatusCode::PERMANENT_REDIRECT)
-    .assert_header(header::LOCATION, format!("/package/{}", test::FINGERPRINT))
+  server
+    .post("/api/revocation")
+    .body(revocation)
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body(format!(
+      "invalid revocation for key {}",
+      private_key.public_key(),
+    ))
     .send();
 }
 
 #[test]
-fn gc_empty_server_removes_nothing() {
-  TestServer::new()
-    .post("/api/gc")
+fn api_revocations() {
+  let server = TestServer::new();
+
+  server
+    .get("/api/revocations")
     .assert_body(
-      api::gc::Response {
-        bytes: 0,
-        directories: BTreeSet::new().into(),
-        files: BTreeSet::new().into(),
+      api::revocations::Response {
+        revocations: BTreeSet::new().into(),
       }
       .encode_to_vec(),
     )
     .send();
-}
 
-#[test]
-fn gc_ignores_non_hash_filenames() {
-  let server = TestServer::new();
+  let private_key = PrivateKey::generate();
 
-  fs::write(server.data_dir.join("files").join("not-a-hash"), "").unwrap();
+  let revocation = Revocation::new(&private_key, 1000, None);
+
+  for time in [2000, 1000, 3000] {
+    server
+      .post("/api/revocation")
+      .body(Revocation::new(&private_key, time, None).encode_to_vec())
+      .send();
+  }
 
   server
-    .post("/api/gc")
+    .get("/api/revocations")
     .assert_body(
-      api::gc::Response {
-        bytes: 0,
-        directories: BTreeSet::new().into(),
-        files: BTreeSet::new().into(),
+      api::revocations::Response {
+        revocations: BTreeSet::from([revocation]).into(),
       }
       .encode_to_vec(),
     )
     .send();
-
-  assert!(
-    server
-      .data_dir
-      .join("files")
-      .join("not-a-hash")
-      .try_exists()
-      .unwrap()
-  );
-}
-
-#[test]
-fn gc_rejects_missing_auth_header() {
-  let admin = PrivateKey::generate();
-  let server = TestServer::builder()
-    .auth_config(AuthConfig {
-      admin: Some(admin.public_key()),
-      audience: Some("filepack.example".into()),
-    })
-    .build();
-
-  server
-    .post("/api/gc")
-    .status(StatusCode::UNAUTHORIZED)
-    .assert_body("missing authorization header")
-    .send();
 }
 
 #[test]
-fn gc_removes_unreachable_and_retains_reachable_data() {
+fn artwork_missing() {
   let server = TestServer::new();
 
-  let retained = PackageBuilder::new().file("foo", b"foo");
+  let artwork = b"foo";
+  server.write_file(artwork);
 
-  let retained_hash = retained.directory().cbor().1;
+  let metadata = Metadata {
+    artwork: Some("cover.png".parse().unwrap()),
+    ..Metadata::default()
+  };
+  let metadata_cbor = metadata.encode_to_vec();
+  server.write_file(&metadata_cbor);
 
-  retained.upload(&server);
+  let (cbor, hash) = Directory::new().insert_file("cover.png,", artwork).cbor();
+  let fingerprint = Fingerprint(hash);
+  server.write_file(&cbor);
 
-  let mut subdirectory = Directory::new();
-  subdirectory.insert_file("baz", b"baz");
-
-  let (subdirectory_cbor, subdirectory_hash) = subdirectory.cbor();
-
-  let package = PackageBuilder::new()
-    .file("bar/baz", b"baz")
-    .file("foo", b"foo");
-
-  let (root_cbor, root_hash) = package.directory().cbor();
+  server.post(format!("/api/directory/{hash}")).send();
+  server.post(format!("/api/package/{fingerprint}")).send();
 
-  let fingerprint = package.upload(&server);
+  let mut corrupt = Directory::new();
+  corrupt.insert_file(Metadata::CBOR_FILENAME, &metadata_cbor);
 
-  server.delete(format!("/api/package/{fingerprint}")).send();
+  let corrupt = corrupt.encode_to_vec();
+  fs::write(
+    server.data_dir.join("files").join(hash.to_string()),
+    &corrupt,
+  )
+  .unwrap();
 
   server
-    .post("/api/gc")
-    .assert_body(
-      api::gc::Response {
-        bytes: root_cbor.len().into_u64() + subdirectory_cbor.len().into_u64() + 3,
-        directories: BTreeSet::from([root_hash, subdirectory_hash]).into(),
-        files: BTreeSet::from([root_hash, subdirectory_hash, Hash::bytes(b"baz")]).into(),
-      }
-      .encode_to_vec(),
-    )
+    .get(format!("/artwork/{fingerprint}"))
+    .status(StatusCode::INTERNAL_SERVER_ERROR)
+    .assert_body(format!(
+      "file `cover.png` missing from package {fingerprint}",
+    ))
     .send();
-
-  server.assert_file(retained_hash);
-  server.assert_file(Hash::bytes(b"foo"));
-
-  assert_eq!(
-    fs::read_dir(server.data_dir.join("files")).unwrap().count(),
-    2,
-  );
 }
 
 #[test]
-fn get_directory_not_found() {
+fn artwork_not_found_without_artwork() {
   let server = TestServer::new();
 
-  let (cbor, hash) = Directory::new().cbor();
-  server.write_file(&cbor);
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata::default())
+    .upload(&server);
 
   server
-    .get(format!("/directory/{hash}"))
+    .get(format!("/artwork/{fingerprint}"))
     .status(StatusCode::NOT_FOUND)
-    .assert_body(format!("directory {hash} not found"))
-    .send();
-}
-
-#[test]
-fn get_directory_succeeds() {
-  let server = TestServer::new();
-
-  let directory = Directory::new();
-  let (cbor, hash) = directory.cbor();
-  server.write_file(&cbor);
-
-  server.post(format!("/api/directory/{hash}")).send();
-
-  server
-    .get(format!("/directory/{hash}"))
-    .assert_page(DirectoryHtml { directory, hash })
+    .assert_body(format!("package {fingerprint} artwork not found"))
     .send();
 }
 
 #[test]
-fn get_package_not_found() {
+fn artwork_package_not_found() {
   let server = TestServer::new();
 
   let fingerprint = Fingerprint(Hash::bytes(b"foo"));
 
   server
-    .get(format!("/package/{fingerprint}"))
+    .get(format!("/artwork/{fingerprint}"))
     .status(StatusCode::NOT_FOUND)
     .assert_body(format!("package {fingerprint} not found"))
     .send();
 }
 
 #[test]
-fn get_package_with_metadata() {
-  let server = TestServer::new();
+fn artwork_response() {
+  #[track_caller]
+  fn case(filename: &str, content_type: &str) {
+    let server = TestServer::new();
 
-  let readme = b"foo";
-  server.write_file(readme);
+    let artwork = b"foo";
+    let artwork_hash = Hash::bytes(artwork);
+    server.write_file(artwork);
 
-  let colophon = b"bar";
-  server.write_file(colophon);
+    let metadata = Metadata {
+      artwork: Some(filename.parse().unwrap()),
+      ..Metadata::default()
+    };
+    let metadata_cbor = metadata.encode_to_vec();
+    server.write_file(&metadata_cbor);
 
-  let metadata = Metadata {
-    artwork: None,
-    creator: None,
-    description: None,
-    homepage: None,
-    language: None,
-    media: None,
-    package: Some(Package {
-      colophon: Some("COLOPHON.md".parse().unwrap()),
-      creator: None,
-      description: None,
-      homepage: None,
-      time: None,
-      title: None,
-    }),
-    readme: Some("README.md".parse().unwrap()),
-    time: None,
-    title: Some("foo".parse().unwrap()),
-  };
-  let metadata_cbor = metadata.encode_to_vec();
-  server.write_file(&metadata_cbor);
+    let (cbor, hash) = Directory::new()
+      .insert_file(filename, artwork)
+      .insert_file(Metadata::CBOR_FILENAME, &metadata_cbor)
+      .cbor();
 
-  let mut directory = Directory::new();
-  directory
-    .insert_file("COLOPHON.md", colophon)
-    .insert_file("README.md", readme)
-    .insert_file(Metadata::CBOR_FILENAME, &metadata_cbor);
-  let (cbor, hash) = directory.cbor();
-  let fingerprint = Fingerprint(hash);
-  server.write_file(&cbor);
+    let fingerprint = Fingerprint(hash);
+    server.write_file(&cbor);
 
-  server.post(format!("/api/directory/{hash}")).send();
-  server.post(format!("/api/package/{fingerprint}")).send();
+    server.post(format!("/api/directory/{hash}")).send();
+    server.post(format!("/api/package/{fingerprint}")).send();
 
-  server
-    .get(format!("/package/{fingerprint}"))
-    .assert_page(PackageHtml {
-      colophon: Some(Hash::bytes(colophon)),
-      directory,
-      fingerprint,
-      metadata: Some(metadata),
-      mounted: false,
-      readme: Some(Hash::bytes(readme)),
-      totals: Totals {
-        directories: 0,
-        directory_size: 0,
-        file_size: metadata_cbor.len().into_u64() + 6,
-        files: 3,
-      },
+    server
+      .get(format!("/artwork/{fingerprint}"))
+      .assert_header(header::ACCEPT_RANGES, "bytes")
+      .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+      .assert_header(header::CONTENT_LENGTH, "3")
+      .assert_header(header::CONTENT_SECURITY_POLICY, "sandbox")
+      .assert_header(header::CONTENT_TYPE, content_type)
+      .assert_header(header::ETAG, format!("\"{artwork_hash}\""))
+      .assert_body(artwork)
+      .send();
+
+    server
+      .get(format!("/artwork/{fingerprint}"))
+      .range("bytes=1-2")
+      .status(StatusCode::PARTIAL_CONTENT)
+      .assert_header(header::ACCEPT_RANGES, "bytes")
+      .assert_header(header::CONTENT_RANGE, "bytes 1-2/3")
+      .assert_header(header::CONTENT_LENGTH, "2")
+      .assert_body("oo")
+      .send();
+  }
+
+  case("cover.png", "image/png");
+  case("cover.jpg", "image/jpeg");
+}
+
+#[test]
+fn closed_server_forbids_writes() {
+  TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: None,
+      keys: BTreeMap::new(),
     })
+    .build()
+    .put(format!("/file/{}", Hash::bytes(b"bar")))
+    .body("bar")
+    .status(StatusCode::FORBIDDEN)
+    .assert_body("writes forbidden")
     .send();
 }
 
 #[test]
-fn get_package_without_metadata() {
-  let server = TestServer::new();
+fn default_serve_matches_parsed() {
+  assert_eq!(
+    Serve::default(),
+    Serve::try_parse_from(["filepack"]).unwrap(),
+  );
+}
 
-  let directory = Directory::new();
-  let (cbor, hash) = directory.cbor();
-  let fingerprint = Fingerprint(hash);
-  server.write_file(&cbor);
+#[test]
+fn delete_package_allowed_for_admin() {
+  let admin = PrivateKey::generate();
+  let alice = PrivateKey::generate();
 
-  server.post(format!("/api/directory/{hash}")).send();
-  server.post(format!("/api/package/{fingerprint}")).send();
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [
+        (admin.public_key(), [Role::Admin].into()),
+        (alice.public_key(), [Role::Upload].into()),
+      ]
+      .into(),
+    })
+    .build();
+
+  let package = PackageBuilder::new().file("foo", b"foo");
 
   server
-    .get(format!("/package/{fingerprint}"))
-    .assert_page(PackageHtml {
-      colophon: None,
-      directory,
-      fingerprint,
-      metadata: None,
-      mounted: false,
-      readme: None,
-      totals: Totals::default(),
-    })
+    .put("/api/bundle")
+    .body(package.bundle())
+    .token(Token::encode(&alice, "filepack.example").unwrap())
     .send();
-}
 
-#[test]
-fn home() {
-  TestServer::new()
-    .get("/")
-    .assert_static("index.html")
+  server
+    .delete(format!("/api/package/{}", package.fingerprint()))
+    .token(Token::encode(&admin, "filepack.example").unwrap())
     .send();
-}
 
-#[test]
-fn install_script() {
-  TestServer::new()
-    .get("/install.sh")
-    .assert_static("install.sh")
+  server
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::new().into(),
+      }
+      .encode_to_vec(),
+    )
     .send();
 }
 
 #[test]
-fn malformed_fingerprint_returns_error() {
-  TestServer::new()
-    .get("/package1invalid")
-    .status(StatusCode::BAD_REQUEST)
-    .assert_body("failed to decode bech32 package fingerprint")
-    .send();
-}
+fn delete_package_forbidden_for_non_owner() {
+  let alice = PrivateKey::generate();
+  let bob = PrivateKey::generate();
 
-#[test]
-fn media_audio_item_file_missing() {
-  let server = TestServer::new();
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [
+        (alice.public_key(), [Role::Delete, Role::Upload].into()),
+        (bob.public_key(), [Role::Delete, Role::Upload].into()),
+      ]
+      .into(),
+    })
+    .build();
 
-  let metadata = Metadata {
-    media: Some(Media::Audio {
-      items: tracks(&["foo.flac"]),
-    }),
-    ..default()
-  };
+  let package = PackageBuilder::new().file("foo", b"foo");
+  let fingerprint = package.fingerprint();
+
+  server
+    .put("/api/bundle")
+    .body(package.bundle())
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .send();
+
+  server
+    .put("/api/bundle")
+    .body(package.bundle())
+    .token(Token::encode(&bob, "filepack.example").unwrap())
+    .send();
+
+  server
+    .delete(format!("/api/package/{fingerprint}"))
+    .token(Token::encode(&bob, "filepack.example").unwrap())
+    .status(StatusCode::FORBIDDEN)
+    .assert_body(format!(
+      "key {} may not delete package {fingerprint}",
+      bob.public_key(),
+    ))
+    .send();
+
+  server
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::from([fingerprint]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+
+  server
+    .delete(format!("/api/package/{fingerprint}"))
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .send();
+
+  server
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::new().into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+}
+
+#[test]
+fn delete_package_not_found() {
+  let server = TestServer::new();
+
+  let fingerprint = PackageBuilder::new().fingerprint();
+
+  server
+    .delete(format!("/api/package/{fingerprint}"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {fingerprint} not found"))
+    .send();
+}
+
+#[test]
+fn delete_package_rejects_missing_auth_header() {
+  let admin = PrivateKey::generate();
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(admin.public_key(), [Role::Admin].into())].into(),
+    })
+    .build();
+
+  let fingerprint = PackageBuilder::new().fingerprint();
+
+  server
+    .delete(format!("/api/package/{fingerprint}"))
+    .status(StatusCode::UNAUTHORIZED)
+    .assert_body("missing authorization header")
+    .send();
+}
+
+#[test]
+fn delete_package_removes_package() {
+  let server = TestServer::new();
+
+  let fingerprint = PackageBuilder::new().file("foo", b"foo").upload(&server);
+
+  server.delete(format!("/api/package/{fingerprint}")).send();
+
+  server
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::new().into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+}
+
+#[test]
+fn domain_required_for_canonical_domain_options() {
+  #[track_caller]
+  fn case(args: &[&str]) {
+    let err = Serve::try_parse_from(["filepack"].iter().chain(args)).unwrap_err();
+    assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+  }
+
+  case(&["--https"]);
+  case(&["--https-port", "443"]);
+  case(&["--redirect", "bar"]);
+  case(&["--redirect-http-to-https"]);
+}
+
+#[test]
+fn domains_include_redirects() {
+  assert_eq!(
+    Serve {
+      domain: Some("foo".into()),
+      redirects: vec!["bar".into()],
+      ..Serve::default()
+    }
+    .domains(),
+    vec!["foo".to_string(), "bar".to_string()],
+  );
+}
+
+#[test]
+fn download_range() {
+  let server = TestServer::new();
+
+  let hash = Hash::bytes(b"foobarbaz");
+  server.write_file(b"foobarbaz");
+
+  server
+    .get(format!("/file/{hash}"))
+    .range("bytes=0-2")
+    .status(StatusCode::PARTIAL_CONTENT)
+    .assert_header(header::ACCEPT_RANGES, "bytes")
+    .assert_header(header::CONTENT_DISPOSITION, "attachment")
+    .assert_header(header::CONTENT_RANGE, "bytes 0-2/9")
+    .assert_header(header::CONTENT_LENGTH, "3")
+    .assert_body("foo")
+    .send();
+}
+
+#[test]
+fn download_response() {
+  let server = TestServer::new();
+
+  let hash = Hash::bytes(b"bar");
+  server.write_file(b"bar");
+
+  server
+    .get(format!("/file/{hash}"))
+    .assert_header(header::ACCEPT_RANGES, "bytes")
+    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+    .assert_header(header::CONTENT_DISPOSITION, "attachment")
+    .assert_header(header::CONTENT_LENGTH, "3")
+    .assert_header(header::CONTENT_SECURITY_POLICY, "sandbox")
+    .assert_header(header::CONTENT_TYPE, "application/octet-stream")
+    .assert_header(header::ETAG, format!("\"{hash}\""))
+    .assert_body("bar")
+    .send();
+}
+
+#[test]
+fn fallback() {
+  TestServer::new()
+    .get("/nonexistent")
+    .assert_static("404.html")
+    .status(StatusCode::NOT_FOUND)
+    .send();
+}
+
+#[test]
+fn favicon() {
+  TestServer::new()
+    .get("/favicon.ico")
+    .assert_static("favicon.png")
+    .send();
+}
+
+#[test]
+fn file_with_path_inline() {
+  let server = TestServer::new();
+
+  server.write_file(b"foo");
+
+  server
+    .get(format!("/file/{}/bar.png", Hash::bytes(b"foo")))
+    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+    .assert_header(header::CONTENT_TYPE, "image/png")
+    .assert_header_absent(header::CONTENT_DISPOSITION)
+    .assert_body("foo")
+    .send();
+}
+
+#[test]
+fn file_with_path_markdown() {
+  let server = TestServer::new();
+
+  server.write_file(b"foo");
+
+  server
+    .get(format!("/file/{}/bar.md", Hash::bytes(b"foo")))
+    .assert_header(header::CONTENT_SECURITY_POLICY, "sandbox")
+    .assert_header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+    .assert_header_absent(header::CONTENT_DISPOSITION)
+    .assert_body("foo")
+    .send();
+}
+
+#[test]
+fn file_with_path_nested() {
+  let server = TestServer::new();
+
+  server.write_file(b"foo");
+
+  server
+    .get(format!("/file/{}/bar/baz.png", Hash::bytes(b"foo")))
+    .assert_header(header::CONTENT_TYPE, "image/png")
+    .assert_body("foo")
+    .send();
+}
+
+#[test]
+fn file_with_path_redirect() {
+  let hash = Hash::bytes(b"foo");
+
+  TestServer::new()
+    .get(format!("/file/{hash}/bar.txt"))
+    .status(StatusCode::TEMPORARY_REDIRECT)
+    .assert_header(header::LOCATION, format!("/file/{hash}"))
+    .send();
+}
+
+#[test]
+fn files_empty() {
+  TestServer::new()
+    .get("/files")
+    .assert_page(FilesHtml { files: Vec::new() })
+    .send();
+}
+
+#[test]
+fn files_non_empty() {
+  let server = TestServer::new();
+
+  server.write_file(b"foo");
+  server.write_file(b"bar");
+  server.write_file(b"baz");
+
+  fs::write(server.data_dir.join("files").join("not-a-hash"), "").unwrap();
+
+  let mut files = vec![
+    Hash::bytes(b"foo"),
+    Hash::bytes(b"bar"),
+    Hash::bytes(b"baz"),
+  ];
+  files.sort();
+
+  server.get("/files").assert_page(FilesHtml { files }).send();
+}
+
+#[test]
+fn fingerprint_redirects_to_package() {
+  TestServer::new()
+    .get(format!("/{}", test::FINGERPRINT))
+    .status(StatusCode::PERMANENT_REDIRECT)
+    .assert_header(header::LOCATION, format!("/package/{}", test::FINGERPRINT))
+    .send();
+}
+
+#[test]
+fn gc_empty_server_removes_nothing() {
+  TestServer::new()
+    .post("/api/gc")
+    .assert_body(
+      api::gc::Response {
+        bytes: 0,
+        directories: BTreeSet::new().into(),
+        files: BTreeSet::new().into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+}
+
+#[test]
+fn gc_ignores_non_hash_filenames() {
+  let server = TestServer::new();
+
+  fs::write(server.data_dir.join("files").join("not-a-hash"), "").unwrap();
+
+  server
+    .post("/api/gc")
+    .assert_body(
+      api::gc::Response {
+        bytes: 0,
+        directories: BTreeSet::new().into(),
+        files: BTreeSet::new().into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+
+  assert!(
+    server
+      .data_dir
+      .join("files")
+      .join("not-a-hash")
+      .try_exists()
+      .unwrap()
+  );
+}
+
+#[test]
+fn gc_rejects_key_without_role() {
+  let alice = PrivateKey::generate();
+
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(alice.public_key(), [Role::Delete, Role::Upload].into())].into(),
+    })
+    .build();
+
+  server
+    .post("/api/gc")
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .status(StatusCode::FORBIDDEN)
+    .assert_body(format!(
+      "key {} does not have role `gc`",
+      alice.public_key(),
+    ))
+    .send();
+}
+
+#[test]
+fn gc_rejects_missing_auth_header() {
+  let admin = PrivateKey::generate();
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(admin.public_key(), [Role::Admin].into())].into(),
+    })
+    .build();
+
+  server
+    .post("/api/gc")
+    .status(StatusCode::UNAUTHORIZED)
+    .assert_body("missing authorization header")
+    .send();
+}
+
+#[test]
+fn gc_removes_outboards() {
+  let server = TestServer::new();
+
+  let content = vec![1; usize::try_from(Outboard::BLOCK_SIZE * 2).unwrap()];
+
+  let hash = Hash::bytes(&content);
+
+  server.put(format!("/file/{hash}")).body(&content).send();
+
+  assert!(
+    server
+      .data_dir
+      .join("outboards")
+      .join(hash.to_string())
+      .try_exists()
+      .unwrap()
+  );
+
+  server
+    .post("/api/gc")
+    .assert_body(
+      api::gc::Response {
+        bytes: content.len().into_u64(),
+        directories: BTreeSet::new().into(),
+        files: BTreeSet::from([hash]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+
+  assert_eq!(
+    fs::read_dir(server.data_dir.join("outboards"))
+      .unwrap()
+      .count(),
+    0,
+  );
+}
+
+#[test]
+fn gc_removes_unreachable_and_retains_reachable_data() {
+  let server = TestServer::new();
+
+  let retained = PackageBuilder::new().file("foo", b"foo");
+
+  let retained_hash = retained.directory().cbor().1;
+
+  retained.upload(&server);
+
+  let mut subdirectory = Directory::new();
+  subdirectory.insert_file("baz", b"baz");
+
+  let (subdirectory_cbor, subdirectory_hash) = subdirectory.cbor();
+
+  let package = PackageBuilder::new()
+    .file("bar/baz", b"baz")
+    .file("foo", b"foo");
+
+  let (root_cbor, root_hash) = package.directory().cbor();
+
+  let fingerprint = package.upload(&server);
+
+  server.delete(format!("/api/package/{fingerprint}")).send();
+
+  server
+    .post("/api/gc")
+    .assert_body(
+      api::gc::Response {
+        bytes: root_cbor.len().into_u64() + subdirectory_cbor.len().into_u64() + 3,
+        directories: BTreeSet::from([root_hash, subdirectory_hash]).into(),
+        files: BTreeSet::from([root_hash, subdirectory_hash, Hash::bytes(b"baz")]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+
+  server.assert_file(retained_hash);
+  server.assert_file(Hash::bytes(b"foo"));
+
+  assert_eq!(
+    fs::read_dir(server.data_dir.join("files")).unwrap().count(),
+    2,
+  );
+}
+
+#[test]
+fn gc_removes_usage() {
+  #[track_caller]
+  fn case(server: &TestServer, bytes: u64, files: u64) {
+    server
+      .get("/api/usage")
+      .assert_body(
+        api::usage::Response {
+          keys: BTreeMap::new(),
+          total: Usage { bytes, files },
+        }
+        .encode_to_vec(),
+      )
+      .send();
+  }
+
+  let server = TestServer::new();
+
+  let hash = Hash::bytes(b"foo");
+
+  server.put(format!("/file/{hash}")).body("foo").send();
+
+  case(&server, 3, 1);
+
+  server
+    .post("/api/gc")
+    .assert_body(
+      api::gc::Response {
+        bytes: 3,
+        directories: BTreeSet::new().into(),
+        files: BTreeSet::from([hash]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+
+  case(&server, 0, 0);
+}
+
+#[test]
+fn get_directory_not_found() {
+  let server = TestServer::new();
+
+  let (cbor, hash) = Directory::new().cbor();
+  server.write_file(&cbor);
+
+  server
+    .get(format!("/directory/{hash}"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("directory {hash} not found"))
+    .send();
+}
+
+#[test]
+fn get_directory_succeeds() {
+  let server = TestServer::new();
+
+  let directory = Directory::new();
+  let (cbor, hash) = directory.cbor();
+  server.write_file(&cbor);
+
+  server.post(format!("/api/directory/{hash}")).send();
+
+  server
+    .get(format!("/directory/{hash}"))
+    .assert_page(DirectoryHtml { directory, hash })
+    .send();
+}
+
+#[test]
+fn get_package_not_found() {
+  let server = TestServer::new();
+
+  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
+
+  server
+    .get(format!("/package/{fingerprint}"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {fingerprint} not found"))
+    .send();
+}
+
+#[test]
+fn get_package_with_metadata() {
+  let server = TestServer::new();
+
+  let readme = b"foo";
+  server.write_file(readme);
+
+  let colophon = b"bar";
+  server.write_file(colophon);
+
+  let metadata = Metadata {
+    artwork: None,
+    creator: None,
+    description: None,
+    homepage: None,
+    language: None,
+    media: None,
+    package: Some(Package {
+      colophon: Some("COLOPHON.md".parse().unwrap()),
+      creator: None,
+      description: None,
+      homepage: None,
+      time: None,
+      title: None,
+    }),
+    readme: Some("README.md".parse().unwrap()),
+    time: None,
+    title: Some("foo".parse().unwrap()),
+  };
+  let metadata_cbor = metadata.encode_to_vec();
+  server.write_file(&metadata_cbor);
+
+  let mut directory = Directory::new();
+  directory
+    .insert_file("COLOPHON.md", colophon)
+    .insert_file("README.md", readme)
+    .insert_file(Metadata::CBOR_FILENAME, &metadata_cbor);
+  let (cbor, hash) = directory.cbor();
+  let fingerprint = Fingerprint(hash);
+  server.write_file(&cbor);
+
+  server.post(format!("/api/directory/{hash}")).send();
+  server.post(format!("/api/package/{fingerprint}")).send();
+
+  server
+    .get(format!("/package/{fingerprint}"))
+    .assert_page(PackageHtml {
+      colophon: Some(Hash::bytes(colophon)),
+      directory,
+      fingerprint,
+      metadata: Some(metadata),
+      mounted: false,
+      readme: Some(Hash::bytes(readme)),
+      signatures: BTreeSet::new(),
+      totals: Totals {
+        directories: 0,
+        directory_size: 0,
+        file_size: metadata_cbor.len().into_u64() + 6,
+        files: 3,
+      },
+    })
+    .send();
+}
+
+#[test]
+fn get_package_without_metadata() {
+  let server = TestServer::new();
+
+  let directory = Directory::new();
+  let (cbor, hash) = directory.cbor();
+  let fingerprint = Fingerprint(hash);
+  server.write_file(&cbor);
+
+  server.post(format!("/api/directory/{hash}")).send();
+  server.post(format!("/api/package/{fingerprint}")).send();
+
+  server
+    .get(format!("/package/{fingerprint}"))
+    .assert_page(PackageHtml {
+      colophon: None,
+      directory,
+      fingerprint,
+      metadata: None,
+      mounted: false,
+      readme: None,
+      signatures: BTreeSet::new(),
+      totals: Totals::default(),
+    })
+    .send();
+}
+
+#[test]
+fn home() {
+  TestServer::new()
+    .get("/")
+    .assert_static("index.html")
+    .send();
+}
+
+#[test]
+fn install_script() {
+  TestServer::new()
+    .get("/install.sh")
+    .assert_static("install.sh")
+    .send();
+}
+
+#[test]
+fn key_quota_rejects_bundle_body() {
+  let alice = PrivateKey::generate();
+
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(alice.public_key(), [Role::Upload].into())].into(),
+    })
+    .quotas(Quotas {
+      keys: [(
+        alice.public_key(),
+        Quota {
+          bytes: Some(64),
+          files: None,
+        },
+      )]
+      .into(),
+      ..default()
+    })
+    .build();
+
+  let package = PackageBuilder::new().file("foo", &[0; 1024]);
+
+  server
+    .put("/api/bundle")
+    .body(package.bundle())
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .status(StatusCode::INSUFFICIENT_STORAGE)
+    .assert_body(format!(
+      "upload exceeds quota of 64 bytes for key {}",
+      alice.public_key(),
+    ))
+    .send();
+
+  server.assert_incoming_empty();
+}
+
+#[test]
+fn key_quota_rejects_file_bytes() {
+  let alice = PrivateKey::generate();
+
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(alice.public_key(), [Role::Upload].into())].into(),
+    })
+    .quotas(Quotas {
+      keys: [(
+        alice.public_key(),
+        Quota {
+          bytes: Some(5),
+          files: None,
+        },
+      )]
+      .into(),
+      ..default()
+    })
+    .build();
+
+  server
+    .put(format!("/file/{}", Hash::bytes(b"foo")))
+    .body("foo")
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .send();
+
+  let hash = Hash::bytes(b"bar");
+
+  server
+    .put(format!("/file/{hash}"))
+    .body("bar")
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .status(StatusCode::INSUFFICIENT_STORAGE)
+    .assert_body(format!(
+      "upload exceeds quota of 5 bytes for key {}",
+      alice.public_key(),
+    ))
+    .send();
+
+  assert!(
+    !server
+      .data_dir
+      .join("files")
+      .join(hash.to_string())
+      .try_exists()
+      .unwrap()
+  );
+
+  server.assert_incoming_empty();
+}
+
+#[test]
+fn key_quota_rejects_file_files() {
+  let alice = PrivateKey::generate();
+
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(alice.public_key(), [Role::Upload].into())].into(),
+    })
+    .quotas(Quotas {
+      keys: [(
+        alice.public_key(),
+        Quota {
+          bytes: None,
+          files: Some(1),
+        },
+      )]
+      .into(),
+      ..default()
+    })
+    .build();
+
+  server
+    .put(format!("/file/{}", Hash::bytes(b"foo")))
+    .body("foo")
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .send();
+
+  let hash = Hash::bytes(b"bar");
+
+  server
+    .put(format!("/file/{hash}"))
+    .body("bar")
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .status(StatusCode::INSUFFICIENT_STORAGE)
+    .assert_body(format!(
+      "upload exceeds quota of 1 file for key {}",
+      alice.public_key(),
+    ))
+    .send();
+
+  assert!(
+    !server
+      .data_dir
+      .join("files")
+      .join(hash.to_string())
+      .try_exists()
+      .unwrap()
+  );
+
+  server.assert_incoming_empty();
+}
+
+#[test]
+fn key_quota_rejects_package() {
+  let alice = PrivateKey::generate();
+
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(alice.public_key(), [Role::Upload].into())].into(),
+    })
+    .quotas(Quotas {
+      keys: [(
+        alice.public_key(),
+        Quota {
+          bytes: None,
+          files: Some(1),
+        },
+      )]
+      .into(),
+      ..default()
+    })
+    .build();
+
+  let package = PackageBuilder::new().file("foo", b"foo");
+
+  server
+    .put("/api/bundle")
+    .body(package.bundle())
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .status(StatusCode::INSUFFICIENT_STORAGE)
+    .assert_body(format!(
+      "upload exceeds quota of 1 file for key {}",
+      alice.public_key(),
+    ))
+    .send();
+
+  server
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::new().into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+}
+
+#[test]
+fn malformed_fingerprint_returns_error() {
+  TestServer::new()
+    .get("/package1invalid")
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body("failed to decode bech32 package fingerprint")
+    .send();
+}
+
+#[test]
+fn media_audio_item_file_missing() {
+  let server = TestServer::new();
+
+  let metadata = Metadata {
+    media: Some(Media::Audio {
+      items: tracks(&["foo.flac"]),
+    }),
+    ..default()
+  };
+
+  let fingerprint = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("foo.flac", b"foo")
+    .upload(&server);
+
+  let metadata_cbor = metadata.encode_to_vec();
+
+  let (cbor, _hash) = Directory::new()
+    .insert_file(Metadata::CBOR_FILENAME, &metadata_cbor)
+    .cbor();
+
+  let hash = Hash::from(fingerprint);
+  fs::write(server.data_dir.join("files").join(hash.to_string()), &cbor).unwrap();
+
+  server
+    .get(format!("/media/audio/{fingerprint}/item/1"))
+    .status(StatusCode::INTERNAL_SERVER_ERROR)
+    .assert_body(format!(
+      "file `foo.flac` missing from package {fingerprint}"
+    ))
+    .send();
+}
+
+#[test]
+fn media_audio_item_out_of_range() {
+  let server = TestServer::new();
+
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Audio {
+        items: tracks(&["foo.flac", "bar.flac"]),
+      }),
+      ..default()
+    })
+    .file("foo.flac", b"foo")
+    .file("bar.flac", b"bar")
+    .upload(&server);
+
+  server
+    .get(format!("/media/audio/{fingerprint}/item/3"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!(
+      "track 3 does not exist, package {fingerprint} has 2 tracks"
+    ))
+    .send();
+
+  server
+    .get(format!("/media/audio/{fingerprint}/item/0"))
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body(
+      "Invalid URL: Cannot parse `item` with value `0`: number would be zero for non-zero type",
+    )
+    .send();
+}
+
+#[test]
+fn media_audio_item_package_not_found() {
+  let server = TestServer::new();
+
+  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
+
+  server
+    .get(format!("/media/audio/{fingerprint}/item/1"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {fingerprint} not found"))
+    .send();
+}
+
+#[test]
+fn media_audio_item_package_without_media() {
+  let server = TestServer::new();
+
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      title: Some("foo".parse().unwrap()),
+      ..default()
+    })
+    .upload(&server);
+
+  server
+    .get(format!("/media/audio/{fingerprint}/item/1"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!(
+      "package {fingerprint} does not have media metadata"
+    ))
+    .send();
+}
+
+#[test]
+fn media_audio_item_package_without_metadata() {
+  let server = TestServer::new();
+
+  let (cbor, hash) = Directory::new().cbor();
+  let fingerprint = Fingerprint(hash);
+  server.write_file(&cbor);
+
+  server.post(format!("/api/directory/{hash}")).send();
+  server.post(format!("/api/package/{fingerprint}")).send();
+
+  server
+    .get(format!("/media/audio/{fingerprint}/item/1"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {fingerprint} does not have metadata"))
+    .send();
+}
+
+#[test]
+fn media_audio_item_ranges() {
+  #[track_caller]
+  fn case(
+    server: &TestServer,
+    fingerprint: Fingerprint,
+    range: &'static str,
+    status: StatusCode,
+    content_range: &str,
+    body: &[u8],
+  ) {
+    let request = server
+      .get(format!("/media/audio/{fingerprint}/item/1"))
+      .range(range)
+      .status(status)
+      .assert_header(header::ACCEPT_RANGES, "bytes")
+      .assert_header(header::CONTENT_RANGE, content_range)
+      .assert_header(header::CONTENT_LENGTH, body.len().to_string())
+      .assert_body(body);
+
+    if status == StatusCode::RANGE_NOT_SATISFIABLE {
+      request.assert_header_absent(header::CACHE_CONTROL).send();
+    } else {
+      request
+        .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+        .send();
+    }
+  }
+
+  let server = TestServer::new();
+
+  let audio: &[u8] = b"foobarbaz";
 
   let fingerprint = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("foo.flac", b"foo")
+    .metadata(&Metadata {
+      media: Some(Media::Audio {
+        items: tracks(&["foo.flac"]),
+      }),
+      ..default()
+    })
+    .file("foo.flac", audio)
     .upload(&server);
 
-  let metadata_cbor = metadata.encode_to_vec();
+  case(
+    &server,
+    fingerprint,
+    "bytes=2-5",
+    StatusCode::PARTIAL_CONTENT,
+    "bytes 2-5/9",
+    b"obar",
+  );
 
-  let (cbor, _hash) = Directory::new()
-    .insert_file(Metadata::CBOR_FILENAME, &metadata_cbor)
-    .cbor();
+  case(
+    &server,
+    fingerprint,
+    "bytes=3-",
+    StatusCode::PARTIAL_CONTENT,
+    "bytes 3-8/9",
+    b"barbaz",
+  );
 
-  let hash = Hash::from(fingerprint);
-  fs::write(server.data_dir.join("files").join(hash.to_string()), &cbor).unwrap();
+  case(
+    &server,
+    fingerprint,
+    "bytes=-3",
+    StatusCode::PARTIAL_CONTENT,
+    "bytes 6-8/9",
+    b"baz",
+  );
+
+  case(
+    &server,
+    fingerprint,
+    "bytes=100-200",
+    StatusCode::RANGE_NOT_SATISFIABLE,
+    "bytes */9",
+    b"",
+  );
+}
+
+#[test]
+fn media_audio_item_response() {
+  let server = TestServer::new();
+
+  let foo: &[u8] = b"foo";
+  let bar: &[u8] = b"barbar";
+
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Audio {
+        items: tracks(&["foo.flac", "bar.mp3"]),
+      }),
+      ..default()
+    })
+    .file("foo.flac", foo)
+    .file("bar.mp3", bar)
+    .upload(&server);
+
+  server
+    .get(format!("/media/audio/{fingerprint}/item/1"))
+    .assert_header(header::ACCEPT_RANGES, "bytes")
+    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+    .assert_header(header::CONTENT_LENGTH, "3")
+    .assert_header(header::CONTENT_TYPE, "audio/flac")
+    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(foo)))
+    .assert_body(foo)
+    .send();
+
+  server
+    .get(format!("/media/audio/{fingerprint}/item/2"))
+    .assert_header(header::CONTENT_LENGTH, "6")
+    .assert_header(header::CONTENT_TYPE, "audio/mpeg")
+    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(bar)))
+    .assert_body(bar)
+    .send();
+}
+
+#[test]
+fn media_image_item_out_of_range() {
+  let server = TestServer::new();
+
+  let foo: &[u8] = b"foo";
+
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Image {
+        items: vec!["foo.png".parse().unwrap()],
+      }),
+      ..default()
+    })
+    .file("foo.png", foo)
+    .upload(&server);
+
+  server
+    .get(format!("/media/image/{fingerprint}/item/2"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!(
+      "image 2 does not exist, package {fingerprint} has 1 image"
+    ))
+    .send();
+}
+
+#[test]
+fn media_image_item_response() {
+  let server = TestServer::new();
+
+  let foo: &[u8] = b"foo";
+  let bar: &[u8] = b"barbar";
+
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Image {
+        items: vec!["foo.png".parse().unwrap(), "bar.jpg".parse().unwrap()],
+      }),
+      ..default()
+    })
+    .file("foo.png", foo)
+    .file("bar.jpg", bar)
+    .upload(&server);
+
+  server
+    .get(format!("/media/image/{fingerprint}/item/1"))
+    .assert_header(header::ACCEPT_RANGES, "bytes")
+    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+    .assert_header(header::CONTENT_LENGTH, "3")
+    .assert_header(header::CONTENT_SECURITY_POLICY, "sandbox")
+    .assert_header(header::CONTENT_TYPE, "image/png")
+    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(foo)))
+    .assert_body(foo)
+    .send();
+
+  server
+    .get(format!("/media/image/{fingerprint}/item/2"))
+    .assert_header(header::CONTENT_LENGTH, "6")
+    .assert_header(header::CONTENT_TYPE, "image/jpeg")
+    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(bar)))
+    .assert_body(bar)
+    .send();
+}
+
+#[test]
+fn media_type_mismatch() {
+  #[track_caller]
+  fn case(server: &TestServer, path: String, body: String) {
+    server
+      .get(path)
+      .status(StatusCode::NOT_FOUND)
+      .assert_body(body)
+      .send();
+  }
+
+  let server = TestServer::new();
+
+  let foo: &[u8] = b"foo";
+
+  let audio = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Audio {
+        items: tracks(&["foo.flac"]),
+      }),
+      ..default()
+    })
+    .file("foo.flac", foo)
+    .upload(&server);
+
+  let image = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Image {
+        items: vec!["foo.png".parse().unwrap()],
+      }),
+      ..default()
+    })
+    .file("foo.png", foo)
+    .upload(&server);
+
+  case(
+    &server,
+    format!("/media/image/{audio}/item/1"),
+    format!("expected media type image but package {audio} is audio"),
+  );
+
+  case(
+    &server,
+    format!("/media/audio/{image}/item/1"),
+    format!("expected media type audio but package {image} is image"),
+  );
+}
+
+#[test]
+fn media_video_item_out_of_range() {
+  let server = TestServer::new();
+
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Video {
+        items: vec!["foo.mp4".parse().unwrap()],
+      }),
+      ..default()
+    })
+    .file("foo.mp4", b"foo")
+    .upload(&server);
 
   server
-    .get(format!("/media/audio/{fingerprint}/item/1"))
-    .status(StatusCode::INTERNAL_SERVER_ERROR)
+    .get(format!("/media/video/{fingerprint}/item/2"))
+    .status(StatusCode::NOT_FOUND)
     .assert_body(format!(
-      "file `foo.flac` missing from package {fingerprint}"
+      "video 2 does not exist, package {fingerprint} has 1 video"
     ))
     .send();
 }
 
 #[test]
-fn media_audio_item_out_of_range() {
+fn media_video_item_response() {
   let server = TestServer::new();
 
   let fingerprint = PackageBuilder::new()
     .metadata(&Metadata {
-      media: Some(Media::Audio {
-        items: tracks(&["foo.flac", "bar.flac"]),
+      media: Some(Media::Video {
+        items: vec!["foo.mp4".parse().unwrap(), "bar.mp4".parse().unwrap()],
       }),
       ..default()
     })
-    .file("foo.flac", b"foo")
-    .file("bar.flac", b"bar")
+    .file("foo.mp4", b"foo")
+    .file("bar.mp4", b"bar")
     .upload(&server);
 
   server
-    .get(format!("/media/audio/{fingerprint}/item/3"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!(
-      "track 3 does not exist, package {fingerprint} has 2 tracks"
-    ))
+    .get(format!("/media/video/{fingerprint}/item/1"))
+    .assert_header(header::ACCEPT_RANGES, "bytes")
+    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+    .assert_header(header::CONTENT_LENGTH, "3")
+    .assert_header(header::CONTENT_TYPE, "video/mp4")
+    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(b"foo")))
+    .assert_body(b"foo")
     .send();
 
   server
-    .get(format!("/media/audio/{fingerprint}/item/0"))
-    .status(StatusCode::BAD_REQUEST)
-    .assert_body(
-      "Invalid URL: Cannot parse `item` with value `0`: number would be zero for non-zero type",
-    )
+    .get(format!("/media/video/{fingerprint}/item/2"))
+    .assert_header(header::CONTENT_LENGTH, "3")
+    .assert_header(header::CONTENT_TYPE, "video/mp4")
+    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(b"bar")))
+    .assert_body(b"bar")
     .send();
 }
 
 #[test]
-fn media_audio_item_package_not_found() {
+fn media_webm_item_response() {
   let server = TestServer::new();
 
-  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Video {
+        items: vec!["foo.webm".parse().unwrap()],
+      }),
+      ..default()
+    })
+    .file("foo.webm", b"foo")
+    .upload(&server);
 
   server
-    .get(format!("/media/audio/{fingerprint}/item/1"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!("package {fingerprint} not found"))
+    .get(format!("/media/video/{fingerprint}/item/1"))
+    .assert_header(header::CONTENT_TYPE, "video/webm")
+    .assert_body(b"foo")
     .send();
 }
 
 #[test]
-fn media_audio_item_package_without_media() {
+fn missing_rejects_unsorted_hashes() {
+  let mut hashes = BTreeSet::from([Hash::bytes(b"foo"), Hash::bytes(b"bar")])
+    .into_iter()
+    .collect::<Vec<_>>();
+
+  hashes.reverse();
+
+  let mut encoder = Encoder::new();
+  let mut map = encoder.map::<u64>(1);
+  map.item(0, hashes);
+  drop(map);
+
+  TestServer::new()
+    .post("/api/missing")
+    .body(encoder.finish())
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body("failed to decode request body")
+    .send();
+}
+
+#[test]
+fn missing_returns_missing_hashes() {
   let server = TestServer::new();
 
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      title: Some("foo".parse().unwrap()),
-      ..default()
-    })
-    .upload(&server);
+  let present = Hash::bytes(b"bar");
+  let absent = Hash::bytes(b"baz");
+
+  server.write_file(b"bar");
 
   server
-    .get(format!("/media/audio/{fingerprint}/item/1"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!(
-      "package {fingerprint} does not have media metadata"
-    ))
+    .post("/api/missing")
+    .body(
+      api::missing::Request {
+        hashes: BTreeSet::from([present, absent]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .assert_body(
+      api::missing::Response {
+        hashes: BTreeSet::from([absent]).into(),
+      }
+      .encode_to_vec(),
+    )
     .send();
 }
 
 #[test]
-fn media_audio_item_package_without_metadata() {
-  let server = TestServer::new();
+fn mount_file() {
+  let metadata = Metadata {
+    media: Some(Media::Web),
+    ..default()
+  };
 
-  let (cbor, hash) = Directory::new().cbor();
-  let fingerprint = Fingerprint(hash);
-  server.write_file(&cbor);
+  let package = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("static/foo.css", b"bar")
+    .file("static/index.html", b"foo");
 
-  server.post(format!("/api/directory/{hash}")).send();
-  server.post(format!("/api/package/{fingerprint}")).send();
+  let server = TestServer::builder().mount(package.fingerprint()).build();
+
+  let fingerprint = package.upload(&server);
 
   server
-    .get(format!("/media/audio/{fingerprint}/item/1"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!("package {fingerprint} does not have metadata"))
+    .get(format!("/mount/{fingerprint}/foo.css"))
+    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+    .assert_header(header::CONTENT_TYPE, "text/css")
+    .assert_header_absent(header::CONTENT_SECURITY_POLICY)
+    .assert_header_absent(header::CONTENT_DISPOSITION)
+    .assert_body("bar")
     .send();
 }
 
 #[test]
-fn media_audio_item_ranges() {
-  #[track_caller]
-  fn case(
-    server: &TestServer,
-    fingerprint: Fingerprint,
-    range: &'static str,
-    status: StatusCode,
-    content_range: &str,
-    body: &[u8],
-  ) {
-    let request = server
-      .get(format!("/media/audio/{fingerprint}/item/1"))
-      .range(range)
-      .status(status)
-      .assert_header(header::ACCEPT_RANGES, "bytes")
-      .assert_header(header::CONTENT_RANGE, content_range)
-      .assert_header(header::CONTENT_LENGTH, body.len().to_string())
-      .assert_body(body);
+fn mount_file_invalid_path() {
+  let metadata = Metadata {
+    media: Some(Media::Web),
+    ..default()
+  };
 
-    if status == StatusCode::RANGE_NOT_SATISFIABLE {
-      request.assert_header_absent(header::CACHE_CONTROL).send();
-    } else {
-      request
-        .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
-        .send();
-    }
-  }
+  let package = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("static/index.html", b"foo");
 
-  let server = TestServer::new();
+  let server = TestServer::builder().mount(package.fingerprint()).build();
 
-  let audio: &[u8] = b"foobarbaz";
+  let fingerprint = package.upload(&server);
 
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Audio {
-        items: tracks(&["foo.flac"]),
-      }),
-      ..default()
-    })
-    .file("foo.flac", audio)
-    .upload(&server);
+  server
+    .get(format!("/mount/{fingerprint}/%2e%2e/foo"))
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body(
+      "Invalid URL: Cannot parse `path` with value `../foo`: path contains invalid component `..`",
+    )
+    .send();
+}
 
-  case(
-    &server,
-    fingerprint,
-    "bytes=2-5",
-    StatusCode::PARTIAL_CONTENT,
-    "bytes 2-5/9",
-    b"obar",
-  );
+#[test]
+fn mount_file_nested() {
+  let metadata = Metadata {
+    media: Some(Media::Web),
+    ..default()
+  };
 
-  case(
-    &server,
-    fingerprint,
-    "bytes=3-",
-    StatusCode::PARTIAL_CONTENT,
-    "bytes 3-8/9",
-    b"barbaz",
-  );
+  let package = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("static/index.html", b"foo")
+    .file("static/foo/bar.txt", b"baz");
 
-  case(
-    &server,
-    fingerprint,
-    "bytes=-3",
-    StatusCode::PARTIAL_CONTENT,
-    "bytes 6-8/9",
-    b"baz",
-  );
+  let server = TestServer::builder().mount(package.fingerprint()).build();
 
-  case(
-    &server,
-    fingerprint,
-    "bytes=100-200",
-    StatusCode::RANGE_NOT_SATISFIABLE,
-    "bytes */9",
-    b"",
-  );
+  let fingerprint = package.upload(&server);
+
+  server
+    .get(format!("/mount/{fingerprint}/foo/bar.txt"))
+    .assert_header(header::CONTENT_TYPE, "text/plain")
+    .assert_body("baz")
+    .send();
+}
+
+#[test]
+fn mount_file_not_found() {
+  let metadata = Metadata {
+    media: Some(Media::Web),
+    ..default()
+  };
+
+  let package = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("static/index.html", b"foo");
+
+  let server = TestServer::builder().mount(package.fingerprint()).build();
+
+  let fingerprint = package.upload(&server);
+
+  server
+    .get(format!("/mount/{fingerprint}/foo"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!(
+      "file `static/foo` not found in package {fingerprint}"
+    ))
+    .send();
 }
 
 #[test]
-fn media_audio_item_response() {
+fn mount_file_not_mounted() {
   let server = TestServer::new();
 
-  let foo: &[u8] = b"foo";
-  let bar: &[u8] = b"barbar";
-
   let fingerprint = PackageBuilder::new()
     .metadata(&Metadata {
-      media: Some(Media::Audio {
-        items: tracks(&["foo.flac", "bar.mp3"]),
-      }),
+      media: Some(Media::Web),
       ..default()
     })
-    .file("foo.flac", foo)
-    .file("bar.mp3", bar)
+    .file("static/index.html", b"foo")
     .upload(&server);
 
   server
-    .get(format!("/media/audio/{fingerprint}/item/1"))
-    .assert_header(header::ACCEPT_RANGES, "bytes")
-    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
-    .assert_header(header::CONTENT_LENGTH, "3")
-    .assert_header(header::CONTENT_TYPE, "audio/flac")
-    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(foo)))
-    .assert_body(foo)
-    .send();
-
-  server
-    .get(format!("/media/audio/{fingerprint}/item/2"))
-    .assert_header(header::CONTENT_LENGTH, "6")
-    .assert_header(header::CONTENT_TYPE, "audio/mpeg")
-    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(bar)))
-    .assert_body(bar)
+    .get(format!("/mount/{fingerprint}/index.html"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {fingerprint} not mounted"))
     .send();
 }
 
 #[test]
-fn media_image_item_out_of_range() {
-  let server = TestServer::new();
+fn mount_file_symlink() {
+  let metadata = Metadata {
+    media: Some(Media::Web),
+    ..default()
+  };
 
-  let foo: &[u8] = b"foo";
+  let package = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("static/index.html", b"foo")
+    .file("static/foo/bar baz.txt", b"bar")
+    .file("secret.txt", b"baz")
+    .symlink("static/qux.html", "static/index.html")
+    .symlink("static/quux", "static/foo")
+    .symlink("static/secret.txt", "secret.txt");
 
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Image {
-        items: vec!["foo.png".parse().unwrap()],
-      }),
-      ..default()
-    })
-    .file("foo.png", foo)
-    .upload(&server);
+  let server = TestServer::builder().mount(package.fingerprint()).build();
+
+  let fingerprint = package.upload(&server);
 
   server
-    .get(format!("/media/image/{fingerprint}/item/2"))
+    .get(format!("/mount/{fingerprint}/qux.html"))
+    .status(StatusCode::TEMPORARY_REDIRECT)
+    .assert_header(header::LOCATION, format!("/mount/{fingerprint}/index.html"))
+    .send();
+
+  server
+    .get(format!("/mount/{fingerprint}/quux/bar%20baz.txt"))
+    .status(StatusCode::TEMPORARY_REDIRECT)
+    .assert_header(
+      header::LOCATION,
+      format!("/mount/{fingerprint}/foo/bar%20baz.txt"),
+    )
+    .send();
+
+  server
+    .get(format!("/mount/{fingerprint}/secret.txt"))
     .status(StatusCode::NOT_FOUND)
     .assert_body(format!(
-      "image 2 does not exist, package {fingerprint} has 1 image"
+      "file `static/secret.txt` not found in package {fingerprint}"
     ))
     .send();
 }
 
 #[test]
-fn media_image_item_response() {
-  let server = TestServer::new();
+fn mount_redirect() {
+  TestServer::new()
+    .get(format!("/mount/{}", test::FINGERPRINT))
+    .status(StatusCode::PERMANENT_REDIRECT)
+    .assert_header(header::LOCATION, format!("/mount/{}/", test::FINGERPRINT))
+    .send();
+}
 
-  let foo: &[u8] = b"foo";
-  let bar: &[u8] = b"barbar";
+#[test]
+fn mount_serves_index_html() {
+  let metadata = Metadata {
+    media: Some(Media::Web),
+    ..default()
+  };
 
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Image {
-        items: vec!["foo.png".parse().unwrap(), "bar.jpg".parse().unwrap()],
-      }),
-      ..default()
-    })
-    .file("foo.png", foo)
-    .file("bar.jpg", bar)
-    .upload(&server);
+  let package = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("static/index.html", b"foo");
+
+  let server = TestServer::builder().mount(package.fingerprint()).build();
+
+  let fingerprint = package.upload(&server);
 
   server
-    .get(format!("/media/image/{fingerprint}/item/1"))
-    .assert_header(header::ACCEPT_RANGES, "bytes")
+    .get(format!("/mount/{fingerprint}/"))
     .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
-    .assert_header(header::CONTENT_LENGTH, "3")
-    .assert_header(header::CONTENT_SECURITY_POLICY, "sandbox")
-    .assert_header(header::CONTENT_TYPE, "image/png")
-    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(foo)))
-    .assert_body(foo)
+    .assert_header(header::CONTENT_TYPE, "text/html")
+    .assert_header_absent(header::CONTENT_SECURITY_POLICY)
+    .assert_header_absent(header::CONTENT_DISPOSITION)
+    .assert_body("foo")
     .send();
+}
+
+#[test]
+fn name_redirects_to_package() {
+  let server = TestServer::new();
+
+  let private_key = PrivateKey::generate();
+
+  let record = NameRecord::new(
+    &private_key,
+    "latest".parse().unwrap(),
+    test::FINGERPRINT.parse().unwrap(),
+    1,
+  );
+
+  server.post("/api/name").body(record.encode_to_vec()).send();
 
   server
-    .get(format!("/media/image/{fingerprint}/item/2"))
-    .assert_header(header::CONTENT_LENGTH, "6")
-    .assert_header(header::CONTENT_TYPE, "image/jpeg")
-    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(bar)))
-    .assert_body(bar)
+    .get(format!("/name/{}/latest", private_key.public_key()))
+    .status(StatusCode::TEMPORARY_REDIRECT)
+    .assert_header(header::LOCATION, format!("/package/{}", test::FINGERPRINT))
     .send();
 }
 
 #[test]
-fn media_type_mismatch() {
-  #[track_caller]
-  fn case(server: &TestServer, path: String, body: String) {
-    server
-      .get(path)
-      .status(StatusCode::NOT_FOUND)
-      .assert_body(body)
-      .send();
-  }
+fn non_fingerprint_bech32_falls_through() {
+  TestServer::new()
+    .get(format!("/{}", test::PUBLIC_KEY))
+    .assert_static("404.html")
+    .status(StatusCode::NOT_FOUND)
+    .send();
+}
+
+#[test]
+fn package_archive() {
+  let package = PackageBuilder::new()
+    .file("foo/bar.txt", b"bar")
+    .file("baz.txt", b"baz")
+    .symlink("qux", "foo/bar.txt");
 
   let server = TestServer::new();
 
-  let foo: &[u8] = b"foo";
+  let fingerprint = package.upload(&server);
 
-  let audio = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Audio {
-        items: tracks(&["foo.flac"]),
-      }),
-      ..default()
-    })
-    .file("foo.flac", foo)
-    .upload(&server);
+  let file = |content: &[u8]| File {
+    executable: false,
+    hash: Hash::bytes(content),
+    size: content.len().into_u64(),
+  };
 
-  let image = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Image {
-        items: vec!["foo.png".parse().unwrap()],
-      }),
-      ..default()
-    })
-    .file("foo.png", foo)
-    .upload(&server);
+  let symlink = Symlink {
+    target: "foo/bar.txt".parse().unwrap(),
+  };
 
-  case(
-    &server,
-    format!("/media/image/{audio}/item/1"),
-    format!("expected media type image but package {audio} is audio"),
-  );
+  let mut manifest = Manifest {
+    embedded: BTreeMap::new(),
+    package: DirectoryTree::new(),
+    signatures: BTreeSet::new(),
+  };
 
-  case(
-    &server,
-    format!("/media/audio/{image}/item/1"),
-    format!("expected media type audio but package {image} is image"),
-  );
+  manifest
+    .package
+    .create_file(&"foo/bar.txt".parse().unwrap(), file(b"bar"))
+    .unwrap();
+
+  manifest
+    .package
+    .create_file(&"baz.txt".parse().unwrap(), file(b"baz"))
+    .unwrap();
+
+  manifest
+    .package
+    .create_symlink(&"qux".parse().unwrap(), symlink.clone())
+    .unwrap();
+
+  assert_eq!(manifest.fingerprint(), fingerprint);
+
+  let mut exporter = Exporter::new(ExportFormat::Tar, Vec::new(), &manifest).unwrap();
+
+  exporter
+    .file(&"baz.txt".parse().unwrap(), file(b"baz"), b"baz".as_slice())
+    .unwrap();
+
+  exporter.directory(&"foo".parse().unwrap()).unwrap();
+
+  exporter
+    .file(
+      &"foo/bar.txt".parse().unwrap(),
+      file(b"bar"),
+      b"bar".as_slice(),
+    )
+    .unwrap();
+
+  exporter.symlink(&"qux".parse().unwrap(), &symlink).unwrap();
+
+  server
+    .get(format!("/package/{fingerprint}.tar"))
+    .assert_header(header::CONTENT_TYPE, "application/x-tar")
+    .assert_header(
+      header::CONTENT_DISPOSITION,
+      format!("attachment; filename=\"{fingerprint}.tar\""),
+    )
+    .assert_body(exporter.finish().unwrap())
+    .send();
 }
 
 #[test]
-fn media_video_item_out_of_range() {
+fn package_archive_package_not_found() {
   let server = TestServer::new();
 
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Video {
-        items: vec!["foo.mp4".parse().unwrap()],
-      }),
-      ..default()
-    })
-    .file("foo.mp4", b"foo")
-    .upload(&server);
+  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
 
   server
-    .get(format!("/media/video/{fingerprint}/item/2"))
+    .get(format!("/package/{fingerprint}.tar"))
     .status(StatusCode::NOT_FOUND)
-    .assert_body(format!(
-      "video 2 does not exist, package {fingerprint} has 1 video"
-    ))
+    .assert_body(format!("package {fingerprint} not found"))
     .send();
 }
 
 #[test]
-fn media_video_item_response() {
+fn package_item_audio() {
   let server = TestServer::new();
 
+  let metadata = Metadata {
+    media: Some(Media::Audio {
+      items: tracks(&["foo.flac"]),
+    }),
+    ..default()
+  };
+
   let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Video {
-        items: vec!["foo.mp4".parse().unwrap(), "bar.mp4".parse().unwrap()],
-      }),
-      ..default()
-    })
-    .file("foo.mp4", b"foo")
-    .file("bar.mp4", b"bar")
+    .metadata(&metadata)
+    .file("foo.flac", b"foo")
     .upload(&server);
-
-  server
-    .get(format!("/media/video/{fingerprint}/item/1"))
-    .assert_header(header::ACCEPT_RANGES, "bytes")
-    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
-    .assert_header(header::CONTENT_LENGTH, "3")
-    .assert_header(header::CONTENT_TYPE, "video/mp4")
-    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(b"foo")))
-    .assert_body(b"foo")
-    .send();
-
-  server
-    .get(format!("/media/video/{fingerprint}/item/2"))
-    .assert_header(header::CONTENT_LENGTH, "3")
-    .assert_header(header::CONTENT_TYPE, "video/mp4")
-    .assert_header(header::ETAG, format!("\"{}\"", Hash::bytes(b"bar")))
-    .assert_body(b"bar")
+
+  server
+    .get(format!("/package/{fingerprint}/item/1"))
+    .assert_page(AudioHtml {
+      audio: 0,
+      fingerprint,
+      metadata,
+    })
     .send();
 }
 
 #[test]
-fn media_webm_item_response() {
+fn package_item_audio_out_of_range() {
   let server = TestServer::new();
 
   let fingerprint = PackageBuilder::new()
     .metadata(&Metadata {
-      media: Some(Media::Video {
-        items: vec!["foo.webm".parse().unwrap()],
+      media: Some(Media::Audio {
+        items: tracks(&["foo.flac"]),
       }),
       ..default()
     })
-    .file("foo.webm", b"foo")
+    .file("foo.flac", b"foo")
     .upload(&server);
 
   server
-    .get(format!("/media/video/{fingerprint}/item/1"))
-    .assert_header(header::CONTENT_TYPE, "video/webm")
-    .assert_body(b"foo")
+    .get(format!("/package/{fingerprint}/item/2"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!(
+      "track 2 does not exist, package {fingerprint} has 1 track"
+    ))
     .send();
 }
 
 #[test]
-fn missing_rejects_unsorted_hashes() {
-  let mut hashes = BTreeSet::from([Hash::bytes(b"foo"), Hash::bytes(b"bar")])
-    .into_iter()
-    .collect::<Vec<_>>();
+fn package_item_image() {
+  let server = TestServer::new();
 
-  hashes.reverse();
+  let metadata = Metadata {
+    media: Some(Media::Image {
+      items: vec![Image {
+        alpha: false,
+        bit_depth: 8,
+        chroma_subsampling: None,
+        color_type: ColorType::Rgb,
+        dimensions: Dimensions {
+          height: 1,
+          width: 2,
+        },
+        orientation: Orientation::new(),
+        path: "foo.png".parse().unwrap(),
+        ty: ImageType::Png,
+      }],
+    }),
+    ..default()
+  };
 
-  let mut encoder = Encoder::new();
-  let mut map = encoder.map::<u64>(1);
-  map.item(0, hashes);
-  drop(map);
+  let fingerprint = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("foo.png", b"foo")
+    .upload(&server);
 
-  TestServer::new()
-    .post("/api/missing")
-    .body(encoder.finish())
-    .status(StatusCode::BAD_REQUEST)
-    .assert_body("failed to decode request body")
+  server
+    .get(format!("/package/{fingerprint}/item/1"))
+    .assert_page(ImageHtml {
+      fingerprint,
+      image: 0,
+      metadata,
+    })
     .send();
 }
 
 #[test]
-fn missing_returns_missing_hashes() {
+fn package_item_image_out_of_range() {
   let server = TestServer::new();
 
-  let present = Hash::bytes(b"bar");
-  let absent = Hash::bytes(b"baz");
-
-  server.write_file(b"bar");
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Image {
+        items: vec![Image {
+          alpha: false,
+          bit_depth: 8,
+          chroma_subsampling: None,
+          color_type: ColorType::Rgb,
+          dimensions: Dimensions {
+            height: 1,
+            width: 1,
+          },
+          orientation: Orientation::new(),
+          path: "foo.png".parse().unwrap(),
+          ty: ImageType::Png,
+        }],
+      }),
+      ..default()
+    })
+    .file("foo.png", b"foo")
+    .upload(&server);
 
   server
-    .post("/api/missing")
-    .body(
-      api::missing::Request {
-        hashes: BTreeSet::from([present, absent]).into(),
-      }
-      .encode_to_vec(),
-    )
-    .assert_body(
-      api::missing::Response {
-        hashes: BTreeSet::from([absent]).into(),
-      }
-      .encode_to_vec(),
-    )
+    .get(format!("/package/{fingerprint}/item/2"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!(
+      "image 2 does not exist, package {fingerprint} has 1 image"
+    ))
     .send();
 }
 
 #[test]
-fn mount_file() {
-  let metadata = Metadata {
-    media: Some(Media::Web),
-    ..default()
-  };
-
-  let package = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("static/foo.css", b"bar")
-    .file("static/index.html", b"foo");
-
-  let server = TestServer::builder().mount(package.fingerprint()).build();
+fn package_item_package_not_found() {
+  let server = TestServer::new();
 
-  let fingerprint = package.upload(&server);
+  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
 
   server
-    .get(format!("/mount/{fingerprint}/foo.css"))
-    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
-    .assert_header(header::CONTENT_TYPE, "text/css")
-    .assert_header_absent(header::CONTENT_SECURITY_POLICY)
-    .assert_header_absent(header::CONTENT_DISPOSITION)
-    .assert_body("bar")
+    .get(format!("/package/{fingerprint}/item/1"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {fingerprint} not found"))
     .send();
 }
 
 #[test]
-fn mount_file_invalid_path() {
+fn package_item_video() {
+  let server = TestServer::new();
+
   let metadata = Metadata {
-    media: Some(Media::Web),
+    media: Some(Media::Video {
+      items: vec![Video {
+        duration: 0,
+        path: "foo.mp4".parse().unwrap(),
+        tracks: vec![
+          Track {
+            codec: Codec::H264,
+            info: TrackInfo::Video {
+              bit_depth: 8,
+              chroma_subsampling: ChromaSubsampling::Yuv420,
+              dimensions: Dimensions {
+                height: 1,
+                width: 2,
+              },
+              frames: 0,
+              orientation: Orientation::new(),
+            },
+            size: 0,
+          },
+          Track {
+            codec: Codec::Aac,
+            info: TrackInfo::Audio {
+              channels: 2,
+              sample_rate: 44100,
+            },
+            size: 0,
+          },
+        ],
+        ty: VideoType::Mp4,
+      }],
+    }),
     ..default()
   };
 
-  let package = PackageBuilder::new()
+  let fingerprint = PackageBuilder::new()
     .metadata(&metadata)
-    .file("static/index.html", b"foo");
-
-  let server = TestServer::builder().mount(package.fingerprint()).build();
-
-  let fingerprint = package.upload(&server);
+    .file("foo.mp4", b"foo")
+    .upload(&server);
 
   server
-    .get(format!("/mount/{fingerprint}/%2e%2e/foo"))
-    .status(StatusCode::BAD_REQUEST)
-    .assert_body(
-      "Invalid URL: Cannot parse `path` with value `../foo`: path contains invalid component `..`",
-    )
+    .get(format!("/package/{fingerprint}/item/1"))
+    .assert_page(VideoHtml {
+      fingerprint,
+      metadata,
+      video: 0,
+    })
     .send();
 }
 
 #[test]
-fn mount_file_nested() {
-  let metadata = Metadata {
-    media: Some(Media::Web),
-    ..default()
-  };
-
-  let package = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("static/index.html", b"foo")
-    .file("static/foo/bar.txt", b"baz");
-
-  let server = TestServer::builder().mount(package.fingerprint()).build();
+fn package_item_video_out_of_range() {
+  let server = TestServer::new();
 
-  let fingerprint = package.upload(&server);
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Video {
+        items: vec!["foo.mp4".parse().unwrap()],
+      }),
+      ..default()
+    })
+    .file("foo.mp4", b"foo")
+    .upload(&server);
 
   server
-    .get(format!("/mount/{fingerprint}/foo/bar.txt"))
-    .assert_header(header::CONTENT_TYPE, "text/plain")
-    .assert_body("baz")
+    .get(format!("/package/{fingerprint}/item/2"))
+    .status(StatusCode::NOT_FOUND)
+    .assert_body(format!(
+      "video 2 does not exist, package {fingerprint} has 1 video"
+    ))
     .send();
 }
 
 #[test]
-fn mount_file_not_found() {
-  let metadata = Metadata {
-    media: Some(Media::Web),
-    ..default()
-  };
-
-  let package = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("static/index.html", b"foo");
-
-  let server = TestServer::builder().mount(package.fingerprint()).build();
+fn package_item_web() {
+  let server = TestServer::new();
 
-  let fingerprint = package.upload(&server);
+  let fingerprint = PackageBuilder::new()
+    .metadata(&Metadata {
+      media: Some(Media::Web),
+      ..default()
+    })
+    .file("static/index.html", b"foo")
+    .upload(&server);
 
   server
-    .get(format!("/mount/{fingerprint}/foo"))
+    .get(format!("/package/{fingerprint}/item/1"))
     .status(StatusCode::NOT_FOUND)
-    .assert_body(format!(
-      "file `static/foo` not found in package {fingerprint}"
-    ))
+    .assert_body("media type web does not have items")
     .send();
 }
 
 #[test]
-fn mount_file_not_mounted() {
+fn package_item_without_media() {
   let server = TestServer::new();
 
   let fingerprint = PackageBuilder::new()
     .metadata(&Metadata {
-      media: Some(Media::Web),
+      title: Some("foo".parse().unwrap()),
       ..default()
     })
-    .file("static/index.html", b"foo")
     .upload(&server);
 
   server
-    .get(format!("/mount/{fingerprint}/index.html"))
+    .get(format!("/package/{fingerprint}/item/1"))
     .status(StatusCode::NOT_FOUND)
-    .assert_body(format!("package {fingerprint} not mounted"))
-    .send();
-}
-
-#[test]
-fn mount_redirect() {
-  TestServer::new()
-    .get(format!("/mount/{}", test::FINGERPRINT))
-    .status(StatusCode::PERMANENT_REDIRECT)
-    .assert_header(header::LOCATION, format!("/mount/{}/", test::FINGERPRINT))
+    .assert_body(format!(
+      "package {fingerprint} does not have media metadata"
+    ))
     .send();
 }
 
 #[test]
-fn mount_serves_index_html() {
-  let metadata = Metadata {
-    media: Some(Media::Web),
-    ..default()
-  };
-
-  let package = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("static/index.html", b"foo");
+fn package_item_without_metadata() {
+  let server = TestServer::new();
 
-  let server = TestServer::builder().mount(package.fingerprint()).build();
+  let (cbor, hash) = Directory::new().cbor();
+  let fingerprint = Fingerprint(hash);
+  server.write_file(&cbor);
 
-  let fingerprint = package.upload(&server);
+  server.post(format!("/api/directory/{hash}")).send();
+  server.post(format!("/api/package/{fingerprint}")).send();
 
   server
-    .get(format!("/mount/{fingerprint}/"))
-    .assert_header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
-    .assert_header(header::CONTENT_TYPE, "text/html")
-    .assert_header_absent(header::CONTENT_SECURITY_POLICY)
-    .assert_header_absent(header::CONTENT_DISPOSITION)
-    .assert_body("foo")
-    .send();
-}
-
-#[test]
-fn non_fingerprint_bech32_falls_through() {
-  TestServer::new()
-    .get(format!("/{}", test::PUBLIC_KEY))
-    .assert_static("404.html")
+    .get(format!("/package/{fingerprint}/item/1"))
     .status(StatusCode::NOT_FOUND)
+    .assert_body(format!("package {fingerprint} does not have metadata"))
     .send();
 }
 
 #[test]
-fn package_item_audio() {
+fn package_media() {
   let server = TestServer::new();
 
   let metadata = Metadata {
@@ -1683,9 +3070,8 @@ fn package_item_audio() {
     .upload(&server);
 
   server
-    .get(format!("/package/{fingerprint}/item/1"))
-    .assert_page(AudioHtml {
-      audio: 0,
+    .get(format!("/package/{fingerprint}/media"))
+    .assert_page(MediaHtml {
       fingerprint,
       metadata,
     })
@@ -1693,30 +3079,149 @@ fn package_item_audio() {
 }
 
 #[test]
-fn package_item_audio_out_of_range() {
+fn package_media_without_media() {
   let server = TestServer::new();
 
   let fingerprint = PackageBuilder::new()
     .metadata(&Metadata {
-      media: Some(Media::Audio {
-        items: tracks(&["foo.flac"]),
-      }),
+      title: Some("foo".parse().unwrap()),
       ..default()
     })
-    .file("foo.flac", b"foo")
     .upload(&server);
 
   server
-    .get(format!("/package/{fingerprint}/item/2"))
+    .get(format!("/package/{fingerprint}/media"))
     .status(StatusCode::NOT_FOUND)
     .assert_body(format!(
-      "track 2 does not exist, package {fingerprint} has 1 track"
+      "package {fingerprint} does not have media metadata"
     ))
     .send();
 }
 
 #[test]
-fn package_item_image() {
+fn package_page_og_image() {
+  let url = "https://example.com".parse::<Url>().unwrap();
+
+  let server = TestServer::builder().url(url.clone()).build();
+
+  let artwork = b"foo";
+  server.write_file(artwork);
+
+  let metadata = Metadata {
+    artwork: Some("bar.png".parse().unwrap()),
+    ..Metadata::default()
+  };
+  let metadata_cbor = metadata.encode_to_vec();
+  server.write_file(&metadata_cbor);
+
+  let mut directory = Directory::new();
+  directory
+    .insert_file("bar.png", artwork)
+    .insert_file(Metadata::CBOR_FILENAME, &metadata_cbor);
+  let (cbor, hash) = directory.cbor();
+  let fingerprint = Fingerprint(hash);
+  server.write_file(&cbor);
+
+  server.post(format!("/api/directory/{hash}")).send();
+  server.post(format!("/api/package/{fingerprint}")).send();
+
+  server
+    .get(format!("/package/{fingerprint}"))
+    .assert_response(
+      PackageHtml {
+        colophon: None,
+        directory,
+        fingerprint,
+        metadata: Some(metadata),
+        mounted: false,
+        readme: None,
+        signatures: BTreeSet::new(),
+        totals: Totals {
+          directories: 0,
+          directory_size: 0,
+          file_size: metadata_cbor.len().into_u64() + 3,
+          files: 2,
+        },
+      }
+      .page(Some(url)),
+    )
+    .send();
+}
+
+#[test]
+fn package_page_renders_audio_media() {
+  let server = TestServer::new();
+
+  let metadata = Metadata {
+    media: Some(Media::Audio {
+      items: vec![
+        Audio {
+          album: "qux".parse().unwrap(),
+          artist: "baz".parse().unwrap(),
+          channels: 2,
+          disc: 1,
+          discs: 1,
+          path: "foo.flac".parse().unwrap(),
+          sample_bits: Some(16),
+          sample_rate: 44100,
+          samples: 9_922_500,
+          size: 0,
+          title: "foo".parse().unwrap(),
+          track: 1,
+          tracks: 2,
+          ty: AudioType::Flac,
+        },
+        Audio {
+          album: "qux".parse().unwrap(),
+          artist: "baz".parse().unwrap(),
+          channels: 2,
+          disc: 1,
+          discs: 1,
+          path: "bar.flac".parse().unwrap(),
+          sample_bits: Some(16),
+          sample_rate: 44100,
+          samples: 44100,
+          size: 0,
+          title: "bar".parse().unwrap(),
+          track: 2,
+          tracks: 2,
+          ty: AudioType::Flac,
+        },
+      ],
+    }),
+    ..default()
+  };
+
+  let totals = Totals {
+    directories: 0,
+    directory_size: 0,
+    file_size: metadata.encode_to_vec().len().into_u64() + 6,
+    files: 3,
+  };
+
+  let fingerprint = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("foo.flac", b"foo")
+    .file("bar.flac", b"bar")
+    .upload(&server);
+
+  server
+    .get(format!("/package/{fingerprint}"))
+    .assert_page(PackageHtml {
+      colophon: None,
+      directory: Directory::new(),
+      fingerprint,
+      metadata: Some(metadata),
+      mounted: false,
+      readme: None,
+      signatures: BTreeSet::new(),
+      totals,
+    })
+    .send();
+}
+
+#[test]
+fn package_page_renders_image_media() {
   let server = TestServer::new();
 
   let metadata = Metadata {
@@ -1738,71 +3243,35 @@ fn package_item_image() {
     ..default()
   };
 
+  let totals = Totals {
+    directories: 0,
+    directory_size: 0,
+    file_size: metadata.encode_to_vec().len().into_u64() + 3,
+    files: 2,
+  };
+
   let fingerprint = PackageBuilder::new()
     .metadata(&metadata)
     .file("foo.png", b"foo")
     .upload(&server);
 
   server
-    .get(format!("/package/{fingerprint}/item/1"))
-    .assert_page(ImageHtml {
+    .get(format!("/package/{fingerprint}"))
+    .assert_page(PackageHtml {
+      colophon: None,
+      directory: Directory::new(),
       fingerprint,
-      image: 0,
-      metadata,
-    })
-    .send();
-}
-
-#[test]
-fn package_item_image_out_of_range() {
-  let server = TestServer::new();
-
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Image {
-        items: vec![Image {
-          alpha: false,
-          bit_depth: 8,
-          chroma_subsampling: None,
-          color_type: ColorType::Rgb,
-          dimensions: Dimensions {
-            height: 1,
-            width: 1,
-          },
-          orientation: Orientation::new(),
-          path: "foo.png".parse().unwrap(),
-          ty: ImageType::Png,
-        }],
-      }),
-      ..default()
-    })
-    .file("foo.png", b"foo")
-    .upload(&server);
-
-  server
-    .get(format!("/package/{fingerprint}/item/2"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!(
-      "image 2 does not exist, package {fingerprint} has 1 image"
-    ))
-    .send();
-}
-
-#[test]
-fn package_item_package_not_found() {
-  let server = TestServer::new();
-
-  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
-
-  server
-    .get(format!("/package/{fingerprint}/item/1"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!("package {fingerprint} not found"))
+      metadata: Some(metadata),
+      mounted: false,
+      readme: None,
+      signatures: BTreeSet::new(),
+      totals,
+    })
     .send();
 }
 
 #[test]
-fn package_item_video() {
+fn package_page_renders_video_media() {
   let server = TestServer::new();
 
   let metadata = Metadata {
@@ -1840,796 +3309,1111 @@ fn package_item_video() {
     ..default()
   };
 
+  let totals = Totals {
+    directories: 0,
+    directory_size: 0,
+    file_size: metadata.encode_to_vec().len().into_u64() + 3,
+    files: 2,
+  };
+
   let fingerprint = PackageBuilder::new()
     .metadata(&metadata)
     .file("foo.mp4", b"foo")
     .upload(&server);
 
   server
-    .get(format!("/package/{fingerprint}/item/1"))
-    .assert_page(VideoHtml {
+    .get(format!("/package/{fingerprint}"))
+    .assert_page(PackageHtml {
+      colophon: None,
+      directory: Directory::new(),
       fingerprint,
-      metadata,
-      video: 0,
+      metadata: Some(metadata),
+      mounted: false,
+      readme: None,
+      signatures: BTreeSet::new(),
+      totals,
     })
     .send();
 }
 
 #[test]
-fn package_item_video_out_of_range() {
+fn package_page_web() {
+  let metadata = Metadata {
+    media: Some(Media::Web),
+    ..default()
+  };
+
+  let metadata_cbor_len = metadata.encode_to_vec().len().into_u64();
+
+  let package = PackageBuilder::new()
+    .metadata(&metadata)
+    .file("static/index.html", b"foo");
+
+  let directory = package.directory();
+
+  let mut static_directory = Directory::new();
+  static_directory.insert_file("index.html", b"foo");
+  let static_cbor_len = static_directory.cbor().0.len().into_u64();
+
+  let server = TestServer::builder().mount(package.fingerprint()).build();
+
+  let fingerprint = package.upload(&server);
+
+  server
+    .get(format!("/package/{fingerprint}"))
+    .assert_page(PackageHtml {
+      colophon: None,
+      directory,
+      fingerprint,
+      metadata: Some(metadata),
+      mounted: true,
+      readme: None,
+      signatures: BTreeSet::new(),
+      totals: Totals {
+        directories: 1,
+        directory_size: static_cbor_len,
+        file_size: metadata_cbor_len + 3,
+        files: 2,
+      },
+    })
+    .send();
+}
+
+#[test]
+fn packages_empty() {
+  TestServer::new()
+    .get("/packages")
+    .assert_page(PackagesHtml {
+      packages: Vec::new(),
+      view: View::List,
+    })
+    .send();
+}
+
+#[test]
+fn packages_grid() {
   let server = TestServer::new();
 
+  let metadata = Metadata {
+    artwork: Some("foo.png".parse().unwrap()),
+    ..default()
+  };
+
+  let totals = Totals {
+    directories: 0,
+    directory_size: 0,
+    file_size: metadata.encode_to_vec().len().into_u64() + 3,
+    files: 2,
+  };
+
   let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Video {
-        items: vec!["foo.mp4".parse().unwrap()],
-      }),
+    .metadata(&metadata)
+    .file("foo.png", b"bar")
+    .upload(&server);
+
+  server
+    .get("/packages?view=grid")
+    .assert_page(PackagesHtml {
+      packages: vec![(fingerprint, Some(metadata), totals)],
+      view: View::Grid,
+    })
+    .send();
+}
+
+#[test]
+fn packages_include_creators_and_titles() {
+  let server = TestServer::new();
+
+  let metadata = Metadata {
+    creator: Some("foo".parse().unwrap()),
+    title: Some("bar".parse().unwrap()),
+    ..default()
+  };
+
+  let totals = Totals {
+    directories: 0,
+    directory_size: 0,
+    file_size: metadata.encode_to_vec().len().into_u64(),
+    files: 1,
+  };
+
+  let fingerprint = PackageBuilder::new().metadata(&metadata).upload(&server);
+
+  server
+    .get("/packages")
+    .assert_page(PackagesHtml {
+      packages: vec![(fingerprint, Some(metadata), totals)],
+      view: View::List,
+    })
+    .send();
+}
+
+#[test]
+fn packages_invalid_view() {
+  TestServer::new()
+    .get("/packages?view=foo")
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body(
+      "Failed to deserialize query string: view: unknown variant `foo`, expected `grid` or `list`",
+    )
+    .send();
+}
+
+#[test]
+fn packages_non_empty() {
+  let server = TestServer::new();
+
+  let mut packages = Vec::new();
+
+  for content in [b"foo".as_slice(), b"bar", b"baz"] {
+    server.write_file(content);
+    let (cbor, hash) = Directory::new().insert_file("file", content).cbor();
+    let fingerprint = Fingerprint(hash);
+    server.write_file(&cbor);
+    server.post(format!("/api/directory/{hash}")).send();
+    server.post(format!("/api/package/{fingerprint}")).send();
+    packages.push((
+      fingerprint,
+      None,
+      Totals {
+        directories: 0,
+        directory_size: 0,
+        file_size: 3,
+        files: 1,
+      },
+    ));
+  }
+
+  packages.sort_by_key(|&(fingerprint, ..)| fingerprint);
+
+  server
+    .get("/packages")
+    .assert_page(PackagesHtml {
+      packages,
+      view: View::List,
+    })
+    .send();
+}
+
+#[test]
+fn ports() {
+  #[track_caller]
+  fn case(serve: Serve, http_port: Option<u16>, https_port: Option<u16>) {
+    assert_eq!(serve.http_port(), http_port);
+    assert_eq!(serve.https_port(), https_port);
+  }
+
+  case(Serve::default(), Some(80), None);
+  case(
+    Serve {
+      https: true,
+      ..Serve::default()
+    },
+    None,
+    Some(443),
+  );
+  case(
+    Serve {
+      https_port: Some(433),
+      ..Serve::default()
+    },
+    None,
+    Some(433),
+  );
+  case(
+    Serve {
+      http: true,
+      https: true,
+      ..Serve::default()
+    },
+    Some(80),
+    Some(443),
+  );
+  case(
+    Serve {
+      http_port: Some(8080),
+      https_port: Some(8443),
+      ..Serve::default()
+    },
+    Some(8080),
+    Some(8443),
+  );
+  case(
+    Serve {
+      redirect_http_to_https: true,
+      ..Serve::default()
+    },
+    Some(80),
+    Some(443),
+  );
+}
+
+#[test]
+fn quota_allows_existing_file() {
+  let server = TestServer::builder()
+    .quotas(Quotas {
+      server: Quota {
+        bytes: Some(3),
+        files: Some(1),
+      },
       ..default()
     })
-    .file("foo.mp4", b"foo")
-    .upload(&server);
+    .build();
+
+  let hash = Hash::bytes(b"foo");
+
+  server.put(format!("/file/{hash}")).body("foo").send();
+
+  server.put(format!("/file/{hash}")).body("foo").send();
+
+  server.assert_file(hash);
+}
+
+#[test]
+fn quota_rejects_bundle_body() {
+  let server = TestServer::builder()
+    .quotas(Quotas {
+      server: Quota {
+        bytes: Some(64),
+        files: None,
+      },
+      ..default()
+    })
+    .build();
+
+  let package = PackageBuilder::new().file("foo", &[0; 1024]);
 
   server
-    .get(format!("/package/{fingerprint}/item/2"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!(
-      "video 2 does not exist, package {fingerprint} has 1 video"
-    ))
+    .put("/api/bundle")
+    .body(package.bundle())
+    .status(StatusCode::INSUFFICIENT_STORAGE)
+    .assert_body("upload exceeds server quota of 64 bytes")
     .send();
+
+  server.assert_incoming_empty();
 }
 
 #[test]
-fn package_item_web() {
-  let server = TestServer::new();
-
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      media: Some(Media::Web),
+fn quota_rejects_bytes() {
+  let server = TestServer::builder()
+    .quotas(Quotas {
+      server: Quota {
+        bytes: Some(5),
+        files: None,
+      },
       ..default()
     })
-    .file("static/index.html", b"foo")
-    .upload(&server);
+    .build();
 
   server
-    .get(format!("/package/{fingerprint}/item/1"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body("media type web does not have items")
+    .put(format!("/file/{}", Hash::bytes(b"foo")))
+    .body("foo")
     .send();
+
+  let hash = Hash::bytes(b"bar");
+
+  server
+    .put(format!("/file/{hash}"))
+    .body("bar")
+    .status(StatusCode::INSUFFICIENT_STORAGE)
+    .assert_body("upload exceeds server quota of 5 bytes")
+    .send();
+
+  assert!(
+    !server
+      .data_dir
+      .join("files")
+      .join(hash.to_string())
+      .try_exists()
+      .unwrap()
+  );
+
+  server.assert_incoming_empty();
 }
 
 #[test]
-fn package_item_without_media() {
-  let server = TestServer::new();
-
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      title: Some("foo".parse().unwrap()),
+fn quota_rejects_files() {
+  let server = TestServer::builder()
+    .quotas(Quotas {
+      server: Quota {
+        bytes: None,
+        files: Some(1),
+      },
       ..default()
     })
-    .upload(&server);
+    .build();
+
+  let package = PackageBuilder::new().file("foo", b"foo");
 
   server
-    .get(format!("/package/{fingerprint}/item/1"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!(
-      "package {fingerprint} does not have media metadata"
-    ))
+    .put("/api/bundle")
+    .body(package.bundle())
+    .status(StatusCode::INSUFFICIENT_STORAGE)
+    .assert_body("upload exceeds server quota of 1 file")
     .send();
 }
 
 #[test]
-fn package_item_without_metadata() {
-  let server = TestServer::new();
+fn redirect_omits_default_ports() {
+  assert_eq!(
+    Serve {
+      domain: Some("foo".into()),
+      ..Serve::default()
+    }
+    .redirect_url()
+    .as_str(),
+    "http://foo/",
+  );
 
-  let (cbor, hash) = Directory::new().cbor();
-  let fingerprint = Fingerprint(hash);
-  server.write_file(&cbor);
+  assert_eq!(
+    Serve {
+      domain: Some("foo".into()),
+      https: true,
+      ..Serve::default()
+    }
+    .redirect_url()
+    .as_str(),
+    "https://foo/",
+  );
+}
 
-  server.post(format!("/api/directory/{hash}")).send();
-  server.post(format!("/api/package/{fingerprint}")).send();
+#[test]
+fn restricted_write_accepts_admin_token() {
+  let admin = PrivateKey::generate();
+  let hash = Hash::bytes(b"bar");
+  let token = Token::encode(&admin, "filepack.example").unwrap();
+
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(admin.public_key(), [Role::Admin].into())].into(),
+    })
+    .build();
 
   server
-    .get(format!("/package/{fingerprint}/item/1"))
-    .status(StatusCode::NOT_FOUND)
-    .assert_body(format!("package {fingerprint} does not have metadata"))
+    .put(format!("/file/{hash}"))
+    .body("bar")
+    .token(token)
     .send();
+
+  server.assert_file(hash);
 }
 
 #[test]
-fn package_media() {
-  let server = TestServer::new();
-
-  let metadata = Metadata {
-    media: Some(Media::Audio {
-      items: tracks(&["foo.flac"]),
-    }),
-    ..default()
-  };
+fn restricted_write_rejects_missing_header() {
+  let admin = PrivateKey::generate();
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(admin.public_key(), [Role::Admin].into())].into(),
+    })
+    .build();
 
-  let fingerprint = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("foo.flac", b"foo")
-    .upload(&server);
+  let hash = Hash::bytes(b"bar");
 
   server
-    .get(format!("/package/{fingerprint}/media"))
-    .assert_page(MediaHtml {
-      fingerprint,
-      metadata,
-    })
+    .put(format!("/file/{hash}"))
+    .body("bar")
+    .status(StatusCode::UNAUTHORIZED)
+    .assert_body("missing authorization header")
     .send();
 }
 
 #[test]
-fn package_media_without_media() {
-  let server = TestServer::new();
-
-  let fingerprint = PackageBuilder::new()
-    .metadata(&Metadata {
-      title: Some("foo".parse().unwrap()),
-      ..default()
+fn restricted_write_rejects_others() {
+  let admin = PrivateKey::generate();
+  let other = PrivateKey::generate();
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(admin.public_key(), [Role::Admin].into())].into(),
     })
-    .upload(&server);
+    .build();
+
+  let hash = Hash::bytes(b"bar");
+  let token = Token::encode(&other, "filepack.example").unwrap();
 
   server
-    .get(format!("/package/{fingerprint}/media"))
-    .status(StatusCode::NOT_FOUND)
+    .put(format!("/file/{hash}"))
+    .body("bar")
+    .token(token)
+    .status(StatusCode::UNAUTHORIZED)
     .assert_body(format!(
-      "package {fingerprint} does not have media metadata"
+      "unknown authorization token issuer {}",
+      other.public_key(),
     ))
     .send();
 }
 
 #[test]
-fn package_page_og_image() {
-  let url = "https://example.com".parse::<Url>().unwrap();
-
-  let server = TestServer::builder().url(url.clone()).build();
+fn search() {
+  #[track_caller]
+  fn case(server: &TestServer, query: &str, packages: &[Fingerprint]) {
+    server
+      .get(format!("/api/search{query}"))
+      .assert_body(
+        api::search::Response {
+          packages: packages.to_vec(),
+          total: packages.len().into_u64(),
+        }
+        .encode_to_vec(),
+      )
+      .send();
+  }
 
-  let artwork = b"foo";
-  server.write_file(artwork);
+  let server = TestServer::new();
 
-  let metadata = Metadata {
-    artwork: Some("bar.png".parse().unwrap()),
-    ..Metadata::default()
-  };
-  let metadata_cbor = metadata.encode_to_vec();
-  server.write_file(&metadata_cbor);
+  let upload = |metadata: Metadata| PackageBuilder::new().metadata(&metadata).upload(&server);
 
-  let mut directory = Directory::new();
-  directory
-    .insert_file("bar.png", artwork)
-    .insert_file(Metadata::CBOR_FILENAME, &metadata_cbor);
-  let (cbor, hash) = directory.cbor();
-  let fingerprint = Fingerprint(hash);
-  server.write_file(&cbor);
+  let foo = upload(Metadata {
+    creator: Some("Alice".parse().unwrap()),
+    language: Some("en".parse().unwrap()),
+    media: Some(Media::Audio { items: Vec::new() }),
+    time: Some("1975".parse().unwrap()),
+    title: Some("Foo Songs".parse().unwrap()),
+    ..default()
+  });
 
-  server.post(format!("/api/directory/{hash}")).send();
-  server.post(format!("/api/package/{fingerprint}")).send();
+  let bar = upload(Metadata {
+    creator: Some("Bob".parse().unwrap()),
+    language: Some("fr".parse().unwrap()),
+    time: Some("1982-06-01".parse().unwrap()),
+    title: Some("bar stories".parse().unwrap()),
+    ..default()
+  });
 
-  server
-    .get(format!("/package/{fingerprint}"))
-    .assert_response(
-      PackageHtml {
-        colophon: None,
-        directory,
-        fingerprint,
-        metadata: Some(metadata),
-        mounted: false,
-        readme: None,
-        totals: Totals {
-          directories: 0,
-          directory_size: 0,
-          file_size: metadata_cbor.len().into_u64() + 3,
-          files: 2,
-        },
-      }
-      .page(Some(url)),
-    )
-    .send();
+  let baz = upload(Metadata {
+    description: Some("songs of the sea".parse().unwrap()),
+    language: Some("en".parse().unwrap()),
+    time: Some("1970-01-01".parse().unwrap()),
+    title: Some("Baz".parse().unwrap()),
+    ..default()
+  });
+
+  let qux = PackageBuilder::new().file("qux", b"qux").upload(&server);
+
+  case(&server, "", &[bar, baz, foo, qux]);
+  case(&server, "?q=", &[bar, baz, foo, qux]);
+  case(&server, "?q=song", &[baz, foo]);
+  case(&server, "?q=SONGS+alice", &[foo]);
+  case(&server, "?q=nothing", &[]);
+  case(&server, "?media=audio", &[foo]);
+  case(&server, "?media=&language=", &[bar, baz, foo, qux]);
+  case(&server, "?language=en", &[baz, foo]);
+  case(&server, "?year=1970..1979", &[baz, foo]);
+  case(&server, "?year=1982", &[bar]);
+  case(&server, "?year=..1974", &[baz]);
+  case(&server, "?sort=time", &[baz, foo, bar, qux]);
+  case(&server, "?sort=title", &[bar, baz, foo, qux]);
+  case(&server, "?q=songs&language=en&year=1975", &[foo]);
 }
 
 #[test]
-fn package_page_renders_audio_media() {
+fn search_excludes_deleted_packages() {
   let server = TestServer::new();
 
-  let metadata = Metadata {
-    media: Some(Media::Audio {
-      items: vec![
-        Audio {
-          album: "qux".parse().unwrap(),
-          artist: "baz".parse().unwrap(),
-          channels: 2,
-          disc: 1,
-          discs: 1,
-          path: "foo.flac".parse().unwrap(),
-          sample_bits: Some(16),
-          sample_rate: 44100,
-          samples: 9_922_500,
-          size: 0,
-          title: "foo".parse().unwrap(),
-          track: 1,
-          tracks: 2,
-          ty: AudioType::Flac,
-        },
-        Audio {
-          album: "qux".parse().unwrap(),
-          artist: "baz".parse().unwrap(),
-          channels: 2,
-          disc: 1,
-          discs: 1,
-          path: "bar.flac".parse().unwrap(),
-          sample_bits: Some(16),
-          sample_rate: 44100,
-          samples: 44100,
-          size: 0,
-          title: "bar".parse().unwrap(),
-          track: 2,
-          tracks: 2,
-          ty: AudioType::Flac,
-        },
-      ],
-    }),
-    ..default()
-  };
-
-  let totals = Totals {
-    directories: 0,
-    directory_size: 0,
-    file_size: metadata.encode_to_vec().len().into_u64() + 6,
-    files: 3,
-  };
-
   let fingerprint = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("foo.flac", b"foo")
-    .file("bar.flac", b"bar")
+    .metadata(&Metadata {
+      title: Some("foo".parse().unwrap()),
+      ..default()
+    })
     .upload(&server);
 
+  server.delete(format!("/api/package/{fingerprint}")).send();
+
   server
-    .get(format!("/package/{fingerprint}"))
-    .assert_page(PackageHtml {
-      colophon: None,
-      directory: Directory::new(),
-      fingerprint,
-      metadata: Some(metadata),
-      mounted: false,
-      readme: None,
-      totals,
-    })
+    .get("/api/search?q=foo")
+    .assert_body(
+      api::search::Response {
+        packages: Vec::new(),
+        total: 0,
+      }
+      .encode_to_vec(),
+    )
     .send();
 }
 
 #[test]
-fn package_page_renders_image_media() {
+fn search_json() {
   let server = TestServer::new();
 
-  let metadata = Metadata {
-    media: Some(Media::Image {
-      items: vec![Image {
-        alpha: false,
-        bit_depth: 8,
-        chroma_subsampling: None,
-        color_type: ColorType::Rgb,
-        dimensions: Dimensions {
-          height: 1,
-          width: 2,
-        },
-        orientation: Orientation::new(),
-        path: "foo.png".parse().unwrap(),
-        ty: ImageType::Png,
-      }],
-    }),
-    ..default()
-  };
-
-  let totals = Totals {
-    directories: 0,
-    directory_size: 0,
-    file_size: metadata.encode_to_vec().len().into_u64() + 3,
-    files: 2,
-  };
-
   let fingerprint = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("foo.png", b"foo")
+    .metadata(&Metadata {
+      title: Some("foo".parse().unwrap()),
+      ..default()
+    })
     .upload(&server);
 
   server
-    .get(format!("/package/{fingerprint}"))
-    .assert_page(PackageHtml {
-      colophon: None,
-      directory: Directory::new(),
-      fingerprint,
-      metadata: Some(metadata),
-      mounted: false,
-      readme: None,
-      totals,
-    })
+    .get("/api/search.json?q=foo")
+    .assert_header(header::CONTENT_TYPE, "application/json")
+    .assert_body(format!(r#"{{"packages":["{fingerprint}"],"total":1}}"#))
     .send();
 }
 
 #[test]
-fn package_page_renders_video_media() {
+fn search_page() {
   let server = TestServer::new();
 
   let metadata = Metadata {
-    media: Some(Media::Video {
-      items: vec![Video {
-        duration: 0,
-        path: "foo.mp4".parse().unwrap(),
-        tracks: vec![
-          Track {
-            codec: Codec::H264,
-            info: TrackInfo::Video {
-              bit_depth: 8,
-              chroma_subsampling: ChromaSubsampling::Yuv420,
-              dimensions: Dimensions {
-                height: 1,
-                width: 2,
-              },
-              frames: 0,
-              orientation: Orientation::new(),
-            },
-            size: 0,
-          },
-          Track {
-            codec: Codec::Aac,
-            info: TrackInfo::Audio {
-              channels: 2,
-              sample_rate: 44100,
-            },
-            size: 0,
-          },
-        ],
-        ty: VideoType::Mp4,
-      }],
-    }),
+    title: Some("foo".parse().unwrap()),
     ..default()
   };
 
   let totals = Totals {
     directories: 0,
     directory_size: 0,
-    file_size: metadata.encode_to_vec().len().into_u64() + 3,
-    files: 2,
+    file_size: metadata.encode_to_vec().len().into_u64(),
+    files: 1,
   };
 
-  let fingerprint = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("foo.mp4", b"foo")
+  let fingerprint = PackageBuilder::new().metadata(&metadata).upload(&server);
+
+  PackageBuilder::new()
+    .metadata(&Metadata {
+      title: Some("bar".parse().unwrap()),
+      ..default()
+    })
     .upload(&server);
 
   server
-    .get(format!("/package/{fingerprint}"))
-    .assert_page(PackageHtml {
-      colophon: None,
-      directory: Directory::new(),
-      fingerprint,
-      metadata: Some(metadata),
-      mounted: false,
-      readme: None,
-      totals,
+    .get("/search?q=foo")
+    .assert_page(SearchHtml {
+      packages: vec![(fingerprint, Some(metadata), totals)],
+      search: Search {
+        q: Some("foo".into()),
+        ..default()
+      },
+      total: 1,
     })
     .send();
 }
 
 #[test]
-fn package_page_web() {
-  let metadata = Metadata {
-    media: Some(Media::Web),
-    ..default()
-  };
+fn search_paging() {
+  let server = TestServer::new();
 
-  let metadata_cbor_len = metadata.encode_to_vec().len().into_u64();
+  let fingerprints = (0..=Search::PAGE_SIZE)
+    .map(|i| {
+      PackageBuilder::new()
+        .metadata(&Metadata {
+          title: Some(format!("{i:03}").parse().unwrap()),
+          ..default()
+        })
+        .upload(&server)
+    })
+    .collect::<Vec<Fingerprint>>();
 
-  let package = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("static/index.html", b"foo");
+  let total = fingerprints.len().into_u64();
 
-  let directory = package.directory();
+  let page_size = Search::PAGE_SIZE.try_into().unwrap();
 
-  let mut static_directory = Directory::new();
-  static_directory.insert_file("index.html", b"foo");
-  let static_cbor_len = static_directory.cbor().0.len().into_u64();
+  for (query, packages) in [
+    ("", &fingerprints[..page_size]),
+    ("?page=1", &fingerprints[..page_size]),
+    ("?page=2", &fingerprints[page_size..]),
+    ("?page=3", &[]),
+  ] {
+    server
+      .get(format!("/api/search{query}"))
+      .assert_body(
+        api::search::Response {
+          packages: packages.to_vec(),
+          total,
+        }
+        .encode_to_vec(),
+      )
+      .send();
+  }
+}
 
-  let server = TestServer::builder().mount(package.fingerprint()).build();
+#[test]
+fn search_rejects_invalid_query() {
+  #[track_caller]
+  fn case(query: &str, message: &str) {
+    TestServer::new()
+      .get(format!("/api/search?{query}"))
+      .status(StatusCode::BAD_REQUEST)
+      .assert_body(format!("Failed to deserialize query string: {message}"))
+      .send();
+  }
 
-  let fingerprint = package.upload(&server);
+  case("year=foo", "year: invalid year range `foo`");
+  case(
+    "year=1979..1970",
+    "year: year range start 1979 is after end 1970",
+  );
+  case("media=foo", "media: Matching variant not found");
+  case("language=foo", "language: unknown language code `foo`");
+  case("sort=foo", "sort: Matching variant not found");
+  case("page=foo", "page: invalid digit found in string");
+}
 
-  server
-    .get(format!("/package/{fingerprint}"))
-    .assert_page(PackageHtml {
-      colophon: None,
-      directory,
-      fingerprint,
-      metadata: Some(metadata),
-      mounted: true,
-      readme: None,
-      totals: Totals {
-        directories: 1,
-        directory_size: static_cbor_len,
-        file_size: metadata_cbor_len + 3,
-        files: 2,
-      },
-    })
-    .send();
+#[test]
+fn server_config() {
+  #[track_caller]
+  fn case(serve: Serve, url: Option<&str>) {
+    assert_eq!(
+      serve.server_config(None).unwrap().url,
+      url.map(|url| url.parse().unwrap()),
+    );
+  }
+
+  case(Serve::default(), None);
+  case(
+    Serve {
+      domain: Some("foo".into()),
+      ..Serve::default()
+    },
+    Some("http://foo/"),
+  );
+  case(
+    Serve {
+      domain: Some("foo".into()),
+      https: true,
+      ..Serve::default()
+    },
+    Some("https://foo/"),
+  );
+
+  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
+
+  assert_eq!(
+    Serve {
+      mounts: vec![fingerprint],
+      ..Serve::default()
+    }
+    .server_config(None)
+    .unwrap()
+    .mounts,
+    HashSet::from([fingerprint]),
+  );
 }
 
 #[test]
-fn packages_empty() {
+fn static_files() {
   TestServer::new()
-    .get("/packages")
-    .assert_page(PackagesHtml {
-      packages: Vec::new(),
-      view: View::List,
-    })
+    .get("/static/index.css")
+    .assert_static("index.css")
     .send();
 }
 
+fn tracks(filenames: &[&str]) -> Vec<Audio> {
+  filenames
+    .iter()
+    .enumerate()
+    .map(|(i, filename)| {
+      let mut audio = filename.parse::<Audio>().unwrap();
+      audio.disc = 1;
+      audio.discs = 1;
+      audio.track = i.into_u64() + 1;
+      audio.tracks = filenames.len().into_u64();
+      audio
+    })
+    .collect()
+}
+
 #[test]
-fn packages_grid() {
+fn upload_bundle() {
   let server = TestServer::new();
 
-  let metadata = Metadata {
-    artwork: Some("foo.png".parse().unwrap()),
-    ..default()
-  };
+  let package = PackageBuilder::new()
+    .file("foo", b"foo")
+    .file("bar/baz", b"baz")
+    .file("bar/qux", b"foo")
+    .symlink("link", "foo");
 
-  let totals = Totals {
-    directories: 0,
-    directory_size: 0,
-    file_size: metadata.encode_to_vec().len().into_u64() + 3,
-    files: 2,
-  };
+  server.put("/api/bundle").body(package.bundle()).send();
 
-  let fingerprint = PackageBuilder::new()
-    .metadata(&metadata)
-    .file("foo.png", b"bar")
-    .upload(&server);
+  server.assert_file(Hash::bytes(b"foo"));
+  server.assert_file(Hash::bytes(b"baz"));
 
   server
-    .get("/packages?view=grid")
-    .assert_page(PackagesHtml {
-      packages: vec![(fingerprint, Some(metadata), totals)],
-      view: View::Grid,
-    })
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::from([package.fingerprint()]).into(),
+      }
+      .encode_to_vec(),
+    )
     .send();
+
+  server.put("/api/bundle").body(package.bundle()).send();
+
+  server.assert_incoming_empty();
 }
 
 #[test]
-fn packages_include_creators_and_titles() {
+fn upload_bundle_invalid() {
   let server = TestServer::new();
 
-  let metadata = Metadata {
-    creator: Some("foo".parse().unwrap()),
-    title: Some("bar".parse().unwrap()),
-    ..default()
-  };
+  server
+    .put("/api/bundle")
+    .body("foo")
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body("invalid bundle: expected bundle map but found text")
+    .send();
 
-  let totals = Totals {
-    directories: 0,
-    directory_size: 0,
-    file_size: metadata.encode_to_vec().len().into_u64(),
-    files: 1,
-  };
+  let mut bundle = PackageBuilder::new().file("foo", b"foo").bundle();
 
-  let fingerprint = PackageBuilder::new().metadata(&metadata).upload(&server);
+  bundle.pop();
 
   server
-    .get("/packages")
-    .assert_page(PackagesHtml {
-      packages: vec![(fingerprint, Some(metadata), totals)],
-      view: View::List,
-    })
+    .put("/api/bundle")
+    .body(bundle)
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body("invalid bundle: I/O error reading bundle")
     .send();
-}
 
-#[test]
-fn packages_invalid_view() {
-  TestServer::new()
-    .get("/packages?view=foo")
-    .status(StatusCode::BAD_REQUEST)
+  server
+    .get("/api/packages")
     .assert_body(
-      "Failed to deserialize query string: view: unknown variant `foo`, expected `grid` or `list`",
+      api::packages::Response {
+        packages: BTreeSet::new().into(),
+      }
+      .encode_to_vec(),
     )
     .send();
+
+  server.assert_incoming_empty();
 }
 
 #[test]
-fn packages_non_empty() {
+fn upload_creates_file() {
   let server = TestServer::new();
 
-  let mut packages = Vec::new();
+  let hash = Hash::bytes(b"bar");
 
-  for content in [b"foo".as_slice(), b"bar", b"baz"] {
-    server.write_file(content);
-    let (cbor, hash) = Directory::new().insert_file("file", content).cbor();
-    let fingerprint = Fingerprint(hash);
-    server.write_file(&cbor);
-    server.post(format!("/api/directory/{hash}")).send();
-    server.post(format!("/api/package/{fingerprint}")).send();
-    packages.push((
-      fingerprint,
-      None,
-      Totals {
-        directories: 0,
-        directory_size: 0,
-        file_size: 3,
-        files: 1,
-      },
-    ));
-  }
+  server.put(format!("/file/{hash}")).body("bar").send();
 
-  packages.sort_by_key(|&(fingerprint, ..)| fingerprint);
+  server.assert_file(hash);
 
-  server
-    .get("/packages")
-    .assert_page(PackagesHtml {
-      packages,
-      view: View::List,
-    })
-    .send();
+  server.assert_incoming_empty();
 }
 
 #[test]
-fn ports() {
-  #[track_caller]
-  fn case(serve: Serve, http_port: Option<u16>, https_port: Option<u16>) {
-    assert_eq!(serve.http_port(), http_port);
-    assert_eq!(serve.https_port(), https_port);
-  }
+fn upload_creates_outboard() {
+  let server = TestServer::new();
 
-  case(Serve::default(), Some(80), None);
-  case(
-    Serve {
-      https: true,
-      ..Serve::default()
-    },
-    None,
-    Some(443),
-  );
-  case(
-    Serve {
-      https_port: Some(433),
-      ..Serve::default()
-    },
-    None,
-    Some(433),
-  );
-  case(
-    Serve {
-      http: true,
-      https: true,
-      ..Serve::default()
-    },
-    Some(80),
-    Some(443),
-  );
-  case(
-    Serve {
-      http_port: Some(8080),
-      https_port: Some(8443),
-      ..Serve::default()
-    },
-    Some(8080),
-    Some(8443),
-  );
-  case(
-    Serve {
-      redirect_http_to_https: true,
-      ..Serve::default()
-    },
-    Some(80),
-    Some(443),
-  );
-}
+  let content = vec![1; usize::try_from(Outboard::BLOCK_SIZE + 1).unwrap()];
 
-#[test]
-fn redirect_omits_default_ports() {
-  assert_eq!(
-    Serve {
-      domain: Some("foo".into()),
-      ..Serve::default()
-    }
-    .redirect_url()
-    .as_str(),
-    "http://foo/",
-  );
+  let hash = Hash::bytes(&content);
 
-  assert_eq!(
-    Serve {
-      domain: Some("foo".into()),
-      https: true,
-      ..Serve::default()
-    }
-    .redirect_url()
-    .as_str(),
-    "https://foo/",
-  );
+  server.put(format!("/file/{hash}")).body(&content).send();
+
+  let tree = fs::read(server.data_dir.join("outboards").join(hash.to_string())).unwrap();
+
+  Outboard::new(hash, content.len().into_u64(), &tree).unwrap();
+
+  server.assert_incoming_empty();
 }
 
 #[test]
-fn restricted_write_accepts_admin_token() {
-  let admin = PrivateKey::generate();
+fn upload_short_circuits_when_file_exists() {
+  let server = TestServer::new();
+
   let hash = Hash::bytes(b"bar");
-  let token = Token::encode(&admin, "filepack.example").unwrap();
 
-  let server = TestServer::builder()
-    .auth_config(AuthConfig {
-      admin: Some(admin.public_key()),
-      audience: Some("filepack.example".into()),
-    })
-    .build();
+  server.write_file(b"bar");
 
-  server
-    .put(format!("/file/{hash}"))
-    .body("bar")
-    .token(token)
-    .send();
+  server.put(format!("/file/{hash}")).body("bar").send();
 
   server.assert_file(hash);
+
+  server.assert_incoming_empty();
 }
 
 #[test]
-fn restricted_write_rejects_missing_header() {
-  let admin = PrivateKey::generate();
-  let server = TestServer::builder()
-    .auth_config(AuthConfig {
-      admin: Some(admin.public_key()),
-      audience: Some("filepack.example".into()),
-    })
-    .build();
+fn upload_with_wrong_hash_fails() {
+  let server = TestServer::new();
 
-  let hash = Hash::bytes(b"bar");
+  let actual = Hash::bytes(b"bar");
+  let expected = Hash::bytes(b"baz");
 
   server
-    .put(format!("/file/{hash}"))
+    .put(format!("/file/{expected}"))
     .body("bar")
-    .status(StatusCode::UNAUTHORIZED)
-    .assert_body("missing authorization header")
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body(format!(
+      "expected upload with hash {expected} but got {actual}"
+    ))
+    .send();
+
+  server.assert_incoming_empty();
+}
+
+#[test]
+fn uppercase_fingerprint_redirects_to_lowercase_package() {
+  TestServer::new()
+    .get(format!("/{}", test::FINGERPRINT.to_uppercase()))
+    .status(StatusCode::PERMANENT_REDIRECT)
+    .assert_header(header::LOCATION, format!("/package/{}", test::FINGERPRINT))
     .send();
 }
 
 #[test]
-fn restricted_write_rejects_others() {
-  let admin = PrivateKey::generate();
-  let other = PrivateKey::generate();
+fn usage() {
+  let alice = PrivateKey::generate();
+  let bob = PrivateKey::generate();
+
   let server = TestServer::builder()
     .auth_config(AuthConfig {
-      admin: Some(admin.public_key()),
       audience: Some("filepack.example".into()),
+      keys: [
+        (alice.public_key(), [Role::Upload].into()),
+        (bob.public_key(), [Role::Upload].into()),
+      ]
+      .into(),
     })
     .build();
 
-  let hash = Hash::bytes(b"bar");
-  let token = Token::encode(&other, "filepack.example").unwrap();
+  let a = PackageBuilder::new().file("foo", b"foo");
+  let b = PackageBuilder::new()
+    .file("foo", b"foo")
+    .file("bar", b"bar");
 
   server
-    .put(format!("/file/{hash}"))
-    .body("bar")
-    .token(token)
-    .status(StatusCode::UNAUTHORIZED)
-    .assert_body("invalid authorization token")
+    .put("/api/bundle")
+    .body(a.bundle())
+    .token(Token::encode(&alice, "filepack.example").unwrap())
     .send();
-}
-
-#[test]
-fn server_config() {
-  #[track_caller]
-  fn case(serve: Serve, url: Option<&str>) {
-    assert_eq!(
-      serve.server_config().url,
-      url.map(|url| url.parse().unwrap()),
-    );
-  }
 
-  case(Serve::default(), None);
-  case(
-    Serve {
-      domain: Some("foo".into()),
-      ..Serve::default()
-    },
-    Some("http://foo/"),
-  );
-  case(
-    Serve {
-      domain: Some("foo".into()),
-      https: true,
-      ..Serve::default()
-    },
-    Some("https://foo/"),
-  );
+  server
+    .put("/api/bundle")
+    .body(b.bundle())
+    .token(Token::encode(&bob, "filepack.example").unwrap())
+    .send();
 
-  let fingerprint = Fingerprint(Hash::bytes(b"foo"));
+  let a_size = a.directory().cbor().0.len().into_u64();
+  let b_size = b.directory().cbor().0.len().into_u64();
 
-  assert_eq!(
-    Serve {
-      mounts: vec![fingerprint],
-      ..Serve::default()
-    }
-    .server_config()
-    .mounts,
-    HashSet::from([fingerprint]),
-  );
+  server
+    .get("/api/usage")
+    .assert_body(
+      api::usage::Response {
+        keys: [
+          (
+            alice.public_key(),
+            Usage {
+              bytes: a_size + 3,
+              files: 2,
+            },
+          ),
+          (
+            bob.public_key(),
+            Usage {
+              bytes: b_size + 6,
+              files: 3,
+            },
+          ),
+        ]
+        .into(),
+        total: Usage {
+          bytes: a_size + b_size + 6,
+          files: 4,
+        },
+      }
+      .encode_to_vec(),
+    )
+    .send();
 }
 
 #[test]
-fn static_files() {
-  TestServer::new()
-    .get("/static/index.css")
-    .assert_static("index.css")
-    .send();
-}
+fn usage_after_delete() {
+  let alice = PrivateKey::generate();
+  let bob = PrivateKey::generate();
 
-fn tracks(filenames: &[&str]) -> Vec<Audio> {
-  filenames
-    .iter()
-    .enumerate()
-    .map(|(i, filename)| {
-      let mut audio = filename.parse::<Audio>().unwrap();
-      audio.disc = 1;
-      audio.discs = 1;
-      audio.track = i.into_u64() + 1;
-      audio.tracks = filenames.len().into_u64();
-      audio
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [
+        (alice.public_key(), [Role::Upload].into()),
+        (bob.public_key(), [Role::Delete, Role::Upload].into()),
+      ]
+      .into(),
     })
-    .collect()
-}
+    .quotas(Quotas {
+      exempt_shared: true,
+      ..default()
+    })
+    .build();
 
-#[test]
-fn upload_creates_file() {
-  let server = TestServer::new();
+  let a = PackageBuilder::new().file("foo", b"foo");
+  let b = PackageBuilder::new()
+    .file("foo", b"foo")
+    .file("bar", b"bar");
 
-  let hash = Hash::bytes(b"bar");
+  server
+    .put("/api/bundle")
+    .body(a.bundle())
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .send();
 
-  server.put(format!("/file/{hash}")).body("bar").send();
+  server
+    .put("/api/bundle")
+    .body(b.bundle())
+    .token(Token::encode(&bob, "filepack.example").unwrap())
+    .send();
 
-  server.assert_file(hash);
+  server
+    .delete(format!("/api/package/{}", b.fingerprint()))
+    .token(Token::encode(&bob, "filepack.example").unwrap())
+    .send();
 
-  server.assert_incoming_empty();
+  let a_size = a.directory().cbor().0.len().into_u64();
+  let b_size = b.directory().cbor().0.len().into_u64();
+
+  server
+    .get("/api/usage")
+    .assert_body(
+      api::usage::Response {
+        keys: [(
+          alice.public_key(),
+          Usage {
+            bytes: a_size + 3,
+            files: 2,
+          },
+        )]
+        .into(),
+        total: Usage {
+          bytes: a_size + b_size + 6,
+          files: 4,
+        },
+      }
+      .encode_to_vec(),
+    )
+    .send();
 }
 
 #[test]
-fn upload_short_circuits_when_file_exists() {
-  let server = TestServer::new();
+fn usage_exempt_shared() {
+  let alice = PrivateKey::generate();
+  let bob = PrivateKey::generate();
 
-  let hash = Hash::bytes(b"bar");
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [
+        (alice.public_key(), [Role::Upload].into()),
+        (bob.public_key(), [Role::Upload].into()),
+      ]
+      .into(),
+    })
+    .quotas(Quotas {
+      exempt_shared: true,
+      keys: [(
+        bob.public_key(),
+        Quota {
+          bytes: None,
+          files: Some(2),
+        },
+      )]
+      .into(),
+      ..default()
+    })
+    .build();
 
-  server.write_file(b"bar");
+  let a = PackageBuilder::new().file("foo", b"foo");
+  let b = PackageBuilder::new()
+    .file("foo", b"foo")
+    .file("bar", b"bar");
 
-  server.put(format!("/file/{hash}")).body("bar").send();
+  server
+    .put("/api/bundle")
+    .body(a.bundle())
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .send();
 
-  server.assert_file(hash);
+  server
+    .put("/api/bundle")
+    .body(b.bundle())
+    .token(Token::encode(&bob, "filepack.example").unwrap())
+    .send();
 
-  server.assert_incoming_empty();
+  let a_size = a.directory().cbor().0.len().into_u64();
+  let b_size = b.directory().cbor().0.len().into_u64();
+
+  server
+    .get("/api/usage")
+    .assert_body(
+      api::usage::Response {
+        keys: [
+          (
+            alice.public_key(),
+            Usage {
+              bytes: a_size,
+              files: 1,
+            },
+          ),
+          (
+            bob.public_key(),
+            Usage {
+              bytes: b_size + 3,
+              files: 2,
+            },
+          ),
+        ]
+        .into(),
+        total: Usage {
+          bytes: a_size + b_size + 6,
+          files: 4,
+        },
+      }
+      .encode_to_vec(),
+    )
+    .send();
 }
 
 #[test]
-fn upload_with_wrong_hash_fails() {
-  let server = TestServer::new();
+fn usage_pending() {
+  let alice = PrivateKey::generate();
 
-  let actual = Hash::bytes(b"bar");
-  let expected = Hash::bytes(b"baz");
+  let server = TestServer::builder()
+    .auth_config(AuthConfig {
+      audience: Some("filepack.example".into()),
+      keys: [(alice.public_key(), [Role::Gc, Role::Upload].into())].into(),
+    })
+    .build();
 
   server
-    .put(format!("/file/{expected}"))
-    .body("bar")
-    .status(StatusCode::BAD_REQUEST)
-    .assert_body(format!(
-      "expected upload with hash {expected} but got {actual}"
-    ))
+    .put(format!("/file/{}", Hash::bytes(b"foo")))
+    .body("foo")
+    .token(Token::encode(&alice, "filepack.example").unwrap())
     .send();
 
-  server.assert_incoming_empty();
-}
+  server
+    .get("/api/usage")
+    .assert_body(
+      api::usage::Response {
+        keys: [(alice.public_key(), Usage { bytes: 3, files: 1 })].into(),
+        total: Usage { bytes: 3, files: 1 },
+      }
+      .encode_to_vec(),
+    )
+    .send();
 
-#[test]
-fn uppercase_fingerprint_redirects_to_lowercase_package() {
-  TestServer::new()
-    .get(format!("/{}", test::FINGERPRINT.to_uppercase()))
-    .status(StatusCode::PERMANENT_REDIRECT)
-    .assert_header(header::LOCATION, format!("/package/{}", test::FINGERPRINT))
+  server
+    .post("/api/gc")
+    .token(Token::encode(&alice, "filepack.example").unwrap())
+    .assert_body(
+      api::gc::Response {
+        bytes: 3,
+        directories: BTreeSet::new().into(),
+        files: BTreeSet::from([Hash::bytes(b"foo")]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+
+  server
+    .get("/api/usage")
+    .assert_body(
+      api::usage::Response {
+        keys: BTreeMap::new(),
+        total: Usage::default(),
+      }
+      .encode_to_vec(),
+    )
     .send();
 }
 
@@ -2742,8 +4526,8 @@ fn verify_directory_rejects_missing_auth_header() {
   let admin = PrivateKey::generate();
   let server = TestServer::builder()
     .auth_config(AuthConfig {
-      admin: Some(admin.public_key()),
       audience: Some("filepack.example".into()),
+      keys: [(admin.public_key(), [Role::Admin].into())].into(),
     })
     .build();
 
@@ -2952,6 +4736,257 @@ fn verify_package_metadata_references_present_file() {
   server.post(format!("/api/package/{fingerprint}")).send();
 }
 
+#[test]
+fn verify_package_policy() {
+  let alice = PrivateKey::generate();
+  let bob = PrivateKey::generate();
+
+  let server = TestServer::builder()
+    .policy(&format!(
+      "
+keys:
+  alice: {}
+  bob: {}
+rules:
+  release:
+    keys: [alice, bob]
+    threshold: 2
+",
+      alice.public_key(),
+      bob.public_key(),
+    ))
+    .build();
+
+  let package = PackageBuilder::new().file("foo", b"foo");
+
+  let fingerprint = package.fingerprint();
+
+  package.root.upload(&server);
+
+  let statement = Statement {
+    fingerprint,
+    timestamp: None,
+    expires: None,
+    purpose: None,
+    comment: None,
+  };
+
+  let request = |signatures: &[&PrivateKey]| {
+    api::package::Request {
+      signatures: signatures
+        .iter()
+        .map(|key| key.sign(&statement))
+        .collect::<BTreeSet<Signature>>()
+        .into(),
+    }
+    .encode_to_vec()
+  };
+
+  server
+    .post(format!("/api/package/{fingerprint}"))
+    .status(StatusCode::FORBIDDEN)
+    .assert_body(format!(
+      "package {fingerprint} does not satisfy signature policy"
+    ))
+    .send();
+
+  server
+    .post(format!("/api/package/{fingerprint}"))
+    .body(request(&[&alice]))
+    .status(StatusCode::FORBIDDEN)
+    .assert_body(format!(
+      "package {fingerprint} does not satisfy signature policy"
+    ))
+    .send();
+
+  server
+    .put("/api/bundle")
+    .body(package.bundle())
+    .status(StatusCode::FORBIDDEN)
+    .assert_body(format!(
+      "package {fingerprint} does not satisfy signature policy"
+    ))
+    .send();
+
+  server
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::new().into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+
+  server
+    .post(format!("/api/package/{fingerprint}"))
+    .body(request(&[&alice, &bob]))
+    .send();
+
+  server
+    .get("/api/packages")
+    .assert_body(
+      api::packages::Response {
+        packages: BTreeSet::from([fingerprint]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .send();
+}
+
+#[test]
+fn verify_package_policy_ignores_expired_signatures() {
+  let alice = PrivateKey::generate();
+  let bob = PrivateKey::generate();
+
+  let server = TestServer::builder()
+    .policy(&format!(
+      "
+keys:
+  alice: {}
+  bob: {}
+rules:
+  release:
+    keys: [alice, bob]
+    threshold: 2
+",
+      alice.public_key(),
+      bob.public_key(),
+    ))
+    .build();
+
+  let package = PackageBuilder::new().file("foo", b"foo");
+
+  let fingerprint = package.fingerprint();
+
+  package.root.upload(&server);
+
+  let statement = Statement {
+    fingerprint,
+    timestamp: None,
+    expires: None,
+    purpose: None,
+    comment: None,
+  };
+
+  let expired = Statement {
+    fingerprint,
+    timestamp: None,
+    expires: Some(1),
+    purpose: None,
+    comment: None,
+  };
+
+  server
+    .post(format!("/api/package/{fingerprint}"))
+    .body(
+      api::package::Request {
+        signatures: BTreeSet::from([alice.sign(&statement), bob.sign(&expired)]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .status(StatusCode::FORBIDDEN)
+    .assert_body(format!(
+      "package {fingerprint} does not satisfy signature policy"
+    ))
+    .send();
+}
+
+#[test]
+fn verify_package_policy_ignores_revoked_signatures() {
+  let alice = PrivateKey::generate();
+  let bob = PrivateKey::generate();
+
+  let server = TestServer::builder()
+    .policy(&format!(
+      "
+keys:
+  alice: {}
+  bob: {}
+rules:
+  release:
+    keys: [alice, bob]
+    threshold: 2
+",
+      alice.public_key(),
+      bob.public_key(),
+    ))
+    .build();
+
+  let package = PackageBuilder::new().file("foo", b"foo");
+
+  let fingerprint = package.fingerprint();
+
+  package.root.upload(&server);
+
+  server
+    .post("/api/revocation")
+    .body(Revocation::new(&bob, 1000, None).encode_to_vec())
+    .send();
+
+  let statement = Statement {
+    fingerprint,
+    timestamp: None,
+    expires: None,
+    purpose: None,
+    comment: None,
+  };
+
+  let revoked = Statement {
+    fingerprint,
+    timestamp: Some(2000),
+    expires: None,
+    purpose: None,
+    comment: None,
+  };
+
+  server
+    .post(format!("/api/package/{fingerprint}"))
+    .body(
+      api::package::Request {
+        signatures: BTreeSet::from([alice.sign(&statement), bob.sign(&revoked)]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .status(StatusCode::FORBIDDEN)
+    .assert_body(format!(
+      "package {fingerprint} does not satisfy signature policy"
+    ))
+    .send();
+}
+
+#[test]
+fn verify_package_signature_invalid() {
+  let server = TestServer::new();
+
+  let key = PrivateKey::generate();
+
+  let fingerprint = PackageBuilder::new().file("foo", b"foo").upload(&server);
+
+  let signature = key.sign(&Statement {
+    fingerprint: test::FINGERPRINT.parse().unwrap(),
+    timestamp: None,
+    expires: None,
+    purpose: None,
+    comment: None,
+  });
+
+  server
+    .post(format!("/api/package/{fingerprint}"))
+    .body(
+      api::package::Request {
+        signatures: BTreeSet::from([signature]).into(),
+      }
+      .encode_to_vec(),
+    )
+    .status(StatusCode::BAD_REQUEST)
+    .assert_body(format!(
+      "invalid signature for package {fingerprint} by key {}",
+      key.public_key(),
+    ))
+    .send();
+}
+
 #[test]
 fn verify_package_unverified() {
   let server = TestServer::new();
@@ -2,10 +2,47 @@ use super::*;
 
 #[derive(Parser)]
 pub(crate) struct Sign {
+  #[arg(help = "Include <COMMENT> in signature", long)]
+  comment: Option<Comment>,
+  #[arg(
+    help = "Write signature to detached signature file <PATH> instead of manifest",
+    long,
+    value_name = "PATH"
+  )]
+  detached: Option<Utf8PathBuf>,
+  #[arg(
+    help = "Make signature expire at <TIME>, an RFC 3339 timestamp",
+    long,
+    value_name = "TIME"
+  )]
+  expires: Option<jiff::Timestamp>,
   #[arg(default_value_t = KeyName::DEFAULT, help = "Sign with <KEY>", long)]
   key: KeyName,
   #[arg(help = MANIFEST_PATH_HELP)]
   path: Option<Utf8PathBuf>,
+  #[arg(help = "State that signature is for <PURPOSE>", long)]
+  purpose: Option<Purpose>,
+  #[arg(
+    conflicts_with_all = ["key", "ssh_key"],
+    help = "Sign with first Ed25519 key in ssh-agent listening on `SSH_AUTH_SOCK`",
+    long,
+  )]
+  ssh_agent: bool,
+  #[arg(
+    help = "Sign with ssh-agent key <PUBKEY>, an OpenSSH Ed25519 public key",
+    long,
+    requires = "ssh_agent",
+    value_name = "PUBKEY",
+    value_parser = ssh::parse_public_key,
+  )]
+  ssh_agent_key: Option<PublicKey>,
+  #[arg(
+    conflicts_with = "key",
+    help = "Sign with unencrypted OpenSSH Ed25519 private key at <PATH>",
+    long,
+    value_name = "PATH"
+  )]
+  ssh_key: Option<Utf8PathBuf>,
   #[arg(help = TIMESTAMP_HELP, long)]
   timestamp: bool,
 }
@@ -14,18 +51,36 @@ impl Sign {
   pub(crate) fn run(self, options: Options) -> Result {
     let (path, mut manifest) = Manifest::load_with_opt_path(self.path.as_deref())?;
 
-    let keychain = Keychain::load(&options)?;
+    let keychain;
 
-    manifest.sign(
-      SignOptions {
-        timestamp: self.timestamp,
-      },
-      &keychain,
-      &self.key,
-    )?;
+    let signer = if self.ssh_agent {
+      Signer::SshAgent(self.ssh_agent_key)
+    } else if let Some(ssh_key) = &self.ssh_key {
+      Signer::PrivateKey(ssh::load_private_key(ssh_key)?)
+    } else {
+      keychain = Keychain::load(&options)?;
+      Signer::Keychain {
+        key: &self.key,
+        keychain: &keychain,
+      }
+    };
 
-    manifest.save(&path)?;
+    let options = SignOptions {
+      comment: self.comment,
+      expires: self.expires,
+      purpose: self.purpose,
+      timestamp: self.timestamp,
+    };
 
-    Ok(())
+    if let Some(detached) = &self.detached {
+      let mut file = SignatureFile::load_opt(detached)?;
+      file
+        .signatures
+        .insert(signer.sign(&manifest.statement(options)?)?);
+      file.save(detached)
+    } else {
+      manifest.sign(options, &signer)?;
+      manifest.save(&path)
+    }
   }
 }
@@ -2,6 +2,12 @@ use super::*;
 
 #[derive(Parser)]
 pub(crate) struct Signatures {
+  #[arg(
+    help = "Add signatures from detached signature file <FILE> to manifest",
+    long = "add",
+    value_name = "FILE"
+  )]
+  add: Vec<Utf8PathBuf>,
   #[arg(long = "format", default_value_t)]
   format: Format,
   #[arg(help = MANIFEST_PATH_HELP)]
@@ -9,21 +15,49 @@ pub(crate) struct Signatures {
 }
 
 #[derive(Serialize)]
-struct Output {
+struct Output<'a> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  comment: Option<&'a Comment>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  expires: Option<u64>,
   public_key: PublicKey,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  purpose: Option<Purpose>,
   timestamp: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  trusted: Option<&'a KeyName>,
 }
 
 impl Signatures {
-  pub(crate) fn run(self) -> Result {
+  pub(crate) fn run(self, options: Options) -> Result {
+    if !self.add.is_empty() {
+      let (path, mut manifest) = Manifest::load_with_opt_path(self.path.as_deref())?;
+
+      for detached in &self.add {
+        manifest
+          .signatures
+          .extend(SignatureFile::load(detached)?.signatures);
+      }
+
+      manifest.verify_signatures()?;
+
+      return manifest.save(&path);
+    }
+
     let manifest = Manifest::load(self.path.as_deref())?;
 
+    let trust_store = TrustStore::load(&options)?;
+
     let signatures = manifest
       .signatures
       .iter()
       .map(|signature| Output {
+        comment: signature.statement().comment.as_ref(),
+        expires: signature.statement().expires,
         public_key: signature.public_key(),
+        purpose: signature.statement().purpose,
         timestamp: signature.statement().timestamp,
+        trusted: trust_store.name(signature.public_key()),
       })
       .collect::<Vec<Output>>();
 
@@ -32,11 +66,19 @@ impl Signatures {
       Format::JsonPretty => println!("{}", serde_json::to_string_pretty(&signatures).unwrap()),
       Format::Tsv => {
         for signature in &signatures {
-          let timestamp = signature
-            .timestamp
-            .map(|t| t.to_string())
-            .unwrap_or_default();
-          println!("{}\t{}", signature.public_key, timestamp);
+          fn column(value: Option<impl ToString>) -> String {
+            value.map(|value| value.to_string()).unwrap_or_default()
+          }
+
+          println!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            signature.public_key,
+            column(signature.timestamp),
+            column(signature.trusted),
+            column(signature.expires),
+            column(signature.purpose),
+            column(signature.comment),
+          );
         }
       }
     }
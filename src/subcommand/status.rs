@@ -0,0 +1,169 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Status {
+  #[arg(long = "format", default_value_t)]
+  format: Format,
+  #[arg(help = "Ignore <PATH>", long, value_name = "PATH")]
+  ignore: Vec<RelativePath>,
+  #[arg(
+    help = "Read manifest from <MANIFEST>, defaults to `<ROOT>/manifest.filepack`",
+    long
+  )]
+  manifest: Option<Utf8PathBuf>,
+  #[arg(
+    help = "Compare files in <ROOT> directory against manifest, defaults to current directory"
+  )]
+  root: Option<Utf8PathBuf>,
+  #[arg(help = "Compare file sizes instead of hashing file contents", long)]
+  size_only: bool,
+}
+
+#[derive(Default, Serialize)]
+struct Output {
+  missing: BTreeSet<RelativePath>,
+  missing_directories: BTreeSet<RelativePath>,
+  modified: BTreeSet<RelativePath>,
+  unchanged: u64,
+  untracked: BTreeSet<RelativePath>,
+  untracked_directories: BTreeSet<RelativePath>,
+}
+
+impl Status {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let root = if let Some(root) = self.root {
+      root
+    } else {
+      current_dir()?
+    };
+
+    let source = self
+      .manifest
+      .unwrap_or_else(|| root.join(Manifest::FILENAME));
+
+    let manifest = Manifest::load_with_path(&source)?;
+
+    let ignored = |path: &RelativePath| self.ignore.iter().any(|ignore| path.starts_with(ignore));
+
+    let walk = Walk::new(&root, &source, &self.ignore)?;
+
+    let mut files = manifest.files();
+
+    files.retain(|path, _file| !ignored(path));
+
+    let mut output = Output::default();
+
+    let mut hash = BTreeMap::new();
+
+    for (path, size) in &walk.files {
+      match files.get(path) {
+        Some(file) if file.size != *size => {
+          output.modified.insert(path.clone());
+        }
+        Some(file) if !self.size_only => {
+          hash.insert(path, *file);
+        }
+        Some(_file) => output.unchanged += 1,
+        None => {
+          output.untracked.insert(path.clone());
+        }
+      }
+    }
+
+    for path in files.keys() {
+      if !walk.files.contains_key(path) {
+        output.missing.insert(path.clone());
+      }
+    }
+
+    let bar = progress_bar::new(&options, hash.values().map(|file| file.size).sum::<u64>());
+
+    for (path, expected) in hash {
+      let actual = options
+        .hash_file(&root.join(path))
+        .context(error::FilesystemIo { path })?;
+
+      if actual.matches(expected) {
+        output.unchanged += 1;
+      } else {
+        output.modified.insert(path.clone());
+      }
+
+      bar.inc(expected.size);
+    }
+
+    let mut symlinks = manifest.symlinks();
+
+    symlinks.retain(|path, _symlink| !ignored(path));
+
+    for (path, symlink) in &walk.symlinks {
+      match symlinks.get(path) {
+        Some(expected) if expected == symlink => output.unchanged += 1,
+        Some(_expected) => {
+          output.modified.insert(path.clone());
+        }
+        None => {
+          output.untracked.insert(path.clone());
+        }
+      }
+    }
+
+    for path in symlinks.keys() {
+      if !walk.symlinks.contains_key(path) {
+        output.missing.insert(path.clone());
+      }
+    }
+
+    let mut empty = manifest.empty_directories();
+
+    empty.retain(|path| !ignored(path));
+
+    for path in &walk.empty {
+      if !empty.contains(path) {
+        output.untracked_directories.insert(path.clone());
+      }
+    }
+
+    for path in empty {
+      if !walk.empty.contains(&path) {
+        output.missing_directories.insert(path);
+      }
+    }
+
+    match self.format {
+      Format::Json => println!("{}", serde_json::to_string(&output).unwrap()),
+      Format::JsonPretty => println!("{}", serde_json::to_string_pretty(&output).unwrap()),
+      Format::Tsv => {
+        for path in &output.missing {
+          println!("missing\t{path}");
+        }
+
+        for path in &output.missing_directories {
+          println!("missing-directory\t{path}");
+        }
+
+        for path in &output.modified {
+          println!("modified\t{path}");
+        }
+
+        for path in &output.untracked {
+          println!("untracked\t{path}");
+        }
+
+        for path in &output.untracked_directories {
+          println!("untracked-directory\t{path}");
+        }
+      }
+    }
+
+    eprintln!(
+      "{} modified, {} missing, {} untracked, {} unchanged",
+      output.modified.len(),
+      output.missing.len() + output.missing_directories.len(),
+      output.untracked.len() + output.untracked_directories.len(),
+      output.unchanged,
+    );
+
+    Ok(())
+  }
+}
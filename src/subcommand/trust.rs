@@ -0,0 +1,25 @@
+use super::*;
+
+mod add;
+mod list;
+mod remove;
+
+#[derive(clap::Subcommand)]
+pub(crate) enum Trust {
+  #[command(about = "Trust public key under name")]
+  Add(add::Add),
+  #[command(about = "List trusted public keys")]
+  List(list::List),
+  #[command(about = "Remove trusted public key")]
+  Remove(remove::Remove),
+}
+
+impl Trust {
+  pub(crate) fn run(self, options: Options) -> Result {
+    match self {
+      Self::Add(add) => add.run(options),
+      Self::List(list) => list.run(options),
+      Self::Remove(remove) => remove.run(options),
+    }
+  }
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Add {
+  #[arg(help = "Record <COMMENT> with key", long)]
+  comment: Option<String>,
+  #[arg(help = "Trust public key <KEY>", index = 2)]
+  key: PublicKey,
+  #[arg(help = "Trust key under name <NAME>", index = 1)]
+  name: KeyName,
+  #[arg(
+    help = "Only trust signatures made at or after <TIME>, e.g., `2025-01-01T00:00:00Z`",
+    long,
+    value_name = "TIME"
+  )]
+  valid_after: Option<jiff::Timestamp>,
+  #[arg(
+    help = "Only trust signatures made before <TIME>, e.g., `2026-01-01T00:00:00Z`",
+    long,
+    value_name = "TIME"
+  )]
+  valid_before: Option<jiff::Timestamp>,
+}
+
+impl Add {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let keychain = Keychain::load(&options)?;
+
+    ensure! {
+      !keychain.keys.contains_key(&self.name),
+      error::TrustedKeyInKeychain { name: self.name },
+    }
+
+    let mut trust_store = TrustStore::load(&options)?;
+
+    trust_store.add(
+      self.name,
+      TrustedKey {
+        comment: self.comment,
+        public_key: self.key,
+        valid_after: self.valid_after,
+        valid_before: self.valid_before,
+      },
+    )?;
+
+    trust_store.save()
+  }
+}
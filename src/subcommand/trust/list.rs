@@ -0,0 +1,50 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct List {
+  #[arg(long = "format", default_value_t)]
+  format: Format,
+}
+
+#[derive(Serialize)]
+struct Output<'a> {
+  #[serde(flatten)]
+  key: &'a TrustedKey,
+  name: &'a KeyName,
+}
+
+impl List {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let trust_store = TrustStore::load(&options)?;
+
+    let keys = trust_store
+      .keys
+      .iter()
+      .map(|(name, key)| Output { key, name })
+      .collect::<Vec<Output>>();
+
+    match self.format {
+      Format::Json => println!("{}", serde_json::to_string(&keys).unwrap()),
+      Format::JsonPretty => println!("{}", serde_json::to_string_pretty(&keys).unwrap()),
+      Format::Tsv => {
+        for Output { key, name } in &keys {
+          println!(
+            "{name}\t{}\t{}\t{}\t{}",
+            key.public_key,
+            key
+              .valid_after
+              .map(|time| time.to_string())
+              .unwrap_or_default(),
+            key
+              .valid_before
+              .map(|time| time.to_string())
+              .unwrap_or_default(),
+            key.comment.as_deref().unwrap_or_default(),
+          );
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
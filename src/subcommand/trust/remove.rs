@@ -0,0 +1,17 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Remove {
+  #[arg(help = "Remove trusted key <NAME>")]
+  name: KeyName,
+}
+
+impl Remove {
+  pub(crate) fn run(self, options: Options) -> Result {
+    let mut trust_store = TrustStore::load(&options)?;
+
+    trust_store.remove(&self.name)?;
+
+    trust_store.save()
+  }
+}
@@ -0,0 +1,106 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Unbundle {
+  #[arg(help = "Read bundle from <BUNDLE>")]
+  bundle: Utf8PathBuf,
+  #[arg(help = "Unbundle to <OUTPUT>")]
+  output: Utf8PathBuf,
+}
+
+impl Unbundle {
+  pub(crate) fn run(self, options: Options) -> Result {
+    ensure! {
+      !filesystem::exists(&self.output)?,
+      error::FileAlreadyExists { path: &self.output },
+    }
+
+    let context = error::BundleRead { path: &self.bundle };
+
+    let mut bundle =
+      BundleReader::new(io::BufReader::new(filesystem::open(&self.bundle)?)).context(context)?;
+
+    let encoded = bundle.archive().encode_to_vec();
+
+    let manifest = bundle.manifest().clone();
+
+    let files = manifest.files();
+
+    let mut paths = BTreeMap::<Hash, Vec<&RelativePath>>::new();
+
+    for (path, file) in &files {
+      paths.entry(file.hash).or_default().push(path);
+    }
+
+    let bar = progress_bar::new(
+      &options,
+      manifest
+        .contents()
+        .values()
+        .map(|(_path, file)| file.size)
+        .sum(),
+    );
+
+    filesystem::create_dir_all(&self.output)?;
+
+    for path in manifest.empty_directories() {
+      filesystem::create_dir_all(&self.output.join(path))?;
+    }
+
+    for (hash, content) in &manifest.embedded {
+      for path in &paths[hash] {
+        let destination = self.output.join(path);
+        filesystem::create_dir_all(destination.parent().unwrap())?;
+        filesystem::write(&destination, content)?;
+      }
+    }
+
+    while let Some((hash, mut reader)) = bundle.next().context(context)? {
+      let mut paths = paths[&hash].iter().map(|path| self.output.join(path));
+
+      let first = paths.next().unwrap();
+
+      let directory = first.parent().unwrap();
+
+      filesystem::create_dir_all(directory)?;
+
+      let mut tempfile =
+        transfer_tempfile(hash, directory).context(error::FilesystemIo { path: directory })?;
+
+      io::copy(&mut reader, &mut bar.wrap_write(&mut tempfile))
+        .context(error::FilesystemIo { path: &first })?;
+
+      tempfile
+        .persist_noclobber(&first)
+        .map_err(|error| error.error)
+        .context(error::FilesystemIo { path: &first })?;
+
+      for destination in paths {
+        filesystem::create_dir_all(destination.parent().unwrap())?;
+        fs::copy(&first, &destination).context(error::FilesystemIo { path: &destination })?;
+      }
+    }
+
+    bundle.finish().context(context)?;
+
+    for (path, file) in &files {
+      if file.executable {
+        filesystem::set_executable(&self.output.join(path))?;
+      }
+    }
+
+    for (path, symlink) in manifest.symlinks() {
+      let destination = self.output.join(&path);
+
+      filesystem::create_dir_all(destination.parent().unwrap())?;
+
+      filesystem::symlink(&symlink.link(&path), &destination)?;
+    }
+
+    filesystem::write(&self.output.join(Manifest::FILENAME), encoded)?;
+
+    bar.finish();
+
+    Ok(())
+  }
+}
@@ -14,11 +14,17 @@ struct Context {
 pub(crate) struct Upload {
   #[arg(help = "Authenticate with key <KEY>", long, value_name = "KEY")]
   auth: Option<KeyName>,
+  #[arg(
+    conflicts_with = "file",
+    help = "Upload bundle in a single request instead of package",
+    long
+  )]
+  bundle: bool,
   #[arg(help = "Upload file instead of package", long)]
   file: bool,
   #[arg(
     help = "Upload <PATH>, defaults to current directory for packages",
-    required_if_eq("file", "true"),
+    required_if_eq_any([("bundle", "true"), ("file", "true")]),
     value_name = "PATH"
   )]
   input: Option<Utf8PathBuf>,
@@ -30,13 +36,33 @@ impl Upload {
   pub(crate) fn run(self, options: Options) -> Result {
     let client = Client::new(&options, self.server.clone(), self.auth.as_ref())?;
 
-    if self.file {
+    if self.bundle {
+      self.upload_bundle(&options, &client)
+    } else if self.file {
       self.upload_file(&options, &client)
     } else {
       self.upload_package(options, client)
     }
   }
 
+  fn upload_bundle(&self, options: &Options, client: &Client) -> Result {
+    let input = self.input.as_deref().unwrap();
+
+    let size = filesystem::metadata(input)?.len();
+
+    let bar = progress_bar::new(options, size);
+
+    let file = filesystem::open(input)?;
+
+    let body = Body::sized(bar.wrap_read(file), size);
+
+    client.put_bundle(body)?;
+
+    bar.finish();
+
+    Ok(())
+  }
+
   fn upload_directory(context: &mut Context, file_path: &Utf8Path, hash: Hash) -> Result {
     let error_context = error::UnarchiveManifest {
       path: &context.path,
@@ -54,9 +80,9 @@ impl Upload {
       let file_path = file_path.join(component);
       match entry {
         Entry::Directory { hash, .. } => Self::upload_directory(context, &file_path, *hash)?,
-        Entry::File { hash, .. } => {
+        Entry::File { hash, size, .. } => {
           if context.missing.contains(hash) {
-            Self::upload_package_file(context, entry, &file_path)?;
+            Self::upload_package_file(context, *hash, *size, &file_path)?;
             context.files_uploaded += 1;
             context
               .progress_bar
@@ -66,6 +92,7 @@ impl Upload {
               ));
           }
         }
+        Entry::Symlink { .. } => {}
       }
     }
 
@@ -77,7 +104,7 @@ impl Upload {
   fn upload_file(&self, options: &Options, client: &Client) -> Result {
     let input = self.input.as_deref().unwrap();
 
-    let File { hash, size } = options
+    let File { hash, size, .. } = options
       .hash_file(input)
       .context(error::FilesystemIo { path: input })?;
 
@@ -101,16 +128,20 @@ impl Upload {
 
     let fingerprint = archive.fingerprint().context(error_context)?;
 
+    let manifest = archive.unpack().context(error_context)?;
+
     if client.has_package(fingerprint)? {
       if !options.quiet {
         eprintln!("server already has package");
       }
 
+      if !manifest.signatures.is_empty() {
+        client.add_signatures(fingerprint, &manifest.signatures)?;
+      }
+
       return Ok(());
     }
 
-    let manifest = archive.unpack().context(error_context)?;
-
     let manifest_files = manifest.files();
 
     let hashes = manifest_files
@@ -158,19 +189,21 @@ impl Upload {
 
     Self::upload_directory(&mut context, &root, fingerprint.into())?;
 
-    context.client.verify_package(fingerprint)?;
+    context
+      .client
+      .verify_package(fingerprint, &manifest.signatures)?;
 
     context.progress_bar.finish();
 
     Ok(())
   }
 
-  fn upload_package_file(context: &Context, expected: &Entry, path: &Utf8Path) -> Result {
+  fn upload_package_file(context: &Context, hash: Hash, size: u64, path: &Utf8Path) -> Result {
     let file = filesystem::open(path)?;
 
-    let body = Body::sized(context.progress_bar.wrap_read(file), expected.size());
+    let body = Body::sized(context.progress_bar.wrap_read(file), size);
 
-    context.client.put_file(expected.hash(), body)?;
+    context.client.put_file(hash, body)?;
 
     Ok(())
   }
@@ -1,7 +1,23 @@
 use super::*;
 
+type Keys<'a> = BTreeMap<PublicKey, (&'a KeyIdentifier, Vec<(PublicKey, Option<&'a TrustedKey>)>)>;
+
+#[derive(Default)]
+struct Verified {
+  bytes: u128,
+  files: u64,
+  signatures: u64,
+}
+
 #[derive(Parser)]
 pub(crate) struct Verify {
+  #[arg(
+    conflicts_with_all = ["ignore", "ignore_missing", "manifest", "root", "trust_cache"],
+    help = "Verify bundle <BUNDLE> in place, without unpacking it",
+    long,
+    value_name = "BUNDLE"
+  )]
+  bundle: Option<Utf8PathBuf>,
   #[arg(help = "Verify package fingerprint is <FINGERPRINT>", long)]
   fingerprint: Option<Fingerprint>,
   #[arg(help = "Ignore <PATH>", long, value_name = "PATH")]
@@ -19,27 +35,213 @@ pub(crate) struct Verify {
     long
   )]
   manifest: Option<Utf8PathBuf>,
+  #[arg(
+    help = "Verify that manifest signatures satisfy policy <POLICY>",
+    long,
+    value_name = "POLICY"
+  )]
+  policy: Option<Utf8PathBuf>,
   #[arg(help = "Print manifest if verification is successful", long)]
   print: bool,
+  #[arg(
+    help = "Only count signatures with purpose <PURPOSE> towards `--key` and `--policy`",
+    long
+  )]
+  purpose: Option<Purpose>,
   #[arg(help = "Verify files in <ROOT> directory against manifest, defaults to current directory")]
   root: Option<Utf8PathBuf>,
+  #[arg(
+    help = "Verify detached signatures in <FILE> in addition to signatures in manifest",
+    long = "signature",
+    value_name = "FILE"
+  )]
+  signatures: Vec<Utf8PathBuf>,
+  #[arg(
+    help = "Use cached hashes for files which have not changed since they were last hashed",
+    long
+  )]
+  trust_cache: bool,
 }
 
 impl Verify {
-  pub(crate) fn run(self, options: Options) -> Result {
-    #[derive(Default)]
-    struct Verified {
-      bytes: u128,
-      files: u64,
-      signatures: u64,
+  fn add_detached_signatures(&self, manifest: &mut Manifest) -> Result {
+    for path in &self.signatures {
+      manifest
+        .signatures
+        .extend(SignatureFile::load(path)?.signatures);
+    }
+
+    Ok(())
+  }
+
+  fn check_fingerprint(&self, source: &Utf8Path, fingerprint: Fingerprint) -> Result {
+    if let Some(expected) = self.fingerprint
+      && fingerprint != expected
+    {
+      let style = Style::stderr();
+      eprintln!(
+        "\
+fingerprint mismatch: `{source}`
+            expected: {}
+              actual: {}",
+        expected.style(style.good()),
+        fingerprint.style(style.bad()),
+      );
+      return Err(error::FingerprintMismatch.build());
+    }
+
+    Ok(())
+  }
+
+  fn finish(
+    &self,
+    manifest: &Manifest,
+    keys: Keys,
+    policy: Option<&Policy>,
+    revocations: &RevocationStore,
+    verified: &Verified,
+  ) -> Result {
+    for signature in &manifest.signatures {
+      if let Some(revocation) = revocations.revoked(signature) {
+        return Err(
+          error::SignatureRevoked {
+            public_key: signature.public_key(),
+            revoked: revocation.timestamp(),
+          }
+          .build(),
+        );
+      }
+    }
+
+    let now = now()?;
+
+    let timestamp = jiff::Timestamp::from_second(now.try_into().unwrap_or(i64::MAX))
+      .unwrap_or(jiff::Timestamp::MAX);
+
+    let signatures = manifest
+      .signatures
+      .iter()
+      .filter(|signature| {
+        let statement = signature.statement();
+        !statement.is_expired_at(now)
+          && self
+            .purpose
+            .is_none_or(|purpose| statement.purpose == Some(purpose))
+      })
+      .collect::<Vec<&Signature>>();
+
+    for (identifier, accepted) in keys.into_values() {
+      let trusted = |signature: &Signature| {
+        accepted
+          .iter()
+          .find(|(key, _trusted)| *key == signature.public_key())
+          .map(|(_key, trusted)| *trusted)
+      };
+
+      let candidates = signatures
+        .iter()
+        .filter_map(|signature| Some((*signature, trusted(signature)?)))
+        .collect::<Vec<(&Signature, Option<&TrustedKey>)>>();
+
+      if candidates.is_empty() {
+        let signature = manifest
+          .signatures
+          .iter()
+          .find(|signature| trusted(signature).is_some())
+          .context(error::SignatureMissing {
+            identifier: identifier.clone(),
+          })?;
+
+        if let Some(expires) = signature.statement().expires
+          && expires <= now
+        {
+          return Err(
+            error::SignatureExpired {
+              expires: jiff::Timestamp::from_second(expires.try_into().unwrap_or(i64::MAX))
+                .unwrap_or(jiff::Timestamp::MAX),
+              identifier: identifier.clone(),
+            }
+            .build(),
+          );
+        }
+
+        return Err(
+          error::SignaturePurposeMismatch {
+            identifier: identifier.clone(),
+            purpose: self.purpose.unwrap(),
+          }
+          .build(),
+        );
+      }
+
+      ensure! {
+        candidates.iter().any(|(signature, trusted)| {
+          trusted.is_none_or(|trusted| trusted.is_valid_for(signature, timestamp))
+        }),
+        error::SignatureOutsideValidity { identifier: identifier.clone() },
+      }
+    }
+
+    if let Some(policy) = policy {
+      let evaluation = policy.evaluate(signatures.iter().copied());
+
+      eprint!("{evaluation}");
+
+      ensure! {
+        evaluation.is_satisfied(),
+        error::PolicyUnsatisfied { path: self.policy.as_ref().unwrap() },
+      }
+    }
+
+    if self.print {
+      println!("{}", serde_json::to_string_pretty(manifest).unwrap());
+    }
+
+    eprint!(
+      "successfully verified {}",
+      Count::new(verified.files, "file")
+    );
+
+    if verified.files > 0 {
+      eprint!(" totaling {}", Count::new(verified.bytes, "byte"));
+    }
+
+    if verified.signatures > 0 {
+      eprint!(" with {}", Count::new(verified.signatures, "signature"));
+    }
+
+    eprintln!();
+
+    Ok(())
+  }
+
+  pub(crate) fn run(self, mut options: Options) -> Result {
+    if self.trust_cache {
+      options.enable_cache()?;
     }
 
     let keychain = Keychain::load(&options)?;
 
+    let trust_store = TrustStore::load(&options)?;
+
+    let revocations = RevocationStore::load(&options)?;
+
     let mut keys = BTreeMap::new();
     for second in &self.keys {
-      let key = keychain.identifier_public_key(second)?;
-      if let Some(first) = keys.insert(key, second) {
+      let (key, trusted) = trust_store.resolve(&keychain, second)?;
+
+      let accepted = iter::once((key, trusted))
+        .chain(
+          revocations
+            .successors(key, |successor| {
+              keychain.name(successor).is_some() || trust_store.trusted(successor).is_some()
+            })
+            .into_iter()
+            .map(|successor| (successor, trust_store.trusted(successor))),
+        )
+        .collect();
+
+      if let Some((first, _accepted)) = keys.insert(key, (second, accepted)) {
         return Err(
           error::DuplicateKey {
             first: first.clone(),
@@ -50,6 +252,12 @@ impl Verify {
       }
     }
 
+    let policy = self.policy.as_deref().map(Policy::load).transpose()?;
+
+    if let Some(bundle) = &self.bundle {
+      return self.verify_bundle(&options, bundle, keys, policy.as_ref(), &revocations);
+    }
+
     let current_dir = current_dir()?;
 
     let root = self.root.clone().unwrap_or_else(|| current_dir.clone());
@@ -71,44 +279,39 @@ impl Verify {
 
     let archive = Archive::load(&source)?;
 
-    let (manifest, totals) = archive
+    let (mut manifest, totals) = archive
       .unpack_with_totals()
       .context(error::UnarchiveManifest { path: &source })?;
 
+    self.add_detached_signatures(&mut manifest)?;
+
     manifest.verify_signatures()?;
 
     let mut verified = Verified::default();
 
     verified.signatures += manifest.signatures.len().into_u64();
 
-    let fingerprint = manifest.fingerprint();
-
-    if let Some(expected) = self.fingerprint
-      && fingerprint != expected
-    {
-      let style = Style::stderr();
-      eprintln!(
-        "\
-fingerprint mismatch: `{source}`
-            expected: {}
-              actual: {}",
-        expected.style(style.good()),
-        fingerprint.style(style.bad()),
-      );
-      return Err(error::FingerprintMismatch.build());
-    }
+    self.check_fingerprint(&source, manifest.fingerprint())?;
 
     let bar = progress_bar::new(&options, totals.file_size);
 
     let mut mismatches = BTreeMap::new();
 
+    let mut ignore_files = BTreeSet::new();
+
     let files = manifest.files();
 
+    let symlinks = manifest.symlinks();
+
     let manifest_empty = manifest.empty_directories();
 
     for ignore in &self.ignore {
       ensure! {
-        !files.keys().chain(&manifest_empty).any(|path| path.starts_with(ignore)),
+        !files
+          .keys()
+          .chain(symlinks.keys())
+          .chain(&manifest_empty)
+          .any(|path| path.starts_with(ignore)),
         error::IgnoredPath { path: ignore.clone() },
       }
     }
@@ -125,7 +328,10 @@ fingerprint mismatch: `{source}`
         result => result.context(error::FilesystemIo { path: &path })?,
       };
 
-      if actual == *expected {
+      if actual.matches(*expected) {
+        if *path.filename() == Walk::IGNORE_FILENAME {
+          ignore_files.insert(path);
+        }
         verified.files += 1;
         verified.bytes += u128::from(expected.size);
       } else {
@@ -135,63 +341,85 @@ fingerprint mismatch: `{source}`
       bar.inc(expected.size);
     }
 
-    if !mismatches.is_empty() {
+    options.commit_cache()?;
+
+    let mut symlink_mismatches = BTreeMap::new();
+
+    for (path, expected) in &symlinks {
+      let link = root.join(path);
+
+      let metadata = match link.symlink_metadata() {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+          ensure! {
+            self.ignore_missing,
+            error::MissingFile { path },
+          }
+          continue;
+        }
+        result => result.context(error::FilesystemIo { path: &link })?,
+      };
+
+      let actual = if metadata.is_symlink() {
+        Some(filesystem::read_link(&link)?)
+      } else {
+        None
+      };
+
+      if actual
+        .as_deref()
+        .and_then(|target| Symlink::resolve(path, target))
+        .as_ref()
+        != Some(expected)
+      {
+        symlink_mismatches.insert(path, (actual, expected));
+      }
+    }
+
+    if !mismatches.is_empty() || !symlink_mismatches.is_empty() {
       for (path, (actual, expected)) in &mismatches {
         File::eprint_mismatch(*actual, **expected, path.as_ref());
       }
 
+      for (path, (actual, expected)) in &symlink_mismatches {
+        Symlink::eprint_mismatch(actual.as_deref(), expected, path.as_ref());
+      }
+
       return Err(
         error::EntryMismatch {
-          count: mismatches.len(),
+          count: mismatches.len() + symlink_mismatches.len(),
         }
         .build(),
       );
     }
 
-    let mut empty = Vec::new();
-
-    for entry in WalkDir::new(&root).sort_by_file_name() {
-      let entry = entry?;
-
-      if entry.depth() == 0 {
-        continue;
-      }
-
-      let path = decode_path(entry.path())?;
-
-      if current_dir.join(path) == current_dir.join(&source) {
-        continue;
-      }
-
-      let path = path.strip_prefix(&root).unwrap();
-
-      let path = RelativePath::try_from(path).context(error::Path { path })?;
-
-      if self.ignore.iter().any(|ignore| path.starts_with(ignore)) {
-        continue;
-      }
+    // only ignore files which are verified package files are honored, so that
+    // an unlisted ignore file cannot hide extraneous files
+    let walk = Walk::with_ignore_files(&root, &source, &self.ignore, |path| {
+      ignore_files.contains(path)
+    })?;
 
-      empty.pop_if(|dir| path.starts_with(dir));
-
-      if entry.file_type().is_dir() {
-        empty.push(path);
-        continue;
+    for path in walk.files.keys() {
+      ensure! {
+        files.contains_key(path),
+        error::ExtraneousFile { path },
       }
+    }
 
+    for path in walk.symlinks.keys() {
       ensure! {
-        files.contains_key(&path),
-        error::ExtraneousFile { path },
+        symlinks.contains_key(path),
+        error::ExtraneousSymlink { path },
       }
     }
 
     for path in &manifest_empty {
       ensure! {
-        empty.iter().any(|dir| dir.starts_with(path)),
+        walk.empty.iter().any(|dir| dir.starts_with(path)),
         error::MissingDirectory { path },
       }
     }
 
-    for path in &empty {
+    for path in &walk.empty {
       ensure! {
         manifest_empty.contains(path),
         error::ExtraneousDirectory { path },
@@ -208,32 +436,65 @@ fingerprint mismatch: `{source}`
       }
     }
 
-    for (key, identifier) in keys {
-      ensure! {
-        manifest.signatures.iter().any(|signature| signature.public_key() == key),
-        error::SignatureMissing { identifier: identifier.clone() },
-      }
-    }
+    self.finish(&manifest, keys, policy.as_ref(), &revocations, &verified)
+  }
 
-    if self.print {
-      println!("{}", serde_json::to_string_pretty(&manifest).unwrap());
-    }
+  fn verify_bundle(
+    &self,
+    options: &Options,
+    path: &Utf8Path,
+    keys: Keys,
+    policy: Option<&Policy>,
+    revocations: &RevocationStore,
+  ) -> Result {
+    let context = error::BundleRead { path };
 
-    eprint!(
-      "successfully verified {}",
-      Count::new(verified.files, "file")
+    let mut bundle =
+      BundleReader::new(io::BufReader::new(filesystem::open(path)?)).context(context)?;
+
+    let mut manifest = bundle.manifest().clone();
+
+    self.add_detached_signatures(&mut manifest)?;
+
+    manifest.verify_signatures()?;
+
+    self.check_fingerprint(path, manifest.fingerprint())?;
+
+    let files = manifest.files();
+
+    let bar = progress_bar::new(
+      options,
+      manifest
+        .contents()
+        .values()
+        .map(|(_path, file)| file.size)
+        .sum(),
     );
 
-    if verified.files > 0 {
-      eprint!(" totaling {}", Count::new(verified.bytes, "byte"));
+    while let Some((_hash, mut reader)) = bundle.next().context(context)? {
+      io::copy(&mut reader, &mut bar.wrap_write(io::sink()))
+        .context(bundle_error::Io)
+        .context(context)?;
     }
 
-    if verified.signatures > 0 {
-      eprint!(" with {}", Count::new(verified.signatures, "signature"));
+    bundle.finish().context(context)?;
+
+    if let Some(cbor) = files
+      .iter()
+      .find(|(path, _file)| **path == Metadata::CBOR_FILENAME)
+      .and_then(|(_path, file)| manifest.embedded.get(&file.hash))
+    {
+      Metadata::decode_from_slice(cbor)
+        .context(error::DecodeMetadataCbor { path })?
+        .check_files(&files.keys().cloned().collect())?;
     }
 
-    eprintln!();
+    let verified = Verified {
+      bytes: files.values().map(|file| u128::from(file.size)).sum(),
+      files: files.len().into_u64(),
+      signatures: manifest.signatures.len().into_u64(),
+    };
 
-    Ok(())
+    self.finish(&manifest, keys, policy, revocations, &verified)
   }
 }
@@ -0,0 +1,126 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Symlink {
+  pub(crate) target: RelativePath,
+}
+
+impl Symlink {
+  pub(crate) fn eprint_mismatch(actual: Option<&Utf8Path>, expected: &Self, path: &str) {
+    let style = Style::stderr();
+
+    eprintln!(
+      "\
+mismatched symlink: `{path}`
+          manifest: {}
+           symlink: {}",
+      expected.target.style(style.good()),
+      actual
+        .map_or_else(|| "not a symlink".into(), Utf8Path::to_string)
+        .style(style.bad()),
+    );
+  }
+
+  pub(crate) fn link(&self, path: &RelativePath) -> Utf8PathBuf {
+    let mut parent = path.components().collect::<Vec<&Component>>();
+    parent.pop();
+
+    let target = self.target.components().collect::<Vec<&Component>>();
+
+    let common = parent
+      .iter()
+      .zip(&target)
+      .take_while(|(a, b)| a == b)
+      .count();
+
+    let mut link = Utf8PathBuf::new();
+
+    for _ in common..parent.len() {
+      link.push("..");
+    }
+
+    for component in &target[common..] {
+      link.push(component.as_str());
+    }
+
+    if link.as_str().is_empty() {
+      link.push(".");
+    }
+
+    link
+  }
+
+  pub(crate) fn resolve(path: &RelativePath, link: &Utf8Path) -> Option<Self> {
+    let mut components = path.components().collect::<Vec<&Component>>();
+    components.pop();
+
+    for component in link.components() {
+      match component {
+        Utf8Component::CurDir => {}
+        Utf8Component::Normal(component) => components.push(Component::new(component).ok()?),
+        Utf8Component::ParentDir => {
+          components.pop()?;
+        }
+        Utf8Component::Prefix(_) | Utf8Component::RootDir => return None,
+      }
+    }
+
+    Some(Self {
+      target: components.as_slice().try_into().ok()?,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn link() {
+    #[track_caller]
+    fn case(path: &str, target: &str, expected: &str) {
+      let path = path.parse::<RelativePath>().unwrap();
+
+      let symlink = Symlink {
+        target: target.parse().unwrap(),
+      };
+
+      let link = symlink.link(&path);
+
+      assert_eq!(link, Utf8Path::new(expected));
+
+      assert_eq!(Symlink::resolve(&path, &link).unwrap(), symlink);
+    }
+
+    case("foo", "bar", "bar");
+    case("foo", "bar/baz", "bar/baz");
+    case("foo/bar", "baz", "../baz");
+    case("foo/bar", "foo/baz", "baz");
+    case("foo/bar/baz", "foo/qux", "../qux");
+    case("foo/bar/baz", "foo", "..");
+    case("foo/bar", "foo", ".");
+  }
+
+  #[test]
+  fn resolve() {
+    #[track_caller]
+    fn case(path: &str, link: &str, expected: Option<&str>) {
+      assert_eq!(
+        Symlink::resolve(&path.parse().unwrap(), Utf8Path::new(link))
+          .map(|symlink| symlink.target.to_string()),
+        expected.map(str::to_owned),
+      );
+    }
+
+    case("foo", "bar", Some("bar"));
+    case("foo", "./bar", Some("bar"));
+    case("foo/bar", "../baz", Some("baz"));
+    case("foo/bar", "baz/../qux", Some("foo/qux"));
+    case("foo", "..", None);
+    case("foo", "../bar", None);
+    case("foo/bar", "../../baz", None);
+    case("foo", ".", None);
+    case("foo", "/bar", None);
+  }
+}
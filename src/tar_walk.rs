@@ -0,0 +1,334 @@
+use {
+  super::*,
+  ignore::gitignore::{Gitignore, GitignoreBuilder},
+  std::io::BufRead,
+  tar::{Archive, EntryType},
+  tempfile::TempDir,
+};
+
+pub(crate) struct TarWalk {
+  pub(crate) empty: Vec<RelativePath>,
+  pub(crate) files: BTreeMap<RelativePath, File>,
+  pub(crate) metadata: Option<String>,
+  pub(crate) paths: Vec<RelativePath>,
+  pub(crate) staging: TempDir,
+  pub(crate) symlinks: BTreeMap<RelativePath, Symlink>,
+}
+
+impl TarWalk {
+  pub(crate) const STDIN: &'static str = "-";
+
+  fn excluded(
+    ignore_files: &HashMap<Utf8PathBuf, Gitignore>,
+    path: &Utf8Path,
+    is_dir: bool,
+  ) -> bool {
+    let ignored = |path: &Utf8Path, is_dir: bool| {
+      Walk::ignored(
+        path
+          .ancestors()
+          .skip(1)
+          .filter_map(|directory| ignore_files.get(directory)),
+        path,
+        is_dir,
+      )
+    };
+
+    ignored(path, is_dir)
+      || path
+        .ancestors()
+        .skip(1)
+        .filter(|directory| !directory.as_str().is_empty())
+        .any(|directory| ignored(directory, true))
+  }
+
+  fn ignore_file(relative: &RelativePath, contents: &str) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(Utf8Path::new(relative).parent().unwrap());
+
+    for line in contents.lines() {
+      builder
+        .add_line(Some(Utf8Path::new(relative).into()), line)
+        .context(error::IgnoreFile { path: relative })?;
+    }
+
+    builder
+      .build()
+      .context(error::IgnoreFile { path: relative })
+  }
+
+  pub(crate) fn new(path: &Utf8Path, ignore: &[RelativePath], bar: &ProgressBar) -> Result<Self> {
+    let staging = TempDir::new().context(error::TempDir)?;
+
+    let mut buffer = vec![0; 64 * KIB];
+    let mut directories = Vec::new();
+    let mut files = BTreeMap::new();
+    let mut ignore_files = HashMap::new();
+    let mut metadata = None;
+    let mut paths = Vec::new();
+    let mut referenced = HashSet::new();
+    let mut seen = HashSet::new();
+    let mut staged = HashSet::new();
+    let mut symlinks = BTreeMap::new();
+
+    let mut archive = Self::open(path, bar)?;
+
+    for entry in archive.entries().context(error::TarRead { path })? {
+      let mut entry = entry.context(error::TarRead { path })?;
+
+      let Some(relative) = Self::relative(path, &entry, ignore)? else {
+        continue;
+      };
+
+      ensure! {
+        seen.insert(relative.clone()),
+        error::TarDuplicateEntry { path: relative },
+      }
+
+      match entry.header().entry_type() {
+        EntryType::Continuous | EntryType::Regular => {
+          let executable =
+            Mode::from(entry.header().mode().context(error::TarRead { path })?).is_executable();
+
+          let file = if relative == Metadata::YAML_FILENAME {
+            let mut yaml = String::new();
+
+            entry
+              .read_to_string(&mut yaml)
+              .context(error::TarRead { path })?;
+
+            referenced = Metadata::deserialize(Metadata::YAML_FILENAME.as_ref(), &yaml)?
+              .files()
+              .into_iter()
+              .collect();
+
+            let file = File {
+              executable,
+              hash: Hash::bytes(yaml.as_bytes()),
+              size: yaml.len().into_u64(),
+            };
+
+            metadata = Some(yaml);
+
+            file
+          } else if Utf8Path::new(&relative).file_name() == Some(Walk::IGNORE_FILENAME) {
+            let mut contents = String::new();
+
+            entry
+              .read_to_string(&mut contents)
+              .context(error::TarRead { path })?;
+
+            let directory = Utf8Path::new(&relative).parent().unwrap().to_owned();
+
+            ignore_files.insert(directory, Self::ignore_file(&relative, &contents)?);
+
+            File {
+              executable,
+              hash: Hash::bytes(contents.as_bytes()),
+              size: contents.len().into_u64(),
+            }
+          } else {
+            let mut output = if referenced.contains(&relative) {
+              staged.insert(relative.clone());
+              Some(Self::stage(&staging, &relative)?)
+            } else {
+              None
+            };
+
+            let mut hasher = Hasher::new();
+
+            loop {
+              let n = entry.read(&mut buffer).context(error::TarRead { path })?;
+
+              if n == 0 {
+                break;
+              }
+
+              hasher.update(&buffer[..n]);
+
+              if let Some((output_path, output)) = &mut output {
+                output
+                  .write_all(&buffer[..n])
+                  .context(error::FilesystemIo {
+                    path: &*output_path,
+                  })?;
+              }
+            }
+
+            File {
+              executable,
+              hash: hasher.finalize().into(),
+              size: hasher.count(),
+            }
+          };
+
+          files.insert(relative.clone(), file);
+        }
+        EntryType::Directory => directories.push(relative.clone()),
+        EntryType::Symlink => {
+          let target = entry
+            .link_name()
+            .context(error::TarRead { path })?
+            .unwrap_or_default();
+
+          let target = decode_path(&target)?;
+
+          let symlink =
+            Symlink::resolve(&relative, target).context(error::SymlinkOutsidePackage {
+              path: &relative,
+              target,
+            })?;
+
+          symlinks.insert(relative.clone(), symlink);
+        }
+        ty => {
+          return Err(
+            error::TarEntryType {
+              path: relative,
+              ty: format!("{ty:?}"),
+            }
+            .build(),
+          );
+        }
+      }
+
+      paths.push(relative);
+    }
+
+    let mut unstaged = files
+      .keys()
+      .filter(|path| referenced.contains(*path) && !staged.contains(*path))
+      .cloned()
+      .collect::<BTreeSet<RelativePath>>();
+
+    if let Some(first) = unstaged.first() {
+      ensure! {
+        path != Self::STDIN,
+        error::TarMetadataOrder { path: first.clone() },
+      }
+
+      let mut archive = Self::open(path, &ProgressBar::hidden())?;
+
+      for entry in archive.entries().context(error::TarRead { path })? {
+        let mut entry = entry.context(error::TarRead { path })?;
+
+        let Some(relative) = Self::relative(path, &entry, ignore)? else {
+          continue;
+        };
+
+        if unstaged.remove(&relative) {
+          let (output_path, mut output) = Self::stage(&staging, &relative)?;
+
+          io::copy(&mut entry, &mut output).context(error::FilesystemIo { path: output_path })?;
+        }
+      }
+    }
+
+    if !ignore_files.is_empty() {
+      let ignored = paths
+        .iter()
+        .filter(|path| {
+          Self::excluded(
+            &ignore_files,
+            path.as_ref(),
+            !files.contains_key(*path) && !symlinks.contains_key(*path),
+          )
+        })
+        .cloned()
+        .collect::<HashSet<RelativePath>>();
+
+      directories.retain(|path| !ignored.contains(path));
+      files.retain(|path, _file| !ignored.contains(path));
+      paths.retain(|path| !ignored.contains(path));
+      symlinks.retain(|path, _symlink| !ignored.contains(path));
+    }
+
+    let mut parents = HashSet::new();
+
+    for path in &paths {
+      parents.extend(
+        Utf8Path::new(path)
+          .ancestors()
+          .skip(1)
+          .map(Utf8Path::to_owned),
+      );
+    }
+
+    let empty = directories
+      .into_iter()
+      .filter(|directory| !parents.contains(Utf8Path::new(directory)))
+      .collect();
+
+    paths.sort();
+
+    Ok(Self {
+      empty,
+      files,
+      metadata,
+      paths,
+      staging,
+      symlinks,
+    })
+  }
+
+  fn open(path: &Utf8Path, bar: &ProgressBar) -> Result<Archive<Box<dyn Read>>> {
+    let reader: Box<dyn Read> = if path == Self::STDIN {
+      Box::new(io::stdin().lock())
+    } else {
+      Box::new(filesystem::open(path)?)
+    };
+
+    let mut reader = BufReader::new(bar.wrap_read(reader));
+
+    let magic = reader.fill_buf().context(error::TarRead { path })?;
+
+    let reader: Box<dyn Read> = if magic.starts_with(&[0x1f, 0x8b]) {
+      Box::new(flate2::bufread::MultiGzDecoder::new(reader))
+    } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+      Box::new(zstd::Decoder::with_buffer(reader).context(error::TarRead { path })?)
+    } else {
+      Box::new(reader)
+    };
+
+    Ok(Archive::new(reader))
+  }
+
+  fn relative(
+    path: &Utf8Path,
+    entry: &tar::Entry<impl Read>,
+    ignore: &[RelativePath],
+  ) -> Result<Option<RelativePath>> {
+    if entry.header().entry_type() == EntryType::XGlobalHeader {
+      return Ok(None);
+    }
+
+    let entry_path = entry.path().context(error::TarRead { path })?;
+
+    let entry_path = decode_path(&entry_path)?
+      .components()
+      .filter(|component| *component != Utf8Component::CurDir)
+      .collect::<Utf8PathBuf>();
+
+    if entry_path.as_str().is_empty() {
+      return Ok(None);
+    }
+
+    let relative =
+      RelativePath::try_from(entry_path.as_path()).context(error::Path { path: &entry_path })?;
+
+    if relative == Manifest::FILENAME || ignore.iter().any(|ignore| relative.starts_with(ignore)) {
+      return Ok(None);
+    }
+
+    Ok(Some(relative))
+  }
+
+  fn stage(staging: &TempDir, relative: &RelativePath) -> Result<(Utf8PathBuf, fs::File)> {
+    let path = decode_path(staging.path())?.join(relative);
+
+    filesystem::create_dir_all(path.parent().unwrap())?;
+
+    let file = fs::File::create(&path).context(error::FilesystemIo { path: &path })?;
+
+    Ok((path, file))
+  }
+}
@@ -4,7 +4,8 @@ pub use self::{directory::DirectoryHtml, package::PackageHtml, page::PageHtml};
 
 pub(crate) use self::{
   audio::AudioHtml, directory_table::DirectoryTableHtml, files::FilesHtml, image::ImageHtml,
-  info::InfoHtml, media::MediaHtml, packages::PackagesHtml, video::VideoHtml,
+  info::InfoHtml, media::MediaHtml, package_table::PackageTableHtml, packages::PackagesHtml,
+  search::SearchHtml, video::VideoHtml,
 };
 
 mod audio;
@@ -15,6 +16,8 @@ mod image;
 mod info;
 mod media;
 mod package;
+mod package_table;
 mod packages;
 mod page;
+mod search;
 mod video;
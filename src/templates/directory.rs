@@ -35,7 +35,13 @@ mod tests {
           },
         },
       )
-      .insert_entry("qux quux.png", Entry::file(Hash::bytes(b"qux"), 1500));
+      .insert_entry("qux quux.png", Entry::file(Hash::bytes(b"qux"), 1500))
+      .insert_entry(
+        "quz",
+        Entry::Symlink {
+          target: "foo/bar".parse().unwrap(),
+        },
+      );
 
     assert_matches_regex!(
       DirectoryHtml {
@@ -70,6 +76,10 @@ mod tests {
                 <td><a href='/file/[[:xdigit:]]{64}/qux%20quux\.png'>qux quux\.png</a></td>
                 <td class=right><a href=/file/[[:xdigit:]]{64} download="qux quux\.png">1\.5 KiB</a></td>
               </tr>
+              <tr>
+                <td><a>quz</a> → <code>foo/bar</code></td>
+                <td></td>
+              </tr>
             </tbody>
           </table>
         "#,
@@ -8,6 +8,7 @@ pub struct PackageHtml {
   pub metadata: Option<Metadata>,
   pub mounted: bool,
   pub readme: Option<Hash>,
+  pub signatures: BTreeSet<Signature>,
   pub totals: Totals,
 }
 
@@ -93,6 +94,7 @@ mod tests {
         metadata: Some(metadata),
         mounted: false,
         readme: None,
+        signatures: BTreeSet::new(),
         totals: Totals {
           directories: 0,
           directory_size: 0,
@@ -203,6 +205,7 @@ mod tests {
         metadata: Some(metadata),
         mounted: false,
         readme: None,
+        signatures: BTreeSet::new(),
         totals: Totals {
           directories: 0,
           directory_size: 0,
@@ -289,6 +292,7 @@ mod tests {
         metadata: Some(metadata),
         mounted: false,
         readme: None,
+        signatures: BTreeSet::new(),
         totals: Totals {
           directories: 0,
           directory_size: 0,
@@ -387,6 +391,7 @@ mod tests {
         metadata: Some(metadata),
         mounted: false,
         readme: None,
+        signatures: BTreeSet::new(),
         totals: Totals {
           directories: 0,
           directory_size: 0,
@@ -465,6 +470,7 @@ mod tests {
       }),
       mounted: false,
       readme: None,
+      signatures: BTreeSet::new(),
       totals: Totals::default(),
     };
 
@@ -488,6 +494,7 @@ mod tests {
       metadata: None,
       mounted: false,
       readme: None,
+      signatures: BTreeSet::new(),
       totals: Totals::default(),
     };
 
@@ -521,6 +528,7 @@ mod tests {
         metadata: Some(metadata),
         mounted: false,
         readme: Some(test::HASH.parse().unwrap()),
+        signatures: BTreeSet::new(),
         totals: Totals {
           directories: 0,
           directory_size: 0,
@@ -581,6 +589,62 @@ mod tests {
     );
   }
 
+  #[test]
+  fn signers() {
+    let fingerprint = test::FINGERPRINT.parse::<Fingerprint>().unwrap();
+
+    let private_key = PrivateKey::generate();
+
+    let signature = private_key.sign(&Statement {
+      fingerprint,
+      timestamp: None,
+      expires: None,
+      purpose: None,
+      comment: None,
+    });
+
+    assert_eq!(
+      PackageHtml {
+        colophon: None,
+        directory: Directory::new(),
+        fingerprint,
+        metadata: None,
+        mounted: false,
+        readme: None,
+        signatures: BTreeSet::from([signature]),
+        totals: Totals::default(),
+      }
+      .to_string(),
+      unindent(&format!(
+        "
+          <h1 class=code>{fingerprint}</h1>
+          <dl>
+            <dt>fingerprint</dt>
+            <dd class=code>{fingerprint}</dd>
+            <dt>size</dt>
+            <dd>0 B</dd>
+            <dt>files</dt>
+            <dd><a href=/directory/{hash}>0 files</a></dd>
+            <dt>signers</dt>
+            <dd class=code>{public_key}</dd>
+          </dl>
+          <table>
+            <thead>
+              <tr>
+                <th>open</th>
+                <th>download</th>
+              </tr>
+            </thead>
+            <tbody>
+            </tbody>
+          </table>
+        ",
+        hash = Hash::from(fingerprint),
+        public_key = private_key.public_key(),
+      )),
+    );
+  }
+
   #[test]
   fn video() {
     let metadata = Metadata {
@@ -626,6 +690,7 @@ mod tests {
         metadata: Some(metadata),
         mounted: false,
         readme: None,
+        signatures: BTreeSet::new(),
         totals: Totals {
           directories: 0,
           directory_size: 0,
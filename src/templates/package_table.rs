@@ -0,0 +1,45 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct PackageTableHtml<'a>(pub(crate) &'a [(Fingerprint, Option<Metadata>, Totals)]);
+
+pub(super) struct Package<'a> {
+  pub(super) artwork: bool,
+  pub(super) creator: Option<&'a str>,
+  pub(super) file_size: u64,
+  pub(super) files: u64,
+  pub(super) fingerprint: Fingerprint,
+  pub(super) media: Option<MediaType>,
+  pub(super) title: Option<&'a str>,
+  pub(super) year: Option<i64>,
+}
+
+impl<'a> PackageTableHtml<'a> {
+  pub(super) fn packages(&self) -> impl Iterator<Item = Package<'a>> + use<'a> {
+    self
+      .0
+      .iter()
+      .map(|(fingerprint, metadata, totals)| Package {
+        artwork: metadata
+          .as_ref()
+          .is_some_and(|metadata| metadata.artwork.is_some()),
+        creator: metadata
+          .as_ref()
+          .and_then(|metadata| metadata.creator.as_deref()),
+        file_size: totals.file_size,
+        files: totals.files,
+        fingerprint: *fingerprint,
+        media: metadata
+          .as_ref()
+          .and_then(|metadata| metadata.media.as_ref())
+          .map(Media::ty),
+        title: metadata
+          .as_ref()
+          .and_then(|metadata| metadata.title.as_deref()),
+        year: metadata
+          .as_ref()
+          .and_then(|metadata| metadata.time.as_ref())
+          .map(Time::year),
+      })
+  }
+}
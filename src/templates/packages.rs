@@ -6,47 +6,6 @@ pub(crate) struct PackagesHtml {
   pub(crate) view: View,
 }
 
-struct Package<'a> {
-  artwork: bool,
-  creator: Option<&'a str>,
-  file_size: u64,
-  files: u64,
-  fingerprint: Fingerprint,
-  media: Option<MediaType>,
-  title: Option<&'a str>,
-  year: Option<i64>,
-}
-
-impl PackagesHtml {
-  fn packages(&self) -> impl Iterator<Item = Package<'_>> {
-    self
-      .packages
-      .iter()
-      .map(|(fingerprint, metadata, totals)| Package {
-        artwork: metadata
-          .as_ref()
-          .is_some_and(|metadata| metadata.artwork.is_some()),
-        creator: metadata
-          .as_ref()
-          .and_then(|metadata| metadata.creator.as_deref()),
-        file_size: totals.file_size,
-        files: totals.files,
-        fingerprint: *fingerprint,
-        media: metadata
-          .as_ref()
-          .and_then(|metadata| metadata.media.as_ref())
-          .map(Media::ty),
-        title: metadata
-          .as_ref()
-          .and_then(|metadata| metadata.title.as_deref()),
-        year: metadata
-          .as_ref()
-          .and_then(|metadata| metadata.time.as_ref())
-          .map(Time::year),
-      })
-  }
-}
-
 impl Page for PackagesHtml {
   fn stylesheet(&self) -> Option<&'static str> {
     Some("/static/packages.css")
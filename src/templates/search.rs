@@ -0,0 +1,116 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct SearchHtml {
+  pub(crate) packages: Vec<(Fingerprint, Option<Metadata>, Totals)>,
+  pub(crate) search: Search,
+  pub(crate) total: u64,
+}
+
+impl SearchHtml {
+  fn pages(&self) -> u64 {
+    self.total.div_ceil(Search::PAGE_SIZE).max(1)
+  }
+}
+
+impl Page for SearchHtml {
+  fn next(&self) -> Option<String> {
+    let page = self.search.page();
+    (page < self.pages()).then(|| self.search.url(page + 1))
+  }
+
+  fn prev(&self) -> Option<String> {
+    let page = self.search.page();
+    (page > 1).then(|| self.search.url(page.min(self.pages()) - 1))
+  }
+
+  fn stylesheet(&self) -> Option<&'static str> {
+    Some("/static/search.css")
+  }
+
+  fn title(&self) -> String {
+    if let Some(q) = &self.search.q {
+      format!("{q} · search · filepack")
+    } else {
+      "search · filepack".into()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq};
+
+  #[test]
+  fn search() {
+    let fingerprint = test::FINGERPRINT.parse::<Fingerprint>().unwrap();
+
+    assert_eq!(
+      SearchHtml {
+        packages: vec![(fingerprint, None, Totals::default())],
+        search: Search {
+          media: Some(MediaType::Image),
+          page: Some(2),
+          q: Some("foo".into()),
+          year: Some("1970..1979".parse().unwrap()),
+          ..default()
+        },
+        total: Search::PAGE_SIZE * 2 + 1,
+      }
+      .to_string(),
+      unindent(&format!(
+        "
+          <header>
+            <h1>Search</h1>
+          </header>
+          <form action=/search>
+            <input aria-label=query name=q placeholder=search type=search value='foo'>
+            <select aria-label=media name=media>
+              <option value=''>any media</option>
+              <option>audio</option>
+              <option selected>image</option>
+              <option>video</option>
+              <option>web</option>
+            </select>
+            <input aria-label=language name=language placeholder=language>
+            <input aria-label=year name=year placeholder=1970..1979 value='1970..1979'>
+            <select aria-label=sort name=sort>
+              <option>time</option>
+              <option selected>title</option>
+            </select>
+            <button>search</button>
+          </form>
+          <p>101 packages</p>
+          <table>
+            <thead>
+              <tr>
+                <th>title</th>
+                <th>creator</th>
+                <th>year</th>
+                <th>media</th>
+                <th class=right>files</th>
+                <th class=right>size</th>
+              </tr>
+            </thead>
+            <tbody>
+              <tr>
+                <td><a href=/package/{fingerprint}><code>{fingerprint}</code></a></td>
+                <td></td>
+                <td></td>
+                <td></td>
+                <td class=right><a href=/directory/{hash}>0</a></td>
+                <td class=right>0 B</td>
+              </tr>
+            </tbody>
+          </table>
+          <nav>
+            <a href='/search?q=foo&amp;media=image&amp;year=1970..1979' rel=prev>prev</a>
+            page 2 of 3
+            <a href='/search?q=foo&amp;media=image&amp;year=1970..1979&amp;page=3' rel=next>next</a>
+          </nav>
+        ",
+        hash = Hash::from(fingerprint),
+      )),
+    );
+  }
+}
@@ -138,6 +138,9 @@ mod tests {
     let statement = Statement {
       fingerprint: FINGERPRINT.parse().unwrap(),
       timestamp: None,
+      expires: None,
+      purpose: None,
+      comment: None,
     };
     let signature = private_key.sign(&statement);
     assert_eq!(signature.to_string(), SIGNATURE);
@@ -33,6 +33,13 @@ impl Time {
       .context(time_error::Days { days })
   }
 
+  pub(crate) fn sort_key(&self) -> (i64, i64) {
+    match self {
+      Self::Day { days } => (self.year(), (*days).into()),
+      Self::Year { year } => (*year, i64::MIN),
+    }
+  }
+
   pub(crate) fn year(&self) -> i64 {
     match self {
       Self::Day { days } => Self::date(*days).unwrap().year().into(),
@@ -185,6 +192,23 @@ mod tests {
     case("-10000-01-01");
   }
 
+  #[test]
+  fn sort_key() {
+    #[track_caller]
+    fn case(a: &str, b: &str) {
+      assert!(
+        a.parse::<Time>().unwrap().sort_key() < b.parse::<Time>().unwrap().sort_key(),
+        "{a} should sort before {b}",
+      );
+    }
+
+    case("1969", "1970");
+    case("1970", "1970-01-01");
+    case("1970-01-01", "1970-01-02");
+    case("1970-12-31", "1971");
+    case("-44-03-15", "0");
+  }
+
   #[test]
   fn valid() {
     #[track_caller]
@@ -47,14 +47,31 @@ impl Token {
     )
   }
 
-  pub(crate) fn verify(admin: PublicKey, audience: Option<&str>, token: &str) -> ServerResult {
-    let key = DecodingKey::from_ed_der(admin.inner().as_bytes());
+  pub(crate) fn issuer(token: &str) -> ServerResult<PublicKey> {
+    let mut validation = Validation::new(Algorithm::EdDSA);
+    validation.insecure_disable_signature_validation();
+    validation.required_spec_claims.clear();
+    validation.validate_aud = false;
+    validation.validate_exp = false;
+
+    let issuer = jsonwebtoken::decode::<Self>(token, &DecodingKey::from_secret(&[]), &validation)
+      .context(server_error::AuthorizationInvalid)?
+      .claims
+      .issuer;
+
+    issuer
+      .parse()
+      .context(server_error::AuthorizationIssuerInvalid { issuer })
+  }
+
+  pub(crate) fn verify(issuer: PublicKey, audience: Option<&str>, token: &str) -> ServerResult {
+    let key = DecodingKey::from_ed_der(issuer.inner().as_bytes());
 
     let mut validation = Validation::new(Algorithm::EdDSA);
     validation.leeway = LEEWAY;
     validation.validate_nbf = true;
     validation.set_audience(audience.as_slice());
-    validation.set_issuer(&[admin.to_string()]);
+    validation.set_issuer(&[issuer.to_string()]);
 
     jsonwebtoken::decode::<Self>(token, &key, &validation)
       .context(server_error::AuthorizationInvalid)?;
@@ -109,6 +126,33 @@ mod tests {
     );
   }
 
+  #[test]
+  fn issuer() {
+    let private_key = PrivateKey::generate();
+    let token = Token::encode(&private_key, AUDIENCE).unwrap();
+    assert_eq!(Token::issuer(&token).unwrap(), private_key.public_key());
+  }
+
+  #[test]
+  fn issuer_invalid() {
+    let private_key = PrivateKey::generate();
+    let now = now().unwrap();
+    let token = mint(
+      &private_key,
+      &Token {
+        audience: AUDIENCE.into(),
+        expiration_time: now + TTL,
+        issued_at_time: now,
+        issuer: "foo".into(),
+        not_before_time: now,
+      },
+    );
+    assert_matches!(
+      Token::issuer(&token).unwrap_err(),
+      ServerError::AuthorizationIssuerInvalid { issuer, .. } if issuer == "foo",
+    );
+  }
+
   fn mint(private_key: &PrivateKey, claims: &Token) -> String {
     let der = private_key.inner_secret().to_pkcs8_der().unwrap();
     jsonwebtoken::encode(
@@ -0,0 +1,92 @@
+use super::*;
+
+pub(crate) struct TrustStore {
+  pub(crate) keys: BTreeMap<KeyName, TrustedKey>,
+  pub(crate) path: Utf8PathBuf,
+}
+
+impl TrustStore {
+  const FILENAME: &'static str = "trusted.yaml";
+
+  pub(crate) fn add(&mut self, name: KeyName, key: TrustedKey) -> Result {
+    ensure! {
+      !self.keys.contains_key(&name),
+      error::TrustedKeyAlreadyExists { name },
+    }
+
+    if let Some(existing) = self.name(key.public_key) {
+      return Err(
+        error::TrustedKeyDuplicate {
+          name: existing.clone(),
+          public_key: key.public_key,
+        }
+        .build(),
+      );
+    }
+
+    if let (Some(after), Some(before)) = (key.valid_after, key.valid_before) {
+      ensure! {
+        after < before,
+        error::TrustedKeyValidity { after, before },
+      }
+    }
+
+    self.keys.insert(name, key);
+
+    Ok(())
+  }
+
+  pub(crate) fn load(options: &Options) -> Result<Self> {
+    let path = options.data_dir()?.join(Self::FILENAME);
+
+    let keys = match filesystem::read_to_string_opt(&path)? {
+      Some(yaml) => {
+        serde_yaml::from_str(&yaml).context(error::DeserializeTrusted { path: &path })?
+      }
+      None => BTreeMap::new(),
+    };
+
+    Ok(Self { keys, path })
+  }
+
+  pub(crate) fn name(&self, public_key: PublicKey) -> Option<&KeyName> {
+    self
+      .keys
+      .iter()
+      .find(|(_name, key)| key.public_key == public_key)
+      .map(|(name, _key)| name)
+  }
+
+  pub(crate) fn remove(&mut self, name: &KeyName) -> Result {
+    self
+      .keys
+      .remove(name)
+      .context(error::TrustedKeyNotFound { name: name.clone() })?;
+
+    Ok(())
+  }
+
+  pub(crate) fn resolve(
+    &self,
+    keychain: &Keychain,
+    identifier: &KeyIdentifier,
+  ) -> Result<(PublicKey, Option<&TrustedKey>)> {
+    if let KeyIdentifier::Name(name) = identifier
+      && !keychain.keys.contains_key(name)
+      && let Some(key) = self.keys.get(name)
+    {
+      return Ok((key.public_key, Some(key)));
+    }
+
+    Ok((keychain.identifier_public_key(identifier)?, None))
+  }
+
+  pub(crate) fn save(&self) -> Result {
+    filesystem::create_dir_all(self.path.parent().unwrap())?;
+    filesystem::write(&self.path, serde_yaml::to_string(&self.keys).unwrap())
+  }
+
+  pub(crate) fn trusted(&self, public_key: PublicKey) -> Option<&TrustedKey> {
+    self.keys.values().find(|key| key.public_key == public_key)
+  }
+}
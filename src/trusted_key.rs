@@ -0,0 +1,107 @@
+use super::*;
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct TrustedKey {
+  pub(crate) comment: Option<String>,
+  pub(crate) public_key: PublicKey,
+  pub(crate) valid_after: Option<jiff::Timestamp>,
+  pub(crate) valid_before: Option<jiff::Timestamp>,
+}
+
+impl TrustedKey {
+  pub(crate) fn is_valid_at(&self, time: jiff::Timestamp) -> bool {
+    self.valid_after.is_none_or(|after| time >= after)
+      && self.valid_before.is_none_or(|before| time < before)
+  }
+
+  pub(crate) fn is_valid_for(&self, signature: &Signature, now: jiff::Timestamp) -> bool {
+    let timestamp = signature.statement().timestamp.map(|timestamp| {
+      i64::try_from(timestamp)
+        .ok()
+        .and_then(|second| jiff::Timestamp::from_second(second).ok())
+    });
+
+    self.is_valid_at(now)
+      && timestamp.is_none_or(|time| time.is_some_and(|time| self.is_valid_at(time)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn backdated_signature_from_expired_key() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+
+    let key = TrustedKey {
+      comment: None,
+      public_key: private_key.public_key(),
+      valid_after: None,
+      valid_before: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+    };
+
+    let signature = private_key.sign(&Statement {
+      comment: None,
+      expires: None,
+      fingerprint: test::FINGERPRINT.parse().unwrap(),
+      purpose: None,
+      timestamp: Some(1_735_689_600),
+    });
+
+    assert!(key.is_valid_for(&signature, "2025-06-01T00:00:00Z".parse().unwrap()));
+    assert!(!key.is_valid_for(&signature, "2026-06-01T00:00:00Z".parse().unwrap()));
+  }
+
+  #[test]
+  fn future_dated_signature_from_key_not_yet_valid() {
+    let private_key = test::PRIVATE_KEY.parse::<PrivateKey>().unwrap();
+
+    let key = TrustedKey {
+      comment: None,
+      public_key: private_key.public_key(),
+      valid_after: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+      valid_before: None,
+    };
+
+    let signature = private_key.sign(&Statement {
+      comment: None,
+      expires: None,
+      fingerprint: test::FINGERPRINT.parse().unwrap(),
+      purpose: None,
+      timestamp: Some(1_735_689_600),
+    });
+
+    assert!(!key.is_valid_for(&signature, "2026-06-01T00:00:00Z".parse().unwrap()));
+  }
+
+  #[test]
+  fn unbounded() {
+    let key = TrustedKey {
+      comment: None,
+      public_key: test::PUBLIC_KEY.parse().unwrap(),
+      valid_after: None,
+      valid_before: None,
+    };
+
+    assert!(key.is_valid_at(jiff::Timestamp::MIN));
+    assert!(key.is_valid_at(jiff::Timestamp::MAX));
+  }
+
+  #[test]
+  fn validity_window() {
+    let key = TrustedKey {
+      comment: None,
+      public_key: test::PUBLIC_KEY.parse().unwrap(),
+      valid_after: Some("2025-01-01T00:00:00Z".parse().unwrap()),
+      valid_before: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+    };
+
+    assert!(!key.is_valid_at("2024-12-31T23:59:59Z".parse().unwrap()));
+    assert!(key.is_valid_at("2025-01-01T00:00:00Z".parse().unwrap()));
+    assert!(key.is_valid_at("2025-12-31T23:59:59Z".parse().unwrap()));
+    assert!(!key.is_valid_at("2026-01-01T00:00:00Z".parse().unwrap()));
+  }
+}
@@ -3,8 +3,10 @@ use super::*;
 #[derive(Display)]
 pub(crate) enum TypeName {
   DatabaseMetadata,
+  FileStat,
   Fingerprint,
   Hash,
+  PublicKey,
 }
 
 impl From<TypeName> for redb::TypeName {
@@ -0,0 +1,47 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Decode, Default, Encode, PartialEq)]
+pub(crate) struct Usage {
+  #[n(0)]
+  pub(crate) bytes: u64,
+  #[n(1)]
+  pub(crate) files: u64,
+}
+
+impl Usage {
+  pub(crate) fn add(&mut self, size: u64) {
+    self.bytes += size;
+    self.files += 1;
+  }
+
+  pub(crate) fn remove(&mut self, size: u64) {
+    self.bytes = self.bytes.saturating_sub(size);
+    self.files = self.files.saturating_sub(1);
+  }
+
+  pub(crate) fn with(self, other: Self) -> Self {
+    Self {
+      bytes: self.bytes + other.bytes,
+      files: self.files + other.files,
+    }
+  }
+
+  pub(crate) fn without(self, other: Self) -> Self {
+    Self {
+      bytes: self.bytes.saturating_sub(other.bytes),
+      files: self.files.saturating_sub(other.files),
+    }
+  }
+}
+
+impl From<(u64, u64)> for Usage {
+  fn from((bytes, files): (u64, u64)) -> Self {
+    Self { bytes, files }
+  }
+}
+
+impl From<Usage> for (u64, u64) {
+  fn from(usage: Usage) -> Self {
+    (usage.bytes, usage.files)
+  }
+}
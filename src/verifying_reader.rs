@@ -0,0 +1,61 @@
+use super::*;
+
+pub(crate) struct VerifyingReader<R> {
+  expected: File,
+  hasher: Hasher,
+  inner: R,
+  size: u64,
+}
+
+impl<R> VerifyingReader<R> {
+  pub(crate) fn new(inner: R, expected: File) -> Self {
+    Self {
+      expected,
+      hasher: Hasher::new(),
+      inner,
+      size: 0,
+    }
+  }
+}
+
+impl<R: Read> Read for VerifyingReader<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let n = self.inner.read(buf)?;
+
+    self.hasher.update(&buf[..n]);
+    self.size += n.into_u64();
+
+    if self.size > self.expected.size {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("file larger than expected size {}", self.expected.size),
+      ));
+    }
+
+    if n == 0 && !buf.is_empty() {
+      if self.size != self.expected.size {
+        return Err(io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!(
+            "file size {} does not match expected size {}",
+            self.size, self.expected.size,
+          ),
+        ));
+      }
+
+      let actual = Hash::from(self.hasher.finalize());
+
+      if actual != self.expected.hash {
+        return Err(io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!(
+            "file hash {actual} does not match expected hash {}",
+            self.expected.hash,
+          ),
+        ));
+      }
+    }
+
+    Ok(n)
+  }
+}
@@ -1,7 +1,7 @@
 use super::*;
 
 #[derive(Clone, Copy, Debug, Decode, Default, Encode, PartialEq)]
-pub(crate) enum Version {
+pub enum Version {
   #[default]
   #[n(0)]
   Zero,
@@ -0,0 +1,173 @@
+use {
+  super::*,
+  ignore::{
+    Match,
+    gitignore::{Gitignore, GitignoreBuilder},
+  },
+};
+
+pub(crate) struct Walk {
+  pub(crate) empty: Vec<RelativePath>,
+  pub(crate) files: BTreeMap<RelativePath, u64>,
+  pub(crate) paths: Vec<RelativePath>,
+  pub(crate) symlinks: BTreeMap<RelativePath, Symlink>,
+}
+
+impl Walk {
+  pub(crate) const IGNORE_FILENAME: &'static str = ".filepackignore";
+
+  pub(crate) fn file_size(&self) -> Result<u64> {
+    let mut total = 0u64;
+
+    for size in self.files.values() {
+      total = total
+        .checked_add(*size)
+        .context(error::TotalFileSizeOverflow)?;
+    }
+
+    Ok(total)
+  }
+
+  pub(crate) fn ignored<'a>(
+    ignore_files: impl IntoIterator<Item = &'a Gitignore>,
+    path: &Utf8Path,
+    is_dir: bool,
+  ) -> bool {
+    for gitignore in ignore_files {
+      match gitignore.matched(path, is_dir) {
+        Match::Ignore(_) => return true,
+        Match::None => {}
+        Match::Whitelist(_) => return false,
+      }
+    }
+
+    false
+  }
+
+  fn load_ignore_file(
+    root: &Utf8Path,
+    directory: &Utf8Path,
+    honor: &impl Fn(&RelativePath) -> bool,
+  ) -> Result<Option<Gitignore>> {
+    let path = directory.join(Self::IGNORE_FILENAME);
+
+    let relative = path.strip_prefix(root).unwrap();
+
+    let relative = RelativePath::try_from(relative).context(error::Path { path: relative })?;
+
+    if !honor(&relative) || !filesystem::exists(&path)? {
+      return Ok(None);
+    }
+
+    let mut builder = GitignoreBuilder::new(directory);
+
+    if let Some(err) = builder.add(&path) {
+      return Err(err).context(error::IgnoreFile { path });
+    }
+
+    let gitignore = builder.build().context(error::IgnoreFile { path })?;
+
+    Ok(Some(gitignore))
+  }
+
+  pub(crate) fn new(root: &Utf8Path, manifest: &Utf8Path, ignore: &[RelativePath]) -> Result<Self> {
+    Self::with_ignore_files(root, manifest, ignore, |_path| true)
+  }
+
+  // only honor ignore files at paths relative to `root` for which `honor`
+  // returns true
+  pub(crate) fn with_ignore_files(
+    root: &Utf8Path,
+    manifest: &Utf8Path,
+    ignore: &[RelativePath],
+    honor: impl Fn(&RelativePath) -> bool,
+  ) -> Result<Self> {
+    let current_dir = current_dir()?;
+
+    let manifest = current_dir.join(manifest).lexiclean();
+
+    let mut empty = Vec::new();
+
+    let mut files = BTreeMap::new();
+
+    let mut paths = Vec::new();
+
+    let mut symlinks = BTreeMap::new();
+
+    let mut ignore_files = Vec::new();
+
+    let mut entries = WalkDir::new(root).sort_by_file_name().into_iter();
+
+    while let Some(entry) = entries.next() {
+      let entry = entry?;
+
+      let path = decode_path(entry.path())?;
+
+      let is_dir = entry.file_type().is_dir();
+
+      if entry.depth() == 0 {
+        ignore_files
+          .extend(Self::load_ignore_file(root, path, &honor)?.map(|gitignore| (0, gitignore)));
+        continue;
+      }
+
+      if current_dir.join(path).lexiclean() == manifest {
+        continue;
+      }
+
+      let relative = path.strip_prefix(root).unwrap();
+
+      let relative = RelativePath::try_from(relative).context(error::Path { path: relative })?;
+
+      ignore_files.retain(|(depth, _gitignore)| *depth < entry.depth());
+
+      if ignore.iter().any(|ignore| relative.starts_with(ignore))
+        || Self::ignored(
+          ignore_files
+            .iter()
+            .rev()
+            .map(|(_depth, gitignore)| gitignore),
+          path,
+          is_dir,
+        )
+      {
+        if is_dir {
+          entries.skip_current_dir();
+        }
+        continue;
+      }
+
+      empty.pop_if(|dir| relative.starts_with(dir));
+
+      paths.push(relative.clone());
+
+      if entry.file_type().is_symlink() {
+        let target = filesystem::read_link(path)?;
+
+        let symlink = Symlink::resolve(&relative, &target)
+          .context(error::SymlinkOutsidePackage { path, target })?;
+
+        symlinks.insert(relative, symlink);
+
+        continue;
+      }
+
+      if is_dir {
+        ignore_files.extend(
+          Self::load_ignore_file(root, path, &honor)?.map(|gitignore| (entry.depth(), gitignore)),
+        );
+        empty.push(relative);
+        continue;
+      }
+
+      files.insert(relative, filesystem::metadata(path)?.len());
+    }
+
+    Ok(Self {
+      empty,
+      files,
+      paths,
+      symlinks,
+    })
+  }
+}
@@ -0,0 +1,133 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct YearRange {
+  pub(crate) end: Option<i64>,
+  pub(crate) start: Option<i64>,
+}
+
+impl YearRange {
+  pub(crate) fn range(self) -> RangeInclusive<i64> {
+    self.start.unwrap_or(i64::MIN)..=self.end.unwrap_or(i64::MAX)
+  }
+}
+
+impl Display for YearRange {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match (self.start, self.end) {
+      (Some(start), Some(end)) if start == end => write!(f, "{start}"),
+      (start, end) => {
+        if let Some(start) = start {
+          write!(f, "{start}")?;
+        }
+
+        write!(f, "..")?;
+
+        if let Some(end) = end {
+          write!(f, "{end}")?;
+        }
+
+        Ok(())
+      }
+    }
+  }
+}
+
+impl FromStr for YearRange {
+  type Err = YearRangeError;
+
+  fn from_str(input: &str) -> Result<Self, Self::Err> {
+    let year = |s: &str| {
+      if s.is_empty() {
+        Ok(None)
+      } else {
+        s.parse::<i64>()
+          .map(Some)
+          .ok()
+          .context(year_range_error::Invalid { input })
+      }
+    };
+
+    let (start, end) = if let Some((start, end)) = input.split_once("..") {
+      (year(start)?, year(end)?)
+    } else {
+      let year = year(input)?;
+      (year, year)
+    };
+
+    ensure!(
+      start.is_some() || end.is_some(),
+      year_range_error::Invalid { input },
+    );
+
+    if let (Some(start), Some(end)) = (start, end) {
+      ensure!(start <= end, year_range_error::Reversed { end, start });
+    }
+
+    Ok(Self { end, start })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    #[track_caller]
+    fn case(s: &str) {
+      assert_eq!(s.parse::<YearRange>().unwrap().to_string(), s);
+    }
+
+    case("1970");
+    case("1970..1979");
+    case("1970..");
+    case("..1979");
+    case("-500..-400");
+  }
+
+  #[test]
+  fn invalid() {
+    #[track_caller]
+    fn case(s: &str) {
+      assert_eq!(
+        s.parse::<YearRange>().unwrap_err(),
+        YearRangeError::Invalid { input: s.into() },
+      );
+    }
+
+    case("");
+    case("..");
+    case("foo");
+    case("1970..foo");
+    case("1970...1979");
+  }
+
+  #[test]
+  fn range() {
+    assert_eq!(
+      "1970..1979".parse::<YearRange>().unwrap().range(),
+      1970..=1979
+    );
+    assert_eq!("1970".parse::<YearRange>().unwrap().range(), 1970..=1970);
+    assert_eq!(
+      "1970..".parse::<YearRange>().unwrap().range(),
+      1970..=i64::MAX
+    );
+    assert_eq!(
+      "..1979".parse::<YearRange>().unwrap().range(),
+      i64::MIN..=1979
+    );
+  }
+
+  #[test]
+  fn reversed() {
+    assert_eq!(
+      "1979..1970".parse::<YearRange>().unwrap_err(),
+      YearRangeError::Reversed {
+        end: 1970,
+        start: 1979,
+      },
+    );
+  }
+}
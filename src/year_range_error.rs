@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Snafu)]
+#[snafu(context(suffix(false)), visibility(pub(crate)))]
+pub(crate) enum YearRangeError {
+  #[snafu(display("invalid year range `{input}`"))]
+  Invalid { input: String },
+  #[snafu(display("year range start {start} is after end {end}"))]
+  Reversed { end: i64, start: i64 },
+}
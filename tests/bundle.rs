@@ -0,0 +1,147 @@
+use super::*;
+
+#[test]
+fn bundles_are_deterministic() {
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .write("foo/baz/qux", "qux")
+    .args(["create", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "bar.bundle"])
+    .success();
+
+  assert_eq!(
+    fs::read(test.path().join("foo.bundle")).unwrap(),
+    fs::read(test.path().join("bar.bundle")).unwrap(),
+  );
+}
+
+#[test]
+fn mismatched_file_is_rejected() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success()
+    .write("foo/bar", "baz")
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .stderr_regex(
+      "error: failed to bundle `bar`
+.*file hash [0-9a-f]{64} does not match expected hash [0-9a-f]{64}
+",
+    )
+    .failure()
+    .assert_file_count(".", 1);
+}
+
+#[test]
+fn output_already_exists() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .touch("foo.bundle")
+    .args(["create", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .stderr("error: `foo.bundle` already exists\n")
+    .failure();
+}
+
+#[test]
+fn round_trip() {
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .write("foo/baz/bar", "bar")
+    .write("foo/qux", "qux")
+    .create_dir("foo/empty")
+    .touch("foo/README.md")
+    .write("foo/metadata.yaml", "title: Foo\nreadme: README.md\n")
+    .arg("keygen")
+    .success()
+    .args(["create", "--sign", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .success()
+    .args(["unbundle", "foo.bundle", "bar"])
+    .success()
+    .args(["verify", "bar"])
+    .stderr("successfully verified 6 files totaling 55 bytes with 1 signature\n")
+    .success()
+    .assert_dir("bar/empty");
+
+  assert_eq!(
+    fs::read(test.path().join("foo/manifest.filepack")).unwrap(),
+    fs::read(test.path().join("bar/manifest.filepack")).unwrap(),
+  );
+}
+
+#[cfg(unix)]
+#[test]
+fn round_trip_executable_and_symlink() {
+  use std::os::unix::fs::PermissionsExt;
+
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .chmod("foo/bar", 0o755)
+    .relative_symlink("bar", "foo/baz")
+    .args(["create", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .success()
+    .args(["unbundle", "foo.bundle", "bar"])
+    .success()
+    .args(["verify", "bar"])
+    .stderr("successfully verified 1 file totaling 3 bytes\n")
+    .success();
+
+  assert_eq!(
+    fs::read_link(test.path().join("bar/baz")).unwrap(),
+    Path::new("bar"),
+  );
+
+  assert_eq!(
+    fs::metadata(test.path().join("bar/bar"))
+      .unwrap()
+      .permissions()
+      .mode()
+      & 0o100,
+    0o100,
+  );
+}
+
+#[test]
+fn unbundle_output_already_exists() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .create_dir("bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .success()
+    .args(["unbundle", "foo.bundle", "bar"])
+    .stderr("error: `bar` already exists\n")
+    .failure();
+}
+
+#[test]
+fn unbundle_truncated_bundle() {
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .success();
+
+  let bundle = fs::read(test.path().join("foo.bundle")).unwrap();
+
+  fs::write(test.path().join("foo.bundle"), &bundle[..bundle.len() - 1]).unwrap();
+
+  test
+    .args(["unbundle", "foo.bundle", "bar"])
+    .stderr_regex(
+      "error: I/O error at `bar.bar`
+.*file size 2 does not match expected size 3
+",
+    )
+    .failure();
+}
@@ -0,0 +1,120 @@
+use super::*;
+
+#[test]
+fn file_matches() {
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .arg("create")
+    .success()
+    .args(["prove", "foo/bar", "--output", "proof"])
+    .success();
+
+  let fingerprint = fingerprint(&test);
+
+  let hash = Hash::bytes(b"bar").to_string();
+
+  test
+    .args([
+      "check-proof",
+      "proof",
+      "--fingerprint",
+      &fingerprint,
+      "--file",
+      "foo/bar",
+    ])
+    .stdout(json_pretty! {
+      hash: hash,
+      path: "foo/bar",
+      size: 3,
+    })
+    .success();
+}
+
+#[test]
+fn file_mismatch() {
+  let test = Test::new()
+    .write("foo", "foo")
+    .arg("create")
+    .success()
+    .args(["prove", "foo", "--output", "proof"])
+    .success();
+
+  let fingerprint = fingerprint(&test);
+
+  test
+    .write("foo", "bar")
+    .args([
+      "check-proof",
+      "proof",
+      "--fingerprint",
+      &fingerprint,
+      "--file",
+      "foo",
+    ])
+    .stderr_regex("mismatched file: `foo`\n.*error: file did not match manifest entry\n")
+    .failure();
+}
+
+fn fingerprint(test: &Test) -> String {
+  Manifest::load(Some(&test.path().join("manifest.filepack")))
+    .unwrap()
+    .fingerprint()
+    .to_string()
+}
+
+#[test]
+fn invalid_proof() {
+  Test::new()
+    .write("proof", "foo")
+    .args([
+      "check-proof",
+      "proof",
+      "--fingerprint",
+      &Fingerprint::from(Hash::bytes(b"foo")).to_string(),
+    ])
+    .stderr_regex("error: failed to decode proof at `proof`\n.*")
+    .failure();
+}
+
+#[test]
+fn valid() {
+  let test = Test::new()
+    .write("foo", "foo")
+    .write("bar/baz", "baz")
+    .arg("create")
+    .success()
+    .args(["prove", "bar/baz", "--output", "proof"])
+    .success();
+
+  let fingerprint = fingerprint(&test);
+
+  let hash = Hash::bytes(b"baz").to_string();
+
+  test
+    .remove_file("bar/baz")
+    .remove_file("manifest.filepack")
+    .args(["check-proof", "proof", "--fingerprint", &fingerprint])
+    .stdout(json_pretty! {
+      hash: hash,
+      path: "bar/baz",
+      size: 3,
+    })
+    .success();
+}
+
+#[test]
+fn wrong_fingerprint() {
+  let other = Fingerprint::from(Hash::bytes(b"foo"));
+
+  Test::new()
+    .write("foo", "foo")
+    .arg("create")
+    .success()
+    .args(["prove", "foo", "--output", "proof"])
+    .success()
+    .args(["check-proof", "proof", "--fingerprint", &other.to_string()])
+    .stderr_regex(
+      "error: invalid proof at `proof`\n.*proof directory hash mismatch: expected .* but got .*\n",
+    )
+    .failure();
+}
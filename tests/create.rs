@@ -51,6 +51,38 @@ fn empty_directories_are_included() {
     .success();
 }
 
+#[cfg(unix)]
+#[test]
+fn executable_bit_is_recorded() {
+  Test::new()
+    .touch("foo")
+    .chmod("foo", 0o755)
+    .touch("bar")
+    .arg("create")
+    .assert_manifest(
+      "manifest.filepack",
+      json_pretty! {
+        embedded: {},
+        package: {
+          bar: {
+            hash: EMPTY_HASH,
+            size: 0
+          },
+          foo: {
+            executable: true,
+            hash: EMPTY_HASH,
+            size: 0
+          }
+        },
+        signatures: [],
+      },
+    )
+    .success()
+    .arg("verify")
+    .stderr("successfully verified 2 files totaling 0 bytes\n")
+    .success();
+}
+
 #[test]
 fn file_in_subdirectory() {
   Test::new()
@@ -77,6 +109,62 @@ fn file_in_subdirectory() {
     .success();
 }
 
+#[test]
+fn filepackignore() {
+  Test::new()
+    .write(".filepackignore", "*.o\n!keep.o\nbuild/\n")
+    .touch("a.o")
+    .touch("keep.o")
+    .touch("build/foo")
+    .touch("sub/build")
+    .write("sub/.filepackignore", "*.txt\n!a.o\n")
+    .touch("sub/a.o")
+    .touch("sub/a.txt")
+    .touch("b.txt")
+    .arg("create")
+    .success()
+    .args(["files", "--format", "tsv"])
+    .stdout_regex(
+      "
+        \\.filepackignore\t.*
+        b\\.txt\t.*
+        keep\\.o\t.*
+        sub/\\.filepackignore\t.*
+        sub/a\\.o\t.*
+        sub/build\t.*
+      ",
+    )
+    .success()
+    .touch("c.o")
+    .touch("sub/b.txt")
+    .arg("verify")
+    .stderr("successfully verified 6 files totaling 30 bytes\n")
+    .success();
+}
+
+#[test]
+fn filepackignore_invalid() {
+  Test::new()
+    .write(".filepackignore", "{foo\n")
+    .arg("create")
+    .stderr_regex("error: invalid ignore file `.*\\.filepackignore`\n.*")
+    .failure();
+}
+
+#[test]
+fn filepackignore_is_package_file() {
+  Test::new()
+    .write(".filepackignore", "foo\n")
+    .touch("foo")
+    .touch("bar")
+    .arg("create")
+    .success()
+    .write(".filepackignore", "bar\n")
+    .arg("verify")
+    .stderr_regex("mismatched file: `.filepackignore`\n.*")
+    .failure();
+}
+
 #[test]
 fn force_overwrites_manifest() {
   Test::new()
@@ -127,6 +215,324 @@ fn force_overwrites_manifest_with_destination() {
     .success();
 }
 
+#[test]
+fn from_tar() {
+  let test = Test::new();
+
+  fs::write(
+    test.path().join("foo.tar"),
+    tar(&[("bar", "bar"), ("baz/qux", "")]),
+  )
+  .unwrap();
+
+  test
+    .args(["create", "--from-tar", "foo.tar"])
+    .assert_manifest(
+      "manifest.filepack",
+      json_pretty! {
+        embedded: {},
+        package: {
+          bar: {
+            hash: "f2e897eed7d206cd855d441598fa521abc75aa96953e97c030c9612c30c1293d",
+            size: 3
+          },
+          baz: {
+            qux: {
+              hash: EMPTY_HASH,
+              size: 0
+            }
+          }
+        },
+        signatures: [],
+      },
+    )
+    .success();
+}
+
+#[test]
+fn from_tar_duplicate_entry() {
+  let test = Test::new();
+
+  fs::write(
+    test.path().join("foo.tar"),
+    tar(&[("bar", "a"), ("bar", "b")]),
+  )
+  .unwrap();
+
+  test
+    .args(["create", "--from-tar", "foo.tar"])
+    .stderr("error: tar archive contains duplicate entry `bar`\n")
+    .failure();
+}
+
+#[test]
+fn from_tar_empty_directory() {
+  let test = Test::new();
+
+  fs::write(
+    test.path().join("foo.tar"),
+    tar(&[("bar/", ""), ("baz/qux/", "")]),
+  )
+  .unwrap();
+
+  test
+    .args(["create", "--from-tar", "foo.tar"])
+    .assert_manifest(
+      "manifest.filepack",
+      json_pretty! {
+        embedded: {},
+        package: {
+          bar: {},
+          baz: {
+            qux: {}
+          }
+        },
+        signatures: [],
+      },
+    )
+    .success();
+}
+
+#[test]
+fn from_tar_filepackignore() {
+  let test = Test::new()
+    .write("foo/.filepackignore", "*.o\n!keep.o\nbuild/\n")
+    .touch("foo/a.o")
+    .touch("foo/keep.o")
+    .touch("foo/build/bar")
+    .touch("foo/sub/build")
+    .write("foo/sub/.filepackignore", "*.txt\n!a.o\n")
+    .touch("foo/sub/a.o")
+    .touch("foo/sub/a.txt")
+    .touch("foo/b.txt")
+    .args(["create", "--manifest", "foo.json", "foo"])
+    .success();
+
+  let expected = fs::read(test.path().join("foo.json")).unwrap();
+
+  fs::write(
+    test.path().join("bar.tar"),
+    tar(&[
+      ("a.o", ""),
+      ("b.txt", ""),
+      ("build/", ""),
+      ("build/bar", ""),
+      ("keep.o", ""),
+      ("sub/a.o", ""),
+      ("sub/a.txt", ""),
+      ("sub/build", ""),
+      ("sub/.filepackignore", "*.txt\n!a.o\n"),
+      (".filepackignore", "*.o\n!keep.o\nbuild/\n"),
+    ]),
+  )
+  .unwrap();
+
+  let test = test
+    .args(["create", "--from-tar", "bar.tar", "--manifest", "bar.json"])
+    .success();
+
+  assert_eq!(fs::read(test.path().join("bar.json")).unwrap(), expected);
+}
+
+#[test]
+fn from_tar_gzip() {
+  let test = Test::new();
+
+  let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+  encoder.write_all(&tar(&[("bar", "")])).unwrap();
+
+  fs::write(test.path().join("foo.tar.gz"), encoder.finish().unwrap()).unwrap();
+
+  test
+    .args(["create", "--from-tar", "foo.tar.gz"])
+    .assert_manifest(
+      "manifest.filepack",
+      json_pretty! {
+        embedded: {},
+        package: {
+          bar: {
+            hash: EMPTY_HASH,
+            size: 0
+          }
+        },
+        signatures: [],
+      },
+    )
+    .success();
+}
+
+#[test]
+fn from_tar_lint() {
+  let test = Test::new();
+
+  fs::write(test.path().join("foo.tar"), tar(&[("aux", "")])).unwrap();
+
+  test
+    .args(["create", "--from-tar", "foo.tar", "--deny", "compatibility"])
+    .stderr(
+      "
+        error: path failed lint: `aux`
+               └─ Windows does not allow files named `aux`
+        error: 1 lint error
+      ",
+    )
+    .failure()
+    .args(["create", "--from-tar", "foo.tar"])
+    .success();
+}
+
+#[test]
+fn from_tar_matches_directory() {
+  let test = Test::new()
+    .write("foo/bar.md", "bar")
+    .write("foo/baz/qux", "qux")
+    .create_dir("foo/empty")
+    .write("foo/metadata.yaml", "title: Foo\nreadme: bar.md")
+    .args(["create", "--manifest", "foo.json", "foo"])
+    .success();
+
+  let expected = fs::read(test.path().join("foo.json")).unwrap();
+
+  fs::write(
+    test.path().join("bar.tar"),
+    tar(&[
+      ("bar.md", "bar"),
+      ("baz/qux", "qux"),
+      ("empty/", ""),
+      ("metadata.yaml", "title: Foo\nreadme: bar.md"),
+    ]),
+  )
+  .unwrap();
+
+  let test = test
+    .args(["create", "--from-tar", "bar.tar", "--manifest", "bar.json"])
+    .success();
+
+  assert_eq!(fs::read(test.path().join("bar.json")).unwrap(), expected);
+}
+
+#[test]
+fn from_tar_metadata() {
+  let test = Test::new();
+
+  fs::write(
+    test.path().join("foo.tar"),
+    tar(&[
+      ("metadata.yaml", "title: Foo\nreadme: README.md"),
+      ("README.md", "bar"),
+    ]),
+  )
+  .unwrap();
+
+  test
+    .args(["create", "--from-tar", "foo.tar"])
+    .success()
+    .arg("manifest")
+    .stdout_regex(r#".*"README\.md".*"metadata\.filemeta".*"metadata\.yaml".*"#)
+    .success();
+}
+
+#[test]
+fn from_tar_metadata_missing_file() {
+  let test = Test::new();
+
+  fs::write(
+    test.path().join("foo.tar"),
+    tar(&[("metadata.yaml", "title: Foo\nreadme: README.md")]),
+  )
+  .unwrap();
+
+  test
+    .args(["create", "--from-tar", "foo.tar"])
+    .stderr("error: file referenced in metadata missing: `README.md`\n")
+    .failure();
+}
+
+#[test]
+fn from_tar_metadata_order_stdin() {
+  Test::new()
+    .args(["create", "--from-tar", "-"])
+    .stdin(
+      str::from_utf8(&tar(&[
+        ("README.md", "bar"),
+        ("metadata.yaml", "title: Foo\nreadme: README.md"),
+      ]))
+      .unwrap(),
+    )
+    .stderr(
+      "error: file `README.md` referenced by metadata precedes `metadata.yaml` \
+      in tar archive read from standard input\n",
+    )
+    .failure();
+}
+
+#[test]
+fn from_tar_stdin() {
+  Test::new()
+    .args(["create", "--from-tar", "-"])
+    .stdin(str::from_utf8(&tar(&[("bar", "")])).unwrap())
+    .assert_manifest(
+      "manifest.filepack",
+      json_pretty! {
+        embedded: {},
+        package: {
+          bar: {
+            hash: EMPTY_HASH,
+            size: 0
+          }
+        },
+        signatures: [],
+      },
+    )
+    .success();
+}
+
+#[test]
+fn from_tar_symlink_outside_package() {
+  let test = Test::new();
+
+  let mut builder = ::tar::Builder::new(Vec::new());
+  let mut header = ::tar::Header::new_gnu();
+  header.set_entry_type(::tar::EntryType::Symlink);
+  header.set_size(0);
+  builder.append_link(&mut header, "bar", "../foo").unwrap();
+
+  fs::write(test.path().join("foo.tar"), builder.into_inner().unwrap()).unwrap();
+
+  test
+    .args(["create", "--from-tar", "foo.tar"])
+    .stderr("error: symlink at `bar` points outside of package: `../foo`\n")
+    .failure();
+}
+
+#[test]
+fn from_tar_zstd() {
+  let test = Test::new();
+
+  fs::write(
+    test.path().join("foo.tar.zst"),
+    zstd::encode_all(tar(&[("bar", "")]).as_slice(), 0).unwrap(),
+  )
+  .unwrap();
+
+  test
+    .args(["create", "--from-tar", "foo.tar.zst"])
+    .assert_manifest(
+      "manifest.filepack",
+      json_pretty! {
+        embedded: {},
+        package: {
+          bar: {
+            hash: EMPTY_HASH,
+            size: 0
+          }
+        },
+        signatures: [],
+      },
+    )
+    .success();
+}
+
 #[test]
 fn ignore_directory() {
   Test::new()
@@ -596,14 +1002,207 @@ fn single_non_empty_file() {
 }
 
 #[test]
-fn symlink_error() {
+fn symlink_absolute_error() {
   Test::new()
+    .touch("foo")
     .symlink("foo", "bar")
     .args(["create", "."])
-    .stderr("error: symlink at `bar`\n")
+    .stderr_regex("error: symlink at `bar` points outside of package: `.*foo`\n")
     .failure();
 }
 
+#[test]
+fn symlink_dangling_lint() {
+  Test::new()
+    .write("foo", "foo")
+    .relative_symlink("foo", "bar")
+    .relative_symlink("qux", "baz")
+    .args(["create", "--deny", "content"])
+    .stderr(
+      "
+        error: path failed lint: `baz`
+               └─ symlink target `qux` does not exist
+        error: 1 lint error
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn symlink_is_recorded() {
+  Test::new()
+    .touch("foo/bar")
+    .relative_symlink("foo/bar", "baz")
+    .relative_symlink("../baz", "foo/qux")
+    .arg("create")
+    .assert_manifest(
+      "manifest.filepack",
+      json_pretty! {
+        embedded: {},
+        package: {
+          baz: {
+            target: "foo/bar"
+          },
+          foo: {
+            bar: {
+              hash: EMPTY_HASH,
+              size: 0
+            },
+            qux: {
+              target: "baz"
+            }
+          }
+        },
+        signatures: [],
+      },
+    )
+    .success()
+    .arg("verify")
+    .stderr("successfully verified 1 file totaling 0 bytes\n")
+    .success();
+}
+
+#[test]
+fn symlink_outside_package_error() {
+  Test::new()
+    .relative_symlink("../foo", "bar")
+    .args(["create", "."])
+    .stderr("error: symlink at `bar` points outside of package: `../foo`\n")
+    .failure();
+}
+
+fn tar(entries: &[(&str, &str)]) -> Vec<u8> {
+  let mut builder = ::tar::Builder::new(Vec::new());
+
+  for (path, content) in entries {
+    let mut header = ::tar::Header::new_gnu();
+    header.set_mode(0o644);
+
+    if path.ends_with('/') {
+      header.set_entry_type(::tar::EntryType::Directory);
+      header.set_size(0);
+      builder
+        .append_data(&mut header, path, std::io::empty())
+        .unwrap();
+    } else {
+      header.set_size(content.len().into_u64());
+      builder
+        .append_data(&mut header, path, content.as_bytes())
+        .unwrap();
+    }
+  }
+
+  builder.into_inner().unwrap()
+}
+
+#[test]
+fn update_creates_hash_cache() {
+  let test = Test::new()
+    .write("foo", "foo")
+    .args(["create", "--update"])
+    .success();
+
+  assert!(test.path().join("cache.redb").is_file());
+}
+
+#[test]
+fn update_discards_signatures_if_fingerprint_changes() {
+  let test = Test::new()
+    .arg("keygen")
+    .success()
+    .write("foo/bar", "bar")
+    .args(["create", "--sign", "foo"])
+    .success()
+    .write("foo/bar", "baz")
+    .args(["create", "--update", "foo"])
+    .success()
+    .args(["verify", "foo"])
+    .stderr("successfully verified 1 file totaling 3 bytes\n")
+    .success();
+
+  let manifest = Manifest::load(Some(&test.path().join("foo"))).unwrap();
+
+  assert!(manifest.signatures.is_empty());
+}
+
+#[test]
+fn update_keeps_signatures_if_fingerprint_unchanged() {
+  Test::new()
+    .arg("keygen")
+    .success()
+    .write("foo/bar", "bar")
+    .args(["create", "--sign", "foo"])
+    .success()
+    .args(["create", "--update", "foo"])
+    .success()
+    .args(["verify", "foo"])
+    .stderr("successfully verified 1 file totaling 3 bytes with 1 signature\n")
+    .success();
+}
+
+#[test]
+fn update_overwrites_manifest() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "--update", "foo"])
+    .success()
+    .write("foo/baz", "baz")
+    .args(["create", "--update", "foo"])
+    .success()
+    .args(["verify", "foo"])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success()
+    .write("foo/bar", "qux")
+    .args(["create", "--update", "foo"])
+    .success()
+    .args(["verify", "foo"])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success();
+}
+
+#[test]
+fn update_overwrites_metadata() {
+  Test::new()
+    .touch("foo/bar")
+    .write("foo/metadata.yaml", "title: Foo")
+    .args(["create", "foo"])
+    .success()
+    .write("foo/metadata.yaml", "title: Bar")
+    .args(["create", "--update", "foo"])
+    .success()
+    .args(["verify", "foo"])
+    .stderr("successfully verified 3 files totaling 16 bytes\n")
+    .success();
+}
+
+#[test]
+fn update_rehashes_files_rewritten_with_preserved_mtime() {
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success();
+
+  let path = test.path().join("foo/bar");
+
+  let modified = fs::metadata(&path).unwrap().modified().unwrap();
+
+  fs::write(&path, "baz").unwrap();
+
+  fs::File::options()
+    .write(true)
+    .open(&path)
+    .unwrap()
+    .set_modified(modified)
+    .unwrap();
+
+  test
+    .args(["create", "--update", "foo"])
+    .success()
+    .args(["verify", "foo"])
+    .stderr("successfully verified 1 file totaling 3 bytes\n")
+    .success();
+}
+
 #[test]
 fn with_manifest_path() {
   Test::new()
@@ -124,6 +124,73 @@ fn delete_package_succeeds() {
   server.terminate().success();
 }
 
+#[test]
+fn restricted_delete_requires_delete_role() {
+  let server = Test::new()
+    .write(
+      "authorization.yaml",
+      format!(
+        "
+          keys:
+            master:
+              key: {PUBLIC_KEY}
+              roles: [upload]
+        "
+      ),
+    )
+    .ready_address()
+    .args([
+      "serve",
+      "--address",
+      "127.0.0.1",
+      "--http-port",
+      "0",
+      "--domain",
+      "127.0.0.1",
+      "--restrict-writes",
+      "--authorization",
+      "authorization.yaml",
+    ])
+    .spawn();
+
+  let test = Test::new()
+    .write_keypair("master")
+    .write("pkg/foo", "bar")
+    .args(["create", "pkg"])
+    .success();
+
+  let fingerprint = Manifest::load(Some(&test.path().join("pkg/manifest.filepack")))
+    .unwrap()
+    .fingerprint();
+
+  test
+    .args([
+      "upload",
+      "--server",
+      &server.address(),
+      "--auth",
+      "master",
+      "pkg",
+    ])
+    .stderr("uploading 1 of 1 file\n")
+    .success()
+    .args([
+      "delete",
+      "--server",
+      &server.address(),
+      "--auth",
+      "master",
+      &fingerprint.to_string(),
+    ])
+    .stderr_regex(&format!(
+      "error: response from http://.* failed with status 403 Forbidden: key {PUBLIC_KEY} does \
+      not have role `delete`\n"
+    ))
+    .failure();
+
+  server.terminate().success();
+}
+
 #[test]
 fn restricted_delete_succeeds_with_auth() {
   let server = Test::new()
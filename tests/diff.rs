@@ -0,0 +1,210 @@
+use super::*;
+
+#[test]
+fn identical() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["diff", "foo", "foo/manifest.filepack", "--format", "json"])
+    .stdout(json! {
+      added: {},
+      added_directories: [],
+      added_symlinks: {},
+      modified: {},
+      modified_symlinks: {},
+      moved: {},
+      removed: {},
+      removed_directories: [],
+      removed_symlinks: {},
+    })
+    .success();
+}
+
+#[test]
+fn manifests() {
+  let old = Hash::bytes(b"old").to_string();
+  let new = Hash::bytes(b"new").to_string();
+  let foo = Hash::bytes(b"foo").to_string();
+  let bar = Hash::bytes(b"bar").to_string();
+  let baz = Hash::bytes(b"baz").to_string();
+
+  Test::new()
+    .write("a/modified", "old")
+    .write("a/removed", "foo")
+    .write("a/dir/moved", "bar")
+    .create_dir("a/empty")
+    .args(["create", "a"])
+    .success()
+    .write("b/modified", "new")
+    .write("b/added", "baz")
+    .write("b/renamed", "bar")
+    .create_dir("b/new")
+    .args(["create", "b"])
+    .success()
+    .args(["diff", "a", "b"])
+    .stdout(json_pretty! {
+      added: {
+        added: {
+          hash: baz,
+          size: 3,
+        },
+      },
+      added_directories: ["new"],
+      added_symlinks: {},
+      modified: {
+        modified: {
+          new: {
+            hash: new,
+            size: 3,
+          },
+          old: {
+            hash: old,
+            size: 3,
+          },
+        },
+      },
+      modified_symlinks: {},
+      moved: {
+        renamed: {
+          file: {
+            hash: bar,
+            size: 3,
+          },
+          from: "dir/moved",
+        },
+      },
+      removed: {
+        removed: {
+          hash: foo,
+          size: 3,
+        },
+      },
+      removed_directories: ["empty"],
+      removed_symlinks: {},
+    })
+    .success();
+}
+
+#[test]
+fn missing_target() {
+  Test::new()
+    .arg("create")
+    .success()
+    .args(["diff", "."])
+    .stderr_regex(
+      "error: the following required arguments were not provided:\n  <--root <ROOT>|TO>\n.*",
+    )
+    .status(USAGE_ERROR);
+}
+
+#[test]
+fn root() {
+  Test::new()
+    .write("foo", "foo")
+    .write("bar", "bar")
+    .arg("create")
+    .success()
+    .write("foo", "baz")
+    .rename("bar", "qux")
+    .write("new", "new")
+    .args(["diff", ".", "--root", ".", "--format", "tsv"])
+    .stdout(
+      "
+        added\tnew
+        modified\tfoo
+        moved\tbar\tqux
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn root_and_to_conflict() {
+  Test::new()
+    .arg("create")
+    .success()
+    .args(["diff", ".", ".", "--root", "."])
+    .stderr_regex(".*cannot be used with.*")
+    .status(USAGE_ERROR);
+}
+
+#[test]
+fn root_symlink_outside_package_error() {
+  Test::new()
+    .arg("create")
+    .success()
+    .touch("foo")
+    .relative_symlink("../foo", "bar")
+    .args(["diff", ".", "--root", "."])
+    .stderr_regex("error: symlink at `.*bar` points outside of package: `../foo`\n")
+    .failure();
+}
+
+#[test]
+fn symlinks() {
+  Test::new()
+    .write("foo", "foo")
+    .write("bar", "bar")
+    .relative_symlink("foo", "modified")
+    .relative_symlink("foo", "removed")
+    .relative_symlink("foo", "to-file")
+    .write("to-symlink", "baz")
+    .arg("create")
+    .success()
+    .remove_file("modified")
+    .relative_symlink("bar", "modified")
+    .remove_file("removed")
+    .relative_symlink("foo", "added")
+    .remove_file("to-file")
+    .write("to-file", "qux")
+    .remove_file("to-symlink")
+    .relative_symlink("bar", "to-symlink")
+    .args(["diff", ".", "--root", ".", "--format", "tsv"])
+    .stdout(
+      "
+        added\tto-file
+        added-symlink\tadded
+        added-symlink\tto-symlink
+        modified-symlink\tmodified
+        removed\tto-symlink
+        removed-symlink\tremoved
+        removed-symlink\tto-file
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn symlinks_json() {
+  Test::new()
+    .write("foo", "foo")
+    .write("bar", "bar")
+    .relative_symlink("foo", "link")
+    .arg("create")
+    .success()
+    .remove_file("link")
+    .relative_symlink("bar", "link")
+    .args(["diff", ".", "--root", ".", "--format", "json"])
+    .stdout(json! {
+      added: {},
+      added_directories: [],
+      added_symlinks: {},
+      modified: {},
+      modified_symlinks: {
+        link: {
+          new: {
+            target: "bar",
+          },
+          old: {
+            target: "foo",
+          },
+        },
+      },
+      moved: {},
+      removed: {},
+      removed_directories: [],
+      removed_symlinks: {},
+    })
+    .success();
+}
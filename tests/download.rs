@@ -67,7 +67,6 @@ fn download_fails_if_output_already_exists() {
 #[test]
 fn download_fails_on_hash_mismatch() {
   let expected = Hash::bytes(b"baz");
-  let actual = Hash::bytes(b"bar");
 
   let server = Test::new()
     .serve()
@@ -84,7 +83,7 @@ fn download_fails_on_hash_mismatch() {
       "foo",
     ])
     .stderr(&format!(
-      "error: downloaded file hash mismatch: expected {expected} but got {actual}\n",
+      "error: downloaded block at offset 0 of file {expected} does not match outboard\n",
     ))
     .failure();
 
@@ -115,6 +114,50 @@ fn download_fails_with_404_when_file_missing() {
   server.terminate().success();
 }
 
+#[test]
+fn download_falls_back_to_whole_file_hash_mismatch() {
+  let hash = Hash::bytes(b"foo");
+
+  let address = static_server(BTreeMap::from([(format!("/file/{hash}"), b"bar".to_vec())]));
+
+  let test = Test::new()
+    .args([
+      "download",
+      "--server",
+      &address,
+      "--file",
+      &hash.to_string(),
+      "foo",
+    ])
+    .stderr(&format!(
+      "error: downloaded file hash mismatch: expected {hash} but got {}\n",
+      Hash::bytes(b"bar"),
+    ))
+    .failure();
+
+  assert!(!test.path().join("foo").exists());
+  assert!(!test.path().join(format!("{hash}.partial")).exists());
+}
+
+#[test]
+fn download_falls_back_to_whole_file_without_outboard() {
+  let hash = Hash::bytes(b"foo");
+
+  let address = static_server(BTreeMap::from([(format!("/file/{hash}"), b"foo".to_vec())]));
+
+  Test::new()
+    .args([
+      "download",
+      "--server",
+      &address,
+      "--file",
+      &hash.to_string(),
+      "foo",
+    ])
+    .assert_file("foo", "foo")
+    .success();
+}
+
 #[test]
 fn download_package_fails_if_output_directory_already_exists() {
   Test::new()
@@ -258,6 +301,238 @@ fn download_package_fails_on_hash_mismatch() {
   server.terminate().success();
 }
 
+#[cfg(unix)]
+#[test]
+fn download_package_resumes() {
+  let server = Test::new().serve().spawn();
+
+  let test = Test::new()
+    .write("foo", "aaa")
+    .write("sub/bar", "bbb")
+    .relative_symlink("foo", "baz")
+    .args(["create", "."])
+    .success()
+    .args(["upload", "--server", &server.address(), "manifest.filepack"])
+    .stderr("uploading 2 of 2 files\n")
+    .success();
+
+  let fingerprint = Manifest::load(Some(&test.path().join("manifest.filepack")))
+    .unwrap()
+    .fingerprint()
+    .to_string();
+
+  let address = server.address();
+
+  let args = [
+    "download",
+    "--server",
+    &address,
+    "--package",
+    &fingerprint,
+    "out",
+  ];
+
+  let resume = [
+    "download",
+    "--resume",
+    "--server",
+    &address,
+    "--package",
+    &fingerprint,
+    "out",
+  ];
+
+  Test::new()
+    .args(args)
+    .success()
+    .remove_file("out/manifest.filepack")
+    .remove_file("out/sub/bar")
+    .args(args)
+    .stderr("error: `out` already exists\n")
+    .failure()
+    .args(resume)
+    .assert_file("out/sub/bar", "bbb")
+    .success()
+    .args(["verify", "out"])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success()
+    .remove_file("out/manifest.filepack")
+    .write("out/foo", "xxx")
+    .args(resume)
+    .stderr_path("error: `out/foo` already exists\n")
+    .failure();
+
+  server.terminate().success();
+}
+
+#[test]
+fn download_restores_executable_bit() {
+  let server = Test::new().serve().spawn();
+
+  let test = Test::new()
+    .write("foo", "aaa")
+    .chmod("foo", 0o755)
+    .write("bar", "bbb")
+    .args(["create", "."])
+    .success();
+
+  let fingerprint = Manifest::load(Some(&test.path().join("manifest.filepack")))
+    .unwrap()
+    .fingerprint();
+
+  test
+    .args(["upload", "--server", &server.address(), "manifest.filepack"])
+    .stderr("uploading 2 of 2 files\n")
+    .success();
+
+  Test::new()
+    .args([
+      "download",
+      "--server",
+      &server.address(),
+      "--package",
+      &fingerprint.to_string(),
+      "out",
+    ])
+    .success()
+    .args(["verify", "out"])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success()
+    .chmod("out/foo", 0o644)
+    .args(["verify", "out"])
+    .stderr_regex("mismatched file: `foo`\n.*")
+    .failure();
+
+  server.terminate().success();
+}
+
+#[cfg(unix)]
+#[test]
+fn download_restores_symlinks() {
+  let server = Test::new().serve().spawn();
+
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .relative_symlink("foo/bar", "baz")
+    .relative_symlink("../baz", "foo/qux")
+    .args(["create", "."])
+    .success();
+
+  let fingerprint = Manifest::load(Some(&test.path().join("manifest.filepack")))
+    .unwrap()
+    .fingerprint();
+
+  test
+    .args(["upload", "--server", &server.address(), "manifest.filepack"])
+    .stderr("uploading 1 of 1 file\n")
+    .success();
+
+  let test = Test::new()
+    .args([
+      "download",
+      "--server",
+      &server.address(),
+      "--package",
+      &fingerprint.to_string(),
+      "out",
+    ])
+    .success();
+
+  assert_eq!(
+    fs::read_link(test.path().join("out/baz")).unwrap(),
+    Path::new("foo/bar")
+  );
+  assert_eq!(
+    fs::read_link(test.path().join("out/foo/qux")).unwrap(),
+    Path::new("../baz")
+  );
+
+  test
+    .args(["verify", "out"])
+    .stderr("successfully verified 1 file totaling 3 bytes\n")
+    .success();
+
+  server.terminate().success();
+}
+
+#[test]
+fn download_resumes_after_block_mismatch() {
+  let content = (0..(1 << 18) * 2 + 100)
+    .map(|i: u32| (i % 251) as u8)
+    .collect::<Vec<u8>>();
+
+  let hash = Hash::bytes(&content);
+
+  let server = Test::new()
+    .serve()
+    .write(&format!("files/{hash}"), content.clone())
+    .spawn();
+
+  let client = Test::new()
+    .args([
+      "download",
+      "--server",
+      &server.address(),
+      "--file",
+      &hash.to_string(),
+      "foo",
+    ])
+    .success()
+    .remove_file("foo");
+
+  let mut corrupt = content.clone();
+  corrupt[(1 << 18) + 1] ^= 1;
+
+  let server = server
+    .terminate()
+    .success()
+    .write(&format!("files/{hash}"), corrupt)
+    .serve()
+    .spawn();
+
+  let client = client
+    .args([
+      "download",
+      "--server",
+      &server.address(),
+      "--file",
+      &hash.to_string(),
+      "foo",
+    ])
+    .stderr(&format!(
+      "error: downloaded block at offset 262144 of file {hash} does not match outboard\n",
+    ))
+    .failure();
+
+  let partial = client.path().join(format!("{hash}.partial"));
+
+  assert_eq!(fs::read(&partial).unwrap(), content[..1 << 18]);
+
+  let server = server
+    .terminate()
+    .success()
+    .write(&format!("files/{hash}"), content.clone())
+    .serve()
+    .spawn();
+
+  let client = client
+    .args([
+      "download",
+      "--server",
+      &server.address(),
+      "--file",
+      &hash.to_string(),
+      "foo",
+    ])
+    .success();
+
+  assert_eq!(fs::read(client.path().join("foo")).unwrap(), content);
+
+  assert!(!partial.exists());
+
+  server.terminate().success();
+}
+
 #[test]
 fn download_retrieves_file() {
   let server = Test::new()
@@ -337,6 +612,52 @@ fn download_retrieves_package() {
   server.terminate().success();
 }
 
+#[test]
+fn download_retrieves_package_by_name() {
+  let server = Test::new().serve().spawn();
+
+  let test = Test::new()
+    .write("pkg/foo", "aaa")
+    .write_keypair("master")
+    .args(["create", "pkg"])
+    .success();
+
+  let fingerprint = Manifest::load(Some(&test.path().join("pkg/manifest.filepack")))
+    .unwrap()
+    .fingerprint();
+
+  test
+    .args(["upload", "--server", &server.address(), "pkg"])
+    .stderr("uploading 1 of 1 file\n")
+    .success()
+    .args([
+      "name",
+      "publish",
+      "--server",
+      &server.address(),
+      "latest",
+      &fingerprint.to_string(),
+    ])
+    .stderr("published `latest` with sequence number 1\n")
+    .success();
+
+  Test::new()
+    .args([
+      "download",
+      "--server",
+      &server.address(),
+      "--name",
+      "latest",
+      "--key",
+      PUBLIC_KEY,
+      "out",
+    ])
+    .assert_file("out/foo", "aaa")
+    .success();
+
+  server.terminate().success();
+}
+
 #[test]
 fn download_retrieves_package_with_metadata() {
   let server = Test::new().serve().spawn();
@@ -402,3 +723,45 @@ fn server_url_must_be_http_or_https() {
     )
     .status(USAGE_ERROR);
 }
+
+fn static_server(files: BTreeMap<String, Vec<u8>>) -> String {
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+
+  let address = format!("http://{}", listener.local_addr().unwrap());
+
+  thread::spawn(move || {
+    for stream in listener.incoming() {
+      let mut stream = stream.unwrap();
+
+      let mut request = Vec::new();
+
+      while !request.ends_with(b"\r\n\r\n") {
+        let mut byte = [0];
+        if stream.read(&mut byte).unwrap() == 0 {
+          break;
+        }
+        request.push(byte[0]);
+      }
+
+      let request = str::from_utf8(&request).unwrap();
+
+      let path = request.split(' ').nth(1).unwrap_or_default();
+
+      let (status, body) = match files.get(path) {
+        Some(body) => ("200 OK", body.as_slice()),
+        None => ("404 Not Found", b"not found".as_slice()),
+      };
+
+      write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len(),
+      )
+      .unwrap();
+
+      stream.write_all(body).unwrap();
+    }
+  });
+
+  address
+}
@@ -0,0 +1,223 @@
+use super::*;
+
+#[test]
+fn embedded_files_are_exported_from_manifest() {
+  let test = Test::new()
+    .touch("foo/README.md")
+    .write("foo/metadata.yaml", "title: Foo\nreadme: README.md\n")
+    .args(["create", "foo"])
+    .success()
+    .args(["export", "--manifest", "foo", "--format", "tar", "foo.tar"])
+    .success();
+
+  tar::Archive::new(fs::File::open(test.path().join("foo.tar")).unwrap())
+    .unpack(test.path().join("bar"))
+    .unwrap();
+
+  test
+    .args(["verify", "bar"])
+    .stderr_regex("successfully verified 3 files totaling .* bytes\n")
+    .success();
+}
+
+#[test]
+fn exports_are_deterministic() {
+  let mut test = Test::new()
+    .arg("keygen")
+    .success()
+    .write("foo/bar/baz", "baz")
+    .write("foo/qux", "qux")
+    .args(["create", "foo"])
+    .success()
+    .write("bar/qux", "qux")
+    .write("bar/bar/baz", "baz")
+    .args(["create", "bar"])
+    .success()
+    .args(["sign", "bar"])
+    .success();
+
+  for format in ["tar", "tar.zst", "zip"] {
+    test = test
+      .args([
+        "export",
+        "--manifest",
+        "foo",
+        "--format",
+        format,
+        &format!("foo.{format}"),
+      ])
+      .success()
+      .args([
+        "export",
+        "--manifest",
+        "bar",
+        "--format",
+        format,
+        &format!("bar.{format}"),
+      ])
+      .success();
+
+    assert_eq!(
+      fs::read(test.path().join(format!("foo.{format}"))).unwrap(),
+      fs::read(test.path().join(format!("bar.{format}"))).unwrap(),
+    );
+  }
+}
+
+#[test]
+fn mismatched_file_is_rejected() {
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success()
+    .write("foo/bar", "baz")
+    .args(["export", "--manifest", "foo", "--format", "tar", "foo.tar"])
+    .stderr(&format!(
+      "
+        error: failed to export `bar`
+               └─ file hash {} does not match expected hash {}
+      ",
+      Hash::bytes(b"baz"),
+      Hash::bytes(b"bar"),
+    ))
+    .failure();
+
+  assert!(!test.path().join("foo.tar").exists());
+}
+
+#[test]
+fn output_already_exists() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success()
+    .touch("foo.tar")
+    .args(["export", "--manifest", "foo", "--format", "tar", "foo.tar"])
+    .stderr("error: `foo.tar` already exists\n")
+    .failure();
+}
+
+#[cfg(unix)]
+#[test]
+fn tar() {
+  let test = Test::new()
+    .write("foo/bar/baz", "baz")
+    .write("foo/qux", "qux")
+    .chmod("foo/qux", 0o755)
+    .create_dir("foo/empty")
+    .relative_symlink("bar/baz", "foo/link")
+    .args(["create", "foo"])
+    .success()
+    .args(["export", "--manifest", "foo", "--format", "tar", "foo.tar"])
+    .success();
+
+  let mut archive = tar::Archive::new(fs::File::open(test.path().join("foo.tar")).unwrap());
+
+  let entries = archive
+    .entries()
+    .unwrap()
+    .map(|entry| {
+      let entry = entry.unwrap();
+      let header = entry.header();
+      assert_eq!(header.mtime().unwrap(), 0);
+      assert_eq!(header.uid().unwrap(), 0);
+      assert_eq!(header.gid().unwrap(), 0);
+      (
+        entry.path().unwrap().to_str().unwrap().to_owned(),
+        header.mode().unwrap(),
+        entry
+          .link_name()
+          .unwrap()
+          .map(|link| link.to_str().unwrap().to_owned()),
+      )
+    })
+    .collect::<Vec<(String, u32, Option<String>)>>();
+
+  assert_eq!(
+    entries,
+    [
+      ("manifest.filepack".into(), 0o644, None),
+      ("bar/".into(), 0o755, None),
+      ("bar/baz".into(), 0o644, None),
+      ("empty/".into(), 0o755, None),
+      ("link".into(), 0o777, Some("bar/baz".into())),
+      ("qux".into(), 0o755, None),
+    ],
+  );
+
+  tar::Archive::new(fs::File::open(test.path().join("foo.tar")).unwrap())
+    .unpack(test.path().join("bar"))
+    .unwrap();
+
+  test
+    .args(["verify", "bar"])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success();
+}
+
+#[test]
+fn tar_zst() {
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["export", "--manifest", "foo", "--format", "tar", "foo.tar"])
+    .success()
+    .args([
+      "export",
+      "--manifest",
+      "foo",
+      "--format",
+      "tar.zst",
+      "foo.tar.zst",
+    ])
+    .success();
+
+  assert_eq!(
+    zstd::decode_all(fs::File::open(test.path().join("foo.tar.zst")).unwrap()).unwrap(),
+    fs::read(test.path().join("foo.tar")).unwrap(),
+  );
+}
+
+#[cfg(unix)]
+#[test]
+fn zip() {
+  let test = Test::new()
+    .write("foo/bar/baz", "baz")
+    .write("foo/qux", "qux")
+    .chmod("foo/qux", 0o755)
+    .relative_symlink("bar/baz", "foo/link")
+    .args(["create", "foo"])
+    .success()
+    .args(["export", "--manifest", "foo", "--format", "zip", "foo.zip"])
+    .success();
+
+  let mut archive =
+    zip::ZipArchive::new(fs::File::open(test.path().join("foo.zip")).unwrap()).unwrap();
+
+  let mut entries = Vec::new();
+
+  for i in 0..archive.len() {
+    let mut file = archive.by_index(i).unwrap();
+    let mut content = String::new();
+    if !file.is_dir() && file.name() != "manifest.filepack" {
+      file.read_to_string(&mut content).unwrap();
+    }
+    entries.push((
+      file.name().to_owned(),
+      file.unix_mode().unwrap() & 0o777,
+      content,
+    ));
+  }
+
+  assert_eq!(
+    entries,
+    [
+      ("manifest.filepack".into(), 0o644, String::new()),
+      ("bar/".into(), 0o755, String::new()),
+      ("bar/baz".into(), 0o644, "baz".into()),
+      ("link".into(), 0o777, "bar/baz".into()),
+      ("qux".into(), 0o755, "qux".into()),
+    ],
+  );
+}
@@ -0,0 +1,333 @@
+use super::*;
+
+#[test]
+fn embedded_files_are_extracted_from_manifest() {
+  Test::new()
+    .touch("src/README.md")
+    .write("src/metadata.yaml", "title: Foo\nreadme: README.md\n")
+    .args(["create", "src"])
+    .success()
+    .create_dir("store")
+    .write(&format!("store/{EMPTY_HASH}"), "")
+    .write(
+      &format!("store/{}", Hash::bytes(b"title: Foo\nreadme: README.md\n")),
+      "title: Foo\nreadme: README.md\n",
+    )
+    .args(["extract", "--manifest", "src", "--source", "store", "out"])
+    .success()
+    .args(["verify", "out"])
+    .stderr_regex("successfully verified 3 files totaling .* bytes\n")
+    .success();
+}
+
+#[cfg(unix)]
+#[test]
+fn executable_bit_is_restored() {
+  use std::os::unix::fs::PermissionsExt;
+
+  let test = Test::new()
+    .write("src/foo", "foo")
+    .chmod("src/foo", 0o755)
+    .write("src/bar", "bar")
+    .args(["create", "src"])
+    .success()
+    .args(["extract", "--manifest", "src", "--source", "src", "out"])
+    .success();
+
+  let mode = |path: &str| {
+    fs::metadata(test.path().join(path))
+      .unwrap()
+      .permissions()
+      .mode()
+  };
+
+  assert_ne!(mode("out/foo") & 0o100, 0);
+  assert_eq!(mode("out/bar") & 0o111, 0);
+
+  test
+    .args(["verify", "out"])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success();
+}
+
+#[test]
+fn file_missing_from_sources() {
+  Test::new()
+    .write("src/foo", "foo")
+    .args(["create", "src"])
+    .success()
+    .create_dir("store")
+    .args(["extract", "--manifest", "src", "--source", "store", "out"])
+    .stderr(&format!(
+      "error: file `foo` with hash `{}` not found in any source\n",
+      Hash::bytes(b"foo"),
+    ))
+    .failure();
+}
+
+#[test]
+fn from_content_store() {
+  let foo = Hash::bytes(b"foo");
+  let bar = Hash::bytes(b"bar");
+
+  Test::new()
+    .write("src/foo", "foo")
+    .write("src/sub/bar", "bar")
+    .create_dir("src/empty")
+    .args(["create", "src"])
+    .success()
+    .write(&format!("store/{foo}"), "foo")
+    .write(&format!("store/{bar}"), "bar")
+    .args([
+      "extract",
+      "--manifest",
+      "src/manifest.filepack",
+      "--source",
+      "store",
+      "out",
+    ])
+    .assert_file("out/foo", "foo")
+    .assert_file("out/sub/bar", "bar")
+    .assert_dir("out/empty")
+    .success()
+    .args(["verify", "out"])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success();
+}
+
+#[test]
+fn from_package_directory() {
+  Test::new()
+    .write("src/foo", "foo")
+    .write("src/sub/bar", "bar")
+    .args(["create", "src"])
+    .success()
+    .args(["extract", "--manifest", "src", "--source", "src", "out"])
+    .assert_file("out/foo", "foo")
+    .assert_file("out/sub/bar", "bar")
+    .success()
+    .args(["verify", "out"])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success();
+}
+
+#[test]
+fn from_server() {
+  let server = Test::new().serve().spawn();
+
+  let test = Test::new()
+    .write("foo", "foo")
+    .write("sub/bar", "bar")
+    .args(["create", "."])
+    .success();
+
+  let fingerprint = Manifest::load(Some(&test.path().join("manifest.filepack")))
+    .unwrap()
+    .fingerprint();
+
+  test
+    .args(["upload", "--server", &server.address(), "manifest.filepack"])
+    .stderr("uploading 2 of 2 files\n")
+    .success();
+
+  Test::new()
+    .args([
+      "extract",
+      "--package",
+      &fingerprint.to_string(),
+      "--source",
+      &server.address(),
+      "out",
+    ])
+    .assert_file("out/foo", "foo")
+    .assert_file("out/sub/bar", "bar")
+    .success()
+    .args(["verify", "out", "--fingerprint", &fingerprint.to_string()])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success();
+
+  server.terminate().success();
+}
+
+#[test]
+fn hash_mismatch() {
+  let foo = Hash::bytes(b"foo");
+
+  Test::new()
+    .write("src/foo", "foo")
+    .args(["create", "src"])
+    .success()
+    .write(&format!("store/{foo}"), "bar")
+    .args(["extract", "--manifest", "src", "--source", "store", "out"])
+    .stderr(&format!(
+      "error: extracted file `out/foo` hash mismatch: expected {foo} but got {}\n",
+      Hash::bytes(b"bar"),
+    ))
+    .failure();
+}
+
+#[test]
+fn hash_mismatch_falls_through_to_next_source() {
+  let foo = Hash::bytes(b"foo");
+
+  Test::new()
+    .write("src/foo", "foo")
+    .args(["create", "src"])
+    .success()
+    .write(&format!("store/{foo}"), "bar")
+    .args([
+      "extract",
+      "--manifest",
+      "src",
+      "--source",
+      "store",
+      "--source",
+      "src",
+      "out",
+    ])
+    .assert_file("out/foo", "foo")
+    .success();
+}
+
+#[test]
+fn manifest_conflict() {
+  let manifest = Hash::bytes(b"manifest");
+
+  Test::new()
+    .write("src/manifest.filepack", "manifest")
+    .args(["create", "src", "--manifest", "package.filepack"])
+    .success()
+    .write(&format!("store/{manifest}"), "manifest")
+    .args([
+      "extract",
+      "--manifest",
+      "package.filepack",
+      "--source",
+      "store",
+      "out",
+    ])
+    .stderr("error: package file `manifest.filepack` would be overwritten by extracted manifest\n")
+    .failure();
+}
+
+#[test]
+fn output_already_exists() {
+  Test::new()
+    .write("src/foo", "foo")
+    .args(["create", "src"])
+    .success()
+    .create_dir("out")
+    .args(["extract", "--manifest", "src", "--source", "src", "out"])
+    .stderr("error: `out` already exists\n")
+    .failure();
+}
+
+#[test]
+fn package_from_package_directory() {
+  let test = Test::new()
+    .write("src/foo", "foo")
+    .write("src/sub/bar", "bar")
+    .args(["create", "src"])
+    .success();
+
+  let fingerprint = Manifest::load(Some(&test.path().join("src")))
+    .unwrap()
+    .fingerprint();
+
+  test
+    .args([
+      "extract",
+      "--package",
+      &fingerprint.to_string(),
+      "--source",
+      "src",
+      "out",
+    ])
+    .assert_file("out/foo", "foo")
+    .assert_file("out/sub/bar", "bar")
+    .success()
+    .args(["verify", "out", "--fingerprint", &fingerprint.to_string()])
+    .stderr("successfully verified 2 files totaling 6 bytes\n")
+    .success();
+}
+
+#[test]
+fn package_missing_from_sources() {
+  let fingerprint = Fingerprint::from(Hash::bytes(b"foo"));
+
+  Test::new()
+    .create_dir("store")
+    .args([
+      "extract",
+      "--package",
+      &fingerprint.to_string(),
+      "--source",
+      "store",
+      "out",
+    ])
+    .stderr(&format!(
+      "error: directory `{}` not found in any source\n",
+      Hash::bytes(b"foo"),
+    ))
+    .failure();
+}
+
+#[test]
+fn source_required() {
+  Test::new()
+    .args(["extract", "out"])
+    .stderr_regex(".*required arguments were not provided.*--source <SOURCE>.*")
+    .status(USAGE_ERROR);
+}
+
+#[test]
+fn sources_are_tried_in_order() {
+  let foo = Hash::bytes(b"foo");
+
+  Test::new()
+    .write("src/foo", "foo")
+    .write("src/bar", "bar")
+    .args(["create", "src"])
+    .success()
+    .write(&format!("store/{foo}"), "foo")
+    .args([
+      "extract",
+      "--manifest",
+      "src",
+      "--source",
+      "store",
+      "--source",
+      "src",
+      "out",
+    ])
+    .assert_file("out/foo", "foo")
+    .assert_file("out/bar", "bar")
+    .success();
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks_are_restored() {
+  let test = Test::new()
+    .write("src/foo/bar", "bar")
+    .relative_symlink("foo/bar", "src/baz")
+    .relative_symlink("../baz", "src/foo/qux")
+    .args(["create", "src"])
+    .success()
+    .args(["extract", "--manifest", "src", "--source", "src", "out"])
+    .success();
+
+  assert_eq!(
+    fs::read_link(test.path().join("out/baz")).unwrap(),
+    Path::new("foo/bar")
+  );
+  assert_eq!(
+    fs::read_link(test.path().join("out/foo/qux")).unwrap(),
+    Path::new("../baz")
+  );
+
+  test
+    .args(["verify", "out"])
+    .stderr("successfully verified 1 file totaling 3 bytes\n")
+    .success();
+}
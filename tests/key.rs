@@ -1,5 +1,29 @@
 use super::*;
 
+#[test]
+fn decrypt_incorrect_passphrase() {
+  Test::new()
+    .arg("keygen")
+    .success()
+    .args(["key", "encrypt"])
+    .env("FILEPACK_PASSPHRASE", "hunter2")
+    .success()
+    .args(["key", "decrypt"])
+    .env("FILEPACK_PASSPHRASE", "hunter3")
+    .stderr_regex("error: invalid private key `.*master.private`\n.*incorrect passphrase\n")
+    .failure();
+}
+
+#[test]
+fn decrypt_not_encrypted() {
+  Test::new()
+    .arg("keygen")
+    .success()
+    .args(["key", "decrypt"])
+    .stderr_regex("error: private key not encrypted: `.*master.private`\n")
+    .failure();
+}
+
 #[test]
 fn default() {
   let test = Test::new()
@@ -14,6 +38,38 @@ fn default() {
   test.arg("key").stdout(format!("{public_key}\n")).success();
 }
 
+#[test]
+fn encrypt() {
+  let test = Test::new().arg("keygen").success();
+
+  let private_key = test.read_private_key("keychain/master.private");
+
+  let test = test
+    .args(["key", "encrypt", "--key", "master"])
+    .env("FILEPACK_PASSPHRASE", "hunter2")
+    .assert_file_regex("keychain/master.private", "encrypted1a.{200}\n")
+    .success()
+    .args(["key", "decrypt"])
+    .env("FILEPACK_PASSPHRASE", "hunter2")
+    .success();
+
+  assert_eq!(
+    test.read_private_key("keychain/master.private"),
+    private_key
+  );
+}
+
+#[test]
+fn encrypt_empty_passphrase() {
+  Test::new()
+    .arg("keygen")
+    .success()
+    .args(["key", "encrypt"])
+    .env("FILEPACK_PASSPHRASE", "")
+    .stderr("error: passphrase may not be empty\n")
+    .failure();
+}
+
 #[test]
 fn master() {
   let test = Test::new()
@@ -79,3 +135,56 @@ fn named() {
     .stdout(format!("{public_key}\n"))
     .success();
 }
+
+#[test]
+fn revoke() {
+  Test::new()
+    .write_keypair("master")
+    .args(["key", "revoke", "--time", "2025-01-01T00:00:00Z"])
+    .stdout_regex("revocation1a.*\n")
+    .assert_file_regex("revocations.txt", "revocation1a.*\n")
+    .success()
+    .arg("key")
+    .stdout(format!("{PUBLIC_KEY}\n"))
+    .stderr("key `master` revoked at `2025-01-01T00:00:00Z`\n")
+    .success();
+}
+
+#[test]
+fn revoke_self_replacement() {
+  Test::new()
+    .write_keypair("master")
+    .args(["key", "revoke", "--replacement", "master"])
+    .stderr(&format!(
+      "error: key `{PUBLIC_KEY}` cannot be its own replacement\n"
+    ))
+    .failure();
+}
+
+#[test]
+fn revoke_with_replacement() {
+  let test = Test::new()
+    .write_keypair("master")
+    .args(["keygen", "--name", "replacement"])
+    .success();
+
+  let replacement = test.read("keychain/replacement.public");
+
+  test
+    .args([
+      "key",
+      "revoke",
+      "--replacement",
+      "replacement",
+      "--time",
+      "2025-01-01T00:00:00Z",
+    ])
+    .stdout_regex("revocation1a.*\n")
+    .success()
+    .arg("key")
+    .stdout(format!("{PUBLIC_KEY}\n"))
+    .stderr(&format!(
+      "key `master` revoked at `2025-01-01T00:00:00Z`\nreplaced by `{replacement}`\n"
+    ))
+    .success();
+}
@@ -34,6 +34,31 @@ fn default_name() {
   assert_eq!(private_key.public_key(), public_key);
 }
 
+#[test]
+fn encrypt() {
+  let test = Test::new()
+    .args(["keygen", "--encrypt"])
+    .env("FILEPACK_PASSPHRASE", "hunter2")
+    .assert_file_regex("keychain/master.public", "public1a.{58}\n")
+    .assert_file_regex("keychain/master.private", "encrypted1a.{200}\n")
+    .success();
+
+  let public_key = test.read_public_key("keychain/master.public");
+
+  let test = test
+    .args(["key", "encrypt"])
+    .stderr_regex("error: private key already encrypted: `.*master.private`\n")
+    .failure()
+    .args(["key", "decrypt"])
+    .env("FILEPACK_PASSPHRASE", "hunter2")
+    .assert_file_regex("keychain/master.private", "private1a.{110}\n")
+    .success();
+
+  let private_key = test.read_private_key("keychain/master.private");
+
+  assert_eq!(private_key.public_key(), public_key);
+}
+
 #[test]
 fn invalid_name() {
   Test::new()
@@ -0,0 +1,179 @@
+use super::*;
+
+#[test]
+fn delete() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keys", "delete", "master"])
+    .stdin("y\n")
+    .stderr(&format!(
+      "delete key `master` with public key `{PUBLIC_KEY}`? [y/N] "
+    ))
+    .assert_file_count("keychain", 0)
+    .success()
+    .args(["keys", "list", "--format", "tsv"])
+    .success();
+}
+
+#[test]
+fn delete_missing() {
+  Test::new()
+    .args(["keys", "delete", "--yes", "master"])
+    .stderr_regex("error: public key not found: `.*master.public`\n")
+    .failure();
+}
+
+#[test]
+fn delete_unconfirmed() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keys", "delete", "master"])
+    .stdin("n\n")
+    .stderr(&format!(
+      "delete key `master` with public key `{PUBLIC_KEY}`? [y/N] error: deletion of key `master` not confirmed\n"
+    ))
+    .assert_file_count("keychain", 2)
+    .failure();
+}
+
+#[test]
+fn delete_yes() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keys", "delete", "--yes", "master"])
+    .assert_file_count("keychain", 0)
+    .success();
+}
+
+#[test]
+fn export() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keys", "export", "master"])
+    .stdout(format!("{PUBLIC_KEY}\n"))
+    .success();
+}
+
+#[test]
+fn import() {
+  Test::new()
+    .args(["keys", "import", "deploy"])
+    .stdin(&format!("{PRIVATE_KEY}\n"))
+    .stdout(format!("{PUBLIC_KEY}\n"))
+    .assert_file("keychain/deploy.private", &format!("{PRIVATE_KEY}\n"))
+    .assert_file("keychain/deploy.public", &format!("{PUBLIC_KEY}\n"))
+    .success()
+    .args(["key", "--key", "deploy"])
+    .stdout(format!("{PUBLIC_KEY}\n"))
+    .success();
+}
+
+#[test]
+fn import_duplicate() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keys", "import", "deploy"])
+    .stdin(PRIVATE_KEY)
+    .stderr("error: private key is already in keychain as `master`\n")
+    .failure();
+}
+
+#[test]
+fn import_encrypted() {
+  let test = Test::new()
+    .args(["keygen", "--encrypt", "--name", "original"])
+    .env("FILEPACK_PASSPHRASE", "hunter2")
+    .success();
+
+  let private_key = test.read("keychain/original.private");
+  let public_key = test.read("keychain/original.public");
+
+  test
+    .args(["keys", "delete", "--yes", "original"])
+    .success()
+    .args(["keys", "import", "deploy"])
+    .stdin(&private_key)
+    .stdout(format!("{public_key}\n"))
+    .success()
+    .args(["keys", "list", "--format", "tsv"])
+    .stdout(format!("deploy\t{public_key}\t0600\ttrue\n"))
+    .success();
+}
+
+#[test]
+fn import_existing_name() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keys", "import", "master"])
+    .stdin(PRIVATE_KEY)
+    .stderr_regex("error: public key already exists: `.*master.public`\n")
+    .failure();
+}
+
+#[test]
+fn import_invalid() {
+  Test::new()
+    .args(["keys", "import", "deploy"])
+    .stdin("foo")
+    .stderr_regex("error: invalid private key on standard input\n.*")
+    .failure();
+}
+
+#[test]
+fn import_invalid_name() {
+  Test::new()
+    .args(["keys", "import", "@deploy"])
+    .stderr_regex("error: invalid value '@deploy' for '<NAME>'.*")
+    .status(USAGE_ERROR);
+}
+
+#[test]
+fn list() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keys", "list", "--format", "tsv"])
+    .stdout(format!("master\t{PUBLIC_KEY}\t0600\tfalse\n"))
+    .success()
+    .args(["keys", "list", "--format", "json"])
+    .stdout(format!(
+      r#"[{{"encrypted":false,"mode":"0600","name":"master","public_key":"{PUBLIC_KEY}","secure":true}}]
+"#
+    ))
+    .success();
+}
+
+#[test]
+fn list_empty() {
+  Test::new().args(["keys", "list"]).stdout("[]\n").success();
+}
+
+#[test]
+fn rename() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keys", "rename", "master", "deploy"])
+    .assert_file_count("keychain", 2)
+    .success()
+    .args(["keys", "export", "deploy"])
+    .stdout(format!("{PUBLIC_KEY}\n"))
+    .success();
+}
+
+#[test]
+fn rename_existing() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keygen", "--name", "deploy"])
+    .success()
+    .args(["keys", "rename", "master", "deploy"])
+    .stderr_regex("error: public key already exists: `.*deploy.public`\n")
+    .failure();
+}
+
+#[test]
+fn rename_missing() {
+  Test::new()
+    .args(["keys", "rename", "master", "deploy"])
+    .stderr_regex("error: public key not found: `.*master.public`\n")
+    .failure();
+}
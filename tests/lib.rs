@@ -30,14 +30,19 @@ use {
 
 mod archive;
 mod bech32;
+mod bundle;
+mod check_proof;
 mod child;
 mod contains;
 mod create;
 mod data_dir;
 mod dedent;
 mod delete;
+mod diff;
 mod download;
 mod expected;
+mod export;
+mod extract;
 mod files;
 mod fingerprint;
 mod gc;
@@ -47,6 +52,7 @@ mod json;
 mod key;
 mod keychain;
 mod keygen;
+mod keys;
 mod languages;
 mod lint;
 mod lints;
@@ -54,11 +60,18 @@ mod man;
 mod manifest;
 mod metadata;
 mod misc;
+mod name;
+mod prove;
+mod revocation;
 mod serve;
 mod sign;
 mod signatures;
 mod size;
+#[cfg(unix)]
+mod ssh_agent;
+mod status;
 mod test;
+mod trust;
 mod upload;
 mod verify;
 
@@ -158,6 +158,16 @@ fn deny_junk_ignores_compatibility() {
     .failure();
 }
 
+#[test]
+fn deny_junk_respects_filepackignore() {
+  Test::new()
+    .write(".filepackignore", ".DS_Store\n")
+    .touch(".DS_Store")
+    .touch("foo/.DS_Store")
+    .args(["create", "--deny", "junk", "."])
+    .success();
+}
+
 #[test]
 fn deny_lint() {
   if cfg!(windows) {
@@ -0,0 +1,175 @@
+use super::*;
+
+fn fingerprint(content: &str) -> String {
+  let test = Test::new()
+    .write("foo", content)
+    .args(["create", "."])
+    .success();
+
+  Manifest::load(Some(&test.path().join("manifest.filepack")))
+    .unwrap()
+    .fingerprint()
+    .to_string()
+}
+
+#[test]
+fn publish_and_resolve() {
+  let server = Test::new().serve().spawn();
+
+  let a = fingerprint("a");
+  let b = fingerprint("b");
+
+  let publisher = Test::new()
+    .write_keypair("master")
+    .args([
+      "name",
+      "publish",
+      "--server",
+      &server.address(),
+      "latest",
+      &a,
+    ])
+    .stderr("published `latest` with sequence number 1\n")
+    .success();
+
+  let resolver = Test::new()
+    .args([
+      "name",
+      "resolve",
+      "--server",
+      &server.address(),
+      "--key",
+      PUBLIC_KEY,
+      "latest",
+    ])
+    .stdout(format!("{a}\n"))
+    .success();
+
+  publisher
+    .args([
+      "name",
+      "publish",
+      "--server",
+      &server.address(),
+      "latest",
+      &b,
+    ])
+    .stderr("published `latest` with sequence number 2\n")
+    .success();
+
+  resolver
+    .args([
+      "name",
+      "resolve",
+      "--server",
+      &server.address(),
+      "--key",
+      PUBLIC_KEY,
+      "latest",
+    ])
+    .stdout(format!("{b}\n"))
+    .success();
+
+  server.terminate().success();
+}
+
+#[test]
+fn publish_rejects_local_rollback() {
+  let server = Test::new().serve().spawn();
+
+  let a = fingerprint("a");
+
+  Test::new()
+    .write_keypair("master")
+    .args([
+      "name",
+      "publish",
+      "--server",
+      &server.address(),
+      "--sequence",
+      "5",
+      "latest",
+      &a,
+    ])
+    .stderr("published `latest` with sequence number 5\n")
+    .success()
+    .args([
+      "name",
+      "publish",
+      "--server",
+      &server.address(),
+      "--sequence",
+      "4",
+      "latest",
+      &a,
+    ])
+    .stderr(&format!(
+      "error: name `latest` for key `{PUBLIC_KEY}` has sequence number 4, but sequence number 5 \
+      was previously seen\n"
+    ))
+    .failure();
+
+  server.terminate().success();
+}
+
+#[test]
+fn publish_rejects_stale_sequence() {
+  let server = Test::new().serve().spawn();
+
+  let a = fingerprint("a");
+  let b = fingerprint("b");
+
+  Test::new()
+    .write_keypair("master")
+    .args([
+      "name",
+      "publish",
+      "--server",
+      &server.address(),
+      "--sequence",
+      "5",
+      "latest",
+      &a,
+    ])
+    .stderr("published `latest` with sequence number 5\n")
+    .success()
+    .args([
+      "name",
+      "publish",
+      "--server",
+      &server.address(),
+      "--sequence",
+      "5",
+      "latest",
+      &b,
+    ])
+    .stderr_regex(&format!(
+      "error: response from http://.* failed with status 409 Conflict: sequence number 5 for \
+      name `latest` of key {PUBLIC_KEY} is not greater than current sequence number 5\n"
+    ))
+    .failure();
+
+  server.terminate().success();
+}
+
+#[test]
+fn resolve_not_found() {
+  let server = Test::new().serve().spawn();
+
+  Test::new()
+    .args([
+      "name",
+      "resolve",
+      "--server",
+      &server.address(),
+      "--key",
+      PUBLIC_KEY,
+      "latest",
+    ])
+    .stderr(&format!(
+      "error: name `latest` for key `{PUBLIC_KEY}` not found\n"
+    ))
+    .failure();
+
+  server.terminate().success();
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+#[test]
+fn file_not_found() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .arg("create")
+    .success()
+    .args(["prove", "foo", "--output", "proof"])
+    .stderr("error: manifest does not contain file `foo`\n")
+    .failure();
+}
+
+#[test]
+fn manifest_option() {
+  let test = Test::new()
+    .write("foo/bar/baz", "baz")
+    .args(["create", "foo"])
+    .success()
+    .args(["prove", "--manifest", "foo", "bar/baz", "--output", "proof"])
+    .success();
+
+  let fingerprint = Manifest::load(Some(&test.path().join("foo")))
+    .unwrap()
+    .fingerprint();
+
+  let hash = Hash::bytes(b"baz").to_string();
+
+  test
+    .args([
+      "check-proof",
+      "proof",
+      "--fingerprint",
+      &fingerprint.to_string(),
+    ])
+    .stdout(json_pretty! {
+      hash: hash,
+      path: "bar/baz",
+      size: 3,
+    })
+    .success();
+}
+
+#[test]
+fn output_required() {
+  Test::new()
+    .args(["prove", "foo"])
+    .stderr_regex(".*required arguments were not provided.*--output <OUTPUT>.*")
+    .status(USAGE_ERROR);
+}
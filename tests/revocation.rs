@@ -0,0 +1,84 @@
+use super::*;
+
+#[test]
+fn add_and_list() {
+  let test = Test::new()
+    .write_keypair("master")
+    .args(["key", "revoke", "--time", "2025-01-01T00:00:00Z"])
+    .stdout_regex("revocation1a.*\n")
+    .success();
+
+  let revocation = test.read("revocations.txt");
+
+  test
+    .data_dir("bob")
+    .args(["revocation", "add", &revocation])
+    .success()
+    .data_dir("bob")
+    .args(["revocation", "list", "--format", "tsv"])
+    .stdout(format!("{PUBLIC_KEY}\t2025-01-01T00:00:00Z\t\n"))
+    .success()
+    .data_dir("bob")
+    .args(["revocation", "list", "--format", "json"])
+    .stdout(format!(
+      r#"[{{"public_key":"{PUBLIC_KEY}","revocation":"{revocation}","time":"2025-01-01T00:00:00Z"}}]
+"#
+    ))
+    .success();
+}
+
+#[test]
+fn earliest_revocation_is_kept() {
+  Test::new()
+    .write_keypair("master")
+    .args(["key", "revoke", "--time", "2025-01-01T00:00:00Z"])
+    .stdout_regex("revocation1a.*\n")
+    .success()
+    .args(["key", "revoke", "--time", "2026-01-01T00:00:00Z"])
+    .stdout_regex("revocation1a.*\n")
+    .success()
+    .args(["revocation", "list", "--format", "tsv"])
+    .stdout(format!("{PUBLIC_KEY}\t2025-01-01T00:00:00Z\t\n"))
+    .success();
+}
+
+#[test]
+fn invalid_revocation_file() {
+  Test::new()
+    .write("revocations.txt", "foo\n")
+    .args(["revocation", "list"])
+    .stderr_regex("error: invalid revocation on line 1 of `.*revocations.txt`\n.*")
+    .failure();
+}
+
+#[test]
+fn list_empty() {
+  Test::new()
+    .args(["revocation", "list"])
+    .stdout("[]\n")
+    .success();
+}
+
+#[test]
+fn publish_and_fetch() {
+  let server = Test::new().serve().spawn();
+
+  Test::new()
+    .write_keypair("master")
+    .args(["key", "revoke", "--time", "2025-01-01T00:00:00Z"])
+    .stdout_regex("revocation1a.*\n")
+    .success()
+    .args(["revocation", "publish", "--server", &server.address()])
+    .stderr("published 1 revocation\n")
+    .success();
+
+  Test::new()
+    .args(["revocation", "fetch", "--server", &server.address()])
+    .stderr("added 1 revocation\n")
+    .success()
+    .args(["revocation", "list", "--format", "tsv"])
+    .stdout(format!("{PUBLIC_KEY}\t2025-01-01T00:00:00Z\t\n"))
+    .success();
+
+  server.terminate().success();
+}
@@ -1,5 +1,45 @@
 use super::*;
 
+#[test]
+fn add() {
+  Test::new()
+    .arg("keygen")
+    .success()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["sign", "--detached", "foo.signature", "foo"])
+    .success()
+    .args(["signatures", "--add", "foo.signature", "foo"])
+    .success()
+    .args(["verify", "foo", "--key", "master"])
+    .stderr("successfully verified 1 file totaling 0 bytes with 1 signature\n")
+    .success();
+}
+
+#[test]
+fn add_fingerprint_mismatch() {
+  Test::new()
+    .arg("keygen")
+    .success()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .touch("baz/bob")
+    .args(["create", "baz"])
+    .success()
+    .args(["sign", "--detached", "baz.signature", "baz"])
+    .success()
+    .args(["signatures", "--add", "baz.signature", "foo"])
+    .stderr_regex(
+      "error: signature fingerprint `package1.*` does not match package fingerprint `package1.*`\n",
+    )
+    .failure()
+    .args(["signatures", "foo"])
+    .stdout("[]\n")
+    .success();
+}
+
 #[test]
 fn defaults_to_current_directory() {
   Test::new()
@@ -64,6 +104,68 @@ fn signature_without_time() {
     .success();
 }
 
+#[test]
+fn statement_fields() {
+  let test = Test::new()
+    .arg("keygen")
+    .success()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args([
+      "sign",
+      "--purpose",
+      "release",
+      "--comment",
+      "tested on linux",
+      "--expires",
+      "2100-01-01T00:00:00Z",
+      "foo",
+    ])
+    .success();
+
+  let public_key = test.read("keychain/master.public");
+
+  test
+    .args(["signatures", "--format", "json", "foo"])
+    .stdout(format!(
+      r#"[{{"comment":"tested on linux","expires":4102444800,"public_key":"{public_key}","purpose":"release","timestamp":null}}]
+"#
+    ))
+    .success()
+    .args(["signatures", "--format", "tsv", "foo"])
+    .stdout(format!(
+      "{public_key}\t\t\t4102444800\trelease\ttested on linux\n"
+    ))
+    .success();
+}
+
+#[test]
+fn trusted_name() {
+  let test = Test::new()
+    .arg("keygen")
+    .success()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["sign", "foo"])
+    .success();
+
+  let public_key = test.read("keychain/master.public");
+
+  test
+    .args(["trust", "add", "alice", &public_key])
+    .success()
+    .args(["signatures", "--format", "json", "foo"])
+    .stdout(format!(
+      "[{{\"public_key\":\"{public_key}\",\"timestamp\":null,\"trusted\":\"alice\"}}]\n"
+    ))
+    .success()
+    .args(["signatures", "--format", "tsv", "foo"])
+    .stdout(format!("{public_key}\t\talice\t\t\t\n"))
+    .success();
+}
+
 #[test]
 fn tsv_format() {
   let test = Test::new()
@@ -79,7 +181,7 @@ fn tsv_format() {
 
   test
     .args(["signatures", "--format", "tsv", "foo"])
-    .stdout(format!("{public_key}\t\n"))
+    .stdout(format!("{public_key}\t\t\t\t\t\n"))
     .success();
 }
 
@@ -98,6 +200,6 @@ fn tsv_format_with_time() {
 
   test
     .args(["signatures", "--format", "tsv", "foo"])
-    .stdout_regex(&format!("{public_key}\t\\d+\n"))
+    .stdout_regex(&format!("{public_key}\t\\d+\t\t\t\t\n"))
     .success();
 }
@@ -0,0 +1,114 @@
+use {
+  super::*,
+  base64::{Engine, engine::general_purpose::STANDARD},
+  ed25519_dalek::{Signer, SigningKey},
+  std::os::unix::net::{UnixListener, UnixStream},
+};
+
+const ED25519: &[u8] = b"ssh-ed25519";
+
+#[derive(Clone, Copy)]
+pub(crate) enum Behavior {
+  ForgeSignature,
+  Honest,
+  OversizeResponse,
+}
+
+pub(crate) fn openssh_public_key() -> String {
+  format!(
+    "ssh-ed25519 {}",
+    STANDARD.encode(public_key_blob(&SigningKey::from_bytes(&[1; 32]))),
+  )
+}
+
+fn public_key_blob(signing_key: &SigningKey) -> Vec<u8> {
+  let mut blob = Vec::new();
+  write_string(&mut blob, ED25519);
+  write_string(&mut blob, &signing_key.verifying_key().to_bytes());
+  blob
+}
+
+fn read_string(data: &mut &[u8]) -> Vec<u8> {
+  let len = usize::try_from(u32::from_be_bytes(data[..4].try_into().unwrap())).unwrap();
+  let string = data[4..4 + len].to_vec();
+  *data = &data[4 + len..];
+  string
+}
+
+fn respond(stream: &mut UnixStream, signing_key: &SigningKey, behavior: Behavior) {
+  let blob = public_key_blob(signing_key);
+
+  loop {
+    let mut len = [0; 4];
+
+    if stream.read_exact(&mut len).is_err() {
+      return;
+    }
+
+    let mut request = vec![0; usize::try_from(u32::from_be_bytes(len)).unwrap()];
+    stream.read_exact(&mut request).unwrap();
+
+    let mut response = Vec::new();
+
+    match request[0] {
+      11 => {
+        response.push(12);
+        response.extend_from_slice(&2u32.to_be_bytes());
+        write_string(&mut response, b"\0\0\0\x07ssh-rsa");
+        write_string(&mut response, b"rsa");
+        write_string(&mut response, &blob);
+        write_string(&mut response, b"ed25519");
+      }
+      13 => {
+        let mut data = &request[1..];
+        assert_eq!(read_string(&mut data), blob);
+        let message = read_string(&mut data);
+        assert_eq!(data, [0, 0, 0, 0]);
+        let mut signature = Vec::new();
+        write_string(&mut signature, ED25519);
+        let message = match behavior {
+          Behavior::ForgeSignature => [message, b"forged".to_vec()].concat(),
+          Behavior::Honest | Behavior::OversizeResponse => message,
+        };
+        write_string(&mut signature, &signing_key.sign(&message).to_bytes());
+        response.push(14);
+        write_string(&mut response, &signature);
+      }
+      ty => panic!("unexpected request type {ty}"),
+    }
+
+    if let Behavior::OversizeResponse = behavior {
+      stream.write_all(&u32::MAX.to_be_bytes()).unwrap();
+      return;
+    }
+
+    let mut message = Vec::new();
+    write_string(&mut message, &response);
+    stream.write_all(&message).unwrap();
+  }
+}
+
+pub(crate) fn spawn_ssh_agent(socket: &Utf8Path) -> PublicKey {
+  spawn_ssh_agent_with(socket, Behavior::Honest)
+}
+
+pub(crate) fn spawn_ssh_agent_with(socket: &Utf8Path, behavior: Behavior) -> PublicKey {
+  let signing_key = SigningKey::from_bytes(&[1; 32]);
+
+  let public_key = PublicKey::from_bytes(signing_key.verifying_key().to_bytes()).unwrap();
+
+  let listener = UnixListener::bind(socket).unwrap();
+
+  thread::spawn(move || {
+    for stream in listener.incoming() {
+      respond(&mut stream.unwrap(), &signing_key, behavior);
+    }
+  });
+
+  public_key
+}
+
+fn write_string(buffer: &mut Vec<u8>, string: &[u8]) {
+  buffer.extend_from_slice(&u32::try_from(string.len()).unwrap().to_be_bytes());
+  buffer.extend_from_slice(string);
+}
@@ -0,0 +1,122 @@
+use super::*;
+
+#[test]
+fn changes() {
+  Test::new()
+    .write("modified", "foo")
+    .write("unchanged", "foo")
+    .write("missing", "foo")
+    .create_dir("empty")
+    .arg("create")
+    .success()
+    .write("modified", "bar")
+    .remove_file("missing")
+    .remove_dir("empty")
+    .write("untracked", "foo")
+    .create_dir("new")
+    .args(["status", "--format", "tsv"])
+    .stdout(
+      "
+        missing\tmissing
+        missing-directory\tempty
+        modified\tmodified
+        untracked\tuntracked
+        untracked-directory\tnew
+      ",
+    )
+    .stderr("1 modified, 2 missing, 2 untracked, 1 unchanged\n")
+    .success();
+}
+
+#[test]
+fn clean() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .create_dir("baz")
+    .arg("create")
+    .success()
+    .args(["status", "--format", "json"])
+    .stdout(json! {
+      missing: [],
+      missing_directories: [],
+      modified: [],
+      unchanged: 1,
+      untracked: [],
+      untracked_directories: [],
+    })
+    .stderr("0 modified, 0 missing, 0 untracked, 1 unchanged\n")
+    .success();
+}
+
+#[test]
+fn filepackignore() {
+  Test::new()
+    .write(".filepackignore", "*.log\n")
+    .write("foo", "foo")
+    .arg("create")
+    .success()
+    .write("bar.log", "bar")
+    .write("baz/qux.log", "qux")
+    .write("baz/.filepackignore", "!qux.log\n")
+    .args(["status", "--format", "tsv"])
+    .stdout("untracked\tbaz/.filepackignore\nuntracked\tbaz/qux.log\n")
+    .stderr("0 modified, 0 missing, 2 untracked, 2 unchanged\n")
+    .success();
+}
+
+#[test]
+fn ignore() {
+  Test::new()
+    .write("foo", "foo")
+    .write("bar/baz", "baz")
+    .arg("create")
+    .success()
+    .remove_file("bar/baz")
+    .write("bar/qux", "qux")
+    .args(["status", "--ignore", "bar", "--format", "tsv"])
+    .stderr("0 modified, 0 missing, 0 untracked, 1 unchanged\n")
+    .success();
+}
+
+#[test]
+fn manifest_option() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo", "--manifest", "manifest.filepack"])
+    .success()
+    .write("foo/bar", "baz")
+    .args([
+      "status",
+      "foo",
+      "--manifest",
+      "manifest.filepack",
+      "--format",
+      "tsv",
+    ])
+    .stdout("modified\tbar\n")
+    .stderr("1 modified, 0 missing, 0 untracked, 0 unchanged\n")
+    .success();
+}
+
+#[test]
+fn missing_manifest() {
+  Test::new()
+    .arg("status")
+    .stderr_regex("error: manifest `.*manifest.filepack` not found\n")
+    .failure();
+}
+
+#[test]
+fn size_only() {
+  Test::new()
+    .write("foo", "foo")
+    .write("bar", "bar")
+    .arg("create")
+    .success()
+    .write("foo", "baz")
+    .write("bar", "quux")
+    .args(["status", "--size-only", "--format", "tsv"])
+    .stdout("modified\tbar\n")
+    .stderr("1 modified, 0 missing, 0 untracked, 1 unchanged\n")
+    .success();
+}
@@ -152,6 +152,15 @@ impl Test {
     self
   }
 
+  pub(crate) fn relative_symlink(self, target: &str, link: &str) -> Self {
+    let link = self.join(link);
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(target, link).unwrap();
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_file(target, link).unwrap();
+    self
+  }
+
   pub(crate) fn remove_dir(self, path: &str) -> Self {
     fs::remove_dir(self.join(path)).unwrap();
     self
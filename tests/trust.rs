@@ -0,0 +1,197 @@
+use super::*;
+
+#[test]
+fn add_and_list() {
+  Test::new()
+    .args([
+      "trust",
+      "add",
+      "alice",
+      PUBLIC_KEY,
+      "--comment",
+      "Alice's release key",
+      "--valid-after",
+      "2025-01-01T00:00:00Z",
+    ])
+    .success()
+    .assert_file(
+      "trusted.yaml",
+      &format!(
+        "alice:\n  comment: Alice's release key\n  public_key: {PUBLIC_KEY}\n  valid_after: 2025-01-01T00:00:00Z\n"
+      ),
+    )
+    .args(["trust", "list", "--format", "tsv"])
+    .stdout(format!(
+      "alice\t{PUBLIC_KEY}\t2025-01-01T00:00:00Z\t\tAlice's release key\n"
+    ))
+    .success()
+    .args(["trust", "list", "--format", "json"])
+    .stdout(format!(
+      r#"[{{"comment":"Alice's release key","public_key":"{PUBLIC_KEY}","valid_after":"2025-01-01T00:00:00Z","name":"alice"}}]
+"#
+    ))
+    .success();
+}
+
+#[test]
+fn add_duplicate_name() {
+  Test::new()
+    .args(["trust", "add", "alice", PUBLIC_KEY])
+    .success()
+    .args(["trust", "add", "alice", PUBLIC_KEY])
+    .stderr("error: trusted key `alice` already exists\n")
+    .failure();
+}
+
+#[test]
+fn add_duplicate_public_key() {
+  Test::new()
+    .args(["trust", "add", "alice", PUBLIC_KEY])
+    .success()
+    .args(["trust", "add", "bob", PUBLIC_KEY])
+    .stderr(&format!(
+      "error: public key `{PUBLIC_KEY}` is already trusted as `alice`\n"
+    ))
+    .failure();
+}
+
+#[test]
+fn add_invalid_validity_window() {
+  Test::new()
+    .args([
+      "trust",
+      "add",
+      "alice",
+      PUBLIC_KEY,
+      "--valid-after",
+      "2026-01-01T00:00:00Z",
+      "--valid-before",
+      "2025-01-01T00:00:00Z",
+    ])
+    .stderr(
+      "error: validity window start 2026-01-01T00:00:00Z is not before end 2025-01-01T00:00:00Z\n",
+    )
+    .failure();
+}
+
+#[test]
+fn add_keychain_name() {
+  Test::new()
+    .arg("keygen")
+    .success()
+    .args(["trust", "add", "master", PUBLIC_KEY])
+    .stderr("error: key `master` already exists in keychain\n")
+    .failure();
+}
+
+#[test]
+fn list_empty() {
+  Test::new().args(["trust", "list"]).stdout("[]\n").success();
+}
+
+#[test]
+fn remove() {
+  Test::new()
+    .args(["trust", "add", "alice", PUBLIC_KEY])
+    .success()
+    .args(["trust", "remove", "alice"])
+    .success()
+    .args(["trust", "list", "--format", "json"])
+    .stdout("[]\n")
+    .success()
+    .args(["trust", "remove", "alice"])
+    .stderr("error: trusted key `alice` not found\n")
+    .failure();
+}
+
+#[test]
+fn verify_key_outside_validity_window() {
+  let test = Test::new()
+    .arg("keygen")
+    .success()
+    .touch("foo/bar")
+    .args(["create", "--sign", "foo"])
+    .success();
+
+  let public_key = test.read("keychain/master.public");
+
+  test
+    .args([
+      "trust",
+      "add",
+      "alice",
+      &public_key,
+      "--valid-before",
+      "2020-01-01T00:00:00Z",
+    ])
+    .success()
+    .args(["verify", "foo", "--key", "alice"])
+    .stderr("error: signature by key `alice` is outside of its trusted validity window\n")
+    .failure();
+}
+
+#[test]
+fn verify_key_with_one_signature_inside_validity_window() {
+  let test = Test::new()
+    .arg("keygen")
+    .success()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["sign", "--timestamp", "foo"])
+    .success();
+
+  thread::sleep(Duration::from_millis(1100));
+
+  let valid_after = jiff::Timestamp::from_second(
+    SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap()
+      .as_secs()
+      .try_into()
+      .unwrap(),
+  )
+  .unwrap();
+
+  let test = test
+    .args(["sign", "--timestamp", "--comment", "second", "foo"])
+    .success();
+
+  let public_key = test.read("keychain/master.public");
+
+  test
+    .args([
+      "trust",
+      "add",
+      "alice",
+      &public_key,
+      "--valid-after",
+      &valid_after.to_string(),
+    ])
+    .success()
+    .args(["verify", "foo", "--key", "alice"])
+    .stderr("successfully verified 1 file totaling 0 bytes with 2 signatures\n")
+    .success();
+}
+
+#[test]
+fn verify_trusted_key() {
+  let test = Test::new()
+    .arg("keygen")
+    .success()
+    .touch("foo/bar")
+    .args(["create", "--sign", "foo"])
+    .success();
+
+  let public_key = test.read("keychain/master.public");
+
+  test
+    .args(["trust", "add", "alice", &public_key])
+    .success()
+    .args(["verify", "foo", "--key", "alice"])
+    .stderr("successfully verified 1 file totaling 0 bytes with 1 signature\n")
+    .success()
+    .args(["verify", "foo", "--key", "bob"])
+    .stderr_regex("error: public key not found: `.*bob.public`\n")
+    .failure();
+}
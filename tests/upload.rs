@@ -157,7 +157,77 @@ fn server_url_must_be_http_or_https() {
 }
 
 #[test]
-fn signatures_are_not_uploaded() {
+fn signatures_are_added_when_server_already_has_package() {
+  let server = Test::new().serve().spawn();
+
+  let alice = Test::new()
+    .arg("keygen")
+    .success()
+    .write("pkg/foo", "aaa")
+    .args(["create", "pkg"])
+    .success()
+    .args(["sign", "pkg/manifest.filepack"])
+    .success()
+    .args([
+      "upload",
+      "--server",
+      &server.address(),
+      "pkg/manifest.filepack",
+    ])
+    .stderr("uploading 1 of 1 file\n")
+    .success();
+
+  let bob = Test::new()
+    .arg("keygen")
+    .success()
+    .write("pkg/foo", "aaa")
+    .args(["create", "pkg"])
+    .success()
+    .args(["sign", "pkg/manifest.filepack"])
+    .success()
+    .args([
+      "upload",
+      "--server",
+      &server.address(),
+      "pkg/manifest.filepack",
+    ])
+    .stderr("server already has package\n")
+    .success();
+
+  let alice = Manifest::load(Some(&alice.path().join("pkg/manifest.filepack"))).unwrap();
+  let bob = Manifest::load(Some(&bob.path().join("pkg/manifest.filepack"))).unwrap();
+
+  let fingerprint = alice.fingerprint();
+
+  assert_eq!(bob.fingerprint(), fingerprint);
+
+  let downloaded = Test::new()
+    .args([
+      "download",
+      "--server",
+      &server.address(),
+      "--package",
+      &fingerprint.to_string(),
+      "out",
+    ])
+    .success();
+
+  assert_eq!(
+    Manifest::load(Some(&downloaded.path().join("out/manifest.filepack")))
+      .unwrap()
+      .signatures,
+    alice
+      .signatures
+      .into_iter()
+      .chain(bob.signatures)
+      .collect::<BTreeSet<_>>(),
+  );
+
+  server.terminate().success();
+}
+
+#[test]
+fn signatures_are_uploaded() {
   let server = Test::new().serve().spawn();
 
   let test = Test::new()
@@ -190,11 +260,9 @@ fn signatures_are_not_uploaded() {
     ])
     .success();
 
-  assert!(
-    Manifest::load(Some(&downloaded.path().join("out/manifest.filepack")))
-      .unwrap()
-      .signatures
-      .is_empty(),
+  assert_eq!(
+    Manifest::load(Some(&downloaded.path().join("out/manifest.filepack"))).unwrap(),
+    manifest,
   );
 
   server.terminate().success();
@@ -216,6 +284,33 @@ fn upload_auth_requires_https() {
     .failure();
 }
 
+#[test]
+fn upload_bundle() {
+  let server = Test::new()
+    .serve()
+    .assert_file(&format!("files/{}", Hash::bytes(b"aaa")), "aaa")
+    .assert_file(&format!("files/{}", Hash::bytes(b"bbb")), "bbb")
+    .spawn();
+
+  Test::new()
+    .write("foo/bar", "aaa")
+    .write("foo/baz/qux", "bbb")
+    .args(["create", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .success()
+    .args([
+      "upload",
+      "--server",
+      &server.address(),
+      "--bundle",
+      "foo.bundle",
+    ])
+    .success();
+
+  server.terminate().success();
+}
+
 #[test]
 fn upload_creates_file() {
   let server = Test::new()
@@ -439,6 +534,82 @@ fn upload_package_fails_when_root_not_directory_cbor() {
     .failure();
 }
 
+#[test]
+fn upload_package_refused_by_policy() {
+  let test = Test::new()
+    .arg("keygen")
+    .success()
+    .write("foo", "aaa")
+    .args(["create", "."])
+    .success();
+
+  let public_key = test.read("keychain/master.public");
+
+  let server = Test::new()
+    .write(
+      "policy.yaml",
+      format!(
+        "
+          keys:
+            master: {public_key}
+          rules:
+            release:
+              keys: [master]
+              threshold: 1
+        "
+      ),
+    )
+    .ready_address()
+    .args([
+      "serve",
+      "--address",
+      "127.0.0.1",
+      "--http-port",
+      "0",
+      "--policy",
+      "policy.yaml",
+    ])
+    .spawn();
+
+  test
+    .args(["upload", "--server", &server.address(), "manifest.filepack"])
+    .stderr_regex(".*403 Forbidden.*does not satisfy signature policy.*")
+    .failure()
+    .args(["sign", "manifest.filepack"])
+    .success()
+    .args(["upload", "--server", &server.address(), "manifest.filepack"])
+    .stderr("uploading 0 of 3 files\n")
+    .success();
+
+  server.terminate().success();
+}
+
+#[test]
+fn upload_package_refused_by_quota() {
+  let server = Test::new()
+    .ready_address()
+    .args([
+      "serve",
+      "--address",
+      "127.0.0.1",
+      "--http-port",
+      "0",
+      "--quota-files",
+      "1",
+    ])
+    .spawn();
+
+  Test::new()
+    .write("foo", "aaa")
+    .args(["create", "."])
+    .success()
+    .args(["upload", "--server", &server.address(), "manifest.filepack"])
+    .stderr_regex(".*507 Insufficient Storage: upload exceeds server quota of 1 file\n")
+    .failure();
+
+  server.terminate().success();
+}
+
 #[test]
 fn upload_package_serves_package_html() {
   let server = Test::new().serve().spawn();
@@ -494,6 +665,7 @@ fn upload_package_serves_package_html() {
       metadata: Some(metadata),
       mounted: false,
       readme: None,
+      signatures: BTreeSet::new(),
       totals,
     },
   );
@@ -1,5 +1,75 @@
 use super::*;
 
+#[test]
+fn bundle() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .success()
+    .remove_file("foo/bar")
+    .remove_file("foo/manifest.filepack")
+    .remove_dir("foo")
+    .args(["verify", "--bundle", "foo.bundle"])
+    .stderr("successfully verified 1 file totaling 3 bytes\n")
+    .success();
+}
+
+#[test]
+fn bundle_fingerprint_mismatch() {
+  Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .success()
+    .args([
+      "verify",
+      "--bundle",
+      "foo.bundle",
+      "--fingerprint",
+      "package1a4uf5nw04lxs6dgzqfh4rdhxffxdukfwf4hq39d7vn2fu4eqlxf3ql7ykr3",
+    ])
+    .stderr_regex(
+      "fingerprint mismatch: `foo.bundle`\n.*expected: package1a4uf5nw04lxs6dgzqfh4rdhxffxdukfwf4hq39d7vn2fu4eqlxf3ql7ykr3\n.*actual: package1a.*\nerror: fingerprint mismatch\n",
+    )
+    .failure();
+}
+
+#[test]
+fn bundle_truncated() {
+  let test = Test::new()
+    .write("foo/bar", "bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["bundle", "--manifest", "foo", "foo.bundle"])
+    .success();
+
+  let mut bundle = fs::read(test.path().join("foo.bundle")).unwrap();
+  bundle.pop();
+
+  test
+    .write("foo.bundle", bundle)
+    .args(["verify", "--bundle", "foo.bundle"])
+    .stderr_regex(
+      "error: failed to read bundle `foo.bundle`\n.*file size 2 does not match expected size 3\n",
+    )
+    .failure();
+}
+
+#[test]
+fn detached_signature_invalid() {
+  Test::new()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .write("foo.signature", "foo\n")
+    .args(["verify", "foo", "--signature", "foo.signature"])
+    .stderr_regex("error: invalid signature on line 1 of `foo.signature`\n.*")
+    .failure();
+}
+
 #[test]
 fn duplicate_key_literal() {
   Test::new()
@@ -34,6 +104,49 @@ fn duplicate_key_named_and_literal() {
     .failure();
 }
 
+#[cfg(unix)]
+#[test]
+fn executable_bit_mismatch() {
+  Test::new()
+    .write("foo", "foo")
+    .args(["create", "."])
+    .success()
+    .chmod("foo", 0o755)
+    .args(["verify", "."])
+    .stderr(
+      "
+        mismatched file: `foo`
+               manifest: 04e0bb39f30b1a3feb89f536c93be15055482df748674b00d26e5a75777702e9 (3 bytes)
+                   file: 04e0bb39f30b1a3feb89f536c93be15055482df748674b00d26e5a75777702e9 (3 bytes, executable)
+        error: 1 mismatched file
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn expired_signature() {
+  Test::new()
+    .write_keypair("master")
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .write(
+      "foo.signature",
+      concat!(
+        "signature1a67dndhhmae7p6fsfnj0z37zf78cde6mwqgtms0y87h8ldlvvflyq0l2vw9nq2mhapw340mkpur0ej3j",
+        "del2zzgh8hhx8cdezjsndqjtssqld3hcj3aa9ej2ykqu5uy0wtyxzhrf48ew8n6nw9xjdekcm49e9kg3ly707ne",
+        "acr5qu3vmv9fdt08fteqtkj940q6l47q04r3j5gzgeqr9f5wcqqqqqqmmm48c",
+      ),
+    )
+    .args(["verify", "foo", "--signature", "foo.signature"])
+    .stderr("successfully verified 1 file totaling 0 bytes with 1 signature\n")
+    .success()
+    .args(["verify", "foo", "--signature", "foo.signature", "--key", "master"])
+    .stderr("error: signature for key `master` expired at `2001-09-09T01:46:40Z`\n")
+    .failure();
+}
+
 #[test]
 fn extra_fields_are_not_allowed() {
   Test::new()
@@ -81,6 +194,18 @@ fn extraneous_file_error() {
     .failure();
 }
 
+#[test]
+fn extraneous_symlink() {
+  Test::new()
+    .touch("foo")
+    .arg("create")
+    .success()
+    .relative_symlink("foo", "bar")
+    .arg("verify")
+    .stderr("error: extraneous symlink not in manifest: `bar`\n")
+    .failure();
+}
+
 #[test]
 fn file_not_found_error_message() {
   Test::new()
@@ -102,6 +227,45 @@ fn file_not_found_error_message() {
     .failure();
 }
 
+#[test]
+fn filepackignore_in_manifest_is_honored() {
+  Test::new()
+    .write(".filepackignore", "*.o\n")
+    .write("foo", "foo")
+    .arg("create")
+    .success()
+    .touch("bar.o")
+    .arg("verify")
+    .stderr("successfully verified 2 files totaling 7 bytes\n")
+    .success();
+}
+
+#[test]
+fn filepackignore_not_in_manifest_is_extraneous() {
+  Test::new()
+    .write("foo", "foo")
+    .arg("create")
+    .success()
+    .write(".filepackignore", "bar\n")
+    .touch("bar")
+    .arg("verify")
+    .stderr("error: extraneous file not in manifest: `.filepackignore`\n")
+    .failure();
+}
+
+#[test]
+fn filepackignore_not_in_manifest_is_not_applied() {
+  Test::new()
+    .write("foo", "foo")
+    .arg("create")
+    .success()
+    .write("sub/.filepackignore", "bar\n")
+    .touch("sub/bar")
+    .args(["verify", "--ignore", "sub/.filepackignore"])
+    .stderr("error: extraneous file not in manifest: `sub/bar`\n")
+    .failure();
+}
+
 #[test]
 fn hash_mismatch() {
   Test::new()
@@ -316,6 +480,19 @@ fn missing_signature_for_named_key() {
     .failure();
 }
 
+#[test]
+fn missing_symlink() {
+  Test::new()
+    .touch("foo")
+    .relative_symlink("foo", "bar")
+    .arg("create")
+    .success()
+    .remove_file("bar")
+    .arg("verify")
+    .stderr("error: file missing: `bar`\n")
+    .failure();
+}
+
 #[test]
 fn multiple_keys() {
   let test = Test::new()
@@ -483,6 +660,125 @@ fn non_unicode_path_error() {
     .failure();
 }
 
+#[test]
+fn policy() {
+  let test = Test::new()
+    .arg("keygen")
+    .success()
+    .args(["keygen", "--name", "deploy"])
+    .success()
+    .touch("foo/bar")
+    .args(["create", "--sign", "foo"])
+    .success();
+
+  let master = test.read("keychain/master.public");
+  let deploy = test.read("keychain/deploy.public");
+
+  let test = test.write(
+    "policy.yaml",
+    format!(
+      "
+        keys:
+          alice: {master}
+          bob: {deploy}
+        rules:
+          any:
+            keys: [alice, bob]
+            threshold: 1
+          both:
+            keys: [alice, bob]
+            threshold: 2
+      "
+    ),
+  );
+
+  test
+    .args(["verify", "foo", "--policy", "policy.yaml"])
+    .stderr(
+      "
+        policy signers: alice
+        policy rule `any` satisfied: 1 of 1 required signatures
+        policy rule `both` not satisfied: 1 of 2 required signatures
+        successfully verified 1 file totaling 0 bytes with 1 signature
+      ",
+    )
+    .success();
+}
+
+#[test]
+fn policy_invalid() {
+  Test::new()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .write(
+      "policy.yaml",
+      "
+        keys: {}
+        rules:
+          release:
+            keys: [alice]
+            threshold: 1
+      ",
+    )
+    .args(["verify", "foo", "--policy", "policy.yaml"])
+    .stderr_regex(
+      "error: invalid policy `policy.yaml`\n.*rule `release` references unknown key `alice`\n",
+    )
+    .failure();
+}
+
+#[test]
+fn policy_not_satisfied() {
+  let test = Test::new()
+    .arg("keygen")
+    .success()
+    .args(["keygen", "--name", "deploy"])
+    .success()
+    .touch("foo/bar")
+    .args(["create", "--sign", "foo"])
+    .success();
+
+  let master = test.read("keychain/master.public");
+  let deploy = test.read("keychain/deploy.public");
+
+  test
+    .write(
+      "policy.yaml",
+      format!(
+        "
+          keys:
+            alice: {master}
+            bob: {deploy}
+          rules:
+            release:
+              keys: [alice, bob]
+              threshold: 2
+        "
+      ),
+    )
+    .args(["verify", "foo", "--policy", "policy.yaml"])
+    .stderr(
+      "
+        policy signers: alice
+        policy rule `release` not satisfied: 1 of 2 required signatures
+        error: package does not satisfy policy `policy.yaml`
+      ",
+    )
+    .failure()
+    .args(["sign", "--key", "deploy", "foo/manifest.filepack"])
+    .success()
+    .args(["verify", "foo", "--policy", "policy.yaml"])
+    .stderr(
+      "
+        policy signers: alice, bob
+        policy rule `release` satisfied: 2 of 2 required signatures
+        successfully verified 1 file totaling 0 bytes with 2 signatures
+      ",
+    )
+    .success();
+}
+
 #[test]
 fn print() {
   Test::new()
@@ -528,6 +824,143 @@ fn print_includes_embedded_files() {
     .success();
 }
 
+#[test]
+fn purpose() {
+  Test::new()
+    .args(["keygen", "--name", "alice"])
+    .success()
+    .args(["keygen", "--name", "bob"])
+    .success()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["sign", "--key", "alice", "--purpose", "release", "foo"])
+    .success()
+    .args(["sign", "--key", "bob", "--purpose", "review", "foo"])
+    .success()
+    .args(["verify", "foo", "--key", "alice", "--key", "bob"])
+    .stderr("successfully verified 1 file totaling 0 bytes with 2 signatures\n")
+    .success()
+    .args(["verify", "foo", "--key", "alice", "--purpose", "release"])
+    .stderr("successfully verified 1 file totaling 0 bytes with 2 signatures\n")
+    .success()
+    .args(["verify", "foo", "--key", "bob", "--purpose", "release"])
+    .stderr("error: signature for key `bob` does not have purpose `release`\n")
+    .failure();
+}
+
+#[test]
+fn revoked_key() {
+  Test::new()
+    .write_keypair("master")
+    .touch("foo/bar")
+    .args(["create", "--sign", "foo"])
+    .success()
+    .args(["key", "revoke", "--time", "2025-01-01T00:00:00Z"])
+    .stdout_regex("revocation1a.*\n")
+    .success()
+    .args(["verify", "foo"])
+    .stderr(&format!(
+      "error: signature by key `{PUBLIC_KEY}` not trusted, key was revoked at `2025-01-01T00:00:00Z`\n"
+    ))
+    .failure();
+}
+
+#[test]
+fn revoked_key_signature_made_after_revocation() {
+  Test::new()
+    .write_keypair("master")
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["sign", "--timestamp", "foo"])
+    .success()
+    .args(["key", "revoke", "--time", "2025-01-01T00:00:00Z"])
+    .stdout_regex("revocation1a.*\n")
+    .success()
+    .args(["verify", "foo"])
+    .stderr(&format!(
+      "error: signature by key `{PUBLIC_KEY}` not trusted, key was revoked at `2025-01-01T00:00:00Z`\n"
+    ))
+    .failure();
+}
+
+#[test]
+fn revoked_key_signature_made_before_revocation() {
+  Test::new()
+    .write_keypair("master")
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["sign", "--timestamp", "foo"])
+    .success()
+    .args(["key", "revoke", "--time", "2100-01-01T00:00:00Z"])
+    .stdout_regex("revocation1a.*\n")
+    .success()
+    .args(["verify", "foo", "--key", "master"])
+    .stderr("successfully verified 1 file totaling 0 bytes with 1 signature\n")
+    .success();
+}
+
+#[test]
+fn rotated_key() {
+  Test::new()
+    .write_keypair("master")
+    .args(["keygen", "--name", "replacement"])
+    .success()
+    .args(["key", "revoke", "--replacement", "replacement"])
+    .stdout_regex("revocation1a.*\n")
+    .success()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["sign", "--key", "replacement", "foo"])
+    .success()
+    .args(["verify", "foo", "--key", PUBLIC_KEY])
+    .stderr("successfully verified 1 file totaling 0 bytes with 1 signature\n")
+    .success()
+    .args(["verify", "foo", "--key", "master"])
+    .stderr("successfully verified 1 file totaling 0 bytes with 1 signature\n")
+    .success();
+}
+
+#[test]
+fn rotated_key_requires_pinned_replacement() {
+  let test = Test::new()
+    .write_keypair("master")
+    .args(["keygen", "--name", "attacker"])
+    .success()
+    .args(["key", "revoke", "--replacement", "attacker"])
+    .stdout_regex("revocation1a.*\n")
+    .success()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["sign", "--key", "attacker", "foo"])
+    .success();
+
+  let revocation = test.read("revocations.txt");
+  let attacker = test.read("keychain/attacker.public");
+
+  test
+    .data_dir("verifier")
+    .args(["revocation", "add", revocation.trim()])
+    .success()
+    .data_dir("verifier")
+    .args(["verify", "foo", "--key", PUBLIC_KEY])
+    .stderr(&format!(
+      "error: no signature found for key `{PUBLIC_KEY}`\n"
+    ))
+    .failure()
+    .data_dir("verifier")
+    .args(["trust", "add", "successor", attacker.trim()])
+    .success()
+    .data_dir("verifier")
+    .args(["verify", "foo", "--key", PUBLIC_KEY])
+    .stderr("successfully verified 1 file totaling 0 bytes with 1 signature\n")
+    .success();
+}
+
 #[test]
 fn signature_fingerprint_mismatch() {
   let test = Test::new()
@@ -689,6 +1122,80 @@ fn size_mismatch() {
     .failure();
 }
 
+#[test]
+fn ssh_public_key_invalid() {
+  Test::new()
+    .touch("foo/bar")
+    .args(["create", "foo"])
+    .success()
+    .args(["verify", "foo", "--key", "ssh-rsa AAAA"])
+    .stderr_regex(
+      "error: invalid value 'ssh-rsa AAAA' for '--key <KEY>': invalid OpenSSH public key `ssh-rsa AAAA`\n.*",
+    )
+    .status(2);
+}
+
+#[test]
+fn symlink_mismatch() {
+  Test::new()
+    .touch("foo")
+    .touch("baz")
+    .relative_symlink("foo", "bar")
+    .arg("create")
+    .success()
+    .remove_file("bar")
+    .relative_symlink("./baz", "bar")
+    .arg("verify")
+    .stderr(
+      "
+        mismatched symlink: `bar`
+                  manifest: foo
+                   symlink: ./baz
+        error: 1 mismatched file
+      ",
+    )
+    .failure()
+    .remove_file("bar")
+    .relative_symlink("./foo", "bar")
+    .arg("verify")
+    .stderr("successfully verified 2 files totaling 0 bytes\n")
+    .success()
+    .remove_file("bar")
+    .touch("bar")
+    .arg("verify")
+    .stderr(
+      "
+        mismatched symlink: `bar`
+                  manifest: foo
+                   symlink: not a symlink
+        error: 1 mismatched file
+      ",
+    )
+    .failure();
+}
+
+#[test]
+fn trust_cache() {
+  Test::new()
+    .write("foo/bar", "foo")
+    .args(["create", "--update", "foo"])
+    .success()
+    .args(["verify", "--trust-cache", "foo"])
+    .stderr("successfully verified 1 file totaling 3 bytes\n")
+    .success()
+    .write("foo/bar", "bar")
+    .args(["verify", "--trust-cache", "foo"])
+    .stderr(
+      "
+        mismatched file: `bar`
+               manifest: 04e0bb39f30b1a3feb89f536c93be15055482df748674b00d26e5a75777702e9 (3 bytes)
+                   file: f2e897eed7d206cd855d441598fa521abc75aa96953e97c030c9612c30c1293d (3 bytes)
+        error: 1 mismatched file
+      ",
+    )
+    .failure();
+}
+
 #[test]
 fn unarchive_error() {
   let mut dir_encoder = Encoder::new();